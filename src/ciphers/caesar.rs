@@ -8,13 +8,19 @@
 
 /// Caesar cipher to rotate cipher text by shift and return an owned String.
 pub fn caesar(cipher: &str, shift: u8) -> String {
-    cipher
-        .chars()
+    caesar_encrypt(cipher, shift as i32)
+}
+
+/// Rotates every ascii letter of `text` by `shift` letters within its case, a negative shift
+/// rotating backwards. The shift wraps around modulo 26, and other characters are left unchanged.
+pub fn caesar_encrypt(text: &str, shift: i32) -> String {
+    let shift = shift.rem_euclid(26) as u8;
+    text.chars()
         .map(|c| {
             if c.is_ascii_alphabetic() {
                 let first = if c.is_ascii_lowercase() { b'a' } else { b'A' };
                 // modulo the distance to keep character range
-                (first + (c as u8 + shift - first) % 26) as char
+                (first + (c as u8 - first + shift) % 26) as char
             } else {
                 c
             }
@@ -22,6 +28,49 @@ pub fn caesar(cipher: &str, shift: u8) -> String {
         .collect()
 }
 
+/// Undoes `caesar_encrypt` with the same shift.
+pub fn caesar_decrypt(text: &str, shift: i32) -> String {
+    caesar_encrypt(text, -(shift % 26))
+}
+
+/// The frequencies of the letters in English text, in percent.
+const ENGLISH_FREQUENCIES: [f64; 26] = [
+    8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015, 6.094, 6.966, 0.153, 0.772, 4.025, 2.406,
+    6.749, 7.507, 1.929, 0.095, 5.987, 6.327, 9.056, 2.758, 0.978, 2.360, 0.150, 1.974, 0.074,
+];
+
+/// Finds the most likely plaintext of an English `ciphertext`, returning it along with the
+/// shift, in 0..26, that encrypts it into `ciphertext`.
+///
+/// Every shift is tried, keeping the one whose letter counts are the closest to the English
+/// letter frequencies by the chi-squared statistic. Short texts may not have enough letters for
+/// their frequencies to tell, and a text without letters is returned as is, with a shift of 0.
+pub fn caesar_crack(ciphertext: &str) -> (String, i32) {
+    let mut counts = [0_usize; 26];
+    for c in ciphertext.chars().filter(char::is_ascii_alphabetic) {
+        counts[(c.to_ascii_lowercase() as u8 - b'a') as usize] += 1;
+    }
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return (ciphertext.to_string(), 0);
+    }
+
+    let chi_squared = |shift: usize| -> f64 {
+        (0..26)
+            .map(|letter| {
+                // the ciphertext letter the plaintext letter becomes
+                let observed = counts[(letter + shift) % 26] as f64;
+                let expected = ENGLISH_FREQUENCIES[letter] / 100.0 * total as f64;
+                (observed - expected).powi(2) / expected
+            })
+            .sum()
+    };
+    let shift = (0..26)
+        .min_by(|&a, &b| chi_squared(a).total_cmp(&chi_squared(b)))
+        .unwrap() as i32;
+    (caesar_decrypt(ciphertext, shift), shift)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,4 +89,65 @@ mod tests {
     fn caesar_unicode() {
         assert_eq!(caesar("attack at dawn 攻", 5), "fyyfhp fy ifbs 攻");
     }
+
+    #[test]
+    fn caesar_large_shift() {
+        // the shift used to overflow a byte
+        assert_eq!(caesar("xyz", 255), "stu");
+    }
+
+    #[test]
+    fn encrypt_wraps_around() {
+        assert_eq!(caesar_encrypt("Hello, World!", 3), "Khoor, Zruog!");
+        assert_eq!(caesar_encrypt("xyz XYZ", 3), "abc ABC");
+        assert_eq!(caesar_encrypt("abc", -1), "zab");
+        assert_eq!(caesar_encrypt("abc", 29), "def");
+        assert_eq!(caesar_encrypt("abc", -27), "zab");
+        assert_eq!(caesar_encrypt("abc", 26), "abc");
+        assert_eq!(caesar_decrypt("Khoor, Zruog!", 3), "Hello, World!");
+        let extreme = caesar_encrypt("abc", i32::MIN);
+        assert_eq!(caesar_decrypt(&extreme, i32::MIN), "abc");
+    }
+
+    #[test]
+    fn rot_13_is_its_own_inverse() {
+        let text = "The Quick Brown Fox Jumps Over The Lazy Dog, 1234!";
+        let rotated = caesar_encrypt(text, 13);
+        assert_eq!(
+            rotated,
+            "Gur Dhvpx Oebja Sbk Whzcf Bire Gur Ynml Qbt, 1234!"
+        );
+        assert_eq!(caesar_encrypt(&rotated, 13), text);
+        assert_eq!(caesar_decrypt(text, 13), rotated);
+    }
+
+    #[test]
+    fn decrypt_undoes_encrypt() {
+        let text = "attack at dawn 攻, ATTACK AT DUSK!";
+        for shift in -60..60 {
+            assert_eq!(caesar_decrypt(&caesar_encrypt(text, shift), shift), text);
+            assert_eq!(caesar_decrypt(text, shift), caesar_encrypt(text, -shift));
+        }
+    }
+
+    #[test]
+    fn crack_english_sentences() {
+        let sentences = [
+            "The quick brown fox jumps over the lazy dog while the farmer sleeps in the barn.",
+            "It was the best of times, it was the worst of times, it was the age of wisdom.",
+            "Meet me near the old clock tower at seven tonight and bring the documents.",
+        ];
+        for plaintext in sentences {
+            for shift in 0..26 {
+                let ciphertext = caesar_encrypt(plaintext, shift);
+                assert_eq!(caesar_crack(&ciphertext), (plaintext.to_string(), shift));
+            }
+        }
+    }
+
+    #[test]
+    fn crack_without_letters() {
+        assert_eq!(caesar_crack(""), (String::new(), 0));
+        assert_eq!(caesar_crack("1234 !?"), ("1234 !?".to_string(), 0));
+    }
 }
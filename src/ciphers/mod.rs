@@ -20,13 +20,15 @@ mod xor;
 pub use self::aes::{aes_decrypt, aes_encrypt, AesKey};
 pub use self::another_rot13::another_rot13;
 pub use self::base64::{base64_decode, base64_encode};
-pub use self::caesar::caesar;
+pub use self::caesar::{caesar, caesar_crack, caesar_decrypt, caesar_encrypt};
 pub use self::chacha::chacha20;
 pub use self::diffie_hellman::DiffieHellman;
 pub use self::hashing_traits::Hasher;
 pub use self::hashing_traits::HMAC;
 pub use self::kerninghan::kerninghan;
-pub use self::morse_code::{decode, encode};
+pub use self::morse_code::{
+    decode, encode, morse_decode, morse_decode_with_case, morse_encode, LetterCase, MorseError,
+};
 pub use self::polybius::{decode_ascii, encode_ascii};
 pub use self::rot13::rot13;
 pub use self::salsa::salsa20;
@@ -34,5 +36,5 @@ pub use self::sha256::SHA256;
 pub use self::tea::{tea_decrypt, tea_encrypt};
 pub use self::theoretical_rot13::theoretical_rot13;
 pub use self::transposition::transposition;
-pub use self::vigenere::vigenere;
+pub use self::vigenere::{vigenere, vigenere_decrypt, vigenere_encrypt, CipherError};
 pub use self::xor::xor;
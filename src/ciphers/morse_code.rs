@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::fmt;
 use std::io;
 
 const UNKNOWN_CHARACTER: &str = "........";
@@ -8,7 +9,6 @@ pub fn encode(message: &str) -> String {
     let dictionary = _morse_dictionary();
     message
         .chars()
-        .into_iter()
         .map(|char| char.to_uppercase().to_string())
         .map(|letter| dictionary.get(letter.as_str()))
         .map(|option| option.unwrap_or(&UNKNOWN_CHARACTER).to_string())
@@ -125,6 +125,75 @@ pub fn decode(string: &str) -> Result<String, io::Error> {
     Ok(partitions.join(" "))
 }
 
+/// The reasons a message cannot be converted to or from morse code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MorseError {
+    /// The character of the message has no morse code.
+    UnknownCharacter(char),
+    /// The sequence of dots and dashes is not the code of any character.
+    UnknownSequence(String),
+}
+
+impl fmt::Display for MorseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MorseError::UnknownCharacter(c) => write!(f, "no morse code for {c:?}"),
+            MorseError::UnknownSequence(code) => write!(f, "unknown morse code {code:?}"),
+        }
+    }
+}
+
+impl std::error::Error for MorseError {}
+
+/// The case of the letters decoded from morse code, which does not tell them apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LetterCase {
+    Upper,
+    Lower,
+}
+
+/// Convert a message to morse code, separating the codes of its characters with a space
+/// and its words with a `/`.
+///
+/// Unlike `encode`, which replaces them, characters without a morse code are rejected.
+pub fn morse_encode(text: &str) -> Result<String, MorseError> {
+    let dictionary = _morse_dictionary();
+    text.chars()
+        .map(|c| {
+            dictionary
+                .get(c.to_uppercase().to_string().as_str())
+                .copied()
+                .ok_or(MorseError::UnknownCharacter(c))
+        })
+        .collect::<Result<Vec<&str>, MorseError>>()
+        .map(|codes| codes.join(" "))
+}
+
+/// Convert morse code to a message in upper case.
+///
+/// See `morse_decode_with_case`.
+pub fn morse_decode(code: &str) -> Result<String, MorseError> {
+    morse_decode_with_case(code, LetterCase::Upper)
+}
+
+/// Convert morse code to a message, with its letters in the given case.
+///
+/// The codes of the characters are separated by whitespace, any amount of it, and a `/`
+/// stands for a space between words, so that leading, trailing or repeated spaces are
+/// ignored. Unlike `decode`, which replaces them, unknown codes are rejected.
+pub fn morse_decode_with_case(code: &str, case: LetterCase) -> Result<String, MorseError> {
+    let dictionary = _morse_to_alphanumeric_dictionary();
+    code.split_whitespace()
+        .map(|token| match dictionary.get(token) {
+            Some(character) => Ok(match case {
+                LetterCase::Upper => character.to_string(),
+                LetterCase::Lower => character.to_lowercase(),
+            }),
+            None => Err(MorseError::UnknownSequence(token.to_string())),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,4 +264,81 @@ mod tests {
 
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn sos() {
+        assert_eq!(morse_encode("SOS"), Ok("... --- ...".to_string()));
+        assert_eq!(morse_encode("sos"), Ok("... --- ...".to_string()));
+        assert_eq!(morse_decode("... --- ..."), Ok("SOS".to_string()));
+    }
+
+    #[test]
+    fn letters_and_digits_round_trip() {
+        let characters = ('A'..='Z').chain('0'..='9');
+        for c in characters {
+            let code = morse_encode(&c.to_string()).unwrap();
+            assert!(code.chars().all(|symbol| symbol == '.' || symbol == '-'));
+            assert_eq!(morse_decode(&code), Ok(c.to_string()));
+            assert_eq!(
+                morse_decode_with_case(&code, LetterCase::Lower),
+                Ok(c.to_ascii_lowercase().to_string())
+            );
+        }
+        let text = "THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG 0123456789";
+        assert_eq!(
+            morse_decode(&morse_encode(text).unwrap()),
+            Ok(text.to_string())
+        );
+    }
+
+    #[test]
+    fn words() {
+        assert_eq!(
+            morse_encode("Hello Morse"),
+            Ok(".... . .-.. .-.. --- / -- --- .-. ... .".to_string())
+        );
+        assert_eq!(
+            morse_decode_with_case(".... . .-.. .-.. --- / -- --- .-. ... .", LetterCase::Lower),
+            Ok("hello morse".to_string())
+        );
+        // punctuation is lowercase as it is
+        assert_eq!(
+            morse_decode_with_case(".-- .... .- - .----. ... / ..--..", LetterCase::Lower),
+            Ok("what's ?".to_string())
+        );
+    }
+
+    #[test]
+    fn extra_spaces() {
+        assert_eq!(morse_decode("  ...   ---  ...  "), Ok("SOS".to_string()));
+        assert_eq!(
+            morse_decode(".... ../- .... . .-. . "),
+            Err(MorseError::UnknownSequence("../-".to_string()))
+        );
+        assert_eq!(
+            morse_decode("\t.... .. / - .... . .-. .\n"),
+            Ok("HI THERE".to_string())
+        );
+        assert_eq!(morse_decode(""), Ok(String::new()));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            morse_encode("Error {}"),
+            Err(MorseError::UnknownCharacter('{'))
+        );
+        assert_eq!(
+            morse_encode("naïve"),
+            Err(MorseError::UnknownCharacter('ï'))
+        );
+        assert_eq!(
+            morse_decode("... ------- ..."),
+            Err(MorseError::UnknownSequence("-------".to_string()))
+        );
+        assert_eq!(
+            morse_decode("..x"),
+            Err(MorseError::UnknownSequence("..x".to_string()))
+        );
+    }
 }
@@ -6,28 +6,50 @@
 //! When we reach the last key character, we start over from the first one.
 //! This implementation does not rotate unicode characters.
 
-/// Vigenère cipher to rotate plain_text text by key and return an owned String.
-pub fn vigenere(plain_text: &str, key: &str) -> String {
-    // Remove all unicode and non-ascii characters from key
-    let key: String = key.chars().filter(|&c| c.is_ascii_alphabetic()).collect();
-    let key = key.to_ascii_lowercase();
+use std::fmt;
 
-    let key_len = key.len();
-    if key_len == 0 {
-        return String::from(plain_text);
+/// The reasons a key cannot be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CipherError {
+    /// The key has no characters.
+    EmptyKey,
+    /// The key has a character that is not an ascii letter.
+    NonAlphabeticKey,
+}
+
+impl fmt::Display for CipherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CipherError::EmptyKey => write!(f, "empty key"),
+            CipherError::NonAlphabeticKey => write!(f, "key with a character other than a letter"),
+        }
     }
+}
 
-    let mut index = 0;
+impl std::error::Error for CipherError {}
 
-    plain_text
-        .chars()
+/// Rotates the ascii letters of `text` by the offsets of the letters of `key`, which must
+/// be ascii letters, forwards or backwards.
+fn rotate(text: &str, key: &[u8], forwards: bool) -> String {
+    let mut shifts = key
+        .iter()
+        .map(|&k| {
+            let shift = k.to_ascii_lowercase() - b'a';
+            if forwards {
+                shift
+            } else {
+                (26 - shift) % 26
+            }
+        })
+        .cycle();
+
+    text.chars()
         .map(|c| {
             if c.is_ascii_alphabetic() {
                 let first = if c.is_ascii_lowercase() { b'a' } else { b'A' };
-                let shift = key.as_bytes()[index % key_len] - b'a';
-                index += 1;
+                let shift = shifts.next().unwrap();
                 // modulo the distance to keep character range
-                (first + (c as u8 + shift - first) % 26) as char
+                (first + (c as u8 - first + shift) % 26) as char
             } else {
                 c
             }
@@ -35,6 +57,41 @@ pub fn vigenere(plain_text: &str, key: &str) -> String {
         .collect()
 }
 
+/// Vigenère cipher to rotate plain_text text by key and return an owned String.
+pub fn vigenere(plain_text: &str, key: &str) -> String {
+    // Remove all unicode and non-ascii characters from key
+    let key: Vec<u8> = key.bytes().filter(u8::is_ascii_alphabetic).collect();
+    if key.is_empty() {
+        return String::from(plain_text);
+    }
+    rotate(plain_text, &key, true)
+}
+
+fn check_key(key: &str) -> Result<&[u8], CipherError> {
+    if key.is_empty() {
+        Err(CipherError::EmptyKey)
+    } else if !key.bytes().all(|b| b.is_ascii_alphabetic()) {
+        Err(CipherError::NonAlphabeticKey)
+    } else {
+        Ok(key.as_bytes())
+    }
+}
+
+/// Encrypts `text` by rotating each ascii letter by the offset of the next letter of
+/// `key`, in either case, from 'a', starting over from the first one after the last one.
+/// Other characters are left unchanged, and do not use up a letter of the key.
+///
+/// Unlike `vigenere`, which ignores the characters of the key that are not letters, the key
+/// is rejected unless it is made of ascii letters only.
+pub fn vigenere_encrypt(text: &str, key: &str) -> Result<String, CipherError> {
+    Ok(rotate(text, check_key(key)?, true))
+}
+
+/// Undoes `vigenere_encrypt` with the same key.
+pub fn vigenere_decrypt(text: &str, key: &str) -> Result<String, CipherError> {
+    Ok(rotate(text, check_key(key)?, false))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,4 +140,74 @@ mod tests {
     fn vigenere_empty_key() {
         assert_eq!(vigenere("Lorem ipsum", ""), "Lorem ipsum");
     }
+
+    #[test]
+    fn attack_at_dawn() {
+        assert_eq!(
+            vigenere_encrypt("ATTACKATDAWN", "LEMON"),
+            Ok("LXFOPVEFRNHR".to_string())
+        );
+        assert_eq!(
+            vigenere_decrypt("LXFOPVEFRNHR", "LEMON"),
+            Ok("ATTACKATDAWN".to_string())
+        );
+    }
+
+    #[test]
+    fn mixed_case() {
+        assert_eq!(
+            vigenere_encrypt("Attack at Dawn!", "LeMoN"),
+            Ok("Lxfopv ef Rnhr!".to_string())
+        );
+        assert_eq!(
+            vigenere_encrypt("Attack at Dawn!", "lemon"),
+            vigenere_encrypt("Attack at Dawn!", "LEMON")
+        );
+        assert_eq!(
+            vigenere_decrypt("Lxfopv ef Rnhr!", "lEmOn"),
+            Ok("Attack at Dawn!".to_string())
+        );
+        // the same as the older function for a key of letters only
+        let text = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+        assert_eq!(
+            vigenere_encrypt(text, "spaces"),
+            Ok(vigenere(text, "spaces"))
+        );
+    }
+
+    #[test]
+    fn invalid_keys() {
+        assert_eq!(vigenere_encrypt("text", ""), Err(CipherError::EmptyKey));
+        assert_eq!(vigenere_decrypt("text", ""), Err(CipherError::EmptyKey));
+        for key in ["lem on", "lemon1", "😉key", "é"] {
+            assert_eq!(
+                vigenere_encrypt("text", key),
+                Err(CipherError::NonAlphabeticKey)
+            );
+            assert_eq!(
+                vigenere_decrypt("text", key),
+                Err(CipherError::NonAlphabeticKey)
+            );
+        }
+    }
+
+    #[test]
+    fn decrypt_undoes_encrypt() {
+        let printable: String = (b' '..=b'~').map(char::from).collect();
+        let keys = ["a", "z", "LEMON", "key", "ALongerKeyThanSomeTexts", "Zz"];
+        for key in keys {
+            for start in 0..printable.len() {
+                let text = &printable[start..];
+                let encrypted = vigenere_encrypt(text, key).unwrap();
+                assert_eq!(vigenere_decrypt(&encrypted, key), Ok(text.to_string()));
+                // only the letters change
+                for (a, b) in text.chars().zip(encrypted.chars()) {
+                    assert_eq!(a.is_ascii_alphabetic(), b.is_ascii_alphabetic());
+                    assert!(a.is_ascii_alphabetic() || a == b);
+                }
+            }
+        }
+        // "a" leaves everything unchanged
+        assert_eq!(vigenere_encrypt(&printable, "a"), Ok(printable.clone()));
+    }
 }
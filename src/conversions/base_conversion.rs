@@ -0,0 +1,174 @@
+// Writes numbers in any base from 2 to 36, with the digits 0 to 9 followed by
+// the letters a to z.
+// Source: https://en.wikipedia.org/wiki/Positional_notation
+
+use std::fmt;
+
+/// The reasons a number cannot be converted to or from a base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseError {
+    /// The base is not in 2..=36.
+    InvalidBase,
+    /// The character is not a digit of the base.
+    InvalidDigit(char),
+    /// The string has no digits.
+    Empty,
+    /// The number does not fit in a `u64`.
+    Overflow,
+}
+
+impl fmt::Display for BaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BaseError::InvalidBase => write!(f, "base out of the range 2 to 36"),
+            BaseError::InvalidDigit(c) => write!(f, "invalid digit {c:?}"),
+            BaseError::Empty => write!(f, "no digits"),
+            BaseError::Overflow => write!(f, "number too large"),
+        }
+    }
+}
+
+impl std::error::Error for BaseError {}
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+fn check_base(base: u8) -> Result<u64, BaseError> {
+    if (2..=36).contains(&base) {
+        Ok(base as u64)
+    } else {
+        Err(BaseError::InvalidBase)
+    }
+}
+
+/// Returns `n` written in `base`, with lowercase letters and without leading
+/// zeros, 0 being "0".
+pub fn to_base(n: u64, base: u8) -> Result<String, BaseError> {
+    let base = check_base(base)?;
+    let mut digits = Vec::new();
+    let mut rest = n;
+    loop {
+        digits.push(DIGITS[(rest % base) as usize]);
+        rest /= base;
+        if rest == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    Ok(String::from_utf8(digits).unwrap())
+}
+
+/// Returns the number `s` writes in `base`, its letters being in upper or lower
+/// case. Leading zeros are allowed, but no sign or whitespace.
+pub fn from_base(s: &str, base: u8) -> Result<u64, BaseError> {
+    let base = check_base(base)?;
+    if s.is_empty() {
+        return Err(BaseError::Empty);
+    }
+    s.chars().try_fold(0_u64, |n, c| {
+        let digit = c
+            .to_digit(36)
+            .map(u64::from)
+            .filter(|&digit| digit < base)
+            .ok_or(BaseError::InvalidDigit(c))?;
+        n.checked_mul(base)
+            .and_then(|n| n.checked_add(digit))
+            .ok_or(BaseError::Overflow)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversions::decimal_to_binary;
+
+    #[test]
+    fn zero() {
+        for base in 2..=36 {
+            assert_eq!(to_base(0, base), Ok("0".to_string()));
+            assert_eq!(from_base("0", base), Ok(0));
+            assert_eq!(from_base("000", base), Ok(0));
+        }
+    }
+
+    #[test]
+    fn binary() {
+        for n in 0..1024 {
+            let binary = to_base(n, 2).unwrap();
+            assert_eq!(binary, decimal_to_binary(n));
+            assert_eq!(binary, format!("{n:b}"));
+            assert_eq!(from_base(&binary, 2), Ok(n));
+            assert_eq!(to_base(n, 10).unwrap(), n.to_string());
+            assert_eq!(from_base(&n.to_string(), 10), Ok(n));
+        }
+    }
+
+    #[test]
+    fn hexadecimal() {
+        assert_eq!(to_base(255, 16), Ok("ff".to_string()));
+        assert_eq!(to_base(0xdead_beef, 16), Ok("deadbeef".to_string()));
+        assert_eq!(from_base("DeadBeef", 16), Ok(0xdead_beef));
+        assert_eq!(to_base(u64::MAX, 16), Ok("ffffffffffffffff".to_string()));
+        assert_eq!(from_base("ffffffffffffffff", 16), Ok(u64::MAX));
+        let mut n = 1_u64;
+        while let Some(next) = n.checked_mul(7) {
+            let hex = to_base(n, 16).unwrap();
+            assert_eq!(hex, format!("{n:x}"));
+            assert_eq!(from_base(&hex.to_uppercase(), 16), Ok(n));
+            n = next;
+        }
+    }
+
+    #[test]
+    fn base_36() {
+        // the first half of a UUID, as a number
+        let n = 0x123e_4567_e89b_12d3;
+        let encoded = to_base(n, 36).unwrap();
+        assert_eq!(encoded, "9zjq7h474moj");
+        assert_eq!(from_base(&encoded, 36), Ok(n));
+        assert_eq!(to_base(35, 36), Ok("z".to_string()));
+        assert_eq!(to_base(36, 36), Ok("10".to_string()));
+        assert_eq!(from_base("ZZ", 36), Ok(1295));
+        assert_eq!(to_base(u64::MAX, 36), Ok("3w5e11264sgsf".to_string()));
+    }
+
+    #[test]
+    fn every_base() {
+        let numbers = [1, 2, 35, 36, 1000, 123_456_789, u32::MAX as u64, u64::MAX];
+        for base in 2..=36 {
+            for &n in &numbers {
+                let encoded = to_base(n, base).unwrap();
+                assert_eq!(u64::from_str_radix(&encoded, base as u32), Ok(n));
+                assert_eq!(from_base(&encoded, base), Ok(n));
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_base() {
+        for base in [0, 1, 37, 255] {
+            assert_eq!(to_base(5, base), Err(BaseError::InvalidBase));
+            assert_eq!(from_base("1", base), Err(BaseError::InvalidBase));
+        }
+    }
+
+    #[test]
+    fn invalid_digits() {
+        assert_eq!(from_base("102", 2), Err(BaseError::InvalidDigit('2')));
+        assert_eq!(from_base("12a", 10), Err(BaseError::InvalidDigit('a')));
+        assert_eq!(from_base("fg", 16), Err(BaseError::InvalidDigit('g')));
+        assert_eq!(from_base("-1", 10), Err(BaseError::InvalidDigit('-')));
+        assert_eq!(from_base(" 1", 10), Err(BaseError::InvalidDigit(' ')));
+        assert_eq!(from_base("١", 10), Err(BaseError::InvalidDigit('١')));
+        assert_eq!(from_base("", 10), Err(BaseError::Empty));
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(from_base("18446744073709551615", 10), Ok(u64::MAX));
+        assert_eq!(
+            from_base("18446744073709551616", 10),
+            Err(BaseError::Overflow)
+        );
+        assert_eq!(from_base(&"1".repeat(65), 2), Err(BaseError::Overflow));
+    }
+}
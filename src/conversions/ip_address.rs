@@ -0,0 +1,237 @@
+// An IPv4 address is a 32-bit number written as four decimal bytes separated by
+// dots, like 192.168.0.1, and an IPv6 address a 128-bit number written as eight
+// hexadecimal groups of 16 bits separated by colons.
+// Sources: https://en.wikipedia.org/wiki/IPv4#Address_representations
+// https://en.wikipedia.org/wiki/IPv6_address#Representation
+
+use std::fmt;
+
+/// The reasons a string is not an IP address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpError {
+    /// There are not four octets in an IPv4 address, or eight groups in an IPv6 one.
+    WrongPartCount,
+    /// An octet or a group is empty or has a character other than a digit.
+    InvalidPart,
+    /// An octet is larger than 255.
+    OutOfRange,
+    /// An octet other than 0 starts with 0, which some parsers read as octal.
+    LeadingZero,
+    /// An IPv6 address uses "::" more than once, so the zeros it stands for are ambiguous.
+    MultipleCompressions,
+}
+
+impl fmt::Display for IpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpError::WrongPartCount => write!(f, "wrong number of octets or groups"),
+            IpError::InvalidPart => write!(f, "invalid octet or group"),
+            IpError::OutOfRange => write!(f, "octet larger than 255"),
+            IpError::LeadingZero => write!(f, "octet with a leading zero"),
+            IpError::MultipleCompressions => write!(f, "\"::\" used more than once"),
+        }
+    }
+}
+
+impl std::error::Error for IpError {}
+
+fn parse_octet(octet: &str) -> Result<u32, IpError> {
+    if octet.is_empty() || !octet.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(IpError::InvalidPart);
+    }
+    if octet.len() > 1 && octet.starts_with('0') {
+        return Err(IpError::LeadingZero);
+    }
+    match octet.parse::<u32>() {
+        Ok(value) if value <= 255 => Ok(value),
+        _ => Err(IpError::OutOfRange),
+    }
+}
+
+/// Returns the number of the IPv4 address `addr` in dotted decimal notation,
+/// its first octet being the most significant byte.
+///
+/// The address must have exactly four octets of decimal digits, from 0 to 255,
+/// written without leading zeros.
+pub fn ipv4_to_u32(addr: &str) -> Result<u32, IpError> {
+    let octets: Vec<&str> = addr.split('.').collect();
+    if octets.len() != 4 {
+        return Err(IpError::WrongPartCount);
+    }
+    octets
+        .into_iter()
+        .try_fold(0, |n, octet| Ok(n << 8 | parse_octet(octet)?))
+}
+
+/// Returns the IPv4 address of `n` in dotted decimal notation.
+pub fn u32_to_ipv4(n: u32) -> String {
+    let [a, b, c, d] = n.to_be_bytes();
+    format!("{a}.{b}.{c}.{d}")
+}
+
+/// Returns the groups of 16 bits written in `part`, a part of an IPv6 address
+/// separated by colons, which may end with an IPv4 address for the last two
+/// groups when `last` is set.
+fn parse_groups(part: &str, last: bool) -> Result<Vec<u16>, IpError> {
+    if part.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut groups = Vec::new();
+    let fields: Vec<&str> = part.split(':').collect();
+    for (i, field) in fields.iter().enumerate() {
+        if last && i == fields.len() - 1 && field.contains('.') {
+            let ipv4 = ipv4_to_u32(field)?;
+            groups.extend([(ipv4 >> 16) as u16, ipv4 as u16]);
+        } else if (1..=4).contains(&field.len()) && field.bytes().all(|b| b.is_ascii_hexdigit()) {
+            groups.push(u16::from_str_radix(field, 16).unwrap());
+        } else {
+            return Err(IpError::InvalidPart);
+        }
+    }
+    Ok(groups)
+}
+
+/// Returns the number of the IPv6 address `addr`, its first group being the most
+/// significant one.
+///
+/// The address has eight groups of one to four hexadecimal digits, in upper or
+/// lower case, separated by colons. A single "::" may stand for one or more
+/// groups of zeros, and the last two groups may be written as an IPv4 address,
+/// as in "::ffff:192.0.2.1".
+pub fn ipv6_to_u128(addr: &str) -> Result<u128, IpError> {
+    let groups = match addr.split_once("::") {
+        Some((head, tail)) => {
+            if tail.contains("::") {
+                return Err(IpError::MultipleCompressions);
+            }
+            let head = parse_groups(head, false)?;
+            let tail = parse_groups(tail, true)?;
+            if head.len() + tail.len() > 7 {
+                return Err(IpError::WrongPartCount);
+            }
+            let zeros = vec![0; 8 - head.len() - tail.len()];
+            [head, zeros, tail].concat()
+        }
+        None => parse_groups(addr, true)?,
+    };
+    if groups.len() != 8 {
+        return Err(IpError::WrongPartCount);
+    }
+    Ok(groups
+        .into_iter()
+        .fold(0, |n, group| n << 16 | group as u128))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn known_ipv4_addresses() {
+        assert_eq!(ipv4_to_u32("127.0.0.1"), Ok(2_130_706_433));
+        assert_eq!(ipv4_to_u32("255.255.255.255"), Ok(u32::MAX));
+        assert_eq!(ipv4_to_u32("0.0.0.0"), Ok(0));
+        assert_eq!(ipv4_to_u32("192.168.1.10"), Ok(0xc0a8_010a));
+        assert_eq!(u32_to_ipv4(2_130_706_433), "127.0.0.1");
+        assert_eq!(u32_to_ipv4(u32::MAX), "255.255.255.255");
+        assert_eq!(u32_to_ipv4(0), "0.0.0.0");
+        assert_eq!(u32_to_ipv4(0x0a00_00ff), "10.0.0.255");
+    }
+
+    #[test]
+    fn invalid_ipv4_addresses() {
+        assert_eq!(ipv4_to_u32("1.2.3"), Err(IpError::WrongPartCount));
+        assert_eq!(ipv4_to_u32("1.2.3.4.5"), Err(IpError::WrongPartCount));
+        assert_eq!(ipv4_to_u32(""), Err(IpError::WrongPartCount));
+        assert_eq!(ipv4_to_u32("256.0.0.1"), Err(IpError::OutOfRange));
+        assert_eq!(ipv4_to_u32("1.2.3.99999999999"), Err(IpError::OutOfRange));
+        assert_eq!(ipv4_to_u32("01.2.3.4"), Err(IpError::LeadingZero));
+        assert_eq!(ipv4_to_u32("1.2.3.00"), Err(IpError::LeadingZero));
+        assert_eq!(ipv4_to_u32("1..3.4"), Err(IpError::InvalidPart));
+        assert_eq!(ipv4_to_u32("1.2.3.-4"), Err(IpError::InvalidPart));
+        assert_eq!(ipv4_to_u32("1.2.3.+4"), Err(IpError::InvalidPart));
+        assert_eq!(ipv4_to_u32("1.2.3.4 "), Err(IpError::InvalidPart));
+        assert_eq!(ipv4_to_u32("a.b.c.d"), Err(IpError::InvalidPart));
+    }
+
+    #[test]
+    fn ipv4_round_trip() {
+        let mut rng = PCG32::new_default(57);
+        for _ in 0..10_000 {
+            let n = rng.get_u32();
+            let addr = u32_to_ipv4(n);
+            assert_eq!(addr, Ipv4Addr::from(n).to_string());
+            assert_eq!(ipv4_to_u32(&addr), Ok(n));
+        }
+    }
+
+    #[test]
+    fn known_ipv6_addresses() {
+        assert_eq!(ipv6_to_u128("::1"), Ok(1));
+        assert_eq!(ipv6_to_u128("::"), Ok(0));
+        assert_eq!(ipv6_to_u128("1::"), Ok(1 << 112));
+        assert_eq!(ipv6_to_u128("0:0:0:0:0:0:0:1"), Ok(1));
+        assert_eq!(
+            ipv6_to_u128("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"),
+            Ok(u128::MAX)
+        );
+        assert_eq!(
+            ipv6_to_u128("2001:db8::ff00:42:8329"),
+            Ok(0x2001_0db8_0000_0000_0000_ff00_0042_8329)
+        );
+        assert_eq!(
+            ipv6_to_u128("2001:0DB8:0000:0000:0000:FF00:0042:8329"),
+            Ok(0x2001_0db8_0000_0000_0000_ff00_0042_8329)
+        );
+        // "::" standing for a single group
+        assert_eq!(
+            ipv6_to_u128("1:2:3::5:6:7:8"),
+            Ok(0x1_0002_0003_0000_0005_0006_0007_0008)
+        );
+        assert_eq!(ipv6_to_u128("::ffff:192.0.2.1"), Ok(0xffff_c000_0201));
+    }
+
+    #[test]
+    fn invalid_ipv6_addresses() {
+        assert_eq!(ipv6_to_u128("1::2::3"), Err(IpError::MultipleCompressions));
+        assert_eq!(ipv6_to_u128(":::"), Err(IpError::InvalidPart));
+        assert_eq!(ipv6_to_u128("1:2:3:4:5:6:7"), Err(IpError::WrongPartCount));
+        assert_eq!(
+            ipv6_to_u128("1:2:3:4:5:6:7:8:9"),
+            Err(IpError::WrongPartCount)
+        );
+        assert_eq!(
+            ipv6_to_u128("1:2:3:4::5:6:7:8"),
+            Err(IpError::WrongPartCount)
+        );
+        assert_eq!(ipv6_to_u128("12345::"), Err(IpError::InvalidPart));
+        assert_eq!(ipv6_to_u128("g::"), Err(IpError::InvalidPart));
+        assert_eq!(ipv6_to_u128(":1:2:3:4:5:6:7"), Err(IpError::InvalidPart));
+        assert_eq!(ipv6_to_u128("1:2:3:4:5:6:7:"), Err(IpError::InvalidPart));
+        assert_eq!(ipv6_to_u128(""), Err(IpError::WrongPartCount));
+        assert_eq!(ipv6_to_u128("1.2.3.4::"), Err(IpError::InvalidPart));
+        assert_eq!(ipv6_to_u128("::256.0.0.1"), Err(IpError::OutOfRange));
+    }
+
+    #[test]
+    fn matches_the_standard_library() {
+        let mut rng = PCG32::new_default(75);
+        for _ in 0..10_000 {
+            // many zero groups, to make "::" appear at various places
+            let n = (0..8).fold(0_u128, |n, _| {
+                let group = if rng.get_u32().is_multiple_of(2) {
+                    0
+                } else {
+                    rng.get_u32() as u16
+                };
+                n << 16 | group as u128
+            });
+            let addr = Ipv6Addr::from(n);
+            assert_eq!(ipv6_to_u128(&addr.to_string()), Ok(n), "{addr}");
+            let full: Vec<String> = addr.segments().iter().map(|g| format!("{g:X}")).collect();
+            assert_eq!(ipv6_to_u128(&full.join(":")), Ok(n), "{addr}");
+        }
+    }
+}
@@ -1,3 +1,22 @@
+mod base_conversion;
 mod decimal_to_binary;
+mod ip_address;
+mod rgb_cmyk_conversion;
+mod rgb_hsl_conversion;
+mod rgb_hsv_conversion;
+mod roman_numerals;
+mod temperature;
 
+pub use self::base_conversion::{from_base, to_base, BaseError};
 pub use self::decimal_to_binary::decimal_to_binary;
+pub use self::ip_address::{ipv4_to_u32, ipv6_to_u128, u32_to_ipv4, IpError};
+pub use self::rgb_cmyk_conversion::{cmyk_to_rgb, rgb_to_cmyk};
+pub use self::rgb_hsl_conversion::{hsl_to_rgb, rgb_to_hsl};
+pub use self::rgb_hsv_conversion::{hsv_to_rgb, rgb_to_hsv};
+pub use self::roman_numerals::{from_roman, to_roman, RomanError};
+pub use self::temperature::{
+    celsius_to_fahrenheit, celsius_to_kelvin, celsius_to_rankine, fahrenheit_to_celsius,
+    fahrenheit_to_kelvin, fahrenheit_to_rankine, kelvin_to_celsius, kelvin_to_fahrenheit,
+    kelvin_to_rankine, rankine_to_celsius, rankine_to_fahrenheit, rankine_to_kelvin,
+    TemperatureError,
+};
@@ -0,0 +1,76 @@
+// CMYK describes a color by the amounts of cyan, magenta, yellow and black ink
+// printed on white paper, each one absorbing part of the light. Here they are
+// percentages, from 0 to 100.
+// Source: https://en.wikipedia.org/wiki/CMYK_color_model
+
+/// Converts a color given by its red, green and blue components to its cyan,
+/// magenta, yellow and black percentages, rounded to the closest integers.
+///
+/// The black ink makes up for the largest component, and the other inks for the
+/// rest of each component, so that at least one of them is 0. Black is
+/// (0, 0, 0, 100).
+pub fn rgb_to_cmyk((r, g, b): (u8, u8, u8)) -> (u8, u8, u8, u8) {
+    let max = r.max(g).max(b) as f64;
+    if max == 0.0 {
+        return (0, 0, 0, 100);
+    }
+    let ink = |c: u8| (100.0 * (max - c as f64) / max).round() as u8;
+    let black = (100.0 * (255.0 - max) / 255.0).round() as u8;
+    (ink(r), ink(g), ink(b), black)
+}
+
+/// Converts a color given by its cyan, magenta, yellow and black percentages to
+/// its red, green and blue components, rounded to the closest integers.
+///
+/// It is the inverse of `rgb_to_cmyk`, up to the rounding of the percentages,
+/// which can take a component up to 3 away from the original one. Percentages
+/// above 100 are taken to be 100.
+pub fn cmyk_to_rgb((c, m, y, k): (u8, u8, u8, u8)) -> (u8, u8, u8) {
+    let white = 255.0 * (1.0 - k.min(100) as f64 / 100.0);
+    let component = |ink: u8| (white * (1.0 - ink.min(100) as f64 / 100.0)).round() as u8;
+    (component(c), component(m), component(y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_colors() {
+        assert_eq!(rgb_to_cmyk((255, 255, 255)), (0, 0, 0, 0));
+        assert_eq!(rgb_to_cmyk((0, 0, 0)), (0, 0, 0, 100));
+        assert_eq!(rgb_to_cmyk((255, 0, 0)), (0, 100, 100, 0));
+        assert_eq!(rgb_to_cmyk((0, 255, 255)), (100, 0, 0, 0));
+        assert_eq!(rgb_to_cmyk((128, 128, 128)), (0, 0, 0, 50));
+        assert_eq!(rgb_to_cmyk((255, 200, 0)), (0, 22, 100, 0));
+        assert_eq!(rgb_to_cmyk((51, 102, 153)), (67, 33, 0, 40));
+    }
+
+    #[test]
+    fn known_colors_back() {
+        assert_eq!(cmyk_to_rgb((0, 0, 0, 0)), (255, 255, 255));
+        assert_eq!(cmyk_to_rgb((0, 0, 0, 100)), (0, 0, 0));
+        // the black ink covers the other ones
+        assert_eq!(cmyk_to_rgb((30, 60, 90, 100)), (0, 0, 0));
+        assert_eq!(cmyk_to_rgb((0, 100, 100, 0)), (255, 0, 0));
+        assert_eq!(cmyk_to_rgb((0, 0, 0, 50)), (128, 128, 128));
+        assert_eq!(cmyk_to_rgb((100, 50, 0, 20)), (0, 102, 204));
+        assert_eq!(cmyk_to_rgb((250, 0, 0, 0)), (0, 255, 255));
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(3) {
+            for g in (0..=255).step_by(3) {
+                for b in (0..=255).step_by(3) {
+                    let (c, m, y, k) = rgb_to_cmyk((r, g, b));
+                    assert!(c.min(m).min(y) == 0 && c.max(m).max(y).max(k) <= 100);
+                    let back = cmyk_to_rgb((c, m, y, k));
+                    assert!(r.abs_diff(back.0) <= 3, "{:?} {back:?}", (r, g, b));
+                    assert!(g.abs_diff(back.1) <= 3, "{:?} {back:?}", (r, g, b));
+                    assert!(b.abs_diff(back.2) <= 3, "{:?} {back:?}", (r, g, b));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+// HSL describes a color by its hue, the angle of its position on the color
+// wheel in degrees, its saturation, and its lightness, going from black through
+// the pure color to white.
+// Source: https://en.wikipedia.org/wiki/HSL_and_HSV
+
+/// Returns the hue of the color in degrees, in [0, 360).
+///
+/// Gray colors have no hue, which is taken to be 0.
+pub(super) fn hue((r, g, b): (u8, u8, u8)) -> f64 {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    if max == min {
+        return 0.0;
+    }
+    let (r, g, b) = (r as f64, g as f64, b as f64);
+    let chroma = (max - min) as f64;
+    // the hue is the angle from the largest component, whose color is 120
+    // degrees from the other two, towards the second largest one
+    let sector = if max as f64 == r {
+        ((g - b) / chroma).rem_euclid(6.0)
+    } else if max as f64 == g {
+        (b - r) / chroma + 2.0
+    } else {
+        (r - g) / chroma + 4.0
+    };
+    (60.0 * sector) % 360.0
+}
+
+/// Returns the color with the given hue in degrees, taken modulo 360, and
+/// chroma, the difference between its largest and smallest components, whose
+/// smallest component is `min`.
+pub(super) fn from_hue_and_chroma(hue: f64, chroma: f64, min: f64) -> (u8, u8, u8) {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    // the second largest component
+    let second = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second),
+    };
+    let to_u8 = |c: f64| ((c + min) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (to_u8(r), to_u8(g), to_u8(b))
+}
+
+/// Converts a color given by its red, green and blue components to its hue in
+/// degrees, in [0, 360), saturation, in [0, 1], and lightness, in [0, 1].
+///
+/// The lightness is the mean of the largest and smallest components, and the
+/// saturation is the chroma relative to the largest one possible at that
+/// lightness. Grays, white and black included, have a saturation of 0 and,
+/// by convention, a hue of 0.
+pub fn rgb_to_hsl(rgb: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = rgb;
+    let (max, min) = (r.max(g).max(b) as i32, r.min(g).min(b) as i32);
+    let lightness = (max + min) as f64 / 510.0;
+    // computed on the integers, so that the largest chroma gives exactly 1
+    let largest_chroma = 255 - (max + min - 255).abs();
+    let saturation = if max == min {
+        0.0
+    } else {
+        (max - min) as f64 / largest_chroma as f64
+    };
+    (hue(rgb), saturation, lightness)
+}
+
+/// Converts a color given by its hue in degrees, saturation and lightness to
+/// its red, green and blue components, rounded to the closest integers.
+///
+/// The hue is taken modulo 360, and the saturation and lightness are clamped to
+/// [0, 1].
+pub fn hsl_to_rgb((hue, saturation, lightness): (f64, f64, f64)) -> (u8, u8, u8) {
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    from_hue_and_chroma(hue, chroma, lightness - chroma / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    fn assert_close(actual: (f64, f64, f64), expected: (f64, f64, f64)) {
+        let close = (actual.0 - expected.0).abs() < 1e-9
+            && (actual.1 - expected.1).abs() < 1e-9
+            && (actual.2 - expected.2).abs() < 1e-9;
+        assert!(close, "{actual:?} is not {expected:?}");
+    }
+
+    #[test]
+    fn known_colors() {
+        assert_eq!(rgb_to_hsl((255, 0, 0)), (0.0, 1.0, 0.5));
+        assert_eq!(rgb_to_hsl((255, 255, 255)), (0.0, 0.0, 1.0));
+        assert_eq!(rgb_to_hsl((0, 0, 0)), (0.0, 0.0, 0.0));
+        assert_eq!(rgb_to_hsl((0, 255, 0)), (120.0, 1.0, 0.5));
+        assert_eq!(rgb_to_hsl((0, 0, 255)), (240.0, 1.0, 0.5));
+        assert_eq!(rgb_to_hsl((255, 255, 0)), (60.0, 1.0, 0.5));
+        assert_eq!(rgb_to_hsl((255, 0, 255)), (300.0, 1.0, 0.5));
+        assert_close(rgb_to_hsl((128, 128, 128)), (0.0, 0.0, 128.0 / 255.0));
+        // teal, and a dark desaturated orange
+        assert_close(rgb_to_hsl((0, 128, 128)), (180.0, 1.0, 64.0 / 255.0));
+        assert_close(rgb_to_hsl((153, 102, 51)), (30.0, 0.5, 0.4));
+    }
+
+    #[test]
+    fn known_colors_back() {
+        assert_eq!(hsl_to_rgb((0.0, 1.0, 0.5)), (255, 0, 0));
+        assert_eq!(hsl_to_rgb((0.0, 0.0, 1.0)), (255, 255, 255));
+        assert_eq!(hsl_to_rgb((0.0, 0.0, 0.0)), (0, 0, 0));
+        assert_eq!(hsl_to_rgb((120.0, 1.0, 0.5)), (0, 255, 0));
+        assert_eq!(hsl_to_rgb((240.0, 1.0, 0.25)), (0, 0, 128));
+        assert_eq!(hsl_to_rgb((30.0, 0.5, 0.4)), (153, 102, 51));
+        // the hue of a gray does not matter
+        assert_eq!(hsl_to_rgb((77.0, 0.0, 0.5)), (128, 128, 128));
+    }
+
+    #[test]
+    fn out_of_range_inputs() {
+        assert_eq!(hsl_to_rgb((360.0, 1.0, 0.5)), (255, 0, 0));
+        assert_eq!(hsl_to_rgb((-120.0, 1.0, 0.5)), (0, 0, 255));
+        assert_eq!(hsl_to_rgb((480.0, 1.0, 0.5)), (0, 255, 0));
+        assert_eq!(hsl_to_rgb((0.0, 2.0, 0.5)), (255, 0, 0));
+        assert_eq!(hsl_to_rgb((0.0, 1.0, 1.5)), (255, 255, 255));
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = PCG32::new_default(52);
+        for _ in 0..10_000 {
+            let rgb = (
+                rng.get_u32() as u8,
+                rng.get_u32() as u8,
+                rng.get_u32() as u8,
+            );
+            let (h, s, l) = rgb_to_hsl(rgb);
+            assert!((0.0..360.0).contains(&h), "{rgb:?}");
+            assert!((0.0..=1.0).contains(&s), "{rgb:?}");
+            assert!((0.0..=1.0).contains(&l), "{rgb:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(5) {
+            for g in (0..=255).step_by(5) {
+                for b in (0..=255).step_by(5) {
+                    assert_eq!(hsl_to_rgb(rgb_to_hsl((r, g, b))), (r, g, b));
+                }
+            }
+        }
+        let mut rng = PCG32::new_default(25);
+        for _ in 0..100_000 {
+            let rgb = (
+                rng.get_u32() as u8,
+                rng.get_u32() as u8,
+                rng.get_u32() as u8,
+            );
+            assert_eq!(hsl_to_rgb(rgb_to_hsl(rgb)), rgb);
+        }
+    }
+}
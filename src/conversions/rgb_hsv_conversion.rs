@@ -0,0 +1,110 @@
+// HSV describes a color by its hue, the angle of its position on the color
+// wheel in degrees, its saturation, and its value, the brightness of its
+// largest component. Unlike the lightness of HSL, a value of 1 is the pure
+// color rather than white.
+// Source: https://en.wikipedia.org/wiki/HSL_and_HSV
+
+use super::rgb_hsl_conversion::{from_hue_and_chroma, hue};
+
+/// Converts a color given by its red, green and blue components to its hue in
+/// degrees, in [0, 360), saturation, in [0, 1], and value, in [0, 1].
+///
+/// The value is the largest component, and the saturation is the chroma, the
+/// difference between the largest and smallest components, relative to it.
+/// Grays, white and black included, have a saturation of 0 and, by convention,
+/// a hue of 0.
+pub fn rgb_to_hsv(rgb: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = rgb;
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    let saturation = if max == min {
+        0.0
+    } else {
+        (max - min) as f64 / max as f64
+    };
+    (hue(rgb), saturation, max as f64 / 255.0)
+}
+
+/// Converts a color given by its hue in degrees, saturation and value to its
+/// red, green and blue components, rounded to the closest integers.
+///
+/// The hue is taken modulo 360, and the saturation and value are clamped to
+/// [0, 1].
+pub fn hsv_to_rgb((hue, saturation, value): (f64, f64, f64)) -> (u8, u8, u8) {
+    let saturation = saturation.clamp(0.0, 1.0);
+    let value = value.clamp(0.0, 1.0);
+    let chroma = value * saturation;
+    from_hue_and_chroma(hue, chroma, value - chroma)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversions::rgb_to_hsl;
+    use crate::math::PCG32;
+
+    #[test]
+    fn known_colors() {
+        assert_eq!(rgb_to_hsv((255, 0, 0)), (0.0, 1.0, 1.0));
+        assert_eq!(rgb_to_hsv((0, 0, 0)), (0.0, 0.0, 0.0));
+        assert_eq!(rgb_to_hsv((255, 255, 255)), (0.0, 0.0, 1.0));
+        let (h, s, v) = rgb_to_hsv((128, 128, 128));
+        assert_eq!((h, s), (0.0, 0.0));
+        assert!((v - 0.5).abs() < 0.005);
+        assert_eq!(rgb_to_hsv((0, 255, 0)), (120.0, 1.0, 1.0));
+        assert_eq!(rgb_to_hsv((0, 0, 255)), (240.0, 1.0, 1.0));
+        assert_eq!(rgb_to_hsv((255, 255, 0)), (60.0, 1.0, 1.0));
+        assert_eq!(rgb_to_hsv((0, 51, 102)), (210.0, 1.0, 0.4));
+        assert_eq!(rgb_to_hsv((204, 102, 153)), (330.0, 0.5, 0.8));
+    }
+
+    #[test]
+    fn known_colors_back() {
+        assert_eq!(hsv_to_rgb((0.0, 1.0, 1.0)), (255, 0, 0));
+        assert_eq!(hsv_to_rgb((0.0, 0.0, 0.0)), (0, 0, 0));
+        assert_eq!(hsv_to_rgb((0.0, 0.0, 0.5)), (128, 128, 128));
+        assert_eq!(hsv_to_rgb((210.0, 1.0, 0.4)), (0, 51, 102));
+        assert_eq!(hsv_to_rgb((330.0, 0.5, 0.8)), (204, 102, 153));
+        assert_eq!(hsv_to_rgb((-30.0, 0.5, 0.8)), (204, 102, 153));
+        assert_eq!(hsv_to_rgb((0.0, 1.5, 2.0)), (255, 0, 0));
+    }
+
+    #[test]
+    fn value_is_not_lightness() {
+        // a pure color has the largest value but only half the lightness of white
+        let red = (255, 0, 0);
+        assert_eq!(rgb_to_hsv(red).2, 1.0);
+        assert_eq!(rgb_to_hsl(red).2, 0.5);
+        // they share the hue
+        let mut rng = PCG32::new_default(53);
+        for _ in 0..1000 {
+            let rgb = (
+                rng.get_u32() as u8,
+                rng.get_u32() as u8,
+                rng.get_u32() as u8,
+            );
+            assert_eq!(rgb_to_hsv(rgb).0, rgb_to_hsl(rgb).0);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(5) {
+            for g in (0..=255).step_by(5) {
+                for b in (0..=255).step_by(5) {
+                    let (h, s, v) = rgb_to_hsv((r, g, b));
+                    assert!((0.0..360.0).contains(&h) && (0.0..=1.0).contains(&s));
+                    assert_eq!(hsv_to_rgb((h, s, v)), (r, g, b));
+                }
+            }
+        }
+        let mut rng = PCG32::new_default(35);
+        for _ in 0..100_000 {
+            let rgb = (
+                rng.get_u32() as u8,
+                rng.get_u32() as u8,
+                rng.get_u32() as u8,
+            );
+            assert_eq!(hsv_to_rgb(rgb_to_hsv(rgb)), rgb);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+// Roman numerals write a number with the letters I, V, X, L, C, D and M, worth
+// 1, 5, 10, 50, 100, 500 and 1000, from the largest to the smallest. A letter
+// placed before a larger one is subtracted from it instead, which is only done
+// for IV, IX, XL, XC, CD and CM, so that no letter is repeated more than three
+// times in a row. That makes 3999, MMMCMXCIX, the largest number they can write.
+// Source: https://en.wikipedia.org/wiki/Roman_numerals
+
+use std::fmt;
+
+/// The reasons a number cannot be converted to or from a Roman numeral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RomanError {
+    /// The number is not in 1..=3999.
+    OutOfRange,
+    /// The string is not a Roman numeral in standard form.
+    InvalidInput,
+}
+
+impl fmt::Display for RomanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RomanError::OutOfRange => write!(f, "number out of the range 1 to 3999"),
+            RomanError::InvalidInput => write!(f, "invalid Roman numeral"),
+        }
+    }
+}
+
+impl std::error::Error for RomanError {}
+
+const NUMERALS: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+/// Returns the Roman numeral of `n`, in upper case, or
+/// [`RomanError::OutOfRange`] if `n` is not in 1..=3999.
+pub fn to_roman(n: u32) -> Result<String, RomanError> {
+    if !(1..=3999).contains(&n) {
+        return Err(RomanError::OutOfRange);
+    }
+    let mut rest = n;
+    let mut roman = String::new();
+    for &(value, numeral) in &NUMERALS {
+        while rest >= value {
+            roman.push_str(numeral);
+            rest -= value;
+        }
+    }
+    Ok(roman)
+}
+
+fn letter_value(letter: char) -> Option<u32> {
+    match letter {
+        'I' => Some(1),
+        'V' => Some(5),
+        'X' => Some(10),
+        'L' => Some(50),
+        'C' => Some(100),
+        'D' => Some(500),
+        'M' => Some(1000),
+        _ => None,
+    }
+}
+
+/// Returns the number written by the Roman numeral `s`, in upper or lower case.
+///
+/// Only the standard form is accepted, the one `to_roman` writes: strings such
+/// as "IIX", "IIII", "VX" or "IC" are rejected with [`RomanError::InvalidInput`].
+/// Numerals that would be standard but for starting with more than three Ms are
+/// rejected with [`RomanError::OutOfRange`].
+pub fn from_roman(s: &str) -> Result<u32, RomanError> {
+    let upper = s.to_ascii_uppercase();
+    let letters: Vec<u32> = upper
+        .chars()
+        .map(letter_value)
+        .collect::<Option<_>>()
+        .ok_or(RomanError::InvalidInput)?;
+
+    let mut value = 0;
+    for (i, &letter) in letters.iter().enumerate() {
+        match letters.get(i + 1) {
+            Some(&next) if next > letter => value -= letter as i64,
+            _ => value += letter as i64,
+        }
+    }
+
+    let thousands = upper.bytes().take_while(|&b| b == b'M').count();
+    let below_thousand = (value - 1000 * thousands as i64) as u32;
+    // reading back the standard form rules out any other way of writing the value
+    let standard = match below_thousand {
+        0 => String::new(),
+        1..=999 => to_roman(below_thousand)?,
+        _ => return Err(RomanError::InvalidInput),
+    };
+    if upper[thousands..] != standard || upper.is_empty() {
+        return Err(RomanError::InvalidInput);
+    }
+    if thousands > 3 {
+        return Err(RomanError::OutOfRange);
+    }
+    Ok(value as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_numbers() {
+        assert_eq!(to_roman(1).unwrap(), "I");
+        assert_eq!(to_roman(3).unwrap(), "III");
+        assert_eq!(to_roman(14).unwrap(), "XIV");
+        assert_eq!(to_roman(1994).unwrap(), "MCMXCIV");
+        assert_eq!(to_roman(2024).unwrap(), "MMXXIV");
+        assert_eq!(to_roman(3888).unwrap(), "MMMDCCCLXXXVIII");
+        assert_eq!(to_roman(3999).unwrap(), "MMMCMXCIX");
+        assert_eq!(from_roman("MCMXCIV"), Ok(1994));
+        assert_eq!(from_roman("MMMDCCCLXXXVIII"), Ok(3888));
+    }
+
+    #[test]
+    fn subtractive_pairs() {
+        let pairs = [
+            ("IV", 4),
+            ("IX", 9),
+            ("XL", 40),
+            ("XC", 90),
+            ("CD", 400),
+            ("CM", 900),
+        ];
+        for (numeral, value) in pairs {
+            assert_eq!(to_roman(value).unwrap(), numeral);
+            assert_eq!(from_roman(numeral), Ok(value));
+        }
+        assert_eq!(to_roman(444).unwrap(), "CDXLIV");
+        assert_eq!(to_roman(999).unwrap(), "CMXCIX");
+        assert_eq!(from_roman("CMXCIX"), Ok(999));
+    }
+
+    #[test]
+    fn round_trip() {
+        for n in 1..=3999 {
+            let roman = to_roman(n).unwrap();
+            assert_eq!(from_roman(&roman), Ok(n), "{roman}");
+            assert_eq!(from_roman(&roman.to_lowercase()), Ok(n), "{roman}");
+        }
+    }
+
+    #[test]
+    fn lowercase() {
+        assert_eq!(from_roman("xiv"), Ok(14));
+        assert_eq!(from_roman("mCmXcIv"), Ok(1994));
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(to_roman(0), Err(RomanError::OutOfRange));
+        assert_eq!(to_roman(4000), Err(RomanError::OutOfRange));
+        assert_eq!(to_roman(u32::MAX), Err(RomanError::OutOfRange));
+        assert_eq!(from_roman("MMMM"), Err(RomanError::OutOfRange));
+        assert_eq!(from_roman("MMMMCMXCIX"), Err(RomanError::OutOfRange));
+    }
+
+    #[test]
+    fn invalid_numerals() {
+        for invalid in [
+            "", "IIX", "IIII", "VV", "VX", "IC", "IL", "XM", "XCX", "CMD", "IVI", "DM", "MCMC",
+            "LXL", "XIIII", "ABC", "X I", "12", "Ⅻ",
+        ] {
+            assert_eq!(
+                from_roman(invalid),
+                Err(RomanError::InvalidInput),
+                "{invalid}"
+            );
+        }
+    }
+}
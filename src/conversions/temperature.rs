@@ -0,0 +1,175 @@
+// Conversions between the Celsius, Fahrenheit, Kelvin and Rankine temperature
+// scales. Kelvin and Rankine start at absolute zero, with degrees the size of
+// Celsius and Fahrenheit ones respectively, and a Fahrenheit degree is 5/9 of a
+// Celsius one. Every conversion rejects a temperature below absolute zero on
+// the scale it converts from.
+// Source: https://en.wikipedia.org/wiki/Conversion_of_scales_of_temperature
+
+use std::fmt;
+
+/// The reason a temperature cannot be converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureError {
+    /// The temperature is colder than absolute zero, 0 K.
+    BelowAbsoluteZero,
+}
+
+impl fmt::Display for TemperatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemperatureError::BelowAbsoluteZero => write!(f, "temperature below absolute zero"),
+        }
+    }
+}
+
+impl std::error::Error for TemperatureError {}
+
+// 0 °C in kelvins, and absolute zero in degrees Celsius and Fahrenheit
+const ZERO_CELSIUS_IN_KELVIN: f64 = 273.15;
+const ABSOLUTE_ZERO_CELSIUS: f64 = -ZERO_CELSIUS_IN_KELVIN;
+const ABSOLUTE_ZERO_FAHRENHEIT: f64 = -459.67;
+
+/// Returns `temperature` if it is not below `absolute_zero`, NaN being rejected too.
+fn checked(temperature: f64, absolute_zero: f64) -> Result<f64, TemperatureError> {
+    if temperature >= absolute_zero {
+        Ok(temperature)
+    } else {
+        Err(TemperatureError::BelowAbsoluteZero)
+    }
+}
+
+pub fn celsius_to_fahrenheit(c: f64) -> Result<f64, TemperatureError> {
+    checked(c, ABSOLUTE_ZERO_CELSIUS).map(|c| c * 9.0 / 5.0 + 32.0)
+}
+
+pub fn fahrenheit_to_celsius(f: f64) -> Result<f64, TemperatureError> {
+    checked(f, ABSOLUTE_ZERO_FAHRENHEIT).map(|f| (f - 32.0) * 5.0 / 9.0)
+}
+
+pub fn celsius_to_kelvin(c: f64) -> Result<f64, TemperatureError> {
+    checked(c, ABSOLUTE_ZERO_CELSIUS).map(|c| c + ZERO_CELSIUS_IN_KELVIN)
+}
+
+pub fn kelvin_to_celsius(k: f64) -> Result<f64, TemperatureError> {
+    checked(k, 0.0).map(|k| k - ZERO_CELSIUS_IN_KELVIN)
+}
+
+pub fn celsius_to_rankine(c: f64) -> Result<f64, TemperatureError> {
+    checked(c, ABSOLUTE_ZERO_CELSIUS).map(|c| (c + ZERO_CELSIUS_IN_KELVIN) * 9.0 / 5.0)
+}
+
+pub fn rankine_to_celsius(r: f64) -> Result<f64, TemperatureError> {
+    checked(r, 0.0).map(|r| r * 5.0 / 9.0 - ZERO_CELSIUS_IN_KELVIN)
+}
+
+pub fn fahrenheit_to_kelvin(f: f64) -> Result<f64, TemperatureError> {
+    checked(f, ABSOLUTE_ZERO_FAHRENHEIT).map(|f| (f - ABSOLUTE_ZERO_FAHRENHEIT) * 5.0 / 9.0)
+}
+
+pub fn kelvin_to_fahrenheit(k: f64) -> Result<f64, TemperatureError> {
+    checked(k, 0.0).map(|k| k * 9.0 / 5.0 + ABSOLUTE_ZERO_FAHRENHEIT)
+}
+
+pub fn fahrenheit_to_rankine(f: f64) -> Result<f64, TemperatureError> {
+    checked(f, ABSOLUTE_ZERO_FAHRENHEIT).map(|f| f - ABSOLUTE_ZERO_FAHRENHEIT)
+}
+
+pub fn rankine_to_fahrenheit(r: f64) -> Result<f64, TemperatureError> {
+    checked(r, 0.0).map(|r| r + ABSOLUTE_ZERO_FAHRENHEIT)
+}
+
+pub fn kelvin_to_rankine(k: f64) -> Result<f64, TemperatureError> {
+    checked(k, 0.0).map(|k| k * 9.0 / 5.0)
+}
+
+pub fn rankine_to_kelvin(r: f64) -> Result<f64, TemperatureError> {
+    checked(r, 0.0).map(|r| r * 5.0 / 9.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Result<f64, TemperatureError>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - expected).abs() <= 1e-12 * expected.abs().max(1.0),
+            "{actual} is not {expected}"
+        );
+    }
+
+    #[test]
+    fn water_freezes() {
+        assert_eq!(celsius_to_fahrenheit(0.0), Ok(32.0));
+        assert_eq!(celsius_to_kelvin(0.0), Ok(273.15));
+        assert_eq!(fahrenheit_to_celsius(32.0), Ok(0.0));
+        assert_eq!(kelvin_to_celsius(273.15), Ok(0.0));
+        assert_close(fahrenheit_to_kelvin(32.0), 273.15);
+        assert_close(kelvin_to_fahrenheit(273.15), 32.0);
+        assert_close(celsius_to_rankine(0.0), 491.67);
+        assert_close(fahrenheit_to_rankine(32.0), 491.67);
+    }
+
+    #[test]
+    fn water_boils() {
+        assert_eq!(celsius_to_fahrenheit(100.0), Ok(212.0));
+        assert_eq!(celsius_to_kelvin(100.0), Ok(373.15));
+        assert_eq!(fahrenheit_to_celsius(212.0), Ok(100.0));
+        assert_close(kelvin_to_celsius(373.15), 100.0);
+        assert_close(fahrenheit_to_kelvin(212.0), 373.15);
+        assert_close(kelvin_to_fahrenheit(373.15), 212.0);
+        assert_close(rankine_to_celsius(671.67), 100.0);
+        assert_close(kelvin_to_rankine(373.15), 671.67);
+    }
+
+    #[test]
+    fn absolute_zero() {
+        assert_eq!(kelvin_to_celsius(0.0), Ok(-273.15));
+        assert_eq!(kelvin_to_fahrenheit(0.0), Ok(-459.67));
+        assert_eq!(kelvin_to_rankine(0.0), Ok(0.0));
+        assert_eq!(celsius_to_kelvin(-273.15), Ok(0.0));
+        assert_eq!(fahrenheit_to_rankine(-459.67), Ok(0.0));
+        assert_eq!(rankine_to_kelvin(0.0), Ok(0.0));
+        // -40 is the same on both scales
+        assert_eq!(celsius_to_fahrenheit(-40.0), Ok(-40.0));
+        assert_eq!(fahrenheit_to_celsius(-40.0), Ok(-40.0));
+    }
+
+    #[test]
+    fn below_absolute_zero() {
+        let error = Err(TemperatureError::BelowAbsoluteZero);
+        assert_eq!(celsius_to_fahrenheit(-273.16), error);
+        assert_eq!(celsius_to_kelvin(-300.0), error);
+        assert_eq!(celsius_to_rankine(-273.2), error);
+        assert_eq!(fahrenheit_to_celsius(-460.0), error);
+        assert_eq!(fahrenheit_to_kelvin(-459.68), error);
+        assert_eq!(fahrenheit_to_rankine(-1000.0), error);
+        assert_eq!(kelvin_to_celsius(-0.01), error);
+        assert_eq!(kelvin_to_fahrenheit(-1.0), error);
+        assert_eq!(kelvin_to_rankine(-1e-9), error);
+        assert_eq!(rankine_to_celsius(-1.0), error);
+        assert_eq!(rankine_to_fahrenheit(-0.5), error);
+        assert_eq!(rankine_to_kelvin(-2.0), error);
+        assert_eq!(celsius_to_kelvin(f64::NAN), error);
+    }
+
+    #[test]
+    fn round_trips() {
+        for i in 0..=2000 {
+            let k = i as f64 * 0.731;
+            let c = k - 273.15;
+            let f = c * 1.8 + 32.0;
+            assert_close(fahrenheit_to_celsius(celsius_to_fahrenheit(c).unwrap()), c);
+            assert_close(kelvin_to_celsius(celsius_to_kelvin(c).unwrap()), c);
+            assert_close(rankine_to_celsius(celsius_to_rankine(c).unwrap()), c);
+            assert_close(kelvin_to_fahrenheit(fahrenheit_to_kelvin(f).unwrap()), f);
+            assert_close(rankine_to_fahrenheit(fahrenheit_to_rankine(f).unwrap()), f);
+            assert_close(rankine_to_kelvin(kelvin_to_rankine(k).unwrap()), k);
+            // going around through every scale
+            let around = rankine_to_kelvin(
+                fahrenheit_to_rankine(celsius_to_fahrenheit(c).unwrap()).unwrap(),
+            );
+            assert_close(around, k);
+        }
+    }
+}
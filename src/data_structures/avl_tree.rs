@@ -84,6 +84,38 @@ impl<T: Ord> AVLTree<T> {
         self.length == 0
     }
 
+    /// Returns the smallest value in the tree.
+    pub fn min(&self) -> Option<&T> {
+        self.extreme(Side::Left)
+    }
+
+    /// Returns the largest value in the tree.
+    pub fn max(&self) -> Option<&T> {
+        self.extreme(Side::Right)
+    }
+
+    /// Returns the value at the end of the path that always follows `side`.
+    fn extreme(&self, side: Side) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(child) = node.child(side) {
+            node = child;
+        }
+        Some(&node.value)
+    }
+
+    /// Returns the number of nodes on the longest path from the root to a leaf.
+    pub fn height(&self) -> usize {
+        self.root.as_ref().map_or(0, |n| n.height)
+    }
+
+    /// Returns `true` if the heights of the two subtrees of every node differ by at most 1.
+    ///
+    /// The heights are recomputed from scratch rather than read from the nodes,
+    /// so this also catches nodes whose stored height has gone stale.
+    pub fn is_balanced(&self) -> bool {
+        checked_height(&self.root).is_some()
+    }
+
     /// Returns an iterator that visits the nodes in the tree in order.
     fn node_iter(&self) -> NodeIter<T> {
         let cap = self.root.as_ref().map_or(0, |n| n.height);
@@ -107,6 +139,20 @@ impl<T: Ord> AVLTree<T> {
     }
 }
 
+/// Returns the height of a subtree, or `None` if any of its nodes is unbalanced
+/// or has a stored height that does not match its actual height.
+fn checked_height<T: Ord>(tree: &Option<Box<AVLNode<T>>>) -> Option<usize> {
+    match tree {
+        None => Some(0),
+        Some(node) => {
+            let left = checked_height(&node.left)?;
+            let right = checked_height(&node.right)?;
+            let height = 1 + max(left, right);
+            (left.abs_diff(right) <= 1 && node.height == height).then_some(height)
+        }
+    }
+}
+
 /// Recursive helper function for `AVLTree` insertion.
 fn insert<T: Ord>(tree: &mut Option<Box<AVLNode<T>>>, value: T) -> bool {
     if let Some(node) = tree {
@@ -324,12 +370,7 @@ impl<'a, T: Ord> Iterator for Iter<'a, T> {
 #[cfg(test)]
 mod tests {
     use super::AVLTree;
-
-    /// Returns `true` if all nodes in the tree are balanced.
-    fn is_balanced<T: Ord>(tree: &AVLTree<T>) -> bool {
-        tree.node_iter()
-            .all(|n| (-1..=1).contains(&n.balance_factor()))
-    }
+    use crate::math::PCG32;
 
     #[test]
     fn len() {
@@ -371,10 +412,59 @@ mod tests {
     #[test]
     fn balanced() {
         let mut tree: AVLTree<_> = (1..8).collect();
-        assert!(is_balanced(&tree));
+        assert!(tree.is_balanced());
         for x in 1..8 {
             tree.remove(&x);
-            assert!(is_balanced(&tree));
+            assert!(tree.is_balanced());
+        }
+    }
+
+    #[test]
+    fn min_max() {
+        let mut tree = AVLTree::new();
+        assert_eq!(tree.min(), None);
+        assert_eq!(tree.max(), None);
+        for x in [5, 3, 8, 1, 9, 7] {
+            tree.insert(x);
+        }
+        assert_eq!(tree.min(), Some(&1));
+        assert_eq!(tree.max(), Some(&9));
+        tree.remove(&1);
+        tree.remove(&9);
+        assert_eq!(tree.min(), Some(&3));
+        assert_eq!(tree.max(), Some(&8));
+    }
+
+    #[test]
+    fn height() {
+        let mut tree = AVLTree::new();
+        assert_eq!(tree.height(), 0);
+        tree.insert(1);
+        assert_eq!(tree.height(), 1);
+        // Sorted insertions would make an unbalanced tree degenerate into a list
+        let tree: AVLTree<_> = (0..1023).collect();
+        assert_eq!(tree.height(), 10);
+    }
+
+    #[test]
+    fn random_operations() {
+        let mut rng = PCG32::new_default(1729);
+        let mut tree = AVLTree::new();
+        let mut expected = std::collections::BTreeSet::new();
+        for _ in 0..10_000 {
+            let x = rng.get_u32() % 1000;
+            if rng.get_u32().is_multiple_of(3) {
+                assert_eq!(tree.remove(&x), expected.remove(&x));
+            } else {
+                assert_eq!(tree.insert(x), expected.insert(x));
+            }
+            assert!(tree.is_balanced());
+            assert_eq!(tree.len(), expected.len());
         }
+        assert!(tree.iter().eq(expected.iter()));
+        assert!(tree.iter().zip(tree.iter().skip(1)).all(|(a, b)| a < b));
+        // A balanced tree of n nodes is at most about 1.44 log2(n) high
+        let bound = 1.45 * (tree.len() as f64 + 2.0).log2();
+        assert!((tree.height() as f64) < bound);
     }
 }
@@ -0,0 +1,284 @@
+use std::fmt;
+
+/// The reasons two filters cannot be merged, or bytes cannot be read as a filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BloomFilterError {
+    /// The filters do not have the same number of bits and hash functions.
+    Mismatched,
+    /// The bytes are shorter than the header, or than the bits it announces.
+    Truncated,
+    /// The header announces no bits or no hash functions, or the bytes go on
+    /// after the bits.
+    Invalid,
+}
+
+impl fmt::Display for BloomFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BloomFilterError::Mismatched => write!(f, "filters with different parameters"),
+            BloomFilterError::Truncated => write!(f, "bytes too short for a filter"),
+            BloomFilterError::Invalid => write!(f, "bytes are not a filter"),
+        }
+    }
+}
+
+impl std::error::Error for BloomFilterError {}
+
+/// Length of the header of `BloomFilter::to_bytes`: the number of bits as a
+/// `u64` and the number of hash functions as a `u32`, little endian.
+const HEADER_LEN: usize = 12;
+
+/// A set of byte strings based on a Bloom filter.
+///
+/// A Bloom filter sets `k` bits of a bit array for each item, chosen by `k`
+/// hash functions, and says it contains an item when all of its bits are set.
+/// It never misses an inserted item, but may claim to contain an item that was
+/// never inserted, because other items set all of its bits. The hash functions
+/// are `h1 + i * h2` for `i` in `0..k`, from two base hashes of the item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: usize,
+    num_hashes: u32,
+}
+
+impl BloomFilter {
+    /// Creates a filter whose expected false positive rate is at most
+    /// `false_positive_rate` while it holds up to `expected_elements` items.
+    ///
+    /// For `n` items and a rate `p`, the rate is smallest with about
+    /// `m = -n ln p / (ln 2)^2` bits and `k = m / n ln 2` hash functions. `k` is
+    /// rounded, and then `m` is chosen so that `(1 - e^(-kn/m))^k`, the rate with
+    /// `k` hash functions, is at most `p`, and rounded up to whole words.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `false_positive_rate` is not strictly between 0 and 1.
+    pub fn new(expected_elements: usize, false_positive_rate: f64) -> Self {
+        assert!(
+            false_positive_rate > 0.0 && false_positive_rate < 1.0,
+            "the false positive rate must be between 0 and 1"
+        );
+        let n = expected_elements.max(1) as f64;
+        let k = (-false_positive_rate.log2()).round().max(1.0);
+        let m = -k * n / (1.0 - false_positive_rate.powf(1.0 / k)).ln();
+        let num_bits = (m / 64.0).ceil() as usize * 64;
+        Self::with_parameters(num_bits, k as u32)
+    }
+
+    fn with_parameters(num_bits: usize, num_hashes: u32) -> Self {
+        BloomFilter {
+            bits: vec![0; num_bits.div_ceil(64)],
+            num_bits,
+            num_hashes,
+        }
+    }
+
+    /// Returns the number of bits of the filter.
+    pub fn num_bits(&self) -> usize {
+        self.num_bits
+    }
+
+    /// Returns the number of bits set for each item.
+    pub fn num_hashes(&self) -> u32 {
+        self.num_hashes
+    }
+
+    /// Adds an item to the filter.
+    pub fn insert(&mut self, item: &[u8]) {
+        for bit in bit_indices(item, self.num_bits, self.num_hashes) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Returns `false` if the item was never inserted, and `true` if it
+    /// probably was.
+    pub fn contains(&self, item: &[u8]) -> bool {
+        bit_indices(item, self.num_bits, self.num_hashes)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Adds the items of `other` to this filter, which then contains the items
+    /// inserted in either.
+    ///
+    /// Fails if the filters do not have the same number of bits and of hash
+    /// functions, as when built by `new` with the same arguments.
+    pub fn union(&mut self, other: &BloomFilter) -> Result<(), BloomFilterError> {
+        if self.num_bits != other.num_bits || self.num_hashes != other.num_hashes {
+            return Err(BloomFilterError::Mismatched);
+        }
+        for (word, other_word) in self.bits.iter_mut().zip(&other.bits) {
+            *word |= other_word;
+        }
+        Ok(())
+    }
+
+    /// Writes the filter as its header followed by its bits, as little endian
+    /// `u64` words.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + 8 * self.bits.len());
+        bytes.extend_from_slice(&(self.num_bits as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.num_hashes.to_le_bytes());
+        for word in &self.bits {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Reads a filter written by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BloomFilterError> {
+        if bytes.len() < HEADER_LEN {
+            return Err(BloomFilterError::Truncated);
+        }
+        let num_bits = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let num_hashes = u32::from_le_bytes(bytes[8..HEADER_LEN].try_into().unwrap());
+        let num_bits = usize::try_from(num_bits).map_err(|_| BloomFilterError::Invalid)?;
+        if num_bits == 0 || num_hashes == 0 {
+            return Err(BloomFilterError::Invalid);
+        }
+        let words = &bytes[HEADER_LEN..];
+        let expected_len = num_bits.div_ceil(64).saturating_mul(8);
+        if words.len() < expected_len {
+            return Err(BloomFilterError::Truncated);
+        }
+        if words.len() > expected_len {
+            return Err(BloomFilterError::Invalid);
+        }
+        let mut filter = Self::with_parameters(num_bits, num_hashes);
+        for (word, chunk) in filter.bits.iter_mut().zip(words.chunks_exact(8)) {
+            *word = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        Ok(filter)
+    }
+}
+
+/// The `num_hashes` bits of `item`: `h1 + i * h2` modulo `num_bits`.
+fn bit_indices(item: &[u8], num_bits: usize, num_hashes: u32) -> impl Iterator<Item = usize> {
+    let h1 = fmix64(fnv1a(item, FNV_OFFSET));
+    // odd, so that the bits do not repeat early when the size is a power of two
+    let h2 = fmix64(fnv1a(item, FNV_OFFSET ^ 0x9e37_79b9_7f4a_7c15)) | 1;
+    (0..num_hashes as u64)
+        .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % num_bits as u64) as usize)
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The 64-bit FNV-1a hash of `bytes`, starting from `offset`.
+fn fnv1a(bytes: &[u8], offset: u64) -> u64 {
+    bytes.iter().fold(offset, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// The finalizer of MurmurHash3, which spreads every input bit over the output.
+fn fmix64(mut h: u64) -> u64 {
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^ (h >> 33)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    #[test]
+    fn parameters() {
+        // 10 000 items at 1% take about 9.6 bits each and 7 hash functions
+        let filter = BloomFilter::new(10_000, 0.01);
+        assert_eq!(filter.num_bits(), 95_936);
+        assert_eq!(filter.num_hashes(), 7);
+
+        let filter = BloomFilter::new(0, 0.5);
+        assert_eq!(filter.num_bits(), 64);
+        assert_eq!(filter.num_hashes(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_rate() {
+        BloomFilter::new(100, 1.0);
+    }
+
+    #[test]
+    fn false_positive_rate() {
+        let rate = 0.01;
+        let mut filter = BloomFilter::new(10_000, rate);
+        let mut rng = PCG32::new_default(72);
+        // inserted items have the top bit cleared and queried ones have it set
+        let inserted: Vec<u64> = (0..10_000).map(|_| rng.get_u64() >> 1).collect();
+        for item in &inserted {
+            filter.insert(&item.to_le_bytes());
+        }
+        for item in &inserted {
+            assert!(filter.contains(&item.to_le_bytes()));
+        }
+        let queries = 100_000;
+        let false_positives = (0..queries)
+            .filter(|_| filter.contains(&(rng.get_u64() | 1 << 63).to_le_bytes()))
+            .count();
+        // the expected rate is just below `rate`, and the measured one is off by
+        // about 3% of it with this many queries
+        assert!((false_positives as f64 / queries as f64) < rate * 1.1);
+    }
+
+    #[test]
+    fn union() {
+        let mut a = BloomFilter::new(1000, 0.01);
+        let mut b = BloomFilter::new(1000, 0.01);
+        for i in 0..500u32 {
+            a.insert(&i.to_le_bytes());
+            b.insert(&(i + 500).to_le_bytes());
+        }
+        let mut both = BloomFilter::new(1000, 0.01);
+        for i in 0..1000u32 {
+            both.insert(&i.to_le_bytes());
+        }
+
+        assert_eq!(a.union(&b), Ok(()));
+        for i in 0..1000u32 {
+            assert!(a.contains(&i.to_le_bytes()));
+        }
+        assert_eq!(a, both);
+
+        let other = BloomFilter::new(1000, 0.001);
+        assert_eq!(a.union(&other), Err(BloomFilterError::Mismatched));
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let mut filter = BloomFilter::new(100, 0.05);
+        for word in ["apple", "banana", "cherry"] {
+            filter.insert(word.as_bytes());
+        }
+        let bytes = filter.to_bytes();
+        assert_eq!(bytes.len(), HEADER_LEN + filter.num_bits().div_ceil(64) * 8);
+        let read = BloomFilter::from_bytes(&bytes).unwrap();
+        assert_eq!(read, filter);
+        assert!(read.contains(b"banana"));
+
+        assert_eq!(
+            BloomFilter::from_bytes(&bytes[..5]),
+            Err(BloomFilterError::Truncated)
+        );
+        assert_eq!(
+            BloomFilter::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(BloomFilterError::Truncated)
+        );
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert_eq!(
+            BloomFilter::from_bytes(&longer),
+            Err(BloomFilterError::Invalid)
+        );
+        let mut no_hashes = bytes;
+        no_hashes[8..HEADER_LEN].fill(0);
+        assert_eq!(
+            BloomFilter::from_bytes(&no_hashes),
+            Err(BloomFilterError::Invalid)
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::iter::FromIterator;
+
+/// A first in, first out queue of fixed capacity, based on a Circular Buffer.
+///
+/// The elements are kept in a vector used as a ring: `head` is the slot of the
+/// oldest one, and the others follow it, wrapping around to the start of the
+/// vector. Pushing into a full buffer overwrites the oldest element.
+#[derive(Debug, Clone)]
+pub struct CircularBuffer<T> {
+    slots: Vec<Option<T>>,
+    head: usize,
+    length: usize,
+}
+
+impl<T> CircularBuffer<T> {
+    /// Creates an empty buffer holding up to `capacity` elements.
+    pub fn new(capacity: usize) -> CircularBuffer<T> {
+        CircularBuffer {
+            slots: (0..capacity).map(|_| None).collect(),
+            head: 0,
+            length: 0,
+        }
+    }
+
+    /// Returns the number of elements the buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of elements in the buffer.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the buffer contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns `true` if pushing would overwrite an element.
+    pub fn is_full(&self) -> bool {
+        self.length == self.capacity()
+    }
+
+    /// Adds an element after the newest one.
+    ///
+    /// If the buffer is full, the oldest element is removed to make room and
+    /// returned. A buffer of capacity 0 returns `item` itself.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        if self.capacity() == 0 {
+            return Some(item);
+        }
+        if self.is_full() {
+            let oldest = self.slots[self.head].replace(item);
+            self.head = self.wrap(1);
+            return oldest;
+        }
+        let tail = self.wrap(self.length);
+        self.slots[tail] = Some(item);
+        self.length += 1;
+        None
+    }
+
+    /// Removes the oldest element and returns it.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let oldest = self.slots[self.head].take();
+        self.head = self.wrap(1);
+        self.length -= 1;
+        oldest
+    }
+
+    /// Returns a reference to the oldest element.
+    pub fn peek(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.slots[self.head].as_ref()
+    }
+
+    /// Returns an iterator over the elements, from the oldest to the newest.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            buffer: self,
+            index: 0,
+        }
+    }
+
+    /// The slot `offset` places after the head.
+    fn wrap(&self, offset: usize) -> usize {
+        (self.head + offset) % self.capacity()
+    }
+}
+
+/// Collects the elements into a full buffer of capacity their number.
+impl<T> FromIterator<T> for CircularBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let slots: Vec<Option<T>> = iter.into_iter().map(Some).collect();
+        CircularBuffer {
+            head: 0,
+            length: slots.len(),
+            slots,
+        }
+    }
+}
+
+/// An iterator over the elements of a `CircularBuffer`.
+///
+/// This struct is created by the `iter` method of `CircularBuffer`.
+pub struct Iter<'a, T> {
+    buffer: &'a CircularBuffer<T>,
+    index: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.buffer.length {
+            return None;
+        }
+        let slot = self.buffer.wrap(self.index);
+        self.index += 1;
+        self.buffer.slots[slot].as_ref()
+    }
+}
+
+/// An owning iterator over the elements of a `CircularBuffer`.
+///
+/// This struct is created by the `into_iter` method of `CircularBuffer`.
+pub struct IntoIter<T> {
+    buffer: CircularBuffer<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.pop()
+    }
+}
+
+impl<T> IntoIterator for CircularBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { buffer: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CircularBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CircularBuffer;
+
+    #[test]
+    fn push_and_pop() {
+        let mut buffer = CircularBuffer::new(3);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.pop(), None);
+        assert_eq!(buffer.peek(), None);
+
+        assert_eq!(buffer.push(1), None);
+        assert_eq!(buffer.push(2), None);
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.peek(), Some(&1));
+        assert_eq!(buffer.pop(), Some(1));
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), None);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn wrap_around() {
+        let mut buffer = CircularBuffer::new(3);
+        for round in 0..10 {
+            // the head moves one slot further every round
+            assert_eq!(buffer.push(round), None);
+            assert_eq!(buffer.push(round + 100), None);
+            assert_eq!(buffer.pop(), Some(round));
+            assert_eq!(buffer.peek(), Some(&(round + 100)));
+            assert_eq!(buffer.pop(), Some(round + 100));
+        }
+        buffer.push(1);
+        buffer.push(2);
+        buffer.pop();
+        buffer.push(3);
+        buffer.push(4);
+        assert!(buffer.is_full());
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), [&2, &3, &4]);
+    }
+
+    #[test]
+    fn push_into_full_overwrites_oldest() {
+        let mut buffer = CircularBuffer::new(2);
+        assert_eq!(buffer.push('a'), None);
+        assert_eq!(buffer.push('b'), None);
+        assert!(buffer.is_full());
+        assert_eq!(buffer.push('c'), Some('a'));
+        assert_eq!(buffer.push('d'), Some('b'));
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.pop(), Some('c'));
+        assert_eq!(buffer.pop(), Some('d'));
+
+        let mut empty = CircularBuffer::new(0);
+        assert!(empty.is_full());
+        assert_eq!(empty.push(1), Some(1));
+        assert_eq!(empty.pop(), None);
+    }
+
+    #[test]
+    fn iterators_are_fifo() {
+        let mut buffer = CircularBuffer::new(4);
+        for i in 0..7 {
+            buffer.push(i);
+        }
+        assert_eq!((&buffer).into_iter().collect::<Vec<_>>(), [&3, &4, &5, &6]);
+        assert_eq!(buffer.into_iter().collect::<Vec<_>>(), [3, 4, 5, 6]);
+
+        let mut collected: CircularBuffer<_> = "abc".chars().collect();
+        assert!(collected.is_full());
+        assert_eq!(collected.push('d'), Some('a'));
+        assert_eq!(collected.into_iter().collect::<String>(), "bcd");
+    }
+}
@@ -0,0 +1,296 @@
+use std::iter::FromIterator;
+use std::ops::Index;
+
+/// A double-ended queue, based on a growable Circular Buffer.
+///
+/// The elements are kept in a vector used as a ring: `head` is the slot of the
+/// front element, and the others follow it, wrapping around to the start of
+/// the vector. When the vector is full its capacity is doubled, and the
+/// elements are moved to its start in front-to-back order, so pushes at either
+/// end take amortized `O(1)` time.
+#[derive(Debug, Clone)]
+pub struct Deque<T> {
+    slots: Vec<Option<T>>,
+    head: usize,
+    length: usize,
+}
+
+impl<T> Deque<T> {
+    pub fn new() -> Deque<T> {
+        Deque {
+            slots: Vec::new(),
+            head: 0,
+            length: 0,
+        }
+    }
+
+    /// Returns the number of elements in the deque.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the deque contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Adds an element before the front one.
+    pub fn push_front(&mut self, item: T) {
+        self.grow_if_full();
+        self.head = self.slot(self.slots.len() - 1);
+        self.slots[self.head] = Some(item);
+        self.length += 1;
+    }
+
+    /// Adds an element after the back one.
+    pub fn push_back(&mut self, item: T) {
+        self.grow_if_full();
+        let tail = self.slot(self.length);
+        self.slots[tail] = Some(item);
+        self.length += 1;
+    }
+
+    /// Removes the front element and returns it.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let item = self.slots[self.head].take();
+        self.head = self.slot(1);
+        self.length -= 1;
+        item
+    }
+
+    /// Removes the back element and returns it.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.length -= 1;
+        let tail = self.slot(self.length);
+        self.slots[tail].take()
+    }
+
+    /// Returns a reference to the front element.
+    pub fn peek_front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a reference to the back element.
+    pub fn peek_back(&self) -> Option<&T> {
+        self.get(self.length.checked_sub(1)?)
+    }
+
+    /// Returns a reference to the element `index` places from the front.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.length {
+            return None;
+        }
+        self.slots[self.slot(index)].as_ref()
+    }
+
+    /// Returns an iterator over the elements, from front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            deque: self,
+            front: 0,
+            back: self.length,
+        }
+    }
+
+    /// The slot `offset` places after the head.
+    fn slot(&self, offset: usize) -> usize {
+        (self.head + offset) % self.slots.len()
+    }
+
+    fn grow_if_full(&mut self) {
+        if self.length < self.slots.len() {
+            return;
+        }
+        let capacity = (2 * self.slots.len()).max(4);
+        let mut slots = Vec::with_capacity(capacity);
+        for offset in 0..self.length {
+            let slot = self.slot(offset);
+            slots.push(self.slots[slot].take());
+        }
+        slots.resize_with(capacity, || None);
+        self.slots = slots;
+        self.head = 0;
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Index<usize> for Deque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Deque::new();
+        for item in iter {
+            deque.push_back(item);
+        }
+        deque
+    }
+}
+
+/// An iterator over the elements of a `Deque`.
+///
+/// This struct is created by the `iter` method of `Deque`.
+pub struct Iter<'a, T> {
+    deque: &'a Deque<T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        self.deque.get(self.front - 1)
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.deque.get(self.back)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Deque;
+
+    #[test]
+    fn push_back_pop_front() {
+        let mut deque = Deque::new();
+        assert_eq!(deque.pop_front(), None);
+        for i in 0..10 {
+            deque.push_back(i);
+        }
+        for i in 0..10 {
+            assert_eq!(deque.pop_front(), Some(i));
+        }
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn push_front_pop_back() {
+        let mut deque = Deque::new();
+        assert_eq!(deque.pop_back(), None);
+        for i in 0..10 {
+            deque.push_front(i);
+        }
+        for i in 0..10 {
+            assert_eq!(deque.pop_back(), Some(i));
+        }
+        assert_eq!(deque.pop_back(), None);
+    }
+
+    #[test]
+    fn push_and_pop_at_the_same_end() {
+        let mut deque = Deque::new();
+        for i in 0..6 {
+            deque.push_back(i);
+        }
+        for i in (0..6).rev() {
+            assert_eq!(deque.pop_back(), Some(i));
+        }
+        for i in 0..6 {
+            deque.push_front(i);
+        }
+        for i in (0..6).rev() {
+            assert_eq!(deque.pop_front(), Some(i));
+        }
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn peek() {
+        let mut deque = Deque::new();
+        assert_eq!(deque.peek_front(), None);
+        assert_eq!(deque.peek_back(), None);
+        deque.push_back('b');
+        deque.push_front('a');
+        deque.push_back('c');
+        assert_eq!(deque.peek_front(), Some(&'a'));
+        assert_eq!(deque.peek_back(), Some(&'c'));
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque[1], 'b');
+        assert_eq!(deque.get(3), None);
+    }
+
+    #[test]
+    fn resize_keeps_order() {
+        // the head has wrapped around when the buffer grows
+        let mut deque = Deque::new();
+        let mut expected = std::collections::VecDeque::new();
+        for i in 0..100 {
+            if i % 3 == 0 {
+                deque.push_front(i);
+                expected.push_front(i);
+            } else {
+                deque.push_back(i);
+                expected.push_back(i);
+            }
+            if i % 7 == 0 {
+                assert_eq!(deque.pop_front(), expected.pop_front());
+            }
+            assert!(deque.iter().eq(expected.iter()));
+        }
+        for i in 0..deque.len() {
+            assert_eq!(deque[i], expected[i]);
+        }
+    }
+
+    #[test]
+    fn iter_front_to_back() {
+        let mut deque: Deque<_> = (3..6).collect();
+        deque.push_front(2);
+        deque.push_front(1);
+        deque.push_back(6);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(
+            deque.iter().rev().copied().collect::<Vec<_>>(),
+            [6, 5, 4, 3, 2, 1]
+        );
+        assert_eq!((&deque).into_iter().count(), 6);
+        assert_eq!(Deque::<u8>::new().iter().next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let deque: Deque<_> = (0..3).collect();
+        let _ = deque[3];
+    }
+}
@@ -1,34 +1,60 @@
-use std::ops::{Add, AddAssign};
+use std::ops::{AddAssign, SubAssign};
 
 /// Fenwick Tree / Binary Indexed Tree
 /// Consider we have an array arr[0 . . . n-1]. We would like to
 /// 1. Compute the sum of the first i elements.
 /// 2. Modify the value of a specified element of the array arr[i] = x where 0 <= i <= n-1.Fenwick tree
-pub struct FenwickTree<T: Add + AddAssign + Copy + Default> {
+///
+/// Both operations take `O(log n)` time.
+pub struct FenwickTree<T: AddAssign + SubAssign + Default + Copy> {
     data: Vec<T>,
 }
 
-impl<T: Add<Output = T> + AddAssign + Copy + Default> FenwickTree<T> {
-    /// construct a new FenwickTree with given length
-    pub fn with_len(len: usize) -> Self {
+impl<T: AddAssign + SubAssign + Default + Copy> FenwickTree<T> {
+    /// construct a new FenwickTree of `n` zeros
+    pub fn new(n: usize) -> Self {
         FenwickTree {
-            data: vec![T::default(); len + 1],
+            data: vec![T::default(); n + 1],
         }
     }
 
-    /// add `val` to `idx`
-    pub fn add(&mut self, i: usize, val: T) {
-        assert!(i < self.data.len());
+    /// construct a FenwickTree holding the elements of `arr` in `O(n)`,
+    /// by pushing every partial sum to its parent once instead of calling `update` for each element
+    pub fn from_slice(arr: &[T]) -> Self {
+        let mut data = vec![T::default(); arr.len() + 1];
+        data[1..].copy_from_slice(arr);
+        for i in 1..data.len() {
+            let parent = i + lowbit(i);
+            if parent < data.len() {
+                let partial = data[i];
+                data[parent] += partial;
+            }
+        }
+        FenwickTree { data }
+    }
+
+    /// the number of elements
+    pub fn len(&self) -> usize {
+        self.data.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// add `delta` to the element at `i`
+    pub fn update(&mut self, i: usize, delta: T) {
+        assert!(i < self.len());
         let mut i = i + 1;
         while i < self.data.len() {
-            self.data[i] += val;
+            self.data[i] += delta;
             i += lowbit(i);
         }
     }
 
     /// get the sum of [0, i]
     pub fn prefix_sum(&self, i: usize) -> T {
-        assert!(i < self.data.len());
+        assert!(i < self.len());
         let mut i = i + 1;
         let mut res = T::default();
         while i > 0 {
@@ -37,6 +63,16 @@ impl<T: Add<Output = T> + AddAssign + Copy + Default> FenwickTree<T> {
         }
         res
     }
+
+    /// get the sum of [l, r]
+    pub fn range_sum(&self, l: usize, r: usize) -> T {
+        assert!(l <= r);
+        let mut res = self.prefix_sum(r);
+        if l > 0 {
+            res -= self.prefix_sum(l - 1);
+        }
+        res
+    }
 }
 
 /// get the lowest bit of `i`
@@ -48,19 +84,21 @@ const fn lowbit(x: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::PCG32;
+
     #[test]
     fn it_works() {
-        let mut ft = FenwickTree::with_len(10);
-        ft.add(0, 1);
-        ft.add(1, 2);
-        ft.add(2, 3);
-        ft.add(3, 4);
-        ft.add(4, 5);
-        ft.add(5, 6);
-        ft.add(6, 7);
-        ft.add(7, 8);
-        ft.add(8, 9);
-        ft.add(9, 10);
+        let mut ft = FenwickTree::new(10);
+        ft.update(0, 1);
+        ft.update(1, 2);
+        ft.update(2, 3);
+        ft.update(3, 4);
+        ft.update(4, 5);
+        ft.update(5, 6);
+        ft.update(6, 7);
+        ft.update(7, 8);
+        ft.update(8, 9);
+        ft.update(9, 10);
         assert_eq!(ft.prefix_sum(0), 1);
         assert_eq!(ft.prefix_sum(1), 3);
         assert_eq!(ft.prefix_sum(2), 6);
@@ -71,5 +109,60 @@ mod tests {
         assert_eq!(ft.prefix_sum(7), 36);
         assert_eq!(ft.prefix_sum(8), 45);
         assert_eq!(ft.prefix_sum(9), 55);
+        assert_eq!(ft.range_sum(3, 5), 15);
+        assert_eq!(ft.range_sum(0, 9), 55);
+        assert_eq!(ft.range_sum(7, 7), 8);
+    }
+
+    #[test]
+    fn empty() {
+        let ft = FenwickTree::<i32>::new(0);
+        assert!(ft.is_empty());
+        assert!(FenwickTree::<i32>::from_slice(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range() {
+        let ft = FenwickTree::<i32>::new(3);
+        ft.prefix_sum(3);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = PCG32::new_default(99);
+        let n = 200;
+        let mut values = vec![0i64; n];
+        let mut ft = FenwickTree::new(n);
+        for _ in 0..2000 {
+            let i = rng.get_u32() as usize % n;
+            let delta = (rng.get_u32() % 1000) as i64 - 500;
+            values[i] += delta;
+            ft.update(i, delta);
+
+            let l = rng.get_u32() as usize % n;
+            let r = l + rng.get_u32() as usize % (n - l);
+            assert_eq!(ft.range_sum(l, r), values[l..=r].iter().sum::<i64>());
+        }
+        let mut sum = 0;
+        for (i, &value) in values.iter().enumerate() {
+            sum += value;
+            assert_eq!(ft.prefix_sum(i), sum);
+        }
+    }
+
+    #[test]
+    fn linear_build_matches_updates() {
+        let mut rng = PCG32::new_default(7);
+        for n in [1, 2, 3, 7, 8, 9, 100, 1023, 1024, 1025] {
+            let values: Vec<i64> = (0..n).map(|_| rng.get_u32() as i64 - (1 << 31)).collect();
+            let built = FenwickTree::from_slice(&values);
+            let mut updated = FenwickTree::new(n);
+            for (i, &value) in values.iter().enumerate() {
+                updated.update(i, value);
+            }
+            assert_eq!(built.data, updated.data);
+            assert_eq!(built.len(), n);
+        }
     }
 }
@@ -0,0 +1,242 @@
+/// A min-priority queue of indices below a fixed bound, with decrease-key.
+///
+/// It is a binary heap of indices ordered by their priorities, together with
+/// the position of each index in the heap, so that the priority of an index in
+/// the queue can be lowered in `O(log n)` by moving it up from where it is.
+/// Each index is in the queue at most once.
+#[derive(Debug, Clone)]
+pub struct IndexedPriorityQueue<T: Ord> {
+    heap: Vec<usize>,
+    position: Vec<Option<usize>>,
+    priorities: Vec<Option<T>>,
+}
+
+impl<T: Ord> IndexedPriorityQueue<T> {
+    /// Creates an empty queue for the indices `0..max_indices`.
+    pub fn new(max_indices: usize) -> IndexedPriorityQueue<T> {
+        IndexedPriorityQueue {
+            heap: Vec::new(),
+            position: vec![None; max_indices],
+            priorities: (0..max_indices).map(|_| None).collect(),
+        }
+    }
+
+    /// Returns the number of indices in the queue.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns `true` if the queue contains no indices.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns `true` if `index` is in the queue.
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.position.len() && self.position[index].is_some()
+    }
+
+    /// Returns the priority of `index`, if it is in the queue.
+    pub fn priority(&self, index: usize) -> Option<&T> {
+        self.priorities.get(index)?.as_ref()
+    }
+
+    /// Adds `index` with the given priority.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds or already in the queue.
+    pub fn push(&mut self, index: usize, priority: T) {
+        assert!(!self.contains(index), "index {index} cannot be pushed");
+        self.priorities[index] = Some(priority);
+        self.position[index] = Some(self.heap.len());
+        self.heap.push(index);
+        self.sift_up(self.heap.len() - 1);
+    }
+
+    /// Returns the index with the lowest priority and its priority, without
+    /// removing it.
+    pub fn peek(&self) -> Option<(usize, &T)> {
+        let &index = self.heap.first()?;
+        Some((index, self.priorities[index].as_ref().unwrap()))
+    }
+
+    /// Removes the index with the lowest priority and returns it with its
+    /// priority.
+    pub fn pop(&mut self) -> Option<(usize, T)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let index = self.heap.pop().unwrap();
+        self.position[index] = None;
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some((index, self.priorities[index].take().unwrap()))
+    }
+
+    /// Lowers the priority of `index` to `new_priority`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not in the queue, or if `new_priority` is greater
+    /// than its priority.
+    pub fn decrease_key(&mut self, index: usize, new_priority: T) {
+        assert!(self.contains(index), "index {index} is not in the queue");
+        let priority = self.priorities[index].as_mut().unwrap();
+        assert!(new_priority <= *priority, "the priority cannot increase");
+        *priority = new_priority;
+        self.sift_up(self.position[index].unwrap());
+    }
+
+    fn less(&self, i: usize, j: usize) -> bool {
+        self.priorities[self.heap[i]] < self.priorities[self.heap[j]]
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.position[self.heap[i]] = Some(i);
+        self.position[self.heap[j]] = Some(j);
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.less(i, parent) {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut smallest = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.less(child, smallest) {
+                    smallest = child;
+                }
+            }
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IndexedPriorityQueue;
+    use crate::graph::{dijkstra, WeightedGraph};
+    use crate::math::PCG32;
+
+    #[test]
+    fn push_and_pop() {
+        let mut queue = IndexedPriorityQueue::new(5);
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop(), None);
+        assert_eq!(queue.peek(), None);
+
+        queue.push(3, 30);
+        queue.push(0, 50);
+        queue.push(4, 10);
+        queue.push(1, 40);
+        assert_eq!(queue.len(), 4);
+        assert!(queue.contains(4));
+        assert!(!queue.contains(2));
+        assert!(!queue.contains(10));
+        assert_eq!(queue.peek(), Some((4, &10)));
+
+        assert_eq!(queue.pop(), Some((4, 10)));
+        assert_eq!(queue.pop(), Some((3, 30)));
+        assert!(!queue.contains(3));
+        // popped indices can be pushed again
+        queue.push(3, 45);
+        assert_eq!(queue.pop(), Some((1, 40)));
+        assert_eq!(queue.pop(), Some((3, 45)));
+        assert_eq!(queue.pop(), Some((0, 50)));
+        assert_eq!(queue.pop(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn decrease_key() {
+        let mut queue = IndexedPriorityQueue::new(4);
+        queue.push(0, 5);
+        queue.push(1, 8);
+        queue.push(2, 9);
+        queue.push(3, 7);
+
+        queue.decrease_key(2, 1);
+        assert_eq!(queue.peek(), Some((2, &1)));
+        assert_eq!(queue.priority(2), Some(&1));
+        queue.decrease_key(1, 6);
+        queue.decrease_key(3, 7);
+        assert_eq!(queue.pop(), Some((2, 1)));
+        assert_eq!(queue.pop(), Some((0, 5)));
+        assert_eq!(queue.pop(), Some((1, 6)));
+        assert_eq!(queue.pop(), Some((3, 7)));
+        assert_eq!(queue.priority(3), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_twice() {
+        let mut queue = IndexedPriorityQueue::new(2);
+        queue.push(1, 'a');
+        queue.push(1, 'b');
+    }
+
+    #[test]
+    #[should_panic]
+    fn increase_key() {
+        let mut queue = IndexedPriorityQueue::new(2);
+        queue.push(0, 1);
+        queue.decrease_key(0, 2);
+    }
+
+    fn dijkstra_with_decrease_key(graph: &WeightedGraph, source: usize) -> Vec<Option<u64>> {
+        let mut dist = vec![None; graph.num_vertices()];
+        let mut queue = IndexedPriorityQueue::new(graph.num_vertices());
+        queue.push(source, 0);
+        while let Some((vertex, d)) = queue.pop() {
+            dist[vertex] = Some(d);
+            for &(next, weight) in graph.neighbors(vertex) {
+                let alternative = d + weight;
+                match queue.priority(next) {
+                    Some(&current) if alternative < current => {
+                        queue.decrease_key(next, alternative)
+                    }
+                    None if dist[next].is_none() => queue.push(next, alternative),
+                    _ => {}
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn dijkstra_matches() {
+        let mut rng = PCG32::new_default(74);
+        for _ in 0..20 {
+            let n = 1 + rng.get_u32() as usize % 60;
+            let mut graph = WeightedGraph::new(n);
+            for _ in 0..3 * n {
+                let from = rng.get_u32() as usize % n;
+                let to = rng.get_u32() as usize % n;
+                graph.add_edge(from, to, rng.get_u64() % 100);
+            }
+            for source in 0..n {
+                assert_eq!(
+                    dijkstra_with_decrease_key(&graph, source),
+                    dijkstra(&graph, source)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+type Point = (f64, f64);
+
+/// A K-D tree of points of the plane, for nearest neighbor and range queries.
+///
+/// Each node splits the plane by a line through its point, vertical at even
+/// depths and horizontal at odd ones, and the points on either side go to its
+/// two subtrees. The tree is stored implicitly: the subtree of `points[lo..hi]`
+/// has its root at the middle index, the median along the axis of its depth,
+/// and its subtrees on either side of it, so the tree is perfectly balanced.
+#[derive(Debug, Clone)]
+pub struct KdTree {
+    points: Vec<Point>,
+}
+
+impl KdTree {
+    /// Builds a balanced tree of `points` in `O(n log n)` expected time.
+    pub fn build(mut points: Vec<Point>) -> Self {
+        build(&mut points, 0);
+        KdTree { points }
+    }
+
+    /// Returns the number of points in the tree.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns `true` if the tree contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the point closest to `query`, or `None` if the tree is empty.
+    pub fn nearest_neighbor(&self, query: Point) -> Option<Point> {
+        let mut best = None;
+        nearest(&self.points, 0, query, &mut best);
+        best.map(|(point, _)| point)
+    }
+
+    /// Returns the points at distance at most `radius` from `center`.
+    pub fn range_query(&self, center: Point, radius: f64) -> Vec<Point> {
+        let mut found = Vec::new();
+        within(&self.points, 0, center, radius, &mut found);
+        found
+    }
+}
+
+/// The coordinate of `point` along the axis of nodes at `depth`.
+fn coordinate(point: Point, depth: usize) -> f64 {
+    if depth.is_multiple_of(2) {
+        point.0
+    } else {
+        point.1
+    }
+}
+
+fn squared_distance(a: Point, b: Point) -> f64 {
+    (a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)
+}
+
+fn build(points: &mut [Point], depth: usize) {
+    if points.len() <= 1 {
+        return;
+    }
+    let mid = points.len() / 2;
+    points.select_nth_unstable_by(mid, |a, b| {
+        coordinate(*a, depth).total_cmp(&coordinate(*b, depth))
+    });
+    let (left, right) = points.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+fn nearest(points: &[Point], depth: usize, query: Point, best: &mut Option<(Point, f64)>) {
+    if points.is_empty() {
+        return;
+    }
+    let mid = points.len() / 2;
+    let point = points[mid];
+    let distance = squared_distance(point, query);
+    if best.is_none_or(|(_, d)| distance < d) {
+        *best = Some((point, distance));
+    }
+    // the side of the query first, and the other one only if it can be closer
+    let offset = coordinate(query, depth) - coordinate(point, depth);
+    let (near, far) = if offset < 0.0 {
+        (&points[..mid], &points[mid + 1..])
+    } else {
+        (&points[mid + 1..], &points[..mid])
+    };
+    nearest(near, depth + 1, query, best);
+    if best.is_none_or(|(_, d)| offset * offset < d) {
+        nearest(far, depth + 1, query, best);
+    }
+}
+
+fn within(points: &[Point], depth: usize, center: Point, radius: f64, found: &mut Vec<Point>) {
+    if points.is_empty() {
+        return;
+    }
+    let mid = points.len() / 2;
+    let point = points[mid];
+    if squared_distance(point, center) <= radius * radius {
+        found.push(point);
+    }
+    let offset = coordinate(center, depth) - coordinate(point, depth);
+    if offset <= radius {
+        within(&points[..mid], depth + 1, center, radius, found);
+    }
+    if offset >= -radius {
+        within(&points[mid + 1..], depth + 1, center, radius, found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    fn random_points(rng: &mut PCG32, n: usize) -> Vec<Point> {
+        (0..n)
+            .map(|_| {
+                let x = rng.get_u32() % 1000;
+                let y = rng.get_u32() % 1000;
+                (x as f64 / 10.0, y as f64 / 10.0)
+            })
+            .collect()
+    }
+
+    /// Checks the splits of the subtree of `points` and returns its height.
+    fn check_subtree(points: &[Point], depth: usize) -> usize {
+        if points.is_empty() {
+            return 0;
+        }
+        let mid = points.len() / 2;
+        let split = coordinate(points[mid], depth);
+        assert!(points[..mid].iter().all(|&p| coordinate(p, depth) <= split));
+        assert!(points[mid + 1..]
+            .iter()
+            .all(|&p| coordinate(p, depth) >= split));
+        let left = check_subtree(&points[..mid], depth + 1);
+        let right = check_subtree(&points[mid + 1..], depth + 1);
+        1 + left.max(right)
+    }
+
+    #[test]
+    fn empty() {
+        let tree = KdTree::build(vec![]);
+        assert!(tree.is_empty());
+        assert_eq!(tree.nearest_neighbor((0.0, 0.0)), None);
+        assert!(tree.range_query((0.0, 0.0), 10.0).is_empty());
+    }
+
+    #[test]
+    fn balanced() {
+        let mut rng = PCG32::new_default(77);
+        for n in [1, 2, 3, 7, 8, 100, 1000, 1023, 1024] {
+            let tree = KdTree::build(random_points(&mut rng, n));
+            assert_eq!(tree.len(), n);
+            // the smallest height of a binary tree of n nodes
+            let height = (usize::BITS - n.leading_zeros()) as usize;
+            assert_eq!(check_subtree(&tree.points, 0), height);
+        }
+    }
+
+    #[test]
+    fn nearest_neighbor_matches_brute_force() {
+        let mut rng = PCG32::new_default(7);
+        for n in [1, 5, 50, 500] {
+            let points = random_points(&mut rng, n);
+            let tree = KdTree::build(points.clone());
+            for query in random_points(&mut rng, 200) {
+                let found = tree.nearest_neighbor(query).unwrap();
+                let best = points
+                    .iter()
+                    .map(|&p| squared_distance(p, query))
+                    .fold(f64::INFINITY, f64::min);
+                assert_eq!(squared_distance(found, query), best);
+            }
+        }
+    }
+
+    #[test]
+    fn range_query_matches_brute_force() {
+        let mut rng = PCG32::new_default(17);
+        let points = random_points(&mut rng, 800);
+        let tree = KdTree::build(points.clone());
+        for (i, center) in random_points(&mut rng, 100).into_iter().enumerate() {
+            let radius = (i % 20) as f64;
+            let mut found = tree.range_query(center, radius);
+            let mut expected: Vec<Point> = points
+                .iter()
+                .copied()
+                .filter(|&p| squared_distance(p, center) <= radius * radius)
+                .collect();
+            found.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn points_on_the_circle() {
+        let tree = KdTree::build(vec![(0.0, 0.0), (3.0, 4.0), (5.0, 0.0), (0.0, -5.1)]);
+        let mut found = tree.range_query((0.0, 0.0), 5.0);
+        found.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(found, [(0.0, 0.0), (3.0, 4.0), (5.0, 0.0)]);
+        assert_eq!(tree.nearest_neighbor((2.9, 3.0)), Some((3.0, 4.0)));
+    }
+}
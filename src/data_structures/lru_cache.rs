@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// An internal node of an `LruCache`, in the list from the most to the least
+/// recently used entry.
+struct LruNode<K, V> {
+    key: K,
+    value: V,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// The entry that an `LruCache::put` pushes out of the cache.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Displaced<K, V> {
+    /// The previous value of the key that was put.
+    Replaced(V),
+    /// The least recently used entry, evicted from a full cache.
+    Evicted(K, V),
+}
+
+/// A map of bounded size that evicts its least recently used entry.
+///
+/// The entries form a doubly linked list ordered by their last use, and a hash
+/// map gives the node of each key, so that `get` and `put` take `O(1)`
+/// expected time. Nodes are kept in a vector and link to each other by index;
+/// a full cache reuses the slot of the entry it evicts.
+pub struct LruCache<K: Hash + Eq, V> {
+    map: HashMap<K, usize>,
+    nodes: Vec<LruNode<K, V>>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    capacity: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// Creates an empty cache holding up to `capacity` entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> LruCache<K, V> {
+        assert!(capacity > 0, "the capacity of a cache must be positive");
+        LruCache {
+            map: HashMap::with_capacity(capacity),
+            nodes: Vec::with_capacity(capacity),
+            head: None,
+            tail: None,
+            capacity,
+        }
+    }
+
+    /// Returns the number of entries the cache can hold.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the cache contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns `true` if the cache contains `key`, without counting as a use.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns a reference to the value for `key`, which becomes the most
+    /// recently used entry.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let index = *self.map.get(key)?;
+        self.move_to_front(index);
+        Some(&self.nodes[index].value)
+    }
+
+    /// Inserts a value for `key`, which becomes the most recently used entry.
+    ///
+    /// Returns what leaves the cache, if anything: the previous value of
+    /// `key`, or else the least recently used entry if the cache was full.
+    pub fn put(&mut self, key: K, value: V) -> Option<Displaced<K, V>> {
+        if let Some(&index) = self.map.get(&key) {
+            self.move_to_front(index);
+            let previous = std::mem::replace(&mut self.nodes[index].value, value);
+            return Some(Displaced::Replaced(previous));
+        }
+        if self.nodes.len() < self.capacity {
+            let index = self.nodes.len();
+            self.nodes.push(LruNode {
+                key: key.clone(),
+                value,
+                prev: None,
+                next: None,
+            });
+            self.push_front(index);
+            self.map.insert(key, index);
+            return None;
+        }
+        // the least recently used node is reused for the new entry
+        let index = self.tail.unwrap();
+        self.unlink(index);
+        let node = &mut self.nodes[index];
+        self.map.remove(&node.key);
+        let evicted_key = std::mem::replace(&mut node.key, key.clone());
+        let evicted_value = std::mem::replace(&mut node.value, value);
+        self.push_front(index);
+        self.map.insert(key, index);
+        Some(Displaced::Evicted(evicted_key, evicted_value))
+    }
+
+    fn move_to_front(&mut self, index: usize) {
+        if self.head != Some(index) {
+            self.unlink(index);
+            self.push_front(index);
+        }
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let (prev, next) = (self.nodes[index].prev, self.nodes[index].next);
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    fn push_front(&mut self, index: usize) {
+        self.nodes[index].prev = None;
+        self.nodes[index].next = self.head;
+        match self.head {
+            Some(head) => self.nodes[head].prev = Some(index),
+            None => self.tail = Some(index),
+        }
+        self.head = Some(index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Displaced, LruCache};
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        assert_eq!(cache.put("A", 1), None);
+        assert_eq!(cache.put("B", 2), None);
+        assert_eq!(cache.get(&"A"), Some(&1));
+        assert_eq!(cache.put("C", 3), Some(Displaced::Evicted("B", 2)));
+        assert!(!cache.contains_key(&"B"));
+        assert_eq!(cache.get(&"B"), None);
+        assert_eq!(cache.get(&"A"), Some(&1));
+        assert_eq!(cache.get(&"C"), Some(&3));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn put_existing_key() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        // replacing a value counts as a use of its key
+        assert_eq!(cache.put(1, "uno"), Some(Displaced::Replaced("one")));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.put(3, "three"), Some(Displaced::Evicted(2, "two")));
+        assert_eq!(cache.get(&1), Some(&"uno"));
+        assert_eq!(cache.get(&3), Some(&"three"));
+    }
+
+    #[test]
+    fn contains_key_is_not_a_use() {
+        let mut cache = LruCache::new(2);
+        cache.put('a', 1);
+        cache.put('b', 2);
+        assert!(cache.contains_key(&'a'));
+        assert_eq!(cache.put('c', 3), Some(Displaced::Evicted('a', 1)));
+        assert!(!cache.contains_key(&'a'));
+    }
+
+    #[test]
+    fn capacity_one() {
+        let mut cache = LruCache::new(1);
+        assert!(cache.is_empty());
+        for i in 0..10u32 {
+            let evicted = cache.put(i, i * 10);
+            let expected = i.checked_sub(1).map(|j| Displaced::Evicted(j, j * 10));
+            assert_eq!(evicted, expected);
+            assert_eq!(cache.get(&i), Some(&(i * 10)));
+            assert_eq!(cache.len(), 1);
+        }
+    }
+
+    #[test]
+    fn matches_naive_model() {
+        // the model keeps the keys from the least to the most recently used
+        let mut cache = LruCache::new(4);
+        let mut model: Vec<(u32, u32)> = Vec::new();
+        for step in 0..500u32 {
+            let key = (step * 7 + step / 3) % 9;
+            if step % 3 == 0 {
+                let expected = model.iter().position(|&(k, _)| k == key).map(|i| {
+                    let entry = model.remove(i);
+                    model.push(entry);
+                    entry.1
+                });
+                assert_eq!(cache.get(&key).copied(), expected);
+            } else {
+                let expected = match model.iter().position(|&(k, _)| k == key) {
+                    Some(i) => Some(Displaced::Replaced(model.remove(i).1)),
+                    None if model.len() == 4 => {
+                        let (k, v) = model.remove(0);
+                        Some(Displaced::Evicted(k, v))
+                    }
+                    None => None,
+                };
+                model.push((key, step));
+                assert_eq!(cache.put(key, step), expected);
+            }
+            assert_eq!(cache.len(), model.len());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_capacity() {
+        LruCache::<u8, u8>::new(0);
+    }
+}
@@ -1,13 +1,24 @@
 mod avl_tree;
 mod b_tree;
 mod binary_search_tree;
+mod bloom_filter;
+mod circular_buffer;
+mod deque;
 mod fenwick_tree;
 mod graph;
 mod heap;
+mod indexed_priority_queue;
+mod kd_tree;
 mod linked_list;
+mod lru_cache;
+mod order_statistics_tree;
+mod persistent_array;
 mod queue;
+mod radix_tree;
 mod rb_tree;
 mod segment_tree;
+mod skip_list;
+mod sparse_table;
 mod stack_using_singly_linked_list;
 mod treap;
 mod trie;
@@ -16,14 +27,27 @@ mod union_find;
 pub use self::avl_tree::AVLTree;
 pub use self::b_tree::BTree;
 pub use self::binary_search_tree::BinarySearchTree;
+pub use self::bloom_filter::{BloomFilter, BloomFilterError};
+pub use self::circular_buffer::CircularBuffer;
+pub use self::deque::Deque;
 pub use self::fenwick_tree::FenwickTree;
 pub use self::graph::DirectedGraph;
 pub use self::graph::UndirectedGraph;
 pub use self::heap::Heap;
+pub use self::indexed_priority_queue::IndexedPriorityQueue;
+pub use self::kd_tree::KdTree;
 pub use self::linked_list::LinkedList;
+pub use self::lru_cache::{Displaced, LruCache};
+pub use self::order_statistics_tree::OsTree;
+pub use self::persistent_array::PersistentArray;
 pub use self::queue::Queue;
+pub use self::radix_tree::RadixTree;
 pub use self::rb_tree::RBTree;
-pub use self::segment_tree::SegmentTree;
+pub use self::segment_tree::{
+    LazyMonoid, LazySegmentTree, Max, Min, Monoid, RangeAdd, RangeAssign, SegmentTree, Sum,
+};
+pub use self::skip_list::SkipList;
+pub use self::sparse_table::SparseTable;
 pub use self::stack_using_singly_linked_list::Stack;
 pub use self::treap::Treap;
 pub use self::trie::Trie;
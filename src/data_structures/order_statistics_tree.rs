@@ -0,0 +1,71 @@
+use super::Treap;
+
+/// An order-statistics tree: a binary search tree whose nodes also store the
+/// size of their subtree.
+///
+/// The sizes give the number of values smaller than any value (`rank`), and
+/// the value with a given number of smaller values (`select`), by walking a
+/// single path. A `Treap` keeps them up to date through its rotations, splits
+/// and merges, so it serves as one.
+pub type OsTree<T> = Treap<T>;
+
+#[cfg(test)]
+mod tests {
+    use super::OsTree;
+    use crate::math::PCG32;
+
+    #[test]
+    fn rank_and_select() {
+        let tree: OsTree<_> = [50, 10, 40, 20, 30].into_iter().collect();
+        assert_eq!(tree.select(0), Some(&10));
+        assert_eq!(tree.select(4), Some(&50));
+        assert_eq!(tree.select(5), None);
+        assert_eq!(tree.rank(&10), 0);
+        assert_eq!(tree.rank(&30), 2);
+        // values not in the tree have a rank too
+        assert_eq!(tree.rank(&0), 0);
+        assert_eq!(tree.rank(&35), 3);
+        assert_eq!(tree.rank(&99), 5);
+
+        let empty = OsTree::<i32>::new();
+        assert_eq!(empty.select(0), None);
+        assert_eq!(empty.rank(&1), 0);
+    }
+
+    #[test]
+    fn rank_and_select_are_inverse() {
+        let mut rng = PCG32::new_default(79);
+        let tree: OsTree<_> = (0..300).map(|_| rng.get_u32() % 1000).collect();
+        for k in 0..tree.len() {
+            assert_eq!(tree.rank(tree.select(k).unwrap()), k);
+        }
+        for value in tree.iter() {
+            assert_eq!(tree.select(tree.rank(value)), Some(value));
+        }
+    }
+
+    #[test]
+    fn interleaved_inserts_and_removes() {
+        let mut rng = PCG32::new_default(97);
+        let mut tree = OsTree::new();
+        let mut expected = std::collections::BTreeSet::new();
+        for step in 0..2000 {
+            let x = rng.get_u32() % 200;
+            if step % 3 == 0 {
+                assert_eq!(tree.remove(&x), expected.remove(&x));
+            } else {
+                assert_eq!(tree.insert(x), expected.insert(x));
+            }
+            if step % 20 == 0 {
+                let sorted: Vec<_> = expected.iter().collect();
+                for (k, value) in sorted.iter().enumerate() {
+                    assert_eq!(tree.select(k), Some(*value));
+                }
+                assert_eq!(tree.select(sorted.len()), None);
+                for v in 0..200 {
+                    assert_eq!(tree.rank(&v), expected.range(..v).count());
+                }
+            }
+        }
+    }
+}
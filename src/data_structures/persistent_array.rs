@@ -0,0 +1,213 @@
+use std::rc::Rc;
+
+/// An internal node of a `PersistentArray`.
+///
+/// A branch covering the positions `lo..hi` holds `lo..mid` on its left and
+/// `mid..hi` on its right, where `mid = lo + (hi - lo) / 2`.
+enum Node<T> {
+    Leaf(T),
+    Branch(Rc<Node<T>>, Rc<Node<T>>),
+}
+
+/// An immutable array where every update creates a new version.
+///
+/// The elements are stored in the leaves of a balanced binary tree. `set`
+/// copies only the nodes on the path from the root to the modified leaf and
+/// shares every other node with the previous version, so old versions stay
+/// valid and each update costs `O(log n)` time and memory.
+pub struct PersistentArray<T: Clone> {
+    root: Option<Rc<Node<T>>>,
+    len: usize,
+}
+
+impl<T: Clone> PersistentArray<T> {
+    /// Creates the first version of an array holding the elements of `slice`.
+    pub fn new(slice: &[T]) -> Self {
+        PersistentArray {
+            root: (!slice.is_empty()).then(|| build(slice)),
+            len: slice.len(),
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a reference to the element at `index`.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> &T {
+        assert!(index < self.len, "index {} is out of bounds", index);
+        let mut node = self.root.as_ref().unwrap();
+        let (mut lo, mut hi) = (0, self.len);
+        loop {
+            match node.as_ref() {
+                Node::Leaf(value) => return value,
+                Node::Branch(left, right) => {
+                    let mid = lo + (hi - lo) / 2;
+                    if index < mid {
+                        node = left;
+                        hi = mid;
+                    } else {
+                        node = right;
+                        lo = mid;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns a new version of the array where the element at `index` is `value`.
+    ///
+    /// `self` is left unchanged. Panics if `index` is out of bounds.
+    pub fn set(&self, index: usize, value: T) -> Self {
+        assert!(index < self.len, "index {} is out of bounds", index);
+        let root = self.root.as_ref().unwrap();
+        PersistentArray {
+            root: Some(set(root, 0, self.len, index, value)),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: Clone> Clone for PersistentArray<T> {
+    /// Returns the same version of the array, in `O(1)`.
+    fn clone(&self) -> Self {
+        PersistentArray {
+            root: self.root.clone(),
+            len: self.len,
+        }
+    }
+}
+
+/// Builds a balanced tree over the elements of a non-empty slice.
+fn build<T: Clone>(slice: &[T]) -> Rc<Node<T>> {
+    if slice.len() == 1 {
+        return Rc::new(Node::Leaf(slice[0].clone()));
+    }
+    let (left, right) = slice.split_at(slice.len() / 2);
+    Rc::new(Node::Branch(build(left), build(right)))
+}
+
+/// Returns a copy of the subtree covering `lo..hi` with the element at `index`
+/// replaced, sharing the children that do not contain `index`.
+fn set<T>(node: &Rc<Node<T>>, lo: usize, hi: usize, index: usize, value: T) -> Rc<Node<T>> {
+    match node.as_ref() {
+        Node::Leaf(_) => Rc::new(Node::Leaf(value)),
+        Node::Branch(left, right) => {
+            let mid = lo + (hi - lo) / 2;
+            if index < mid {
+                Rc::new(Node::Branch(
+                    set(left, lo, mid, index, value),
+                    Rc::clone(right),
+                ))
+            } else {
+                Rc::new(Node::Branch(
+                    Rc::clone(left),
+                    set(right, mid, hi, index, value),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Node, PersistentArray};
+    use std::collections::HashSet;
+    use std::rc::Rc;
+
+    fn to_vec<T: Clone>(array: &PersistentArray<T>) -> Vec<T> {
+        (0..array.len()).map(|i| array.get(i).clone()).collect()
+    }
+
+    /// Counts the distinct nodes reachable from any of the versions.
+    fn count_nodes<T: Clone>(versions: &[PersistentArray<T>]) -> usize {
+        fn visit<T>(node: &Rc<Node<T>>, seen: &mut HashSet<*const Node<T>>) {
+            if seen.insert(Rc::as_ptr(node)) {
+                if let Node::Branch(left, right) = node.as_ref() {
+                    visit(left, seen);
+                    visit(right, seen);
+                }
+            }
+        }
+        let mut seen = HashSet::new();
+        for root in versions.iter().filter_map(|v| v.root.as_ref()) {
+            visit(root, &mut seen);
+        }
+        seen.len()
+    }
+
+    #[test]
+    fn get_and_set() {
+        let array = PersistentArray::new(&[1, 2, 3, 4, 5]);
+        assert_eq!(array.len(), 5);
+        assert_eq!(to_vec(&array), vec![1, 2, 3, 4, 5]);
+
+        let updated = array.set(0, 10).set(4, 50);
+        assert_eq!(to_vec(&updated), vec![10, 2, 3, 4, 50]);
+        assert_eq!(to_vec(&array), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn empty_and_single() {
+        let empty = PersistentArray::<i32>::new(&[]);
+        assert!(empty.is_empty());
+        let single = PersistentArray::new(&["a"]);
+        assert_eq!(*single.set(0, "b").get(0), "b");
+        assert_eq!(*single.get(0), "a");
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        PersistentArray::new(&[1, 2, 3]).get(3);
+    }
+
+    #[test]
+    fn history() {
+        let n = 37;
+        let mut versions = vec![PersistentArray::new(&vec![0; n])];
+        let mut expected = vec![vec![0; n]];
+        for k in 1..=100 {
+            let index = (k * 17) % n;
+            let next = versions.last().unwrap().set(index, k);
+            let mut values = expected.last().unwrap().clone();
+            values[index] = k;
+            versions.push(next);
+            expected.push(values);
+        }
+        // Creating version N never changes version N - 1 or any older one
+        for (version, values) in versions.iter().zip(&expected) {
+            assert_eq!(&to_vec(version), values);
+        }
+    }
+
+    #[test]
+    fn branching_versions_share_nodes() {
+        let n = 1000;
+        let values: Vec<usize> = (0..n).collect();
+        let root = PersistentArray::new(&values);
+        assert_eq!(count_nodes(std::slice::from_ref(&root)), 2 * n - 1);
+
+        let k = 50;
+        let mut versions = vec![root.clone()];
+        versions.extend((0..k).map(|i| root.set(i * 19, i + n)));
+        for (i, version) in versions.iter().enumerate().skip(1) {
+            let changed = (i - 1) * 19;
+            assert_eq!(*version.get(changed), i - 1 + n);
+            for j in (0..n).filter(|&j| j != changed) {
+                assert_eq!(*version.get(j), j);
+            }
+        }
+
+        // every update copies one path of at most ceil(log2(n)) + 1 nodes
+        let depth = (n as f64).log2().ceil() as usize + 1;
+        assert!(count_nodes(&versions) <= 2 * n - 1 + k * depth);
+    }
+}
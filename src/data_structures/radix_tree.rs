@@ -0,0 +1,309 @@
+use std::collections::BTreeMap;
+
+/// An internal node of a `RadixTree`.
+///
+/// `label` is the label of the edge coming into the node, empty only for the
+/// root. The children are indexed by the first character of their label, which
+/// differs between siblings.
+#[derive(Debug)]
+struct RadixNode<V> {
+    label: String,
+    entry: Option<(String, V)>,
+    children: BTreeMap<char, RadixNode<V>>,
+}
+
+impl<V> RadixNode<V> {
+    fn new(label: &str, entry: Option<(String, V)>) -> Self {
+        RadixNode {
+            label: label.to_string(),
+            entry,
+            children: BTreeMap::new(),
+        }
+    }
+
+    /// Removes the value for `key` under this node, where `key` is what is left
+    /// of the whole key after the labels down to this node.
+    fn remove(&mut self, key: &str) -> Option<V> {
+        let first = match key.chars().next() {
+            None => return self.entry.take().map(|(_, value)| value),
+            Some(first) => first,
+        };
+        let child = self.children.get_mut(&first)?;
+        let rest = key.strip_prefix(child.label.as_str())?;
+        let value = child.remove(rest)?;
+        if child.entry.is_none() {
+            match child.children.len() {
+                0 => {
+                    self.children.remove(&first);
+                }
+                1 => {
+                    // a node with no value and a single child is merged with it
+                    let (_, grandchild) = child.children.pop_first().unwrap();
+                    child.label.push_str(&grandchild.label);
+                    child.entry = grandchild.entry;
+                    child.children = grandchild.children;
+                }
+                _ => {}
+            }
+        }
+        Some(value)
+    }
+}
+
+/// A map from strings based on a Radix Tree (PATRICIA trie).
+///
+/// A Radix Tree is a `Trie` where every node with a single child and no value
+/// is merged with its child, so the edges are labeled with whole substrings
+/// instead of single characters. A tree holding `n` keys has at most `2n`
+/// nodes, however long the keys are.
+#[derive(Debug)]
+pub struct RadixTree<V> {
+    root: RadixNode<V>,
+    length: usize,
+}
+
+impl<V> RadixTree<V> {
+    pub fn new() -> RadixTree<V> {
+        RadixTree {
+            root: RadixNode::new("", None),
+            length: 0,
+        }
+    }
+
+    /// Returns the number of keys in the tree.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the tree contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Inserts a value for `key`, replacing the value it had if any.
+    pub fn insert(&mut self, key: &str, value: V) {
+        let mut node = &mut self.root;
+        let mut rest = key;
+        while let Some(first) = rest.chars().next() {
+            let child = match node.children.get(&first) {
+                None => {
+                    let leaf = RadixNode::new(rest, Some((key.to_string(), value)));
+                    node.children.insert(first, leaf);
+                    self.length += 1;
+                    return;
+                }
+                Some(child) => child,
+            };
+            let common = common_prefix_len(&child.label, rest);
+            if common < child.label.len() {
+                // the edge to the child is split where `key` leaves it
+                let mut lower = node.children.remove(&first).unwrap();
+                let mut middle = RadixNode::new(&lower.label[..common], None);
+                lower.label.drain(..common);
+                middle
+                    .children
+                    .insert(lower.label.chars().next().unwrap(), lower);
+                node.children.insert(first, middle);
+            }
+            node = node.children.get_mut(&first).unwrap();
+            rest = &rest[common..];
+        }
+        if node.entry.is_none() {
+            self.length += 1;
+        }
+        node.entry = Some((key.to_string(), value));
+    }
+
+    /// Returns a reference to the value for `key`.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        let mut node = &self.root;
+        let mut rest = key;
+        while let Some(first) = rest.chars().next() {
+            node = node.children.get(&first)?;
+            rest = rest.strip_prefix(node.label.as_str())?;
+        }
+        node.entry.as_ref().map(|(_, value)| value)
+    }
+
+    /// Removes `key` from the tree, returning its value if it was there.
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        let value = self.root.remove(key)?;
+        self.length -= 1;
+        Some(value)
+    }
+
+    /// Iterates over the keys starting with `prefix` and their values, in
+    /// lexicographic order of the keys.
+    pub fn with_prefix<'a>(&'a self, prefix: &str) -> impl Iterator<Item = (&'a str, &'a V)> {
+        let mut stack = Vec::new();
+        let mut node = &self.root;
+        let mut rest = prefix;
+        loop {
+            let first = match rest.chars().next() {
+                None => {
+                    stack.push(node);
+                    break;
+                }
+                Some(first) => first,
+            };
+            match node.children.get(&first) {
+                // the prefix may end in the middle of the label
+                Some(child) if child.label.starts_with(rest) => {
+                    stack.push(child);
+                    break;
+                }
+                Some(child) if rest.starts_with(child.label.as_str()) => {
+                    rest = &rest[child.label.len()..];
+                    node = child;
+                }
+                _ => break,
+            }
+        }
+        std::iter::from_fn(move || {
+            while let Some(node) = stack.pop() {
+                stack.extend(node.children.values().rev());
+                if let Some((key, value)) = &node.entry {
+                    return Some((key.as_str(), value));
+                }
+            }
+            None
+        })
+    }
+}
+
+impl<V> Default for RadixTree<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the length in bytes of the longest common prefix of `a` and `b`.
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, x), y)| x != y)
+        .map_or(a.len().min(b.len()), |((i, _), _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 7] = [
+        "romane",
+        "romanus",
+        "romulus",
+        "rubens",
+        "ruber",
+        "rubicon",
+        "rubicundus",
+    ];
+
+    fn count_nodes<V>(node: &RadixNode<V>) -> usize {
+        1 + node.children.values().map(count_nodes).sum::<usize>()
+    }
+
+    #[test]
+    fn insert_and_get() {
+        let mut tree = RadixTree::new();
+        for (i, word) in WORDS.iter().enumerate() {
+            tree.insert(word, i);
+        }
+        assert_eq!(tree.len(), 7);
+        for (i, word) in WORDS.iter().enumerate() {
+            assert_eq!(tree.get(word), Some(&i));
+        }
+        assert_eq!(tree.get("rom"), None);
+        assert_eq!(tree.get("romanes"), None);
+        assert_eq!(tree.get("rubicund"), None);
+        assert_eq!(tree.get(""), None);
+
+        tree.insert("ruber", 10);
+        tree.insert("", 11);
+        assert_eq!(tree.len(), 8);
+        assert_eq!(tree.get("ruber"), Some(&10));
+        assert_eq!(tree.get(""), Some(&11));
+    }
+
+    #[test]
+    fn minimal_node_count() {
+        // r -> om -> an -> {e, us}, om -> ulus, r -> ub -> e -> {ns, r},
+        // ub -> ic -> {on, undus}: 13 nodes below the root
+        let mut tree = RadixTree::new();
+        for word in WORDS {
+            tree.insert(word, ());
+        }
+        assert_eq!(count_nodes(&tree.root), 14);
+
+        let mut reversed = RadixTree::new();
+        for word in WORDS.iter().rev() {
+            reversed.insert(word, ());
+        }
+        assert_eq!(count_nodes(&reversed.root), 14);
+    }
+
+    #[test]
+    fn remove() {
+        let mut tree = RadixTree::new();
+        for word in WORDS {
+            tree.insert(word, word.len());
+        }
+        assert_eq!(tree.remove("rub"), None);
+        assert_eq!(tree.remove("rubiconic"), None);
+        assert_eq!(tree.remove("ruber"), Some(5));
+        assert_eq!(tree.remove("ruber"), None);
+        assert_eq!(tree.get("rubens"), Some(&6));
+        // "e" is merged with "ns" into "ens"
+        assert_eq!(count_nodes(&tree.root), 12);
+
+        // "an" is split into "a" and "n", and merged back
+        tree.insert("roma", 4);
+        assert_eq!(count_nodes(&tree.root), 13);
+        assert_eq!(tree.remove("roma"), Some(4));
+        assert_eq!(count_nodes(&tree.root), 12);
+
+        for word in WORDS {
+            tree.remove(word);
+        }
+        assert!(tree.is_empty());
+        assert_eq!(count_nodes(&tree.root), 1);
+    }
+
+    #[test]
+    fn with_prefix() {
+        let mut tree = RadixTree::new();
+        for (i, word) in WORDS.iter().enumerate() {
+            tree.insert(word, i);
+        }
+        let keys = |prefix| tree.with_prefix(prefix).map(|(k, _)| k).collect::<Vec<_>>();
+        assert_eq!(keys(""), WORDS);
+        assert_eq!(keys("rom"), ["romane", "romanus", "romulus"]);
+        // ending in the middle of an edge
+        assert_eq!(keys("rubic"), ["rubicon", "rubicundus"]);
+        assert_eq!(keys("rubicu"), ["rubicundus"]);
+        assert_eq!(keys("ruber"), ["ruber"]);
+        assert!(keys("rubers").is_empty());
+        assert!(keys("rua").is_empty());
+        assert!(keys("x").is_empty());
+        assert_eq!(
+            tree.with_prefix("romu").collect::<Vec<_>>(),
+            [("romulus", &2)]
+        );
+    }
+
+    #[test]
+    fn multibyte_characters() {
+        let mut tree = RadixTree::new();
+        tree.insert("çay", 1);
+        tree.insert("çiçek", 2);
+        tree.insert("çiğ", 3);
+        assert_eq!(tree.get("çay"), Some(&1));
+        assert_eq!(tree.get("çiçek"), Some(&2));
+        assert_eq!(tree.get("çiğ"), Some(&3));
+        assert_eq!(tree.get("çi"), None);
+        let keys: Vec<_> = tree.with_prefix("çi").map(|(k, _)| k).collect();
+        assert_eq!(keys, ["çiçek", "çiğ"]);
+        assert_eq!(tree.remove("çiçek"), Some(2));
+        assert_eq!(tree.get("çiğ"), Some(&3));
+    }
+}
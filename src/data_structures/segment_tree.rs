@@ -1,87 +1,456 @@
+/// An associative operation with an identity element, used to aggregate ranges.
+///
+/// `combine` does not need to be commutative: the left operand always covers
+/// the positions before those of the right operand.
+pub trait Monoid: Clone {
+    fn identity() -> Self;
+    fn combine(&self, other: &Self) -> Self;
+}
+
+/// An update that can be applied lazily to a whole range of `T`s.
+pub trait LazyMonoid<T: Monoid>: Clone {
+    /// The update that changes nothing.
+    fn identity() -> Self;
+    /// Applies the update to `value`, the aggregate of a range of `len` elements.
+    fn apply(&self, value: &T, len: usize) -> T;
+    /// Returns the single update equivalent to applying `earlier`, then `self`.
+    fn compose(&self, earlier: &Self) -> Self;
+}
+
+/// Aggregates a range by summing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sum<T>(pub T);
+
+/// Aggregates a range by taking its minimum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Min<T>(pub T);
+
+/// Aggregates a range by taking its maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Max<T>(pub T);
+
+/// Adds the same value to every element of a range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeAdd<T>(pub T);
+
+/// Sets every element of a range to the same value; `None` leaves it unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeAssign<T>(pub Option<T>);
+
+macro_rules! impl_monoids {
+    ($($t:ty),*) => {
+        $(
+            impl Monoid for Sum<$t> {
+                fn identity() -> Self {
+                    Sum(0)
+                }
+                fn combine(&self, other: &Self) -> Self {
+                    Sum(self.0 + other.0)
+                }
+            }
+
+            impl Monoid for Min<$t> {
+                fn identity() -> Self {
+                    Min(<$t>::MAX)
+                }
+                fn combine(&self, other: &Self) -> Self {
+                    Min(self.0.min(other.0))
+                }
+            }
+
+            impl Monoid for Max<$t> {
+                fn identity() -> Self {
+                    Max(<$t>::MIN)
+                }
+                fn combine(&self, other: &Self) -> Self {
+                    Max(self.0.max(other.0))
+                }
+            }
+
+            impl LazyMonoid<Sum<$t>> for RangeAdd<$t> {
+                fn identity() -> Self {
+                    RangeAdd(0)
+                }
+                fn apply(&self, value: &Sum<$t>, len: usize) -> Sum<$t> {
+                    Sum(value.0 + self.0 * len as $t)
+                }
+                fn compose(&self, earlier: &Self) -> Self {
+                    RangeAdd(self.0 + earlier.0)
+                }
+            }
+
+            impl LazyMonoid<Min<$t>> for RangeAdd<$t> {
+                fn identity() -> Self {
+                    RangeAdd(0)
+                }
+                fn apply(&self, value: &Min<$t>, _len: usize) -> Min<$t> {
+                    Min(value.0 + self.0)
+                }
+                fn compose(&self, earlier: &Self) -> Self {
+                    RangeAdd(self.0 + earlier.0)
+                }
+            }
+
+            impl LazyMonoid<Max<$t>> for RangeAdd<$t> {
+                fn identity() -> Self {
+                    RangeAdd(0)
+                }
+                fn apply(&self, value: &Max<$t>, _len: usize) -> Max<$t> {
+                    Max(value.0 + self.0)
+                }
+                fn compose(&self, earlier: &Self) -> Self {
+                    RangeAdd(self.0 + earlier.0)
+                }
+            }
+
+            impl LazyMonoid<Sum<$t>> for RangeAssign<$t> {
+                fn identity() -> Self {
+                    RangeAssign(None)
+                }
+                fn apply(&self, value: &Sum<$t>, len: usize) -> Sum<$t> {
+                    self.0.map_or(*value, |x| Sum(x * len as $t))
+                }
+                fn compose(&self, earlier: &Self) -> Self {
+                    RangeAssign(self.0.or(earlier.0))
+                }
+            }
+
+            impl LazyMonoid<Min<$t>> for RangeAssign<$t> {
+                fn identity() -> Self {
+                    RangeAssign(None)
+                }
+                fn apply(&self, value: &Min<$t>, _len: usize) -> Min<$t> {
+                    self.0.map_or(*value, Min)
+                }
+                fn compose(&self, earlier: &Self) -> Self {
+                    RangeAssign(self.0.or(earlier.0))
+                }
+            }
+
+            impl LazyMonoid<Max<$t>> for RangeAssign<$t> {
+                fn identity() -> Self {
+                    RangeAssign(None)
+                }
+                fn apply(&self, value: &Max<$t>, _len: usize) -> Max<$t> {
+                    self.0.map_or(*value, Max)
+                }
+                fn compose(&self, earlier: &Self) -> Self {
+                    RangeAssign(self.0.or(earlier.0))
+                }
+            }
+        )*
+    };
+}
+
+impl_monoids!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
 /// This stucture implements a segmented tree that
 /// can efficiently answer range queries on arrays.
-pub struct SegmentTree<T: Default + Ord + Copy> {
+///
+/// Queries and point updates take `O(log n)` time.
+pub struct SegmentTree<T: Monoid> {
     len: usize,
     buf: Vec<T>,
-    op: Ops,
 }
 
-pub enum Ops {
-    Max,
-    Min,
-}
-
-impl<T: Default + Ord + Copy> SegmentTree<T> {
+impl<T: Monoid> SegmentTree<T> {
     /// function to build the tree
-    pub fn from_vec(arr: &[T], op: Ops) -> Self {
+    pub fn from_slice(arr: &[T]) -> Self {
         let len = arr.len();
-        let mut buf: Vec<T> = vec![T::default(); 2 * len];
-        buf[len..(len + len)].clone_from_slice(&arr[0..len]);
+        let mut buf: Vec<T> = vec![T::identity(); 2 * len];
+        buf[len..(len + len)].clone_from_slice(arr);
         for i in (1..len).rev() {
-            buf[i] = match op {
-                Ops::Max => buf[2 * i].max(buf[2 * i + 1]),
-                Ops::Min => buf[2 * i].min(buf[2 * i + 1]),
-            };
+            buf[i] = buf[2 * i].combine(&buf[2 * i + 1]);
         }
-        SegmentTree { len, buf, op }
+        SegmentTree { len, buf }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
     }
 
-    /// function to get sum on interval [l, r]
-    pub fn query(&self, mut l: usize, mut r: usize) -> T {
-        l += self.len;
-        r += self.len;
-        let mut res = self.buf[l];
-        while l <= r {
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// function to aggregate the interval [l, r]
+    pub fn query(&self, l: usize, r: usize) -> T {
+        assert!(l <= r && r < self.len, "invalid range [{}, {}]", l, r);
+        let mut l = l + self.len;
+        let mut r = r + self.len + 1;
+        // the left and right parts are kept apart so the order of combination is respected
+        let mut left = T::identity();
+        let mut right = T::identity();
+        while l < r {
             if l % 2 == 1 {
-                res = match self.op {
-                    Ops::Max => res.max(self.buf[l]),
-                    Ops::Min => res.min(self.buf[l]),
-                };
+                left = left.combine(&self.buf[l]);
                 l += 1;
             }
-            if r % 2 == 0 {
-                res = match self.op {
-                    Ops::Max => res.max(self.buf[r]),
-                    Ops::Min => res.min(self.buf[r]),
-                };
+            if r % 2 == 1 {
                 r -= 1;
+                right = self.buf[r].combine(&right);
             }
             l /= 2;
             r /= 2;
         }
-        res
+        left.combine(&right)
     }
 
     /// function to update a tree node
-    pub fn update(&mut self, mut idx: usize, val: T) {
-        idx += self.len;
+    pub fn update(&mut self, idx: usize, val: T) {
+        assert!(idx < self.len);
+        let mut idx = idx + self.len;
         self.buf[idx] = val;
         idx /= 2;
 
         while idx != 0 {
-            self.buf[idx] = match self.op {
-                Ops::Max => self.buf[2 * idx].max(self.buf[2 * idx + 1]),
-                Ops::Min => self.buf[2 * idx].min(self.buf[2 * idx + 1]),
-            };
+            self.buf[idx] = self.buf[2 * idx].combine(&self.buf[2 * idx + 1]);
             idx /= 2;
         }
     }
 }
 
+/// A segment tree that also supports updating whole ranges in `O(log n)`.
+///
+/// Updates of a node are stored in `lazy` and only pushed to its children
+/// when a later operation needs to look inside it.
+pub struct LazySegmentTree<T: Monoid, L: LazyMonoid<T>> {
+    len: usize,
+    tree: Vec<T>,
+    lazy: Vec<L>,
+}
+
+impl<T: Monoid, L: LazyMonoid<T>> LazySegmentTree<T, L> {
+    pub fn from_slice(arr: &[T]) -> Self {
+        let len = arr.len();
+        let size = 4 * len.max(1);
+        let mut segment_tree = LazySegmentTree {
+            len,
+            tree: vec![T::identity(); size],
+            lazy: vec![L::identity(); size],
+        };
+        if len > 0 {
+            segment_tree.build(arr, 1, 0, len - 1);
+        }
+        segment_tree
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// function to aggregate the interval [l, r]
+    pub fn query(&mut self, l: usize, r: usize) -> T {
+        assert!(l <= r && r < self.len, "invalid range [{}, {}]", l, r);
+        self.query_node(1, 0, self.len - 1, l, r)
+    }
+
+    /// function to apply `lazy` to every element of the interval [l, r]
+    pub fn range_update(&mut self, l: usize, r: usize, lazy: L) {
+        assert!(l <= r && r < self.len, "invalid range [{}, {}]", l, r);
+        self.update_node(1, 0, self.len - 1, l, r, &lazy);
+    }
+
+    fn build(&mut self, arr: &[T], node: usize, start: usize, end: usize) {
+        if start == end {
+            self.tree[node] = arr[start].clone();
+            return;
+        }
+        let mid = (start + end) / 2;
+        self.build(arr, 2 * node, start, mid);
+        self.build(arr, 2 * node + 1, mid + 1, end);
+        self.tree[node] = self.tree[2 * node].combine(&self.tree[2 * node + 1]);
+    }
+
+    fn apply(&mut self, node: usize, len: usize, lazy: &L) {
+        self.tree[node] = lazy.apply(&self.tree[node], len);
+        self.lazy[node] = lazy.compose(&self.lazy[node]);
+    }
+
+    fn push(&mut self, node: usize, start: usize, end: usize) {
+        let lazy = std::mem::replace(&mut self.lazy[node], L::identity());
+        let mid = (start + end) / 2;
+        self.apply(2 * node, mid - start + 1, &lazy);
+        self.apply(2 * node + 1, end - mid, &lazy);
+    }
+
+    fn query_node(&mut self, node: usize, start: usize, end: usize, l: usize, r: usize) -> T {
+        if r < start || end < l {
+            return T::identity();
+        }
+        if l <= start && end <= r {
+            return self.tree[node].clone();
+        }
+        self.push(node, start, end);
+        let mid = (start + end) / 2;
+        let left = self.query_node(2 * node, start, mid, l, r);
+        let right = self.query_node(2 * node + 1, mid + 1, end, l, r);
+        left.combine(&right)
+    }
+
+    fn update_node(&mut self, node: usize, start: usize, end: usize, l: usize, r: usize, lazy: &L) {
+        if r < start || end < l {
+            return;
+        }
+        if l <= start && end <= r {
+            self.apply(node, end - start + 1, lazy);
+            return;
+        }
+        self.push(node, start, end);
+        let mid = (start + end) / 2;
+        self.update_node(2 * node, start, mid, l, r, lazy);
+        self.update_node(2 * node + 1, mid + 1, end, l, r, lazy);
+        self.tree[node] = self.tree[2 * node].combine(&self.tree[2 * node + 1]);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::PCG32;
+
+    fn wrap<T: Copy, W>(values: &[T], f: fn(T) -> W) -> Vec<W> {
+        values.iter().map(|&x| f(x)).collect()
+    }
+
+    fn random_range(rng: &mut PCG32, n: usize) -> (usize, usize) {
+        let l = rng.get_u32() as usize % n;
+        let r = l + rng.get_u32() as usize % (n - l);
+        (l, r)
+    }
 
     #[test]
     fn it_works() {
         let vec = vec![1, 2, -4, 7, 3, -5, 6, 11, -20, 9, 14, 15, 5, 2, -8];
-        let min_seg_tree = SegmentTree::from_vec(&vec, Ops::Min);
-        assert_eq!(-5, min_seg_tree.query(4, 6));
-        assert_eq!(-20, min_seg_tree.query(0, vec.len() - 1));
-        let mut max_seg_tree = SegmentTree::from_vec(&vec, Ops::Max);
-        assert_eq!(6, max_seg_tree.query(4, 6));
-        assert_eq!(15, max_seg_tree.query(0, vec.len() - 1));
-        max_seg_tree.update(6, 8);
-        assert_eq!(8, max_seg_tree.query(4, 6));
+        let min_seg_tree = SegmentTree::from_slice(&wrap(&vec, Min));
+        assert_eq!(Min(-5), min_seg_tree.query(4, 6));
+        assert_eq!(Min(-20), min_seg_tree.query(0, vec.len() - 1));
+        let mut max_seg_tree = SegmentTree::from_slice(&wrap(&vec, Max));
+        assert_eq!(Max(6), max_seg_tree.query(4, 6));
+        assert_eq!(Max(15), max_seg_tree.query(0, vec.len() - 1));
+        max_seg_tree.update(6, Max(8));
+        assert_eq!(Max(8), max_seg_tree.query(4, 6));
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Concat(String);
+
+    impl Monoid for Concat {
+        fn identity() -> Self {
+            Concat(String::new())
+        }
+        fn combine(&self, other: &Self) -> Self {
+            Concat(self.0.clone() + &other.0)
+        }
+    }
+
+    #[test]
+    fn keeps_order() {
+        let letters: Vec<Concat> = "abcdefghij"
+            .chars()
+            .map(|c| Concat(c.to_string()))
+            .collect();
+        let seg_tree = SegmentTree::from_slice(&letters);
+        assert_eq!(seg_tree.query(0, 9), Concat("abcdefghij".to_string()));
+        assert_eq!(seg_tree.query(3, 6), Concat("defg".to_string()));
+        assert_eq!(seg_tree.query(5, 5), Concat("f".to_string()));
+    }
+
+    #[test]
+    fn range_sum_matches_brute_force() {
+        let mut rng = PCG32::new_default(31337);
+        let n = 100;
+        let mut values: Vec<i64> = (0..n).map(|_| rng.get_u32() as i64 % 1000).collect();
+        let mut seg_tree = SegmentTree::from_slice(&wrap(&values, Sum));
+        for _ in 0..2000 {
+            let i = rng.get_u32() as usize % n;
+            values[i] = rng.get_u32() as i64 % 1000 - 500;
+            seg_tree.update(i, Sum(values[i]));
+
+            let (l, r) = random_range(&mut rng, n);
+            assert_eq!(seg_tree.query(l, r), Sum(values[l..=r].iter().sum()));
+        }
+    }
+
+    #[test]
+    fn range_min_adversarial() {
+        let n = 64;
+        let ascending: Vec<i64> = (0..n).collect();
+        let descending: Vec<i64> = (0..n).rev().collect();
+        let constant = vec![7; n as usize];
+        let extremes: Vec<i64> = (0..n)
+            .map(|i| if i % 2 == 0 { i64::MAX } else { i64::MIN })
+            .collect();
+        let single_dip: Vec<i64> = (0..n).map(|i| if i == 37 { -1 } else { 0 }).collect();
+        for values in [ascending, descending, constant, extremes, single_dip] {
+            let seg_tree = SegmentTree::from_slice(&wrap(&values, Min));
+            for l in 0..values.len() {
+                for r in l..values.len() {
+                    let expected = *values[l..=r].iter().min().unwrap();
+                    assert_eq!(seg_tree.query(l, r), Min(expected));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_range_add() {
+        let mut rng = PCG32::new_default(4242);
+        let n = 50;
+        let mut values: Vec<i64> = (0..n).map(|_| rng.get_u32() as i64 % 100).collect();
+        let mut sums: LazySegmentTree<Sum<i64>, RangeAdd<i64>> =
+            LazySegmentTree::from_slice(&wrap(&values, Sum));
+        let mut mins: LazySegmentTree<Min<i64>, RangeAdd<i64>> =
+            LazySegmentTree::from_slice(&wrap(&values, Min));
+        for _ in 0..1000 {
+            // updates overlap each other and the queries in arbitrary ways
+            let (l, r) = random_range(&mut rng, n);
+            let delta = rng.get_u32() as i64 % 21 - 10;
+            values[l..=r].iter_mut().for_each(|x| *x += delta);
+            sums.range_update(l, r, RangeAdd(delta));
+            mins.range_update(l, r, RangeAdd(delta));
+
+            let (l, r) = random_range(&mut rng, n);
+            assert_eq!(sums.query(l, r), Sum(values[l..=r].iter().sum()));
+            assert_eq!(mins.query(l, r), Min(*values[l..=r].iter().min().unwrap()));
+        }
+    }
+
+    #[test]
+    fn lazy_range_assign() {
+        let mut rng = PCG32::new_default(2024);
+        let n = 50;
+        let mut values = vec![0u64; n];
+        let mut sums: LazySegmentTree<Sum<u64>, RangeAssign<u64>> =
+            LazySegmentTree::from_slice(&wrap(&values, Sum));
+        for _ in 0..1000 {
+            let (l, r) = random_range(&mut rng, n);
+            let value = rng.get_u64() % 1000;
+            values[l..=r].iter_mut().for_each(|x| *x = value);
+            sums.range_update(l, r, RangeAssign(Some(value)));
+
+            let (l, r) = random_range(&mut rng, n);
+            assert_eq!(sums.query(l, r), Sum(values[l..=r].iter().sum()));
+        }
+    }
+
+    #[test]
+    fn lazy_nested_updates() {
+        let mut tree: LazySegmentTree<Sum<i32>, RangeAdd<i32>> =
+            LazySegmentTree::from_slice(&[Sum(0); 8]);
+        tree.range_update(0, 7, RangeAdd(1));
+        tree.range_update(2, 5, RangeAdd(2));
+        tree.range_update(3, 4, RangeAdd(3));
+        let expected = [1, 1, 3, 6, 6, 3, 1, 1];
+        for (i, &x) in expected.iter().enumerate() {
+            assert_eq!(tree.query(i, i), Sum(x));
+        }
+        assert_eq!(tree.query(0, 7), Sum(22));
+        assert!(!tree.is_empty());
+        assert_eq!(tree.len(), 8);
     }
 }
@@ -0,0 +1,356 @@
+use std::{
+    iter::FromIterator,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::math::PCG32;
+
+/// The maximum level used by `SkipList::new`, enough for about 2^16 keys.
+pub const DEFAULT_MAX_LEVEL: usize = 16;
+
+/// An internal node of a `SkipList`.
+///
+/// `forward[l]` is the index of the next node on level `l`; a node of level
+/// `k` takes part in the levels `0..k`.
+struct SkipListNode<K, V> {
+    key: K,
+    value: V,
+    forward: Vec<Option<usize>>,
+}
+
+/// A sorted map based on a Skip List.
+///
+/// A Skip List is a sorted linked list with extra "express lanes": every node
+/// also appears on each higher level with probability 1/2, so a search can skip
+/// over most of the list by walking the top levels first and going down a level
+/// whenever it would overshoot. All operations take `O(log n)` expected time.
+///
+/// Nodes are kept in a vector and link to each other by index; removed slots
+/// are reused by later insertions.
+pub struct SkipList<K: Ord, V> {
+    head: Vec<Option<usize>>,
+    nodes: Vec<Option<SkipListNode<K, V>>>,
+    free: Vec<usize>,
+    length: usize,
+    rng: PCG32,
+}
+
+impl<K: Ord, V> SkipList<K, V> {
+    pub fn new() -> SkipList<K, V> {
+        SkipList::with_max_level(DEFAULT_MAX_LEVEL)
+    }
+
+    /// Creates an empty list whose nodes take part in at most `max_level` levels.
+    ///
+    /// Panics if `max_level` is 0.
+    pub fn with_max_level(max_level: usize) -> SkipList<K, V> {
+        assert!(max_level > 0, "a skip list needs at least one level");
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64;
+        SkipList {
+            head: vec![None; max_level],
+            nodes: Vec::new(),
+            free: Vec::new(),
+            length: 0,
+            rng: PCG32::new_default(seed),
+        }
+    }
+
+    pub fn max_level(&self) -> usize {
+        self.head.len()
+    }
+
+    /// Returns the number of keys in the list.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns a reference to the value stored for `key`.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let update = self.predecessors(key);
+        match self.next(update[0], 0) {
+            Some(index) if self.node(index).key == *key => Some(&self.node(index).value),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the list contains `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Stores `value` for `key`.
+    ///
+    /// Returns the value previously stored for `key`, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let update = self.predecessors(&key);
+        if let Some(index) = self.next(update[0], 0) {
+            let node = self.nodes[index].as_mut().unwrap();
+            if node.key == key {
+                return Some(std::mem::replace(&mut node.value, value));
+            }
+        }
+
+        let level = self.random_level();
+        let forward = (0..level).map(|l| self.next(update[l], l)).collect();
+        let node = SkipListNode {
+            key,
+            value,
+            forward,
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        for (l, &previous) in update.iter().enumerate().take(level) {
+            self.set_next(previous, l, Some(index));
+        }
+        self.length += 1;
+        None
+    }
+
+    /// Removes `key` from the list.
+    ///
+    /// Returns the value that was stored for `key`, if any.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let update = self.predecessors(key);
+        let index = self.next(update[0], 0)?;
+        if self.node(index).key != *key {
+            return None;
+        }
+
+        let node = self.nodes[index].take().unwrap();
+        for (l, &next) in node.forward.iter().enumerate() {
+            self.set_next(update[l], l, next);
+        }
+        self.free.push(index);
+        self.length -= 1;
+        Some(node.value)
+    }
+
+    /// Creates an iterator over the entries of the list, sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            list: self,
+            next: self.head[0],
+        }
+    }
+
+    fn node(&self, index: usize) -> &SkipListNode<K, V> {
+        self.nodes[index].as_ref().unwrap()
+    }
+
+    /// Returns the node following `previous` on `level`, where `None` stands for the head.
+    fn next(&self, previous: Option<usize>, level: usize) -> Option<usize> {
+        match previous {
+            Some(index) => self.node(index).forward[level],
+            None => self.head[level],
+        }
+    }
+
+    fn set_next(&mut self, previous: Option<usize>, level: usize, next: Option<usize>) {
+        match previous {
+            Some(index) => self.nodes[index].as_mut().unwrap().forward[level] = next,
+            None => self.head[level] = next,
+        }
+    }
+
+    /// Returns, for every level, the last node whose key is smaller than `key`.
+    fn predecessors(&self, key: &K) -> Vec<Option<usize>> {
+        let mut update = vec![None; self.max_level()];
+        let mut current = None;
+        for level in (0..self.max_level()).rev() {
+            while let Some(next) = self.next(current, level) {
+                if self.node(next).key >= *key {
+                    break;
+                }
+                current = Some(next);
+            }
+            update[level] = current;
+        }
+        update
+    }
+
+    /// Draws a level from the geometric distribution with p = 1/2: every
+    /// trailing one bit of a random number promotes the node one more level.
+    fn random_level(&mut self) -> usize {
+        let promotions = self.rng.get_u32().trailing_ones() as usize;
+        (1 + promotions).min(self.max_level())
+    }
+}
+
+impl<K: Ord, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SkipList<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut list = SkipList::new();
+        for (key, value) in iter {
+            list.insert(key, value);
+        }
+        list
+    }
+}
+
+/// An iterator over the entries of a `SkipList`.
+///
+/// This struct is created by the `iter` method of `SkipList`.
+pub struct Iter<'a, K: Ord, V> {
+    list: &'a SkipList<K, V>,
+    next: Option<usize>,
+}
+
+impl<'a, K: Ord, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.list.node(self.next?);
+        self.next = node.forward[0];
+        Some((&node.key, &node.value))
+    }
+}
+
+/// An owning iterator over the entries of a `SkipList`.
+///
+/// This struct is created by the `into_iter` method of `SkipList`.
+pub struct IntoIter<K, V> {
+    nodes: Vec<Option<SkipListNode<K, V>>>,
+    next: Option<usize>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes[self.next?].take().unwrap();
+        self.next = node.forward[0];
+        Some((node.key, node.value))
+    }
+}
+
+impl<K: Ord, V> IntoIterator for SkipList<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            nodes: self.nodes,
+            next: self.head[0],
+        }
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a SkipList<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SkipList;
+    use crate::math::PCG32;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn insert_and_get() {
+        let mut list = SkipList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.insert(3, "three"), None);
+        assert_eq!(list.insert(1, "one"), None);
+        assert_eq!(list.insert(2, "two"), None);
+        assert_eq!(list.insert(3, "THREE"), Some("three"));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.get(&3), Some(&"THREE"));
+        assert_eq!(list.get(&4), None);
+        assert!(list.contains_key(&1));
+        assert!(!list.contains_key(&0));
+    }
+
+    #[test]
+    fn sorted_regardless_of_insertion_order() {
+        let mut rng = PCG32::new_default(5);
+        let mut keys: Vec<u32> = (0..500).collect();
+        for i in (1..keys.len()).rev() {
+            keys.swap(i, rng.get_u32() as usize % (i + 1));
+        }
+        let list: SkipList<u32, u32> = keys.iter().map(|&k| (k, k * 2)).collect();
+        assert_eq!(list.len(), 500);
+        let entries: Vec<(u32, u32)> = list.iter().map(|(&k, &v)| (k, v)).collect();
+        let expected: Vec<(u32, u32)> = (0..500).map(|k| (k, k * 2)).collect();
+        assert_eq!(entries, expected);
+    }
+
+    #[test]
+    fn remove() {
+        let mut list: SkipList<i32, ()> = (0..10).map(|k| (k, ())).collect();
+        assert_eq!(list.remove(&5), Some(()));
+        assert_eq!(list.remove(&5), None);
+        assert_eq!(list.remove(&-1), None);
+        assert_eq!(list.remove(&100), None);
+        assert_eq!(list.len(), 9);
+        assert!(!list.contains_key(&5));
+        let keys: Vec<i32> = list.into_iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![0, 1, 2, 3, 4, 6, 7, 8, 9]);
+
+        let mut empty: SkipList<i32, i32> = SkipList::new();
+        assert_eq!(empty.remove(&0), None);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn single_level() {
+        let mut list = SkipList::with_max_level(1);
+        for k in [4, 2, 8, 6] {
+            list.insert(k, k);
+        }
+        list.remove(&2);
+        let keys: Vec<i32> = (&list).into_iter().map(|(&k, _)| k).collect();
+        assert_eq!(keys, vec![4, 6, 8]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_levels() {
+        SkipList::<i32, i32>::with_max_level(0);
+    }
+
+    #[test]
+    fn matches_btree_map() {
+        let mut rng = PCG32::new_default(1234);
+        let mut list = SkipList::with_max_level(8);
+        let mut map = BTreeMap::new();
+        for _ in 0..20000 {
+            let key = rng.get_u32() % 300;
+            match rng.get_u32() % 4 {
+                0 | 1 => {
+                    let value = rng.get_u32();
+                    assert_eq!(list.insert(key, value), map.insert(key, value));
+                }
+                2 => assert_eq!(list.remove(&key), map.remove(&key)),
+                _ => assert_eq!(list.get(&key), map.get(&key)),
+            }
+            assert_eq!(list.len(), map.len());
+        }
+        assert!(list.iter().eq(map.iter()));
+        assert!(list.into_iter().eq(map.into_iter()));
+    }
+}
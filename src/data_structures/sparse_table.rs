@@ -0,0 +1,133 @@
+/// A Sparse Table answering range minimum and maximum queries in `O(1)` time.
+///
+/// `min[j][i]` and `max[j][i]` hold the minimum and maximum of the `2^j`
+/// elements starting at `i`, for every power of two up to the length. Any
+/// interval is covered by two such blocks which may overlap, and overlapping
+/// does not change a minimum or a maximum. Building takes `O(n log n)` time.
+pub struct SparseTable<T: Ord + Copy> {
+    min: Vec<Vec<T>>,
+    max: Vec<Vec<T>>,
+    /// `log[i]` is `floor(log2(i))`, for `i` from 1 to the length
+    log: Vec<usize>,
+}
+
+impl<T: Ord + Copy> SparseTable<T> {
+    pub fn new(arr: &[T]) -> Self {
+        let n = arr.len();
+        let mut log = vec![0; n + 1];
+        for i in 2..=n {
+            log[i] = log[i / 2] + 1;
+        }
+        let mut min = vec![arr.to_vec()];
+        let mut max = vec![arr.to_vec()];
+        for j in 1..=log[n] {
+            let half = 1 << (j - 1);
+            let blocks = n + 1 - (1 << j);
+            min.push(
+                (0..blocks)
+                    .map(|i| min[j - 1][i].min(min[j - 1][i + half]))
+                    .collect(),
+            );
+            max.push(
+                (0..blocks)
+                    .map(|i| max[j - 1][i].max(max[j - 1][i + half]))
+                    .collect(),
+            );
+        }
+        SparseTable { min, max, log }
+    }
+
+    pub fn len(&self) -> usize {
+        self.min[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the minimum of the interval [l, r]
+    pub fn query_min(&self, l: usize, r: usize) -> T {
+        let (j, second) = self.blocks(l, r);
+        self.min[j][l].min(self.min[j][second])
+    }
+
+    /// Returns the maximum of the interval [l, r]
+    pub fn query_max(&self, l: usize, r: usize) -> T {
+        let (j, second) = self.blocks(l, r);
+        self.max[j][l].max(self.max[j][second])
+    }
+
+    /// The level of the two blocks covering [l, r], starting at `l` and at
+    /// the returned index
+    fn blocks(&self, l: usize, r: usize) -> (usize, usize) {
+        assert!(l <= r && r < self.len(), "invalid range [{}, {}]", l, r);
+        let j = self.log[r - l + 1];
+        (j, r + 1 - (1 << j))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparseTable;
+    use crate::math::PCG32;
+
+    #[test]
+    fn log_table() {
+        let table = SparseTable::new(&[0; 17]);
+        assert_eq!(
+            table.log[1..],
+            [0, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 3, 3, 4, 4]
+        );
+        for i in 1..=17usize {
+            assert_eq!(table.log[i], i.ilog2() as usize);
+        }
+        // one level per power of two up to the length
+        assert_eq!(table.min.len(), 5);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = PCG32::new_default(78);
+        for round in 0..5 {
+            let arr: Vec<i32> = (0..100)
+                .map(|_| (rng.get_u32() % (10 + round * 100)) as i32 - 50)
+                .collect();
+            let table = SparseTable::new(&arr);
+            for l in 0..arr.len() {
+                for r in l..arr.len() {
+                    assert_eq!(table.query_min(l, r), *arr[l..=r].iter().min().unwrap());
+                    assert_eq!(table.query_max(l, r), *arr[l..=r].iter().max().unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn single_elements() {
+        let arr = ['d', 'a', 'c', 'b'];
+        let table = SparseTable::new(&arr);
+        for (i, &c) in arr.iter().enumerate() {
+            assert_eq!(table.query_min(i, i), c);
+            assert_eq!(table.query_max(i, i), c);
+        }
+        assert_eq!(table.query_min(0, 3), 'a');
+        assert_eq!(table.query_max(1, 3), 'c');
+
+        let single = SparseTable::new(&[7u8]);
+        assert_eq!(single.len(), 1);
+        assert_eq!(single.query_min(0, 0), 7);
+        assert!(SparseTable::<u8>::new(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_range() {
+        SparseTable::new(&[1, 2, 3]).query_min(2, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        SparseTable::new(&[1, 2, 3]).query_max(0, 3);
+    }
+}
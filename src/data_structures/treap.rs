@@ -10,6 +10,7 @@ use std::{
 struct TreapNode<T: Ord> {
     value: T,
     priority: usize,
+    size: usize,
     left: Option<Box<TreapNode<T>>>,
     right: Option<Box<TreapNode<T>>>,
 }
@@ -52,6 +53,44 @@ impl<T: Ord> Treap<T> {
         false
     }
 
+    /// Returns the number of values in the tree smaller than `value`.
+    ///
+    /// Uses the subtree sizes, in expected `O(log n)` time.
+    pub fn rank(&self, value: &T) -> usize {
+        let mut rank = 0;
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = match value.cmp(&node.value) {
+                Ordering::Equal => return rank + node.size(Side::Left),
+                Ordering::Less => &node.left,
+                Ordering::Greater => {
+                    rank += node.size(Side::Left) + 1;
+                    &node.right
+                }
+            }
+        }
+        rank
+    }
+
+    /// Returns the `k`-th smallest value of the tree, counting from 0.
+    ///
+    /// Uses the subtree sizes, in expected `O(log n)` time.
+    pub fn select(&self, mut k: usize) -> Option<&T> {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            let left = node.size(Side::Left);
+            current = match k.cmp(&left) {
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Less => &node.left,
+                Ordering::Greater => {
+                    k -= left + 1;
+                    &node.right
+                }
+            }
+        }
+        None
+    }
+
     /// Adds a value to the tree
     ///
     /// Returns `true` if the tree did not yet contain the value.
@@ -84,8 +123,45 @@ impl<T: Ord> Treap<T> {
         self.length == 0
     }
 
+    /// Splits the tree into the values smaller than `key` and the values
+    /// greater than or equal to `key`.
+    pub fn split(self, key: &T) -> (Treap<T>, Treap<T>) {
+        let (left, right) = split(self.root, key);
+        (Treap::from_root(left), Treap::from_root(right))
+    }
+
+    /// Joins two trees, where every value of `left` is smaller than every value of `right`.
+    ///
+    /// Together with `split`, this allows cutting out or moving whole ranges
+    /// of values in expected `O(log n)` time.
+    pub fn merge(left: Treap<T>, right: Treap<T>) -> Treap<T> {
+        if let (Some(max), Some(min)) = (left.extreme(Side::Right), right.extreme(Side::Left)) {
+            assert!(
+                max < min,
+                "the values of left must be smaller than those of right"
+            );
+        }
+        Treap::from_root(merge(left.root, right.root))
+    }
+
+    fn from_root(root: Option<Box<TreapNode<T>>>) -> Treap<T> {
+        Treap {
+            length: root.as_ref().map_or(0, |n| n.size),
+            root,
+        }
+    }
+
+    /// Returns the value at the end of the path that always follows `side`.
+    fn extreme(&self, side: Side) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(child) = node.child(side) {
+            node = child;
+        }
+        Some(&node.value)
+    }
+
     /// Returns an iterator that visits the nodes in the tree in order.
-    fn node_iter(&self) -> NodeIter<T> {
+    fn node_iter(&self) -> NodeIter<'_, T> {
         let mut node_iter = NodeIter { stack: Vec::new() };
         // Initialize stack with path to leftmost child
         let mut child = &self.root;
@@ -97,7 +173,7 @@ impl<T: Ord> Treap<T> {
     }
 
     /// Returns an iterator that visits the values in the tree in ascending order.
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             node_iter: self.node_iter(),
         }
@@ -128,6 +204,7 @@ fn insert<T: Ord>(tree: &mut Option<Box<TreapNode<T>>>, value: T) -> bool {
         *tree = Some(Box::new(TreapNode {
             value,
             priority: rand(),
+            size: 1,
             left: None,
             right: None,
         }));
@@ -154,6 +231,7 @@ fn remove<T: Ord>(tree: &mut Option<Box<TreapNode<T>>>, value: &T) -> bool {
                         node.right = Some(right);
                         node.rotate(side);
                         remove(node.child_mut(side), value);
+                        node.update_size();
                         Some(tree.take().unwrap())
                     }
                 };
@@ -169,6 +247,48 @@ fn remove<T: Ord>(tree: &mut Option<Box<TreapNode<T>>>, value: &T) -> bool {
     }
 }
 
+/// An owned subtree of a `Treap`.
+type Tree<T> = Option<Box<TreapNode<T>>>;
+
+/// Recursive helper function for `Treap` splitting.
+fn split<T: Ord>(tree: Tree<T>, key: &T) -> (Tree<T>, Tree<T>) {
+    match tree {
+        None => (None, None),
+        Some(mut node) => {
+            if node.value < *key {
+                let (left, right) = split(node.right.take(), key);
+                node.right = left;
+                node.update_size();
+                (Some(node), right)
+            } else {
+                let (left, right) = split(node.left.take(), key);
+                node.left = right;
+                node.update_size();
+                (left, Some(node))
+            }
+        }
+    }
+}
+
+/// Recursive helper function for `Treap` merging.
+fn merge<T: Ord>(left: Tree<T>, right: Tree<T>) -> Tree<T> {
+    match (left, right) {
+        (None, tree) | (tree, None) => tree,
+        (Some(mut left), Some(mut right)) => {
+            // The root with the higher priority stays on top
+            if left.priority > right.priority {
+                left.right = merge(left.right.take(), Some(right));
+                left.update_size();
+                Some(left)
+            } else {
+                right.left = merge(Some(left), right.left.take());
+                right.update_size();
+                Some(right)
+            }
+        }
+    }
+}
+
 impl<T: Ord> TreapNode<T> {
     /// Returns a reference to the left or right child.
     fn child(&self, side: Side) -> &Option<Box<TreapNode<T>>> {
@@ -191,6 +311,16 @@ impl<T: Ord> TreapNode<T> {
         self.child(side).as_ref().map_or(0, |n| n.priority)
     }
 
+    /// Returns the number of nodes in the left or right subtree.
+    fn size(&self, side: Side) -> usize {
+        self.child(side).as_ref().map_or(0, |n| n.size)
+    }
+
+    /// Recomputes the `size` field.
+    fn update_size(&mut self) {
+        self.size = 1 + self.size(Side::Left) + self.size(Side::Right);
+    }
+
     /// Performs a left or right rotation
     fn rotate(&mut self, side: Side) {
         if self.child_mut(!side).is_none() {
@@ -199,14 +329,17 @@ impl<T: Ord> TreapNode<T> {
 
         let mut subtree = self.child_mut(!side).take().unwrap();
         *self.child_mut(!side) = subtree.child_mut(side).take();
+        self.update_size();
         // Swap root and child nodes in memory
         mem::swap(self, subtree.as_mut());
         // Set old root (subtree) as child of new root (self)
         *self.child_mut(side) = Some(subtree);
+        self.update_size();
     }
 
     /// Performs left or right tree rotations to balance this node.
     fn rebalance(&mut self) {
+        self.update_size();
         match (
             self.priority,
             self.priority(Side::Left),
@@ -220,7 +353,9 @@ impl<T: Ord> TreapNode<T> {
 
     #[cfg(test)]
     fn is_valid(&self) -> bool {
-        self.priority >= self.priority(Side::Left) && self.priority >= self.priority(Side::Right)
+        self.priority >= self.priority(Side::Left)
+            && self.priority >= self.priority(Side::Right)
+            && self.size == 1 + self.size(Side::Left) + self.size(Side::Right)
     }
 }
 
@@ -297,6 +432,7 @@ impl<'a, T: Ord> Iterator for Iter<'a, T> {
 #[cfg(test)]
 mod tests {
     use super::Treap;
+    use crate::math::PCG32;
 
     /// Returns `true` if all nodes in the tree are valid.
     fn is_valid<T: Ord>(tree: &Treap<T>) -> bool {
@@ -349,4 +485,73 @@ mod tests {
             assert!(is_valid(&tree));
         }
     }
+
+    #[test]
+    fn split() {
+        let tree: Treap<_> = (1..10).collect();
+        let (left, right) = tree.split(&4);
+        assert!((1..4).eq(left.iter().copied()));
+        assert!((4..10).eq(right.iter().copied()));
+        assert_eq!(left.len(), 3);
+        assert_eq!(right.len(), 6);
+        assert!(is_valid(&left) && is_valid(&right));
+
+        let (left, right) = Treap::merge(left, right).split(&0);
+        assert!(left.is_empty());
+        assert_eq!(right.len(), 9);
+        let (left, right) = right.split(&100);
+        assert_eq!(left.len(), 9);
+        assert!(right.is_empty());
+    }
+
+    #[test]
+    fn merge() {
+        let left: Treap<_> = (1..5).collect();
+        let right: Treap<_> = (10..15).collect();
+        let mut tree = Treap::merge(left, right);
+        assert_eq!(tree.len(), 9);
+        assert!(is_valid(&tree));
+        assert!(tree.insert(7));
+        assert!(tree.remove(&12));
+        assert!([1, 2, 3, 4, 7, 10, 11, 13, 14].iter().eq(tree.iter()));
+        assert!(Treap::merge(Treap::new(), Treap::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn merge_overlapping() {
+        let left: Treap<_> = (1..5).collect();
+        let right: Treap<_> = (4..8).collect();
+        Treap::merge(left, right);
+    }
+
+    #[test]
+    fn random_operations() {
+        let mut rng = PCG32::new_default(271828);
+        let mut tree = Treap::new();
+        let mut expected = std::collections::BTreeSet::new();
+        for _ in 0..2000 {
+            let x = rng.get_u32() % 500;
+            if rng.get_u32().is_multiple_of(3) {
+                assert_eq!(tree.remove(&x), expected.remove(&x));
+            } else {
+                assert_eq!(tree.insert(x), expected.insert(x));
+            }
+            assert_eq!(tree.len(), expected.len());
+
+            if rng.get_u32().is_multiple_of(50) {
+                assert!(is_valid(&tree));
+                assert!(tree.iter().zip(tree.iter().skip(1)).all(|(a, b)| a < b));
+                // Cutting the tree anywhere and gluing it back must not change it
+                let key = rng.get_u32() % 500;
+                let (left, right) = tree.split(&key);
+                assert!(left.iter().all(|&v| v < key));
+                assert!(right.iter().all(|&v| v >= key));
+                assert!(is_valid(&left) && is_valid(&right));
+                tree = Treap::merge(left, right);
+                assert!(is_valid(&tree));
+                assert!(tree.iter().eq(expected.iter()));
+            }
+        }
+    }
 }
@@ -7,6 +7,28 @@ struct Node<Key: Default, Type: Default> {
     value: Option<Type>,
 }
 
+impl<Key, Type> Node<Key, Type>
+where
+    Key: Default + Eq + Hash,
+    Type: Default,
+{
+    /// Removes the value at the end of `key` under this node, and every node
+    /// left on its path with neither a value nor children.
+    fn remove(&mut self, mut key: impl Iterator<Item = Key>) -> Option<Type> {
+        match key.next() {
+            None => self.value.take(),
+            Some(c) => {
+                let child = self.children.get_mut(&c)?;
+                let value = child.remove(key);
+                if child.value.is_none() && child.children.is_empty() {
+                    self.children.remove(&c);
+                }
+                value
+            }
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Trie<Key, Type>
 where
@@ -42,15 +64,63 @@ where
     where
         Key: Eq + Hash,
     {
+        self.node(key)?.value.as_ref()
+    }
+
+    /// Returns whether some key stored in the trie starts with `prefix`.
+    pub fn starts_with(&self, prefix: impl IntoIterator<Item = Key>) -> bool {
+        self.node(prefix)
+            .is_some_and(|node| node.value.is_some() || !node.children.is_empty())
+    }
+
+    /// Iterates over the keys starting with `prefix` and their values, in
+    /// lexicographic order of the keys.
+    pub fn with_prefix<'a>(
+        &'a self,
+        prefix: impl IntoIterator<Item = Key>,
+    ) -> impl Iterator<Item = (Vec<Key>, &'a Type)> + 'a
+    where
+        Key: Ord + Clone,
+    {
+        let prefix: Vec<Key> = prefix.into_iter().collect();
+        let mut stack = Vec::new();
+        if let Some(node) = self.node(prefix.iter().cloned()) {
+            stack.push((prefix, node));
+        }
+        // a key comes before the keys it is a prefix of, and the children of a
+        // node are pushed in reverse order so the smallest is visited first
+        std::iter::from_fn(move || {
+            while let Some((key, node)) = stack.pop() {
+                let mut children: Vec<_> = node.children.iter().collect();
+                children.sort_unstable_by(|a, b| b.0.cmp(a.0));
+                for (c, child) in children {
+                    let mut child_key = key.clone();
+                    child_key.push(c.clone());
+                    stack.push((child_key, child));
+                }
+                if let Some(value) = &node.value {
+                    return Some((key, value));
+                }
+            }
+            None
+        })
+    }
+
+    fn node(&self, key: impl IntoIterator<Item = Key>) -> Option<&Node<Key, Type>> {
         let mut node = &self.root;
         for c in key.into_iter() {
-            if node.children.contains_key(&c) {
-                node = node.children.get(&c).unwrap()
-            } else {
-                return None;
-            }
+            node = node.children.get(&c)?;
         }
-        node.value.as_ref()
+        Some(node)
+    }
+
+    /// Removes the value stored for `key` and returns it, or `None` if there
+    /// is none. Nodes that are no longer on the path to any value are removed.
+    pub fn remove(&mut self, key: impl IntoIterator<Item = Key>) -> Option<Type>
+    where
+        Key: Eq + Hash,
+    {
+        self.root.remove(key.into_iter())
     }
 }
 
@@ -94,4 +164,160 @@ mod tests {
         assert_eq!(trie.get(vec![42, 6, 1000]), Some(&3));
         assert_eq!(trie.get(vec![43, 44, 45]), None);
     }
+
+    #[test]
+    fn test_remove_leaf() {
+        let mut trie = Trie::new();
+        trie.insert("foo".chars(), 1);
+        trie.insert("bar".chars(), 2);
+
+        assert_eq!(trie.remove("foo".chars()), Some(1));
+        assert_eq!(trie.get("foo".chars()), None);
+        assert_eq!(trie.get("bar".chars()), Some(&2));
+        assert!(!trie.root.children.contains_key(&'f'));
+        assert_eq!(trie.remove("foo".chars()), None);
+    }
+
+    #[test]
+    fn test_remove_prefix() {
+        let mut trie = Trie::new();
+        trie.insert("foo".chars(), 1);
+        trie.insert("foobar".chars(), 2);
+
+        assert_eq!(trie.remove("foo".chars()), Some(1));
+        assert_eq!(trie.get("foo".chars()), None);
+        assert_eq!(trie.get("foobar".chars()), Some(&2));
+
+        trie.insert("foo".chars(), 1);
+        assert_eq!(trie.remove("foobar".chars()), Some(2));
+        assert_eq!(trie.get("foobar".chars()), None);
+        assert_eq!(trie.get("foo".chars()), Some(&1));
+        // the nodes for "bar" are gone, the one for "foo" stays
+        let mut node = &trie.root;
+        for c in "foo".chars() {
+            node = &node.children[&c];
+        }
+        assert!(node.children.is_empty());
+    }
+
+    #[test]
+    fn test_remove_missing() {
+        let mut trie = Trie::new();
+        assert_eq!(trie.remove(vec![1, 2]), None);
+
+        trie.insert(vec![1, 2, 3], 1);
+        assert_eq!(trie.remove(vec![1, 2]), None);
+        assert_eq!(trie.remove(vec![1, 2, 3, 4]), None);
+        assert_eq!(trie.remove(vec![4]), None);
+        assert_eq!(trie.get(vec![1, 2, 3]), Some(&1));
+
+        trie.insert(vec![], 0);
+        assert_eq!(trie.remove(vec![]), Some(0));
+        assert_eq!(trie.remove(vec![]), None);
+    }
+
+    #[test]
+    fn test_interleaved_insert_remove() {
+        let keys = ["a", "ab", "abc", "abd", "b", "bcd", "abcde"];
+        let mut trie = Trie::new();
+        let mut expected = HashMap::new();
+        for round in 0..50 {
+            let key = keys[(round * 5) % keys.len()];
+            if round % 3 == 2 {
+                assert_eq!(trie.remove(key.chars()), expected.remove(key));
+            } else {
+                trie.insert(key.chars(), round);
+                expected.insert(key, round);
+            }
+            for key in keys {
+                assert_eq!(trie.get(key.chars()), expected.get(key));
+            }
+        }
+
+        for key in keys {
+            assert_eq!(trie.remove(key.chars()), expected.remove(key));
+        }
+        assert!(trie.root.children.is_empty());
+    }
+
+    fn build_words() -> Trie<char, i32> {
+        let mut trie = Trie::new();
+        for (i, word) in ["car", "cart", "carbon", "cat", "dog", "do"]
+            .iter()
+            .enumerate()
+        {
+            trie.insert(word.chars(), i as i32);
+        }
+        trie
+    }
+
+    fn collect_prefix(trie: &Trie<char, i32>, prefix: &str) -> Vec<(String, i32)> {
+        trie.with_prefix(prefix.chars())
+            .map(|(key, &value)| (key.into_iter().collect(), value))
+            .collect()
+    }
+
+    #[test]
+    fn test_with_empty_prefix() {
+        let trie = build_words();
+        let words: Vec<String> = collect_prefix(&trie, "")
+            .into_iter()
+            .map(|(word, _)| word)
+            .collect();
+        assert_eq!(words, ["car", "carbon", "cart", "cat", "do", "dog"]);
+        assert!(trie.starts_with("".chars()));
+
+        let empty: Trie<char, i32> = Trie::new();
+        assert_eq!(empty.with_prefix("".chars()).count(), 0);
+        assert!(!empty.starts_with("".chars()));
+    }
+
+    #[test]
+    fn test_with_missing_prefix() {
+        let trie = build_words();
+        assert!(collect_prefix(&trie, "cab").is_empty());
+        assert!(collect_prefix(&trie, "doge").is_empty());
+        assert!(!trie.starts_with("cab".chars()));
+        assert!(!trie.starts_with("doge".chars()));
+    }
+
+    #[test]
+    fn test_with_prefix_of_one_key() {
+        let trie = build_words();
+        assert_eq!(collect_prefix(&trie, "cat"), [("cat".to_string(), 3)]);
+        assert_eq!(collect_prefix(&trie, "carb"), [("carbon".to_string(), 2)]);
+        assert!(trie.starts_with("cat".chars()));
+        assert!(trie.starts_with("carb".chars()));
+    }
+
+    #[test]
+    fn test_with_prefix_of_several_keys() {
+        let mut trie = build_words();
+        assert_eq!(
+            collect_prefix(&trie, "car"),
+            [
+                ("car".to_string(), 0),
+                ("carbon".to_string(), 2),
+                ("cart".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            collect_prefix(&trie, "d"),
+            [("do".to_string(), 5), ("dog".to_string(), 4)]
+        );
+
+        trie.remove("do".chars());
+        trie.remove("dog".chars());
+        assert!(!trie.starts_with("d".chars()));
+
+        let mut trie = Trie::new();
+        trie.insert(vec![2, 1], 'a');
+        trie.insert(vec![1, 3], 'b');
+        trie.insert(vec![1], 'c');
+        let all: Vec<_> = trie.with_prefix(vec![]).collect();
+        assert_eq!(
+            all,
+            [(vec![1], &'c'), (vec![1, 3], &'b'), (vec![2, 1], &'a')]
+        );
+    }
 }
@@ -1,57 +1,71 @@
 /// UnionFind data structure
+///
+/// Uses path compression and union by rank, so that any sequence of `m`
+/// operations on `n` elements runs in `O(m α(n))`.
+///
+/// The default value has no elements.
+#[derive(Debug, Clone, Default)]
 pub struct UnionFind {
     id: Vec<usize>,
-    size: Vec<usize>,
+    rank: Vec<usize>,
     count: usize,
 }
 
 impl UnionFind {
     /// Creates a new UnionFind data structure with n elements
     pub fn new(n: usize) -> Self {
-        let mut id = vec![0; n];
-        let mut size = vec![0; n];
-        for i in 0..n {
-            id[i] = i;
-            size[i] = 1;
+        Self {
+            id: (0..n).collect(),
+            rank: vec![0; n],
+            count: n,
         }
-        Self { id, size, count: n }
     }
 
-    /// Returns the parent of the element
+    /// Returns the representative of the set containing the element
     pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while root != self.id[root] {
+            root = self.id[root];
+        }
+        // path compression: point every element on the way directly to the root
         let mut x = x;
-        while x != self.id[x] {
-            x = self.id[x];
-            // self.id[x] = self.id[self.id[x]]; // path compression
+        while x != root {
+            let next = self.id[x];
+            self.id[x] = root;
+            x = next;
         }
-        x
+        root
     }
 
     /// Unions the sets containing x and y
+    ///
+    /// Returns whether they were in different sets
     pub fn union(&mut self, x: usize, y: usize) -> bool {
         let x = self.find(x);
         let y = self.find(y);
         if x == y {
             return false;
         }
-        if self.size[x] < self.size[y] {
-            self.id[x] = y;
-            self.size[y] += self.size[x];
-        } else {
-            self.id[y] = x;
-            self.size[x] += self.size[y];
+        // union by rank: attach the shallower tree under the deeper one
+        match self.rank[x].cmp(&self.rank[y]) {
+            std::cmp::Ordering::Less => self.id[x] = y,
+            std::cmp::Ordering::Greater => self.id[y] = x,
+            std::cmp::Ordering::Equal => {
+                self.id[y] = x;
+                self.rank[x] += 1;
+            }
         }
         self.count -= 1;
         true
     }
 
     /// Checks if x and y are in the same set
-    pub fn is_same_set(&mut self, x: usize, y: usize) -> bool {
+    pub fn same_component(&mut self, x: usize, y: usize) -> bool {
         self.find(x) == self.find(y)
     }
 
     /// Returns the number of disjoint sets
-    pub fn count(&self) -> usize {
+    pub fn component_count(&self) -> usize {
         self.count
     }
 }
@@ -85,6 +99,58 @@ mod tests {
         assert_eq!(uf.union(8, 9), true);
         assert_eq!(uf.union(9, 0), false);
 
-        assert_eq!(1, uf.count());
+        assert_eq!(1, uf.component_count());
+    }
+
+    #[test]
+    fn default_is_empty() {
+        let uf = UnionFind::default();
+        assert_eq!(uf.component_count(), 0);
+        assert_eq!(UnionFind::new(0).component_count(), 0);
+    }
+
+    #[test]
+    fn component_count_decreases() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.component_count(), 6);
+        assert!(uf.union(0, 1));
+        assert_eq!(uf.component_count(), 5);
+        assert!(uf.union(2, 3));
+        assert_eq!(uf.component_count(), 4);
+        assert!(!uf.union(1, 0));
+        assert_eq!(uf.component_count(), 4);
+        assert!(uf.union(1, 3));
+        assert_eq!(uf.component_count(), 3);
+
+        assert!(uf.same_component(0, 2));
+        assert!(uf.same_component(3, 3));
+        assert!(!uf.same_component(0, 4));
+        assert!(!uf.same_component(4, 5));
+    }
+
+    #[test]
+    fn find_is_idempotent() {
+        let mut uf = UnionFind::new(8);
+        for (x, y) in [(0, 1), (2, 3), (1, 3), (4, 5), (6, 4)] {
+            uf.union(x, y);
+        }
+        for x in 0..8 {
+            let root = uf.find(x);
+            assert_eq!(uf.find(root), root);
+            assert_eq!(uf.find(x), root);
+        }
+    }
+
+    #[test]
+    fn full_union() {
+        let n = 1000;
+        let mut uf = UnionFind::new(n);
+        // merge in a scattered order so that trees of every rank get linked
+        for step in 1..n {
+            assert!(uf.union((step * 7) % n, (step * 7 + 7) % n));
+        }
+        assert_eq!(uf.component_count(), 1);
+        let root = uf.find(0);
+        assert!((0..n).all(|x| uf.find(x) == root));
     }
 }
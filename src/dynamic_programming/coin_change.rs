@@ -10,26 +10,88 @@
 ///     - time complexity: O(amount * coins.length),
 ///     - space complexity: O(amount),
 pub fn coin_change(coins: &[usize], amount: usize) -> Option<usize> {
-    let mut dp = vec![None; amount + 1];
-    dp[0] = Some(0);
+    min_coins(coins, amount)
+}
+
+/// min_coins(coins, amount) returns the fewest number of coins that make up `amount`,
+/// using every coin as many times as needed, or `None` if no combination of the coins does.
+///
+/// Arguments:
+///     * `coins` - coins of different denominations
+///     * `amount` - a total amount of money be made up.
+/// Complexity
+///     - time complexity: O(amount * coins.length),
+///     - space complexity: O(amount),
+pub fn min_coins(coins: &[usize], amount: usize) -> Option<usize> {
+    min_coins_table(coins, amount)[amount].map(|(count, _)| count)
+}
+
+/// min_coins_with_selection(coins, amount) returns the coins of a combination with the fewest coins
+/// making up `amount`, from the largest to the smallest, or `None` if no combination of the coins does.
+///
+/// Complexity
+///     - time complexity: O(amount * coins.length),
+///     - space complexity: O(amount),
+pub fn min_coins_with_selection(coins: &[usize], amount: usize) -> Option<Vec<usize>> {
+    let dp = min_coins_table(coins, amount);
+    let mut selection = Vec::with_capacity(dp[amount]?.0);
+    let mut rest = amount;
+    while rest > 0 {
+        let coin = dp[rest].unwrap().1;
+        selection.push(coin);
+        rest -= coin;
+    }
+    selection.sort_unstable_by(|a, b| b.cmp(a));
+    Some(selection)
+}
+
+/// Returns for every amount `i` up to `amount` the fewest number of coins making up `i`,
+/// along with the last coin of such a combination.
+fn min_coins_table(coins: &[usize], amount: usize) -> Vec<Option<(usize, usize)>> {
+    let mut dp: Vec<Option<(usize, usize)>> = vec![None; amount + 1];
+    dp[0] = Some((0, 0));
 
     // Assume dp[i] is the fewest number of coins making up amount i,
     // then for every coin in coins, dp[i] = min(dp[i - coin] + 1).
-    for i in 0..=amount {
-        for &coin in coins {
-            if i >= coin {
-                dp[i] = match dp[i - coin] {
-                    Some(prev_coins) => match dp[i] {
-                        Some(curr_coins) => Some(curr_coins.min(prev_coins + 1)),
-                        None => Some(prev_coins + 1),
-                    },
-                    None => dp[i],
-                };
+    for i in 1..=amount {
+        for &coin in coins.iter().filter(|&&coin| coin > 0 && coin <= i) {
+            if let Some((prev_coins, _)) = dp[i - coin] {
+                if dp[i].is_none_or(|(curr_coins, _)| prev_coins + 1 < curr_coins) {
+                    dp[i] = Some((prev_coins + 1, coin));
+                }
             }
         }
     }
+    dp
+}
 
-    dp[amount]
+/// count_ways(coins, amount) returns the number of combinations of coins making up `amount`,
+/// where combinations only differing by the order of their coins count once.
+///
+/// Every denomination counts once even if it appears several times in `coins`, and coins of value 0 are ignored.
+///
+/// The count grows quickly with the amount, so it saturates: `usize::MAX` is returned
+/// for any count of `usize::MAX` or more.
+///
+/// Complexity
+///     - time complexity: O(amount * coins.length),
+///     - space complexity: O(amount),
+pub fn count_ways(coins: &[usize], amount: usize) -> usize {
+    let mut denominations: Vec<usize> = coins.iter().copied().filter(|&coin| coin > 0).collect();
+    denominations.sort_unstable();
+    denominations.dedup();
+
+    // ways[i] - the number of combinations making up `i` with the denominations seen so far.
+    // Going through the denominations in the outer loop adds the coins of a combination in a fixed order,
+    // so that every combination is counted once.
+    let mut ways = vec![0_usize; amount + 1];
+    ways[0] = 1;
+    for coin in denominations {
+        for i in coin..=amount {
+            ways[i] = ways[i].saturating_add(ways[i - coin]);
+        }
+    }
+    ways[amount]
 }
 
 #[cfg(test)]
@@ -67,4 +129,80 @@ mod tests {
         let coins = vec![10, 20, 50, 100];
         assert_eq!(None, coin_change(&coins, 5));
     }
+
+    #[test]
+    fn greedy_is_not_optimal() {
+        // greedy would take 9 + 1 + 1
+        let coins = [1, 5, 6, 9];
+        assert_eq!(min_coins(&coins, 11), Some(2));
+        assert_eq!(min_coins_with_selection(&coins, 11), Some(vec![6, 5]));
+    }
+
+    #[test]
+    fn selection() {
+        assert_eq!(
+            min_coins_with_selection(&[1, 2, 5], 11),
+            Some(vec![5, 5, 1])
+        );
+        assert_eq!(min_coins_with_selection(&[1, 2, 5], 0), Some(vec![]));
+        assert_eq!(min_coins_with_selection(&[2], 3), None);
+        assert_eq!(min_coins_with_selection(&[], 3), None);
+        let coins = [2, 3, 5, 7, 11];
+        for amount in 0..200 {
+            let selection = min_coins_with_selection(&coins, amount);
+            assert_eq!(selection.as_ref().map(Vec::len), min_coins(&coins, amount));
+            if let Some(selection) = selection {
+                assert_eq!(selection.iter().sum::<usize>(), amount);
+            }
+        }
+    }
+
+    #[test]
+    fn ways() {
+        // 1+1+1+1+1, 1+1+1+2, 1+2+2, 5
+        assert_eq!(count_ways(&[1, 2, 5], 5), 4);
+        assert_eq!(count_ways(&[2, 5, 3, 6], 10), 5);
+        assert_eq!(count_ways(&[2], 3), 0);
+        assert_eq!(count_ways(&[1, 5, 10, 25, 50, 100], 100), 293);
+    }
+
+    #[test]
+    fn ways_saturate() {
+        // with every coin up to the amount, the ways are the partitions of the amount
+        let coins: Vec<usize> = (1..=1000).collect();
+        assert_eq!(count_ways(&coins[..416], 416), 17_873_792_969_689_876_004);
+        assert_eq!(count_ways(&coins, 1000), usize::MAX);
+    }
+
+    #[test]
+    fn ways_without_coins() {
+        assert_eq!(count_ways(&[], 0), 1);
+        assert_eq!(count_ways(&[], 7), 0);
+        assert_eq!(count_ways(&[3], 0), 1);
+    }
+
+    #[test]
+    fn duplicate_coins() {
+        assert_eq!(count_ways(&[1, 2, 2, 1, 5], 5), 4);
+        assert_eq!(count_ways(&[0, 1, 2, 0, 5], 5), 4);
+        assert_eq!(min_coins(&[5, 5, 1, 1], 11), Some(3));
+        assert_eq!(min_coins(&[0, 3], 6), Some(2));
+    }
+
+    #[test]
+    fn ways_are_combinations() {
+        // brute force over how many coins of every denomination are used
+        fn combinations(coins: &[usize], amount: usize) -> usize {
+            match coins.split_first() {
+                None => usize::from(amount == 0),
+                Some((&coin, rest)) => (0..=amount / coin)
+                    .map(|k| combinations(rest, amount - k * coin))
+                    .sum(),
+            }
+        }
+        let coins = [3, 4, 7, 10];
+        for amount in 0..60 {
+            assert_eq!(count_ways(&coins, amount), combinations(&coins, amount));
+        }
+    }
 }
@@ -0,0 +1,99 @@
+// Counts the distinct ways t appears as a subsequence of s, that is the sets
+// of positions in s whose characters, in order, spell t.
+//
+// ways[i][j] is the count for s[0..i] and t[0..j]: the subsequences that do
+// not use s[i-1], plus, if s[i-1] == t[j-1], the ones that end with it:
+//   ways[i][j] = ways[i-1][j] + (s[i-1] == t[j-1]) * ways[i-1][j-1]
+// Only the row for the previous i is needed, so this takes O(|s| |t|) time
+// and O(|t|) space.
+//
+// The count grows like a binomial coefficient, so it saturates: u64::MAX is
+// returned for any count of u64::MAX or more.
+pub fn distinct_subsequences(s: &str, t: &str) -> u64 {
+    let t: Vec<char> = t.chars().collect();
+    let mut ways = vec![0u64; t.len() + 1];
+    // the empty string appears once in any string
+    ways[0] = 1;
+
+    for c in s.chars() {
+        // backwards, so that ways[j - 1] is still in the previous row
+        for j in (1..=t.len()).rev() {
+            if t[j - 1] == c {
+                ways[j] = ways[j].saturating_add(ways[j - 1]);
+            }
+        }
+    }
+    ways[t.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    // Counts the subsequences by trying every way to pick each character.
+    fn brute_force(s: &[u8], t: &[u8]) -> u64 {
+        match (s.split_first(), t.split_first()) {
+            (_, None) => 1,
+            (None, Some(_)) => 0,
+            (Some((a, s_rest)), Some((b, t_rest))) => {
+                let skipped = brute_force(s_rest, t);
+                if a == b {
+                    skipped + brute_force(s_rest, t_rest)
+                } else {
+                    skipped
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn classic_examples() {
+        assert_eq!(distinct_subsequences("rabbbit", "rabbit"), 3);
+        assert_eq!(distinct_subsequences("babgbag", "bag"), 5);
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(distinct_subsequences("", ""), 1);
+        assert_eq!(distinct_subsequences("abc", ""), 1);
+        assert_eq!(distinct_subsequences("", "a"), 0);
+        assert_eq!(distinct_subsequences("ab", "abc"), 0);
+        assert_eq!(distinct_subsequences("abc", "abc"), 1);
+        assert_eq!(distinct_subsequences("abc", "d"), 0);
+        assert_eq!(distinct_subsequences("abc", "ca"), 0);
+        assert_eq!(distinct_subsequences("ñaña", "ña"), 3);
+    }
+
+    #[test]
+    fn saturates() {
+        // C(64, 32) still fits, C(100, 50) does not
+        let a = |n| "a".repeat(n);
+        assert_eq!(
+            distinct_subsequences(&a(64), &a(32)),
+            1_832_624_140_942_590_534
+        );
+        assert_eq!(distinct_subsequences(&a(100), &a(50)), u64::MAX);
+        assert_eq!(distinct_subsequences(&a(1000), &a(500)), u64::MAX);
+        assert_eq!(distinct_subsequences(&a(1000), &a(1)), 1000);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = PCG32::new_default(96);
+        for _ in 0..1000 {
+            let s: Vec<u8> = (0..rng.get_u32() % 12)
+                .map(|_| b"ab"[rng.get_u32() as usize % 2])
+                .collect();
+            let t: Vec<u8> = (0..rng.get_u32() % 5)
+                .map(|_| b"ab"[rng.get_u32() as usize % 2])
+                .collect();
+            let (s_str, t_str) = (String::from_utf8_lossy(&s), String::from_utf8_lossy(&t));
+            assert_eq!(
+                distinct_subsequences(&s_str, &t_str),
+                brute_force(&s, &t),
+                "{s_str} {t_str}"
+            );
+        }
+    }
+}
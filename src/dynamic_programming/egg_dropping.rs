@@ -50,9 +50,75 @@ pub fn egg_drop(eggs: u32, floors: u32) -> u32 {
     egg_drops[eggs_index][floors_index]
 }
 
+/// `floors_covered(trials, eggs, limit)` returns the number of floors among which
+///     the critical floor can be found with `trials` droppings and `eggs` eggs,
+///     or `limit` if it is more
+///
+/// The first drop is from the floor above the floors covered with one trial and
+///     egg less, the ones below if the egg breaks, and the others above if not, so
+///     f(t, e) = f(t - 1, e - 1) + 1 + f(t - 1, e), which solves to the sum of the
+///     binomial coefficients C(t, i) for i from 1 to e.
+fn floors_covered(trials: usize, eggs: usize, limit: usize) -> usize {
+    let (mut covered, mut binomial) = (0_u128, 1_u128);
+    for i in 1..=eggs.min(trials) {
+        binomial = binomial * (trials - i + 1) as u128 / i as u128;
+        covered += binomial;
+        if covered >= limit as u128 {
+            return limit;
+        }
+    }
+    covered as usize
+}
+
+/// `egg_drop_trials(eggs, floors)` returns the least number of egg droppings
+///     required in the worst case to determine the highest floor from which
+///     an egg will not break upon dropping
+///
+/// Rather than trying every first floor for every number of floors, it looks for
+///     the fewest trials covering `floors` floors by binary search, which takes
+///     O(eggs * log(floors)) time.
+///
+/// Assumptions: eggs > 0
+pub fn egg_drop_trials(eggs: usize, floors: usize) -> usize {
+    assert!(eggs > 0);
+    // floors trials are always enough, dropping from every floor in turn
+    let (mut low, mut high) = (0, floors);
+    while low < high {
+        let trials = low + (high - low) / 2;
+        if floors_covered(trials, eggs, floors) >= floors {
+            high = trials;
+        } else {
+            low = trials + 1;
+        }
+    }
+    low
+}
+
+/// `egg_drop_strategy(eggs, floors)` returns the floors to drop the first egg from,
+///     in order, in an optimal strategy, until it breaks
+///
+/// Once it breaks the same strategy goes on with one egg less among the floors
+///     between the last two drops. With 2 eggs and t trials the first drop is from
+///     floor t, then t - 1 floors higher, then t - 2 floors higher, and so on, and
+///     after a break the second egg tries the floors in between one by one.
+///
+/// Assumptions: eggs > 0
+pub fn egg_drop_strategy(eggs: usize, floors: usize) -> Vec<usize> {
+    let mut trials = egg_drop_trials(eggs, floors);
+    let mut strategy = Vec::with_capacity(trials);
+    let mut floor = 0;
+    while floor < floors {
+        trials -= 1;
+        // the floors below are left to the other eggs if this one breaks
+        floor = (floor + floors_covered(trials, eggs - 1, floors) + 1).min(floors);
+        strategy.push(floor);
+    }
+    strategy
+}
+
 #[cfg(test)]
 mod tests {
-    use super::egg_drop;
+    use super::{egg_drop, egg_drop_strategy, egg_drop_trials};
 
     #[test]
     fn zero_floors() {
@@ -88,4 +154,80 @@ mod tests {
     fn large_floors() {
         assert_eq!(egg_drop(2, 100), 14);
     }
+
+    #[test]
+    fn trials_classic() {
+        assert_eq!(egg_drop_trials(2, 100), 14);
+        assert_eq!(egg_drop_trials(2, 36), 8);
+        assert_eq!(egg_drop_trials(3, 5), 3);
+        assert_eq!(egg_drop_trials(5, 0), 0);
+        assert_eq!(egg_drop_trials(3, 1), 1);
+    }
+
+    #[test]
+    fn trials_one_egg() {
+        for floors in 0..50 {
+            assert_eq!(egg_drop_trials(1, floors), floors);
+            assert_eq!(
+                egg_drop_strategy(1, floors),
+                (1..=floors).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn trials_many_eggs() {
+        // with enough eggs, the floors are found by binary search
+        for floors in 1..2000_usize {
+            let binary_search = (usize::BITS - floors.leading_zeros()) as usize;
+            assert_eq!(egg_drop_trials(binary_search, floors), binary_search);
+            assert_eq!(egg_drop_trials(64, floors), binary_search);
+        }
+        assert_eq!(egg_drop_trials(64, usize::MAX), 64);
+    }
+
+    #[test]
+    fn trials_match_table() {
+        for eggs in 1..5 {
+            for floors in 0..60 {
+                assert_eq!(
+                    egg_drop_trials(eggs, floors),
+                    egg_drop(eggs as u32, floors as u32) as usize
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn two_egg_strategy() {
+        assert_eq!(
+            egg_drop_strategy(2, 100),
+            vec![14, 27, 39, 50, 60, 69, 77, 84, 90, 95, 99, 100]
+        );
+        assert_eq!(egg_drop_strategy(2, 0), vec![]);
+        assert_eq!(egg_drop_strategy(2, 3), vec![2, 3]);
+    }
+
+    #[test]
+    fn two_egg_strategy_is_optimal() {
+        for floors in 1..200 {
+            let strategy = egg_drop_strategy(2, floors);
+            let trials = egg_drop_trials(2, floors);
+            // the egg survives a drop from `critical` and breaks above it
+            for critical in 0..=floors {
+                let mut count = 0;
+                let mut below = 0;
+                for &floor in &strategy {
+                    count += 1;
+                    if floor > critical {
+                        // the second egg goes up one floor at a time
+                        count += (floor - below - 1).min(critical - below + 1);
+                        break;
+                    }
+                    below = floor;
+                }
+                assert!(count <= trials, "{floors} floors, critical {critical}");
+            }
+        }
+    }
 }
@@ -85,6 +85,48 @@ pub fn knapsack(w: usize, weights: Vec<usize>, values: Vec<usize>) -> (usize, us
     (m[n][w], total_weight, items)
 }
 
+/// knapsack_01(weights, values, capacity) returns the maximum value of a set of items
+/// whose weights sum to at most `capacity`, along with the indices of those items (from 0 to `n - 1`, increasing)
+///
+/// Arguments:
+///     * `weights` - set of weights for each item
+///     * `values` - set of values for each item
+///     * `capacity` - knapsack capacity
+///
+/// Complexity
+///     - time complexity: O(nw),
+///     - space complexity: O(w) values, plus O(nw) bits to recover the items,
+///
+/// where `n` and `w` are `number of items` and `knapsack capacity`
+pub fn knapsack_01(weights: &[usize], values: &[usize], capacity: usize) -> (usize, Vec<usize>) {
+    assert_eq!(weights.len(), values.len(), "Number of items in the list of weights doesn't match the number of items in the list of values!");
+    // best[j] - the maximum value with weight at most `j` using the items seen so far
+    let mut best: Vec<usize> = vec![0; capacity + 1];
+    // keep[i][j] - whether item `i` is part of the best knapsack of weight at most `j` using items up to `i`
+    let mut keep: Vec<Vec<bool>> = vec![vec![false; capacity + 1]; weights.len()];
+
+    for (i, (&weight, &value)) in weights.iter().zip(values).enumerate() {
+        // Going down keeps best[j - weight] at its value without item `i`
+        for j in (weight..=capacity).rev() {
+            if best[j - weight] + value > best[j] {
+                best[j] = best[j - weight] + value;
+                keep[i][j] = true;
+            }
+        }
+    }
+
+    let mut items: Vec<usize> = Vec::new();
+    let mut j = capacity;
+    for i in (0..weights.len()).rev() {
+        if keep[i][j] {
+            items.push(i);
+            j -= weights[i];
+        }
+    }
+    items.reverse();
+    (best[capacity], items)
+}
+
 #[cfg(test)]
 mod tests {
     // Took test datasets from https://people.sc.fsu.edu/~jburkardt/datasets/bin_packing/bin_packing.html
@@ -145,4 +187,48 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_knapsack_01_textbook() {
+        assert_eq!(
+            knapsack_01(&[2, 3, 4, 5], &[3, 4, 5, 6], 5),
+            (7, vec![0, 1])
+        );
+        assert_eq!(
+            knapsack_01(&[12, 7, 11, 8, 9], &[24, 13, 23, 15, 16], 26),
+            (51, vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn test_knapsack_01_edge_cases() {
+        assert_eq!(knapsack_01(&[], &[], 10), (0, vec![]));
+        assert_eq!(knapsack_01(&[1, 2], &[5, 6], 0), (0, vec![]));
+        assert_eq!(knapsack_01(&[11, 20, 15], &[5, 6, 7], 10), (0, vec![]));
+        // items without weight always fit
+        assert_eq!(knapsack_01(&[0, 3], &[4, 5], 2), (4, vec![0]));
+    }
+
+    #[test]
+    fn test_knapsack_01_beats_greedy() {
+        // by value per weight, greedy takes the first two items and is then stuck at 160
+        assert_eq!(
+            knapsack_01(&[10, 20, 30], &[60, 100, 120], 50),
+            (220, vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn test_knapsack_01_matches_full_table() {
+        let weights = vec![23, 31, 29, 44, 53, 38, 63, 85, 89, 82];
+        let values = vec![92, 57, 49, 68, 60, 43, 67, 84, 87, 72];
+        for capacity in [0, 30, 100, 165, 300, 700] {
+            let (value, _, items) = knapsack(capacity, weights.clone(), values.clone());
+            let (value_01, items_01) = knapsack_01(&weights, &values, capacity);
+            assert_eq!(value_01, value);
+            assert_eq!(items_01, items.iter().map(|i| i - 1).collect::<Vec<_>>());
+            assert!(items_01.iter().map(|&i| weights[i]).sum::<usize>() <= capacity);
+            assert_eq!(items_01.iter().map(|&i| values[i]).sum::<usize>(), value_01);
+        }
+    }
 }
@@ -2,37 +2,24 @@
 
 /// longest_common_subsequence(a, b) returns the longest common subsequence
 /// between the strings a and b.
+///
+/// Among several longest ones, it returns the one the table of
+/// "Introduction to Algorithms" leads to, such as "BCAB" for "ABCBDAB" and
+/// "BDCAB".
 pub fn longest_common_subsequence(a: &str, b: &str) -> String {
     let a: Vec<_> = a.chars().collect();
     let b: Vec<_> = b.chars().collect();
-    let (na, nb) = (a.len(), b.len());
-
-    // solutions[i][j] is the length of the longest common subsequence
-    // between a[0..i-1] and b[0..j-1]
-    let mut solutions = vec![vec![0; nb + 1]; na + 1];
-
-    for (i, ci) in a.iter().enumerate() {
-        for (j, cj) in b.iter().enumerate() {
-            // if ci == cj, there is a new common character;
-            // otherwise, take the best of the two solutions
-            // at (i-1,j) and (i,j-1)
-            solutions[i + 1][j + 1] = if ci == cj {
-                solutions[i][j] + 1
-            } else {
-                solutions[i][j + 1].max(solutions[i + 1][j])
-            }
-        }
-    }
+    let solutions = solutions(&a, &b);
 
     // reconstitute the solution string from the lengths
     let mut result: Vec<char> = Vec::new();
-    let (mut i, mut j) = (na, nb);
+    let (mut i, mut j) = (a.len(), b.len());
     while i > 0 && j > 0 {
         if a[i - 1] == b[j - 1] {
             result.push(a[i - 1]);
             i -= 1;
             j -= 1;
-        } else if solutions[i - 1][j] > solutions[i][j - 1] {
+        } else if solutions[i - 1][j] >= solutions[i][j - 1] {
             i -= 1;
         } else {
             j -= 1;
@@ -43,9 +30,100 @@ pub fn longest_common_subsequence(a: &str, b: &str) -> String {
     result.iter().collect()
 }
 
+/// lcs_length(a, b) returns the length of the longest common subsequence
+/// between the strings a and b, in characters.
+///
+/// It only keeps one row of the table, over the shorter string, so it takes
+/// O(min(|a|, |b|)) space.
+pub fn lcs_length(a: &str, b: &str) -> usize {
+    let a: Vec<_> = a.chars().collect();
+    let b: Vec<_> = b.chars().collect();
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+
+    // row[j] is the length of the longest common subsequence between the
+    // part of long seen so far and short[0..j-1]
+    let mut row = vec![0; short.len() + 1];
+    for ci in long {
+        // the value of row[j - 1] in the previous row
+        let mut diagonal = 0;
+        for (j, cj) in short.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ci == *cj {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[short.len()]
+}
+
+/// An operation of a character-level diff, turning one string into another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    /// A character of both strings.
+    Keep(char),
+    /// A character of the new string only.
+    Insert(char),
+    /// A character of the old string only.
+    Delete(char),
+}
+
+/// diff(a, b) returns the operations turning a into b, keeping the characters
+/// of a longest common subsequence between them.
+///
+/// Between two kept characters, the deletions come before the insertions.
+pub fn diff(a: &str, b: &str) -> Vec<DiffOp> {
+    let a: Vec<_> = a.chars().collect();
+    let b: Vec<_> = b.chars().collect();
+    let solutions = solutions(&a, &b);
+
+    // walk the table back from the end, so the insertions are found first
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && a[i - 1] == b[j - 1] {
+            ops.push(DiffOp::Keep(a[i - 1]));
+            i -= 1;
+            j -= 1;
+        } else if j > 0 && (i == 0 || solutions[i][j - 1] >= solutions[i - 1][j]) {
+            ops.push(DiffOp::Insert(b[j - 1]));
+            j -= 1;
+        } else {
+            ops.push(DiffOp::Delete(a[i - 1]));
+            i -= 1;
+        }
+    }
+
+    ops.reverse();
+    ops
+}
+
+/// solutions(a, b)[i][j] is the length of the longest common subsequence
+/// between a[0..i-1] and b[0..j-1]
+fn solutions(a: &[char], b: &[char]) -> Vec<Vec<usize>> {
+    let mut solutions = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, ci) in a.iter().enumerate() {
+        for (j, cj) in b.iter().enumerate() {
+            // if ci == cj, there is a new common character;
+            // otherwise, take the best of the two solutions
+            // at (i-1,j) and (i,j-1)
+            solutions[i + 1][j + 1] = if ci == cj {
+                solutions[i][j] + 1
+            } else {
+                solutions[i][j + 1].max(solutions[i + 1][j])
+            }
+        }
+    }
+    solutions
+}
+
 #[cfg(test)]
 mod tests {
-    use super::longest_common_subsequence;
+    use super::*;
+    use crate::math::PCG32;
 
     #[test]
     fn test_longest_common_subsequence() {
@@ -70,4 +148,95 @@ mod tests {
             "世界"
         );
     }
+
+    /// Applies the operations to a, checking that they keep its characters.
+    fn apply(a: &str, ops: &[DiffOp]) -> String {
+        let mut a = a.chars();
+        let mut result = String::new();
+        for op in ops {
+            match *op {
+                DiffOp::Keep(c) => {
+                    assert_eq!(a.next(), Some(c));
+                    result.push(c);
+                }
+                DiffOp::Insert(c) => result.push(c),
+                DiffOp::Delete(c) => assert_eq!(a.next(), Some(c)),
+            }
+        }
+        assert_eq!(a.next(), None);
+        result
+    }
+
+    #[test]
+    fn known_example() {
+        assert_eq!(&longest_common_subsequence("ABCBDAB", "BDCAB"), "BCAB");
+        assert_eq!(lcs_length("ABCBDAB", "BDCAB"), 4);
+        assert_eq!(lcs_length("BDCAB", "ABCBDAB"), 4);
+    }
+
+    #[test]
+    fn lengths() {
+        assert_eq!(lcs_length("", ""), 0);
+        assert_eq!(lcs_length("", "abcd"), 0);
+        assert_eq!(lcs_length("abcd", ""), 0);
+        assert_eq!(lcs_length("abcd", "abcd"), 4);
+        assert_eq!(lcs_length("abcd", "efgh"), 0);
+        assert_eq!(lcs_length("aggtab", "gxtxayb"), 4);
+        assert_eq!(lcs_length("你好，世界", "再见世界"), 2);
+    }
+
+    #[test]
+    fn diffs() {
+        use DiffOp::*;
+        assert_eq!(diff("", ""), vec![]);
+        assert_eq!(diff("", "ab"), vec![Insert('a'), Insert('b')]);
+        assert_eq!(diff("ab", ""), vec![Delete('a'), Delete('b')]);
+        assert_eq!(diff("abc", "abc"), vec![Keep('a'), Keep('b'), Keep('c')]);
+        assert_eq!(
+            diff("ab", "cd"),
+            vec![Delete('a'), Delete('b'), Insert('c'), Insert('d')]
+        );
+        assert_eq!(
+            diff("kitten", "sitting"),
+            vec![
+                Delete('k'),
+                Insert('s'),
+                Keep('i'),
+                Keep('t'),
+                Keep('t'),
+                Delete('e'),
+                Insert('i'),
+                Keep('n'),
+                Insert('g'),
+            ]
+        );
+    }
+
+    #[test]
+    fn random_strings() {
+        let mut rng = PCG32::new_default(93);
+        for _ in 0..500 {
+            let len_a = rng.get_u32() % 15;
+            let len_b = rng.get_u32() % 15;
+            let mut random_string = |len| -> String {
+                (0..len)
+                    .map(|_| (b'a' + (rng.get_u32() % 4) as u8) as char)
+                    .collect()
+            };
+            let (a, b) = (random_string(len_a), random_string(len_b));
+
+            let subsequence = longest_common_subsequence(&a, &b);
+            assert_eq!(subsequence.chars().count(), lcs_length(&a, &b));
+            let ops = diff(&a, &b);
+            assert_eq!(apply(&a, &ops), b);
+            let kept: String = ops
+                .iter()
+                .filter_map(|op| match op {
+                    DiffOp::Keep(c) => Some(*c),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(kept.len(), subsequence.len());
+        }
+    }
 }
@@ -45,9 +45,27 @@ pub fn longest_increasing_subsequence<T: Ord + Clone>(input_array: &[T]) -> Vec<
     out.into_iter().rev().collect()
 }
 
+/// Returns the length of the longest strictly increasing subsequence of `seq`,
+/// in `O(n log n)` time.
+///
+/// See `longest_increasing_subsequence`.
+pub fn lis_length(seq: &[i64]) -> usize {
+    longest_increasing_subsequence(seq).len()
+}
+
+/// Returns one of the longest strictly increasing subsequences of `seq`, in
+/// `O(n log n)` time.
+///
+/// Equal elements never follow each other in it, so `[2, 2, 2]` gives `[2]`.
+/// See `longest_increasing_subsequence`.
+pub fn lis(seq: &[i64]) -> Vec<i64> {
+    longest_increasing_subsequence(seq)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::longest_increasing_subsequence;
+    use super::{lis, lis_length, longest_increasing_subsequence};
+    use crate::math::PCG32;
 
     #[test]
     /// Need to specify generic type T in order to function
@@ -106,4 +124,74 @@ mod tests {
     fn test_negative_elements() {
         assert_eq!(longest_increasing_subsequence(&vec![-2, -1]), vec![-2, -1]);
     }
+
+    /// Computes the length in O(n^2): every element extends the best subsequence
+    /// ending at a smaller element before it.
+    fn quadratic_lis_length(seq: &[i64]) -> usize {
+        let mut lengths = vec![1; seq.len()];
+        for i in 0..seq.len() {
+            for j in 0..i {
+                if seq[j] < seq[i] {
+                    lengths[i] = lengths[i].max(lengths[j] + 1);
+                }
+            }
+        }
+        lengths.into_iter().max().unwrap_or(0)
+    }
+
+    fn assert_is_increasing_subsequence(sub: &[i64], seq: &[i64]) {
+        assert!(sub.windows(2).all(|w| w[0] < w[1]));
+        let mut rest = seq.iter();
+        assert!(sub.iter().all(|x| rest.any(|y| y == x)));
+    }
+
+    #[test]
+    fn test_lis_examples() {
+        assert_eq!(lis_length(&[]), 0);
+        assert_eq!(lis(&[]), vec![]);
+        assert_eq!(lis(&[42]), vec![42]);
+        assert_eq!(lis(&[10, 9, 2, 5, 3, 7, 101, 18]), vec![2, 3, 7, 18]);
+        assert_eq!(lis(&[0, 1, 0, 3, 2, 3]), vec![0, 1, 2, 3]);
+        assert_eq!(lis(&[-2, -1]), vec![-2, -1]);
+    }
+
+    #[test]
+    fn test_lis_monotonic() {
+        let decreasing: Vec<i64> = (0..100).rev().collect();
+        assert_eq!(lis_length(&decreasing), 1);
+        assert_eq!(lis(&decreasing).len(), 1);
+
+        let sorted: Vec<i64> = (-50..50).collect();
+        assert_eq!(lis_length(&sorted), 100);
+        assert_eq!(lis(&sorted), sorted);
+    }
+
+    #[test]
+    fn test_lis_duplicates() {
+        // equal elements do not make a subsequence increasing
+        assert_eq!(lis(&[7, 7, 7, 7]), vec![7]);
+        assert_eq!(lis_length(&[1, 2, 2, 3, 3, 3]), 3);
+        assert_eq!(lis(&[3, 1, 2, 2, 1, 3]), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_lis_against_quadratic() {
+        let mut rng = PCG32::new_default(43);
+        for (n, range) in [
+            (10, 5),
+            (100, 50),
+            (1000, 1 << 20),
+            (10_000, 1000),
+            (10_000, 1 << 30),
+        ] {
+            let seq: Vec<i64> = (0..n)
+                .map(|_| (rng.get_u32() % range) as i64 - range as i64 / 2)
+                .collect();
+            let expected = quadratic_lis_length(&seq);
+            assert_eq!(lis_length(&seq), expected);
+            let sub = lis(&seq);
+            assert_eq!(sub.len(), expected);
+            assert_is_increasing_subsequence(&sub, &seq);
+        }
+    }
 }
@@ -0,0 +1,131 @@
+/// Matrix Chain Multiplication
+/// Given the dimensions of a chain of matrices, find the order of the products computing
+/// their product with the fewest scalar multiplications.
+/// https://en.wikipedia.org/wiki/Matrix_chain_multiplication
+///
+/// Matrix `i` of the chain is `dims[i] x dims[i + 1]`, so there are `dims.len() - 1` matrices.
+/// Returns the minimum number of scalar multiplications, and the table where `split[i][j]`
+/// is the last matrix of the left factor in the optimal product of matrices `i` through `j`.
+///
+/// Arguments:
+///     * `dims` - the dimensions shared by consecutive matrices
+/// Complexity
+///     - time complexity: O(n^3),
+///     - space complexity: O(n^2),
+pub fn matrix_chain_order(dims: &[usize]) -> (usize, Vec<Vec<usize>>) {
+    let n = dims.len().saturating_sub(1);
+    // cost[i][j] - the fewest multiplications computing the product of matrices `i` through `j`
+    let mut cost = vec![vec![0_usize; n]; n];
+    let mut split = vec![vec![0_usize; n]; n];
+
+    // Products of more matrices only depend on products of fewer ones
+    for length in 2..=n {
+        for i in 0..=n - length {
+            let j = i + length - 1;
+            cost[i][j] = usize::MAX;
+            for k in i..j {
+                let candidate = cost[i][k] + cost[k + 1][j] + dims[i] * dims[k + 1] * dims[j + 1];
+                if candidate < cost[i][j] {
+                    cost[i][j] = candidate;
+                    split[i][j] = k;
+                }
+            }
+        }
+    }
+
+    let total = if n == 0 { 0 } else { cost[0][n - 1] };
+    (total, split)
+}
+
+/// print_optimal_parens(split, i, j) renders the optimal product of matrices `i` through `j`
+/// found by `matrix_chain_order`, naming the matrices `A`, `B`, `C`... in order, such as `((AB)(CD))`.
+///
+/// Panics if the chain has more than 26 matrices.
+pub fn print_optimal_parens(split: &[Vec<usize>], i: usize, j: usize) -> String {
+    assert!(j < 26, "only 26 matrices can be named");
+    if i == j {
+        return char::from(b'A' + i as u8).to_string();
+    }
+    let k = split[i][j];
+    format!(
+        "({}{})",
+        print_optimal_parens(split, i, k),
+        print_optimal_parens(split, k + 1, j)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Computes the number of multiplications of the product given by the split table.
+    fn split_cost(dims: &[usize], split: &[Vec<usize>], i: usize, j: usize) -> usize {
+        if i == j {
+            return 0;
+        }
+        let k = split[i][j];
+        split_cost(dims, split, i, k)
+            + split_cost(dims, split, k + 1, j)
+            + dims[i] * dims[k + 1] * dims[j + 1]
+    }
+
+    /// Tries every parenthesization.
+    fn brute_force(dims: &[usize], i: usize, j: usize) -> usize {
+        (i..j)
+            .map(|k| {
+                brute_force(dims, i, k)
+                    + brute_force(dims, k + 1, j)
+                    + dims[i] * dims[k + 1] * dims[j + 1]
+            })
+            .min()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn textbook_example() {
+        let dims = [30, 35, 15, 5, 10, 20, 25];
+        let (total, split) = matrix_chain_order(&dims);
+        assert_eq!(total, 15125);
+        assert_eq!(print_optimal_parens(&split, 0, 5), "((A(BC))((DE)F))");
+        assert_eq!(split_cost(&dims, &split, 0, 5), total);
+    }
+
+    #[test]
+    fn short_chains() {
+        assert_eq!(matrix_chain_order(&[]).0, 0);
+        assert_eq!(matrix_chain_order(&[5]).0, 0);
+
+        let (total, split) = matrix_chain_order(&[10, 20]);
+        assert_eq!(total, 0);
+        assert_eq!(print_optimal_parens(&split, 0, 0), "A");
+
+        let (total, split) = matrix_chain_order(&[10, 20, 30]);
+        assert_eq!(total, 6000);
+        assert_eq!(print_optimal_parens(&split, 0, 1), "(AB)");
+    }
+
+    #[test]
+    fn order_matters() {
+        // (AB)C costs 10*100*5 + 10*5*50 = 7500, A(BC) costs 100*5*50 + 10*100*50 = 75000
+        let (total, split) = matrix_chain_order(&[10, 100, 5, 50]);
+        assert_eq!(total, 7500);
+        assert_eq!(print_optimal_parens(&split, 0, 2), "((AB)C)");
+
+        let (_, split) = matrix_chain_order(&[40, 20, 30, 10, 30]);
+        assert_eq!(print_optimal_parens(&split, 0, 3), "((A(BC))D)");
+        // a sub-chain can be printed as well
+        assert_eq!(print_optimal_parens(&split, 1, 2), "(BC)");
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = crate::math::PCG32::new_default(45);
+        for _ in 0..200 {
+            let len = 2 + rng.get_u32() as usize % 8;
+            let dims: Vec<usize> = (0..len).map(|_| 1 + rng.get_u32() as usize % 50).collect();
+            let (total, split) = matrix_chain_order(&dims);
+            assert_eq!(total, brute_force(&dims, 0, len - 2));
+            assert_eq!(split_cost(&dims, &split, 0, len - 2), total);
+        }
+    }
+}
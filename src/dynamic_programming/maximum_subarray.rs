@@ -27,6 +27,78 @@ pub fn maximum_subarray(array: &[i32]) -> i32 {
     result
 }
 
+/// max_subarray(arr) finds the subarray (containing at least one number) with the largest sum
+/// with Kadane's algorithm, and returns its sum along with the indices of its first and last elements.
+///
+/// The best subarray ending at `i` either extends the best one ending at `i - 1`, if that one
+/// has a positive sum, or is `arr[i]` alone. So only the best subarray ending at the current
+/// position needs to be remembered, and when every number is negative the result is the
+/// largest one alone. The first of several best subarrays is returned.
+///
+/// Arguments:
+///     * `arr` - a non-empty integer array
+/// Complexity
+///     - time complexity: O(arr.length),
+///     - space complexity: O(1),
+pub fn max_subarray(arr: &[i64]) -> (i64, usize, usize) {
+    assert!(
+        !arr.is_empty(),
+        "the array must contain at least one number"
+    );
+    let mut best = (arr[0], 0, 0);
+    let (mut current, mut start) = (arr[0], 0);
+
+    for (i, &x) in arr.iter().enumerate().skip(1) {
+        if current > 0 {
+            current += x;
+        } else {
+            current = x;
+            start = i;
+        }
+        if current > best.0 {
+            best = (current, start, i);
+        }
+    }
+
+    best
+}
+
+/// max_subarray_sum(arr) returns the largest sum of a subarray (containing at least one number).
+///
+/// See `max_subarray`.
+pub fn max_subarray_sum(arr: &[i64]) -> i64 {
+    max_subarray(arr).0
+}
+
+/// max_circular_subarray_sum(arr) returns the largest sum of a subarray (containing at least one number)
+/// when `arr` is circular, so that subarrays may wrap around from its end to its start.
+///
+/// A wrapping subarray leaves out a non-wrapping one in the middle, so its best sum is the total
+/// minus the smallest sum of a subarray, which Kadane's algorithm finds the same way as the largest.
+/// That subarray must not be the whole array, which only matters when every number is negative.
+///
+/// The total and the smallest sum are kept in `i128`, since they may not fit in an `i64` even when
+/// the sums of the circular subarrays, the result among them, do.
+///
+/// Arguments:
+///     * `arr` - a non-empty integer array
+/// Complexity
+///     - time complexity: O(arr.length),
+///     - space complexity: O(1),
+pub fn max_circular_subarray_sum(arr: &[i64]) -> i64 {
+    let straight = max_subarray_sum(arr);
+    if straight < 0 {
+        return straight;
+    }
+    let (mut total, mut current, mut smallest) = (0_i128, 0_i128, i128::MAX);
+    for &x in arr {
+        total += x as i128;
+        current = current.min(0) + x as i128;
+        smallest = smallest.min(current);
+    }
+    straight.max((total - smallest) as i64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,4 +131,91 @@ mod tests {
         let array = vec![-6];
         assert_eq!(maximum_subarray(&array), -6);
     }
+
+    fn brute_force(arr: &[i64]) -> i64 {
+        (0..arr.len())
+            .flat_map(|i| (i..arr.len()).map(move |j| arr[i..=j].iter().sum()))
+            .max()
+            .unwrap()
+    }
+
+    fn brute_force_circular(arr: &[i64]) -> i64 {
+        let n = arr.len();
+        (0..n)
+            .flat_map(|i| (1..=n).map(move |len| (0..len).map(|k| arr[(i + k) % n]).sum()))
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn kadane_classic() {
+        let arr = [-2, 1, -3, 4, -1, 2, 1, -5, 4];
+        assert_eq!(max_subarray_sum(&arr), 6);
+        assert_eq!(max_subarray(&arr), (6, 3, 6));
+    }
+
+    #[test]
+    fn kadane_all_negative() {
+        assert_eq!(max_subarray(&[-3, -1, -8, -2]), (-1, 1, 1));
+        assert_eq!(max_circular_subarray_sum(&[-3, -1, -8, -2]), -1);
+    }
+
+    #[test]
+    fn kadane_single_and_zeros() {
+        assert_eq!(max_subarray(&[7]), (7, 0, 0));
+        assert_eq!(max_subarray(&[-7]), (-7, 0, 0));
+        assert_eq!(max_circular_subarray_sum(&[-7]), -7);
+        assert_eq!(max_subarray(&[0, 0, 0]), (0, 0, 0));
+        assert_eq!(max_circular_subarray_sum(&[0, 0, 0]), 0);
+    }
+
+    #[test]
+    fn circular() {
+        // 4 + 5 wraps around the -20
+        assert_eq!(max_circular_subarray_sum(&[5, -20, 4]), 9);
+        assert_eq!(max_circular_subarray_sum(&[8, -1, 3, 4]), 15);
+        assert_eq!(max_circular_subarray_sum(&[1, -2, 3, -2]), 3);
+        assert_eq!(max_circular_subarray_sum(&[2, -1, -1, 3]), 5);
+        assert_eq!(
+            max_circular_subarray_sum(&[-2, 1, -3, 4, -1, 2, 1, -5, 4]),
+            6
+        );
+    }
+
+    #[test]
+    fn circular_extremes() {
+        assert_eq!(max_circular_subarray_sum(&[i64::MIN, 1]), 1);
+        assert_eq!(max_circular_subarray_sum(&[1, i64::MIN, 2]), 3);
+        assert_eq!(
+            max_circular_subarray_sum(&[i64::MAX, i64::MIN, 0]),
+            i64::MAX
+        );
+        assert_eq!(
+            max_circular_subarray_sum(&[i64::MIN, i64::MIN, 7, i64::MIN]),
+            7
+        );
+        assert_eq!(max_circular_subarray_sum(&[i64::MIN]), i64::MIN);
+    }
+
+    #[test]
+    #[should_panic]
+    fn kadane_empty() {
+        max_subarray(&[]);
+    }
+
+    #[test]
+    fn kadane_matches_brute_force() {
+        let mut rng = crate::math::PCG32::new_default(47);
+        for _ in 0..500 {
+            let len = 1 + rng.get_u32() as usize % 30;
+            let arr: Vec<i64> = (0..len).map(|_| (rng.get_u32() % 41) as i64 - 20).collect();
+            let (sum, start, end) = max_subarray(&arr);
+            assert_eq!(sum, brute_force(&arr));
+            assert_eq!(arr[start..=end].iter().sum::<i64>(), sum);
+            assert_eq!(max_circular_subarray_sum(&arr), brute_force_circular(&arr));
+        }
+        let arr: Vec<i32> = (0..100).map(|_| (rng.get_u32() % 41) as i32 - 20).collect();
+        let wide: Vec<i64> = arr.iter().map(|&x| x as i64).collect();
+        assert_eq!(max_subarray_sum(&wide), maximum_subarray(&arr) as i64);
+    }
 }
@@ -1,4 +1,5 @@
 mod coin_change;
+mod distinct_subsequences;
 mod edit_distance;
 mod egg_dropping;
 mod fibonacci;
@@ -9,15 +10,21 @@ mod longest_common_subsequence;
 mod longest_common_substring;
 mod longest_continuous_increasing_subsequence;
 mod longest_increasing_subsequence;
+mod matrix_chain;
 mod maximal_square;
 mod maximum_subarray;
+mod partition;
 mod rod_cutting;
 mod snail;
 mod subset_generation;
+mod subset_sum;
+mod text_justification;
+mod wildcard_match;
 
-pub use self::coin_change::coin_change;
+pub use self::coin_change::{coin_change, count_ways, min_coins, min_coins_with_selection};
+pub use self::distinct_subsequences::distinct_subsequences;
 pub use self::edit_distance::{edit_distance, edit_distance_se};
-pub use self::egg_dropping::egg_drop;
+pub use self::egg_dropping::{egg_drop, egg_drop_strategy, egg_drop_trials};
 pub use self::fibonacci::classical_fibonacci;
 pub use self::fibonacci::fibonacci;
 pub use self::fibonacci::logarithmic_fibonacci;
@@ -26,13 +33,20 @@ pub use self::fibonacci::memoized_fibonacci;
 pub use self::fibonacci::recursive_fibonacci;
 pub use self::fractional_knapsack::fractional_knapsack;
 pub use self::is_subsequence::is_subsequence;
-pub use self::knapsack::knapsack;
-pub use self::longest_common_subsequence::longest_common_subsequence;
+pub use self::knapsack::{knapsack, knapsack_01};
+pub use self::longest_common_subsequence::{diff, lcs_length, longest_common_subsequence, DiffOp};
 pub use self::longest_common_substring::longest_common_substring;
 pub use self::longest_continuous_increasing_subsequence::longest_continuous_increasing_subsequence;
-pub use self::longest_increasing_subsequence::longest_increasing_subsequence;
+pub use self::longest_increasing_subsequence::{lis, lis_length, longest_increasing_subsequence};
+pub use self::matrix_chain::{matrix_chain_order, print_optimal_parens};
 pub use self::maximal_square::maximal_square;
-pub use self::maximum_subarray::maximum_subarray;
-pub use self::rod_cutting::rod_cut;
+pub use self::maximum_subarray::{
+    max_circular_subarray_sum, max_subarray, max_subarray_sum, maximum_subarray,
+};
+pub use self::partition::{can_partition, min_partition_diff};
+pub use self::rod_cutting::{rod_cut, rod_cut_max_value, rod_cut_pieces};
 pub use self::snail::snail;
 pub use self::subset_generation::list_subset;
+pub use self::subset_sum::{all_subset_sums, subset_sum_exists};
+pub use self::text_justification::{compute_badness_table, justify_text};
+pub use self::wildcard_match::{wildcard_match, wildcard_match_in_alphabet};
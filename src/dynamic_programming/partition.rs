@@ -0,0 +1,103 @@
+//! Partition: splitting numbers into two subsets with sums as close as can be.
+
+use super::subset_sum::reachable_sums;
+
+/// Returns whether `nums` can be split into two subsets of equal sum.
+///
+/// This is subset sum with half of the total as the target, found with a
+/// bitset of the reachable sums in O(n * sum / 64) time for n numbers.
+pub fn can_partition(nums: &[u32]) -> bool {
+    let total: u64 = nums.iter().map(|&num| u64::from(num)).sum();
+    if total % 2 == 1 {
+        return false;
+    }
+    let half = (total / 2) as usize;
+    reachable_sums(nums, half)[half / 64] >> (half % 64) & 1 == 1
+}
+
+/// Returns the least absolute difference between the sums of two subsets
+/// that `nums` is split into.
+///
+/// The smaller sum is the largest subset sum of at most half of the total,
+/// found with a bitset of the reachable sums in O(n * sum / 64) time.
+pub fn min_partition_diff(nums: &[u32]) -> u32 {
+    let total: u64 = nums.iter().map(|&num| u64::from(num)).sum();
+    let half = (total / 2) as usize;
+    let reachable = reachable_sums(nums, half);
+    let smaller = (0..=half)
+        .rev()
+        .find(|&sum| reachable[sum / 64] >> (sum % 64) & 1 == 1)
+        .unwrap() as u64;
+    // moving the largest number of the larger side over can only bring the
+    // sums closer, so the difference is at most the largest number
+    (total - 2 * smaller) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    // The least difference, trying each subset as one side.
+    fn brute_force(nums: &[u32]) -> u32 {
+        let total: u64 = nums.iter().map(|&num| u64::from(num)).sum();
+        (0u32..1 << nums.len())
+            .map(|mask| {
+                let side: u64 = (0..nums.len())
+                    .filter(|i| mask >> i & 1 == 1)
+                    .map(|i| u64::from(nums[i]))
+                    .sum();
+                (total - side).abs_diff(side) as u32
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn classic_examples() {
+        // {11} and {1, 5, 5}
+        assert!(can_partition(&[1, 5, 11, 5]));
+        assert_eq!(min_partition_diff(&[1, 5, 11, 5]), 0);
+        assert!(!can_partition(&[1, 2, 3, 5]));
+        // {5} and {1, 2, 3}
+        assert_eq!(min_partition_diff(&[1, 2, 3, 5]), 1);
+        assert_eq!(min_partition_diff(&[1, 6, 11, 5]), 1);
+    }
+
+    #[test]
+    fn single_element() {
+        assert!(!can_partition(&[4]));
+        assert_eq!(min_partition_diff(&[4]), 4);
+        assert!(can_partition(&[0]));
+        assert_eq!(min_partition_diff(&[0]), 0);
+    }
+
+    #[test]
+    fn zeros() {
+        assert!(can_partition(&[]));
+        assert_eq!(min_partition_diff(&[]), 0);
+        assert!(can_partition(&[0, 0, 0]));
+        assert_eq!(min_partition_diff(&[0, 0, 0]), 0);
+    }
+
+    #[test]
+    fn odd_sum() {
+        assert!(!can_partition(&[1, 2, 4]));
+        assert!(!can_partition(&[3, 3, 3]));
+        // an odd sum is checked before any bitset is built for half of it
+        assert!(!can_partition(&[u32::MAX, u32::MAX, 1]));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = PCG32::new_default(99);
+        for _ in 0..500 {
+            let nums: Vec<u32> = (0..rng.get_u32() % 12)
+                .map(|_| rng.get_u32() % 100)
+                .collect();
+            let expected = brute_force(&nums);
+            assert_eq!(min_partition_diff(&nums), expected, "{nums:?}");
+            assert_eq!(can_partition(&nums), expected == 0, "{nums:?}");
+        }
+    }
+}
@@ -35,9 +35,62 @@ pub fn rod_cut(p: &[usize]) -> usize {
     }
 }
 
+/// `rod_cut_max_value(prices, length)` returns the maximum possible profit from
+/// cutting a rod of `length` into pieces, where a piece of length `l` is worth
+/// `prices[l - 1]`. Pieces are at most `prices.len()` long, so a longer rod
+/// must be cut, and a rod is worth nothing if `prices` is empty.
+///
+/// A piece can be worth more than a longer one, in which case the longer
+/// piece is never used.
+///
+/// # Complexity
+///    - time complexity: O(length * m),
+///    - space complexity: O(length),
+///
+/// where m is the length of `prices`.
+pub fn rod_cut_max_value(prices: &[u64], length: usize) -> u64 {
+    rod_cut_table(prices, length)[length].0
+}
+
+/// `rod_cut_pieces(prices, length)` returns the lengths of the pieces of an
+/// optimal cutting of a rod of `length`, in the order they are cut, with
+/// prices as in `rod_cut_max_value`. The pieces sum to `length`, unless
+/// `prices` is empty and no piece can be cut at all.
+///
+/// # Complexity
+///    - time complexity: O(length * m),
+///    - space complexity: O(length),
+///
+/// where m is the length of `prices`.
+pub fn rod_cut_pieces(prices: &[u64], length: usize) -> Vec<usize> {
+    let table = rod_cut_table(prices, length);
+    let mut pieces = Vec::new();
+    let mut rest = length;
+    while rest > 0 && table[rest].1 > 0 {
+        pieces.push(table[rest].1);
+        rest -= table[rest].1;
+    }
+    pieces
+}
+
+/// The best profit for each length up to `length`, with the length of its
+/// first piece, or 0 if no piece can be cut.
+fn rod_cut_table(prices: &[u64], length: usize) -> Vec<(u64, usize)> {
+    let mut table = vec![(0, 0); length + 1];
+    for rest in 1..=length {
+        for (piece, &price) in prices.iter().enumerate().take(rest) {
+            let value = price + table[rest - piece - 1].0;
+            if table[rest].1 == 0 || value > table[rest].0 {
+                table[rest] = (value, piece + 1);
+            }
+        }
+    }
+    table
+}
+
 #[cfg(test)]
 mod tests {
-    use super::rod_cut;
+    use super::{rod_cut, rod_cut_max_value, rod_cut_pieces};
 
     #[test]
     fn test_rod_cut() {
@@ -52,4 +105,70 @@ mod tests {
         assert_eq!(30, rod_cut(&[1, 5, 8, 9, 10, 17, 17, 20, 24, 30]));
         assert_eq!(12, rod_cut(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]));
     }
+
+    // Checks that the pieces make up the rod and are worth its best profit.
+    fn check_pieces(prices: &[u64], length: usize) {
+        let pieces = rod_cut_pieces(prices, length);
+        assert_eq!(pieces.iter().sum::<usize>(), length);
+        assert!(pieces
+            .iter()
+            .all(|&piece| 1 <= piece && piece <= prices.len()));
+        let value: u64 = pieces.iter().map(|&piece| prices[piece - 1]).sum();
+        assert_eq!(value, rod_cut_max_value(prices, length));
+    }
+
+    #[test]
+    fn test_rod_cut_clrs() {
+        let prices = [1, 5, 8, 9, 10, 17, 17, 20];
+        assert_eq!(rod_cut_max_value(&prices, 4), 10);
+        assert_eq!(rod_cut_pieces(&prices, 4), vec![2, 2]);
+        let best = [0, 1, 5, 8, 10, 13, 17, 18, 22];
+        for (length, &value) in best.iter().enumerate() {
+            assert_eq!(rod_cut_max_value(&prices, length), value);
+            check_pieces(&prices, length);
+        }
+    }
+
+    #[test]
+    fn test_rod_cut_length_zero() {
+        assert_eq!(rod_cut_max_value(&[1, 5, 8], 0), 0);
+        assert!(rod_cut_pieces(&[1, 5, 8], 0).is_empty());
+        assert_eq!(rod_cut_max_value(&[], 0), 0);
+        assert_eq!(rod_cut_max_value(&[], 5), 0);
+        assert!(rod_cut_pieces(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn test_rod_cut_pieces_at_most_prices_len() {
+        // a rod of length 10 must be cut into pieces of length 3 at most
+        let prices = [1, 5, 8];
+        assert_eq!(rod_cut_max_value(&prices, 10), 26);
+        assert_eq!(rod_cut_pieces(&prices, 10), vec![2, 2, 3, 3]);
+        for length in 0..30 {
+            check_pieces(&prices, length);
+        }
+    }
+
+    #[test]
+    fn test_rod_cut_shorter_pieces_worth_more() {
+        // a piece of length 2 is worth more than any longer one
+        let prices = [1, 10, 3, 4, 5];
+        assert_eq!(rod_cut_max_value(&prices, 5), 21);
+        assert_eq!(rod_cut_pieces(&prices, 5), vec![1, 2, 2]);
+        assert_eq!(rod_cut_max_value(&prices, 4), 20);
+        assert_eq!(rod_cut_pieces(&prices, 4), vec![2, 2]);
+    }
+
+    #[test]
+    fn test_rod_cut_matches_rod_cut() {
+        let prices = [6, 4, 8, 2, 5, 8, 2, 3, 7, 11];
+        let profits: Vec<usize> = prices.iter().map(|&price| price as usize).collect();
+        for length in 0..=prices.len() {
+            assert_eq!(
+                rod_cut_max_value(&prices, length) as usize,
+                rod_cut(&profits[..length])
+            );
+            check_pieces(&prices, length);
+        }
+    }
 }
@@ -0,0 +1,185 @@
+//! Subset sum: whether some of the numbers add up to a target, and which.
+
+/// Returns whether some subset of `nums`, possibly empty, sums to `target`.
+///
+/// The sums reachable with the numbers seen so far are kept as the bits of a
+/// bitset, and each number adds the reachable sums shifted by it, so this
+/// takes O(n * target / 64) time and O(target / 64) space for n numbers.
+pub fn subset_sum_exists(nums: &[u32], target: u32) -> bool {
+    // no bitset is needed for a target beyond the sum of the numbers up to it
+    let total: u64 = nums
+        .iter()
+        .filter(|&&num| num <= target)
+        .map(|&num| u64::from(num))
+        .sum();
+    if u64::from(target) > total {
+        return false;
+    }
+
+    let target = target as usize;
+    let reachable = reachable_sums(nums, target);
+    reachable[target / 64] >> (target % 64) & 1 == 1
+}
+
+/// Returns the bitset of the sums up to `limit` of the subsets of `nums`:
+/// bit `s % 64` of word `s / 64` is set if some subset sums to `s`.
+pub(super) fn reachable_sums(nums: &[u32], limit: usize) -> Vec<u64> {
+    let mut reachable = vec![0u64; limit / 64 + 1];
+    reachable[0] = 1;
+    for &num in nums {
+        let num = num as usize;
+        if num > limit {
+            continue;
+        }
+        let (words, bits) = (num / 64, num % 64);
+        // from the highest word down, so that each reads the old bits
+        for i in (words..reachable.len()).rev() {
+            let mut shifted = reachable[i - words] << bits;
+            if bits > 0 && i > words {
+                shifted |= reachable[i - words - 1] >> (64 - bits);
+            }
+            reachable[i] |= shifted;
+        }
+    }
+    reachable
+}
+
+/// Returns every subset of `nums` that sums to `target`, by backtracking.
+///
+/// Equal numbers are interchangeable, so each subset is returned once however
+/// many ways it can be picked from `nums`. The numbers of each subset are in
+/// increasing order, and the subsets in lexicographic order.
+pub fn all_subset_sums(nums: &[u32], target: u32) -> Vec<Vec<u32>> {
+    let mut sorted = nums.to_vec();
+    sorted.sort_unstable();
+    let mut subsets = Vec::new();
+    collect_subsets(&sorted, target, &mut Vec::new(), &mut subsets);
+    subsets
+}
+
+/// Adds to `subsets` the subsets of `nums`, which is sorted, summing to
+/// `remaining`, each after the numbers in `chosen`.
+fn collect_subsets(
+    nums: &[u32],
+    remaining: u32,
+    chosen: &mut Vec<u32>,
+    subsets: &mut Vec<Vec<u32>>,
+) {
+    if remaining == 0 {
+        subsets.push(chosen.clone());
+    }
+    for (i, &num) in nums.iter().enumerate() {
+        // the next numbers are no smaller
+        if num > remaining {
+            break;
+        }
+        // a subset starting with an equal number was already tried
+        if i > 0 && nums[i - 1] == num {
+            continue;
+        }
+        chosen.push(num);
+        collect_subsets(&nums[i + 1..], remaining - num, chosen, subsets);
+        chosen.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    // Every subset that sums to target, sorted and without repeats, by
+    // trying each set of indices.
+    fn brute_force(nums: &[u32], target: u32) -> Vec<Vec<u32>> {
+        let mut subsets: Vec<Vec<u32>> = (0u32..1 << nums.len())
+            .map(|mask| {
+                let mut subset: Vec<u32> = (0..nums.len())
+                    .filter(|i| mask >> i & 1 == 1)
+                    .map(|i| nums[i])
+                    .collect();
+                subset.sort_unstable();
+                subset
+            })
+            .filter(|subset| subset.iter().sum::<u32>() == target)
+            .collect();
+        subsets.sort();
+        subsets.dedup();
+        subsets
+    }
+
+    #[test]
+    fn example() {
+        let nums = [3, 1, 4, 1, 5];
+        assert!(subset_sum_exists(&nums, 9));
+        let mut expected = vec![vec![3, 1, 5], vec![4, 5], vec![3, 1, 1, 4]];
+        for subset in &mut expected {
+            subset.sort_unstable();
+        }
+        expected.sort();
+        assert_eq!(all_subset_sums(&nums, 9), expected);
+        assert!(!subset_sum_exists(&nums, 15));
+        assert!(all_subset_sums(&nums, 15).is_empty());
+    }
+
+    #[test]
+    fn empty() {
+        assert!(!subset_sum_exists(&[], 1));
+        assert!(all_subset_sums(&[], 1).is_empty());
+        // the empty subset sums to 0
+        assert!(subset_sum_exists(&[], 0));
+        assert_eq!(all_subset_sums(&[], 0), vec![Vec::<u32>::new()]);
+        assert_eq!(all_subset_sums(&[2, 3], 0), vec![Vec::<u32>::new()]);
+    }
+
+    #[test]
+    fn single_element() {
+        assert!(subset_sum_exists(&[7], 7));
+        assert_eq!(all_subset_sums(&[7], 7), vec![vec![7]]);
+        assert!(!subset_sum_exists(&[7], 6));
+        assert!(all_subset_sums(&[7], 8).is_empty());
+    }
+
+    #[test]
+    fn large_targets() {
+        assert!(!subset_sum_exists(&[1, 2, 3], u32::MAX));
+        assert!(all_subset_sums(&[1, 2, 3], u32::MAX).is_empty());
+        // the sum of the numbers does not fit in a u32
+        assert!(!subset_sum_exists(&[u32::MAX, u32::MAX], u32::MAX - 1));
+        assert!(all_subset_sums(&[u32::MAX, u32::MAX], u32::MAX - 1).is_empty());
+        assert_eq!(
+            all_subset_sums(&[u32::MAX, 1, u32::MAX], u32::MAX),
+            vec![vec![u32::MAX]]
+        );
+    }
+
+    #[test]
+    fn across_words() {
+        // sums past the first 64 bits of the bitset
+        assert!(subset_sum_exists(&[60, 70, 130], 200));
+        assert!(subset_sum_exists(&[64, 64, 1], 129));
+        assert!(!subset_sum_exists(&[64, 64, 1], 127));
+        assert!(subset_sum_exists(&[100, 200, 300, 7], 507));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = PCG32::new_default(98);
+        for _ in 0..500 {
+            let nums: Vec<u32> = (0..rng.get_u32() % 10)
+                .map(|_| rng.get_u32() % 150)
+                .collect();
+            let target = rng.get_u32() % 400;
+            let expected = brute_force(&nums, target);
+            assert_eq!(
+                all_subset_sums(&nums, target),
+                expected,
+                "{nums:?} {target}"
+            );
+            assert_eq!(
+                subset_sum_exists(&nums, target),
+                !expected.is_empty(),
+                "{nums:?} {target}"
+            );
+        }
+    }
+}
@@ -0,0 +1,240 @@
+/// Breaks words into lines of at most line_width characters, choosing the
+/// breaks that minimize the total badness of the lines, as TeX does, and then
+/// justifies each line to exactly line_width by widening the gaps between its
+/// words, the leftmost ones first. The last line, and lines of a single word,
+/// are left-aligned instead.
+///
+/// The badness of a line is the cube of the number of spaces it is short of
+/// line_width, so a few slightly short lines are preferred to a very short
+/// one, and the last line is free. With cost[i] the least total badness of
+/// the lines for words[i..]:
+///
+/// ```text
+/// cost[n] = 0
+/// cost[i] = min over j >= i of badness(words[i..=j]) + cost[j + 1]
+/// ```
+///
+/// This takes O(n^2) time for n words, with the table of badnesses.
+///
+/// A word longer than line_width is put on a line of its own.
+pub fn justify_text(words: &[&str], line_width: usize) -> Vec<String> {
+    let n = words.len();
+    let badness = compute_badness_table(words, line_width);
+
+    // cost[i] and the last word of the first line for words[i..]
+    let mut cost = vec![0u64; n + 1];
+    let mut line_end = vec![0; n];
+    for i in (0..n).rev() {
+        // the word alone, if it is too long for any line
+        (cost[i], line_end[i]) = (cost[i + 1], i);
+        let fitting = (i..n).filter_map(|j| Some((badness[i][j]?.saturating_add(cost[j + 1]), j)));
+        if let Some(best) = fitting.min() {
+            (cost[i], line_end[i]) = best;
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut i = 0;
+    while i < n {
+        let j = line_end[i];
+        lines.push(justify_line(&words[i..=j], line_width, j == n - 1));
+        i = j + 1;
+    }
+    lines
+}
+
+/// Returns the badness of a line of words[i..=j] in table[i][j], or None if
+/// the line is longer than line_width, or j < i. The badness of the last line
+/// is 0 if it fits, and badnesses too large for a u64 are u64::MAX.
+pub fn compute_badness_table(words: &[&str], line_width: usize) -> Vec<Vec<Option<u64>>> {
+    let n = words.len();
+    let mut table = vec![vec![None; n]; n];
+    for (i, row) in table.iter_mut().enumerate() {
+        // the length of words[i..=j] with a space between each
+        let mut length = 0;
+        for (j, word) in words.iter().enumerate().skip(i) {
+            length += word.chars().count() + usize::from(j > i);
+            if length > line_width {
+                break;
+            }
+            let extra = (line_width - length) as u64;
+            row[j] = Some(if j == n - 1 {
+                0
+            } else {
+                extra.saturating_pow(3)
+            });
+        }
+    }
+    table
+}
+
+fn justify_line(words: &[&str], line_width: usize, last: bool) -> String {
+    if last || words.len() == 1 {
+        return words.join(" ");
+    }
+    let letters: usize = words.iter().map(|word| word.chars().count()).sum();
+    let gaps = words.len() - 1;
+    let spaces = line_width - letters;
+    let mut line = String::from(words[0]);
+    for (k, word) in words[1..].iter().enumerate() {
+        // the first spaces % gaps gaps get one more space
+        let width = spaces / gaps + usize::from(k < spaces % gaps);
+        line.push_str(&" ".repeat(width));
+        line.push_str(word);
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The total badness of lines made of consecutive words, by their ends.
+    fn total_badness(table: &[Vec<Option<u64>>], line_ends: &[usize]) -> Option<u64> {
+        let mut start = 0;
+        let mut total = 0;
+        for &end in line_ends {
+            total += table[start][end]?;
+            start = end + 1;
+        }
+        Some(total)
+    }
+
+    // The last word of each line, as in lines of words joined by spaces.
+    fn line_ends(lines: &[String]) -> Vec<usize> {
+        let mut count = 0;
+        lines
+            .iter()
+            .map(|line| {
+                count += line.split_whitespace().count();
+                count - 1
+            })
+            .collect()
+    }
+
+    // The least total badness, trying every set of breaks.
+    fn brute_force(table: &[Vec<Option<u64>>]) -> Option<u64> {
+        let n = table.len();
+        (0u32..1 << (n - 1))
+            .filter_map(|breaks| {
+                let mut ends: Vec<usize> = (0..n - 1).filter(|i| breaks >> i & 1 == 1).collect();
+                ends.push(n - 1);
+                total_badness(table, &ends)
+            })
+            .min()
+    }
+
+    // Fills each line with as many words as fit.
+    fn greedy(words: &[&str], line_width: usize) -> Vec<usize> {
+        let mut ends = Vec::new();
+        let mut length = 0;
+        for (i, word) in words.iter().enumerate() {
+            if length > 0 && length + 1 + word.len() > line_width {
+                ends.push(i - 1);
+                length = 0;
+            }
+            length += word.len() + usize::from(length > 0);
+        }
+        ends.push(words.len() - 1);
+        ends
+    }
+
+    #[test]
+    fn greedy_is_not_optimal() {
+        let words = ["aaa", "bb", "cc", "ddddd"];
+        let table = compute_badness_table(&words, 6);
+        // "aaa bb" fills the first line, which leaves "cc" 4 short: 4^3 = 64
+        let greedy_ends = greedy(&words, 6);
+        assert_eq!(greedy_ends, vec![1, 2, 3]);
+        assert_eq!(total_badness(&table, &greedy_ends), Some(64));
+        // while "aaa" and "bb cc" are 3 and 1 short: 27 + 1 = 28
+        let lines = justify_text(&words, 6);
+        assert_eq!(lines, vec!["aaa", "bb  cc", "ddddd"]);
+        assert_eq!(total_badness(&table, &line_ends(&lines)), Some(28));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let text = "In olden times when wishing still helped one, there lived a king \
+                    whose daughters were all beautiful";
+        let words: Vec<&str> = text.split_whitespace().collect();
+        for line_width in [20, 30, 45] {
+            let lines = justify_text(&words, line_width);
+            let table = compute_badness_table(&words, line_width);
+            assert_eq!(
+                total_badness(&table, &line_ends(&lines)),
+                brute_force(&table)
+            );
+            for line in &lines[..lines.len() - 1] {
+                assert_eq!(line.chars().count(), line_width, "{line:?}");
+            }
+            let joined: Vec<&str> = lines.iter().flat_map(|l| l.split_whitespace()).collect();
+            assert_eq!(joined, words);
+        }
+    }
+
+    #[test]
+    fn lines_fit() {
+        let words: Vec<&str> = "the quick brown fox jumps over the lazy dog"
+            .split(' ')
+            .collect();
+        for line_width in 5..40 {
+            let lines = justify_text(&words, line_width);
+            assert!(lines.iter().all(|line| line.chars().count() <= line_width));
+            let joined: Vec<&str> = lines.iter().flat_map(|l| l.split_whitespace()).collect();
+            assert_eq!(joined, words);
+        }
+    }
+
+    #[test]
+    fn single_words() {
+        assert_eq!(justify_text(&[], 10), Vec::<String>::new());
+        assert_eq!(justify_text(&["hello"], 10), vec!["hello"]);
+        // each word is alone, left-aligned
+        assert_eq!(
+            justify_text(&["abc", "de", "fgh"], 4),
+            vec!["abc", "de", "fgh"]
+        );
+        // too long for any line
+        assert_eq!(
+            justify_text(&["a", "elephant", "b"], 5),
+            vec!["a", "elephant", "b"]
+        );
+    }
+
+    #[test]
+    fn justified_spacing() {
+        let words = [
+            "This",
+            "is",
+            "an",
+            "example",
+            "of",
+            "text",
+            "justification.",
+        ];
+        assert_eq!(
+            justify_text(&words, 16),
+            vec!["This    is    an", "example  of text", "justification."]
+        );
+    }
+
+    #[test]
+    fn badness_table() {
+        let table = compute_badness_table(&["aaa", "bb", "cc", "ddddd"], 6);
+        assert_eq!(table[0], vec![Some(27), Some(0), None, None]);
+        assert_eq!(table[1], vec![None, Some(64), Some(1), None]);
+        assert_eq!(table[2], vec![None, None, Some(64), None]);
+        // the last line is free
+        assert_eq!(table[3], vec![None, None, None, Some(0)]);
+    }
+
+    #[test]
+    fn wide_lines() {
+        // 2^40 spaces short cubes to more than a u64 holds
+        let table = compute_badness_table(&["a", "b"], 1 << 40);
+        assert_eq!(table[0], vec![Some(u64::MAX), Some(0)]);
+        assert_eq!(table[1], vec![None, Some(0)]);
+        assert_eq!(justify_text(&["a", "b"], 1 << 40), vec!["a b"]);
+    }
+}
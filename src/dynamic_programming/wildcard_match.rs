@@ -0,0 +1,149 @@
+// Glob-style wildcard matching: returns whether the whole of text matches
+// pattern, where '?' matches any single character and '*' matches any
+// sequence of characters, including the empty one.
+//
+// matched[i][j] is whether pattern[0..i] matches text[0..j]:
+// - matched[i][j] = matched[i-1][j] || matched[i][j-1] if pattern[i-1] is '*',
+//   as the '*' matches nothing, or one more character
+// - matched[i][j] = matched[i-1][j-1] && pattern[i-1] matches text[j-1]
+//   otherwise
+// Only the row for the previous i is needed, so this takes O(m n) time and
+// O(n) space for a pattern of length m and a text of length n.
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let text: Vec<char> = text.chars().collect();
+    let mut matched = vec![false; text.len() + 1];
+    // the empty pattern only matches the empty text
+    matched[0] = true;
+
+    for p in pattern.chars() {
+        if p == '*' {
+            // matched[j - 1] is already in the new row
+            for j in 1..=text.len() {
+                matched[j] = matched[j] || matched[j - 1];
+            }
+        } else {
+            // matched[j - 1] is still in the previous row
+            for j in (1..=text.len()).rev() {
+                matched[j] = matched[j - 1] && (p == '?' || p == text[j - 1]);
+            }
+            matched[0] = false;
+        }
+    }
+    matched[text.len()]
+}
+
+// Like wildcard_match, but returns None if the text, or the pattern other
+// than its wildcards, has a character that is not in alphabet.
+pub fn wildcard_match_in_alphabet(pattern: &str, text: &str, alphabet: &str) -> Option<bool> {
+    let in_alphabet = |c: char| alphabet.contains(c);
+    let pattern_ok = pattern
+        .chars()
+        .all(|c| c == '*' || c == '?' || in_alphabet(c));
+    if pattern_ok && text.chars().all(in_alphabet) {
+        Some(wildcard_match(pattern, text))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    // Matches by trying every length for each '*'.
+    fn brute_force(pattern: &[char], text: &[char]) -> bool {
+        match pattern.split_first() {
+            None => text.is_empty(),
+            Some(('*', rest)) => (0..=text.len()).any(|skip| brute_force(rest, &text[skip..])),
+            Some((&p, rest)) => {
+                !text.is_empty() && (p == '?' || p == text[0]) && brute_force(rest, &text[1..])
+            }
+        }
+    }
+
+    #[test]
+    fn star_matches_anything() {
+        for text in ["", "a", "abc", "*?", "日本語"] {
+            assert!(wildcard_match("*", text));
+            assert!(wildcard_match("**", text));
+            assert!(wildcard_match("***", text));
+        }
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        assert!(wildcard_match("?", "a"));
+        assert!(wildcard_match("?", "語"));
+        assert!(!wildcard_match("?", ""));
+        assert!(!wildcard_match("?", "ab"));
+        assert!(wildcard_match("??", "ab"));
+        assert!(wildcard_match("a?c", "abc"));
+        assert!(!wildcard_match("a?c", "ac"));
+    }
+
+    #[test]
+    fn star_between_characters() {
+        for text in ["ab", "acb", "aXXXb", "abbb"] {
+            assert!(wildcard_match("a*b", text));
+        }
+        for text in ["b", "a", "ba", "abc", ""] {
+            assert!(!wildcard_match("a*b", text));
+        }
+        assert!(wildcard_match("*.rs", "main.rs"));
+        assert!(!wildcard_match("*.rs", "main.rs.bak"));
+        assert!(wildcard_match("*a*b*", "xxaxxbxx"));
+        assert!(!wildcard_match("*a*b*", "xxbxxaxx"));
+        assert!(wildcard_match("?*?", "ab"));
+        assert!(!wildcard_match("?*?", "a"));
+    }
+
+    #[test]
+    fn consecutive_stars() {
+        for (pattern, single) in [("**", "*"), ("a**b", "a*b"), ("***?**", "*?")] {
+            for text in ["", "a", "ab", "acb", "ba", "b"] {
+                assert_eq!(
+                    wildcard_match(pattern, text),
+                    wildcard_match(single, text),
+                    "{pattern} {text}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert!(wildcard_match("", ""));
+        assert!(!wildcard_match("", "a"));
+        assert!(!wildcard_match("a", ""));
+    }
+
+    #[test]
+    fn alphabet() {
+        assert_eq!(wildcard_match_in_alphabet("a*b", "aab", "ab"), Some(true));
+        assert_eq!(wildcard_match_in_alphabet("a?b", "aab", "ab"), Some(true));
+        assert_eq!(wildcard_match_in_alphabet("a*b", "aba", "ab"), Some(false));
+        assert_eq!(wildcard_match_in_alphabet("a*b", "acb", "ab"), None);
+        assert_eq!(wildcard_match_in_alphabet("c*", "ab", "ab"), None);
+        assert_eq!(wildcard_match_in_alphabet("**", "", ""), Some(true));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = PCG32::new_default(95);
+        for _ in 0..3000 {
+            let pattern: Vec<char> = (0..rng.get_u32() % 8)
+                .map(|_| ['a', 'b', '?', '*'][rng.get_u32() as usize % 4])
+                .collect();
+            let text: Vec<char> = (0..rng.get_u32() % 10)
+                .map(|_| ['a', 'b'][rng.get_u32() as usize % 2])
+                .collect();
+            let (p, t): (String, String) = (pattern.iter().collect(), text.iter().collect());
+            assert_eq!(
+                wildcard_match(&p, &t),
+                brute_force(&pattern, &text),
+                "{p} {t}"
+            );
+        }
+    }
+}
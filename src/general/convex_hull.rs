@@ -1,26 +1,4 @@
-use std::cmp::Ordering::Equal;
-
-fn sort_by_min_angle(pts: &[(f64, f64)], min: &(f64, f64)) -> Vec<(f64, f64)> {
-    let mut points: Vec<(f64, f64, (f64, f64))> = pts
-        .iter()
-        .map(|x| {
-            (
-                (x.1 - min.1).atan2(x.0 - min.0),
-                // angle
-                (x.1 - min.1).hypot(x.0 - min.0),
-                // distance (we want the closest to be first)
-                *x,
-            )
-        })
-        .collect();
-    points.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
-    points.into_iter().map(|x| x.2).collect()
-}
-
-// calculates the z coordinate of the vector product of vectors ab and ac
-fn calc_z_coord_vector_product(a: &(f64, f64), b: &(f64, f64), c: &(f64, f64)) -> f64 {
-    (b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1)
-}
+use crate::geometry::graham_scan_keeping_collinear;
 
 /*
     If three points are aligned and are part of the convex hull then the three are kept.
@@ -28,40 +6,12 @@ fn calc_z_coord_vector_product(a: &(f64, f64), b: &(f64, f64), c: &(f64, f64)) -
 
     The first point is the one with the lowest y-coordinate and the lowest x-coordinate.
     Points are then given counter-clockwise, and the closest one is given first if needed.
+
+    The scan is `geometry::graham_scan`'s, keeping the aligned points; `graham_scan`
+    itself leaves them out.
 */
 pub fn convex_hull_graham(pts: &[(f64, f64)]) -> Vec<(f64, f64)> {
-    if pts.is_empty() {
-        return vec![];
-    }
-
-    let mut stack: Vec<(f64, f64)> = vec![];
-    let min = pts
-        .iter()
-        .min_by(|a, b| {
-            let ord = a.1.partial_cmp(&b.1).unwrap_or(Equal);
-            match ord {
-                Equal => a.0.partial_cmp(&b.0).unwrap_or(Equal),
-                o => o,
-            }
-        })
-        .unwrap();
-    let points = sort_by_min_angle(pts, min);
-
-    if points.len() <= 3 {
-        return points;
-    }
-
-    for point in points {
-        while stack.len() > 1
-            && calc_z_coord_vector_product(&stack[stack.len() - 2], &stack[stack.len() - 1], &point)
-                < 0.
-        {
-            stack.pop();
-        }
-        stack.push(point);
-    }
-
-    stack
+    graham_scan_keeping_collinear(pts)
 }
 
 #[cfg(test)]
@@ -8,6 +8,10 @@ fn gen_range(range: usize, generator: &mut PCG32) -> usize {
     generator.get_u64() as usize % range
 }
 
+/// Shuffles `array` in place, seeding the random numbers with the current time.
+///
+/// The shuffle itself is `sorting::fisher_yates_shuffle`, which also handles
+/// slices of fewer than two elements.
 pub fn fisher_yates_shuffle(array: &mut [i32]) {
     let seed = match SystemTime::now().duration_since(UNIX_EPOCH) {
         Ok(duration) => duration.as_millis() as u64,
@@ -16,10 +20,31 @@ pub fn fisher_yates_shuffle(array: &mut [i32]) {
 
     let mut random_generator = PCG32::new_default(seed);
 
-    let len = array.len();
+    crate::sorting::fisher_yates_shuffle(array, |range| gen_range(range, &mut random_generator));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_arrays() {
+        let mut empty: [i32; 0] = [];
+        fisher_yates_shuffle(&mut empty);
+        let mut single = [7];
+        fisher_yates_shuffle(&mut single);
+        assert_eq!(single, [7]);
+        let mut pair = [1, 2];
+        fisher_yates_shuffle(&mut pair);
+        pair.sort_unstable();
+        assert_eq!(pair, [1, 2]);
+    }
 
-    for i in 0..(len - 2) {
-        let r = gen_range(len - i, &mut random_generator);
-        array.swap(i, i + r);
+    #[test]
+    fn is_permutation() {
+        let mut array: Vec<i32> = (0..100).collect();
+        fisher_yates_shuffle(&mut array);
+        array.sort_unstable();
+        assert_eq!(array, (0..100).collect::<Vec<i32>>());
     }
 }
@@ -1,6 +1,6 @@
 use std::{
     cmp::Ordering,
-    collections::{BTreeMap, BinaryHeap},
+    collections::{BTreeMap, BinaryHeap, HashMap},
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
@@ -167,6 +167,87 @@ impl HuffmanEncoding {
     }
 }
 
+/// Compresses `data` with a Huffman code built from its byte frequencies.
+///
+/// Returns the bits packed into bytes, most significant bit first, the codebook
+/// and the number of bits actually used, since the last byte may be padded with
+/// zeros. The code is canonical: it only depends on the code length of each
+/// byte, and among codes of the same length, smaller bytes get smaller codes.
+/// When `data` holds a single distinct byte, its code is the single bit `0`.
+pub fn huffman_encode(data: &[u8]) -> (Vec<u8>, HashMap<u8, Vec<bool>>, usize) {
+    let mut counts = [0u64; 256];
+    data.iter().for_each(|&b| counts[b as usize] += 1);
+    let frequencies: Vec<(u8, u64)> = (0..=255u8)
+        .filter(|&b| counts[b as usize] > 0)
+        .map(|b| (b, counts[b as usize]))
+        .collect();
+    if frequencies.is_empty() {
+        return (vec![], HashMap::new(), 0);
+    }
+
+    // The min-heap construction of the dictionary gives the optimal code lengths
+    let dictionary = HuffmanDictionary::new(&frequencies);
+    let mut lengths: Vec<(u32, u8)> = dictionary
+        .alphabet
+        .iter()
+        .map(|(&symbol, value)| (value.bits.max(1), symbol))
+        .collect();
+    lengths.sort_unstable();
+
+    let mut codebook = HashMap::new();
+    let mut code: u64 = 0;
+    let mut previous_length = lengths[0].0;
+    for &(length, symbol) in &lengths {
+        code <<= length - previous_length;
+        previous_length = length;
+        let bits = (0..length).rev().map(|i| code >> i & 1 == 1).collect();
+        codebook.insert(symbol, bits);
+        code += 1;
+    }
+
+    let mut packed = Vec::new();
+    let mut num_bits = 0;
+    for byte in data {
+        for &bit in &codebook[byte] {
+            if num_bits % 8 == 0 {
+                packed.push(0);
+            }
+            if bit {
+                *packed.last_mut().unwrap() |= 0x80 >> (num_bits % 8);
+            }
+            num_bits += 1;
+        }
+    }
+    (packed, codebook, num_bits)
+}
+
+/// Decodes the first `num_bits` bits of `data` with the codebook returned by
+/// [`huffman_encode`].
+///
+/// Panics if `data` is shorter than `num_bits` bits or if these bits do not
+/// split into codes of `codebook`.
+pub fn huffman_decode(data: &[u8], codebook: &HashMap<u8, Vec<bool>>, num_bits: usize) -> Vec<u8> {
+    assert!(
+        num_bits <= data.len() * 8,
+        "not enough data for {num_bits} bits"
+    );
+    let symbols: HashMap<&[bool], u8> = codebook
+        .iter()
+        .map(|(&symbol, code)| (code.as_slice(), symbol))
+        .collect();
+    let mut decoded = Vec::new();
+    let mut code = Vec::new();
+    for i in 0..num_bits {
+        code.push(data[i / 8] & (0x80 >> (i % 8)) != 0);
+        if let Some(&symbol) = symbols.get(code.as_slice()) {
+            decoded.push(symbol);
+            code.clear();
+        }
+    }
+    assert!(code.is_empty(), "the data ends in the middle of a code");
+    decoded
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,4 +301,85 @@ mod tests {
         let decoded = encoded.decode(&dict).unwrap();
         assert_eq!(decoded, bytes);
     }
+
+    fn is_prefix_free(codebook: &HashMap<u8, Vec<bool>>) -> bool {
+        codebook.iter().all(|(a, code_a)| {
+            codebook
+                .iter()
+                .all(|(b, code_b)| a == b || !code_b.starts_with(code_a))
+        })
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = crate::math::PCG32::new_default(31);
+        for _ in 0..200 {
+            let len = rng.get_u32() % 300;
+            let alphabet = 1 + rng.get_u32() % 256;
+            let data: Vec<u8> = (0..len).map(|_| (rng.get_u32() % alphabet) as u8).collect();
+            let (packed, codebook, num_bits) = huffman_encode(&data);
+            assert_eq!(packed.len(), num_bits.div_ceil(8));
+            assert!(is_prefix_free(&codebook));
+            assert_eq!(huffman_decode(&packed, &codebook, num_bits), data);
+        }
+    }
+
+    #[test]
+    fn canonical_codes() {
+        // With dyadic frequencies the code lengths are exactly -log2(p)
+        let data = b"aaaaaaaabbbbccde";
+        let (packed, codebook, num_bits) = huffman_encode(data);
+        let code = |b: u8| -> String {
+            codebook[&b]
+                .iter()
+                .map(|&bit| if bit { '1' } else { '0' })
+                .collect()
+        };
+        assert_eq!(code(b'a'), "0");
+        assert_eq!(code(b'b'), "10");
+        assert_eq!(code(b'c'), "110");
+        assert_eq!(code(b'd'), "1110");
+        assert_eq!(code(b'e'), "1111");
+        assert_eq!(num_bits, 8 + 4 * 2 + 2 * 3 + 4 + 4);
+        // 00000000 10101010 110110 1110 1111, MSB first
+        assert_eq!(
+            packed,
+            vec![0b0000_0000, 0b1010_1010, 0b1101_1011, 0b1011_1100]
+        );
+    }
+
+    #[test]
+    fn close_to_entropy() {
+        let mut rng = crate::math::PCG32::new_default(5);
+        // a skewed distribution: byte k appears with probability about 2^-(k+1)
+        let data: Vec<u8> = (0..100_000)
+            .map(|_| (rng.get_u32() | 1 << 20).trailing_zeros() as u8)
+            .collect();
+        let (_, codebook, num_bits) = huffman_encode(&data);
+        let n = data.len() as f64;
+        let mut counts = HashMap::new();
+        data.iter()
+            .for_each(|b| *counts.entry(b).or_insert(0.0) += 1.0);
+        let entropy: f64 = counts.values().map(|&c| -(c / n) * (c / n).log2()).sum();
+        let average = num_bits as f64 / n;
+        assert!(entropy <= average && average < entropy + 1.0);
+        // frequent symbols are close to their own information content
+        for &symbol in &[0u8, 1, 2, 3] {
+            let information = -(counts[&symbol] / n).log2();
+            assert!((codebook[&symbol].len() as f64 - information).abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn single_symbol_and_empty() {
+        let (packed, codebook, num_bits) = huffman_encode(b"zzzz");
+        assert_eq!(codebook.len(), 1);
+        assert_eq!(codebook[&b'z'], vec![false]);
+        assert_eq!(num_bits, 4);
+        assert_eq!(huffman_decode(&packed, &codebook, num_bits), b"zzzz");
+
+        let (packed, codebook, num_bits) = huffman_encode(b"");
+        assert!(packed.is_empty() && codebook.is_empty() && num_bits == 0);
+        assert_eq!(huffman_decode(&packed, &codebook, num_bits), b"");
+    }
 }
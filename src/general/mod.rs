@@ -9,7 +9,9 @@ mod two_sum;
 pub use self::convex_hull::convex_hull_graham;
 pub use self::fisher_yates_shuffle::fisher_yates_shuffle;
 pub use self::hanoi::hanoi;
-pub use self::huffman_encoding::{HuffmanDictionary, HuffmanEncoding};
+pub use self::huffman_encoding::{
+    huffman_decode, huffman_encode, HuffmanDictionary, HuffmanEncoding,
+};
 pub use self::kmeans::f32::kmeans as kmeans_f32;
 pub use self::kmeans::f64::kmeans as kmeans_f64;
 pub use self::mex::mex_using_set;
@@ -0,0 +1,221 @@
+// The closest pair of points is found by divide and conquer: the points, sorted
+// by x coordinate, are split into halves by a vertical line, and the closest pair
+// is either in one of the halves, or has a point on each side within the smaller
+// of their distances from the line. Sorting the points by y coordinate while
+// merging the halves, like merge sort, makes it O(n log n).
+// Source: https://en.wikipedia.org/wiki/Closest_pair_of_points_problem
+
+type Point = (f64, f64);
+
+fn sqr_dist(a: &Point, b: &Point) -> f64 {
+    (a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)
+}
+
+/// The square of the smallest distance so far, with its two points.
+type Best = (f64, Point, Point);
+
+fn closer(best: Best, a: &Point, b: &Point) -> Best {
+    let d = sqr_dist(a, b);
+    if d < best.0 {
+        (d, *a, *b)
+    } else {
+        best
+    }
+}
+
+/// Returns the two closest points and their distance, or None if there are
+/// fewer than two points.
+///
+/// Duplicate points are at distance 0 from each other. When several pairs are
+/// the closest, any of them may be returned.
+pub fn closest_pair(points: &[Point]) -> Option<(Point, Point, f64)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    let (d, a, b) = closest_pair_aux(&mut points);
+    Some((a, b, d.sqrt()))
+}
+
+/// Finds the closest pair of `points`, at least two of them, which are sorted
+/// by x coordinate, and leaves them sorted by y coordinate.
+fn closest_pair_aux(points: &mut [Point]) -> Best {
+    let n = points.len();
+    if n <= 3 {
+        // bruteforce
+        let mut best = (f64::INFINITY, points[0], points[1]);
+        for i in 0..n {
+            for j in i + 1..n {
+                best = closer(best, &points[i], &points[j]);
+            }
+        }
+        points.sort_by(|a, b| a.1.total_cmp(&b.1));
+        return best;
+    }
+
+    let mid = n / 2;
+    let mid_x = points[mid].0;
+    let (left, right) = points.split_at_mut(mid);
+    let (l, r) = (closest_pair_aux(left), closest_pair_aux(right));
+    let mut best = if l.0 <= r.0 { l } else { r };
+
+    // merges the halves, both sorted by y coordinate
+    let mut merged = Vec::with_capacity(n);
+    let (mut i, mut j) = (0, mid);
+    while i < mid || j < n {
+        if j == n || (i < mid && points[i].1 <= points[j].1) {
+            merged.push(points[i]);
+            i += 1;
+        } else {
+            merged.push(points[j]);
+            j += 1;
+        }
+    }
+    points.copy_from_slice(&merged);
+
+    // a closer pair has both points in the strip around the line, and in the
+    // strip only a few points follow each point by less than the distance
+    let dist = best.0.sqrt();
+    let strip: Vec<Point> = points
+        .iter()
+        .copied()
+        .filter(|p| (p.0 - mid_x).abs() < dist)
+        .collect();
+    for (i, a) in strip.iter().enumerate() {
+        for b in &strip[i + 1..] {
+            if b.1 - a.1 >= best.0.sqrt() {
+                break;
+            }
+            best = closer(best, a, b);
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    fn brute_force(points: &[Point]) -> f64 {
+        let mut best = f64::INFINITY;
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                best = best.min(sqr_dist(a, b));
+            }
+        }
+        best.sqrt()
+    }
+
+    fn is_pair_of(points: &[Point], (a, b, d): (Point, Point, f64)) -> bool {
+        let pos_a = points.iter().position(|p| *p == a);
+        let pos_b = points.iter().rposition(|p| *p == b);
+        match (pos_a, pos_b) {
+            // a duplicate point is at two positions
+            (Some(i), Some(j)) => (i != j || a != b) && sqr_dist(&a, &b).sqrt() == d,
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn too_few_points() {
+        assert_eq!(closest_pair(&[]), None);
+        assert_eq!(closest_pair(&[(1., 2.)]), None);
+    }
+
+    #[test]
+    fn two_points() {
+        let (a, b, d) = closest_pair(&[(0., 0.), (3., 4.)]).unwrap();
+        assert_eq!(d, 5.);
+        assert!((a, b) == ((0., 0.), (3., 4.)) || (a, b) == ((3., 4.), (0., 0.)));
+    }
+
+    #[test]
+    fn identical_points() {
+        assert_eq!(
+            closest_pair(&[(1., 1.), (1., 1.)]),
+            Some(((1., 1.), (1., 1.), 0.))
+        );
+        let points = [(0., 0.), (5., 5.), (9., 1.), (5., 5.), (2., 8.)];
+        assert_eq!(closest_pair(&points), Some(((5., 5.), (5., 5.), 0.)));
+    }
+
+    #[test]
+    fn known_example() {
+        let points = [
+            (2., 3.),
+            (12., 30.),
+            (40., 50.),
+            (5., 1.),
+            (12., 10.),
+            (3., 4.),
+        ];
+        let (a, b, d) = closest_pair(&points).unwrap();
+        assert!((a, b) == ((2., 3.), (3., 4.)) || (a, b) == ((3., 4.), (2., 3.)));
+        assert_eq!(d, 2_f64.sqrt());
+    }
+
+    #[test]
+    fn collinear_points() {
+        let horizontal: Vec<Point> = [0., 10., 3., 21., 15., 7., 30.].map(|x| (x, 1.)).into();
+        assert_eq!(closest_pair(&horizontal).unwrap().2, 3.);
+        // all on the dividing line
+        let vertical: Vec<Point> = [0., 50., -25., 40., 42., 100., 17., 29.]
+            .map(|y| (2., y))
+            .into();
+        let (a, b, d) = closest_pair(&vertical).unwrap();
+        assert_eq!(d, 2.);
+        assert_eq!(a.1.min(b.1), 40.);
+        let diagonal: Vec<Point> = (0..100).map(|i| (i as f64, i as f64)).collect();
+        assert_eq!(closest_pair(&diagonal).unwrap().2, 2_f64.sqrt());
+    }
+
+    #[test]
+    fn pair_across_the_dividing_line() {
+        // far apart on each side, close only across the middle
+        let points = [
+            (0., 0.),
+            (0., 10.),
+            (4.9, 5.),
+            (5.1, 5.),
+            (10., 0.),
+            (10., 10.),
+        ];
+        let (a, b, d) = closest_pair(&points).unwrap();
+        assert!((d - 0.2).abs() < 1e-9);
+        assert_eq!(a.0.min(b.0), 4.9);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = PCG32::new_default(49);
+        for round in 0..300 {
+            // a small range makes many duplicates and collinear points
+            let range = if round % 2 == 0 { 10 } else { 1_000_000 };
+            let n = 2 + rng.get_u32() % 200;
+            let points: Vec<Point> = (0..n)
+                .map(|_| {
+                    (
+                        (rng.get_u32() % range) as f64,
+                        (rng.get_u32() % range) as f64,
+                    )
+                })
+                .collect();
+            let result = closest_pair(&points).unwrap();
+            assert_eq!(result.2, brute_force(&points));
+            assert!(is_pair_of(&points, result));
+        }
+    }
+
+    #[test]
+    fn many_points() {
+        let mut rng = PCG32::new_default(7);
+        let points: Vec<Point> = (0..4000)
+            .map(|_| (rng.get_u32() as f64, rng.get_u32() as f64))
+            .collect();
+        let result = closest_pair(&points).unwrap();
+        assert_eq!(result.2, brute_force(&points));
+        assert!(is_pair_of(&points, result));
+    }
+}
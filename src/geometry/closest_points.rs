@@ -1,108 +1,11 @@
-type Point = (f64, f64);
-use std::cmp::Ordering;
-
-fn point_cmp((a1, a2): &Point, (b1, b2): &Point) -> Ordering {
-    let acmp = f64_cmp(a1, b1);
-    match acmp {
-        Ordering::Equal => f64_cmp(a2, b2),
-        _ => acmp,
-    }
-}
+use super::closest_pair;
 
-fn f64_cmp(a: &f64, b: &f64) -> Ordering {
-    a.partial_cmp(b).unwrap()
-}
+type Point = (f64, f64);
 
 /// returns the two closest points
 /// or None if there are zero or one point
 pub fn closest_points(points: &[Point]) -> Option<(Point, Point)> {
-    let mut points: Vec<Point> = points.to_vec();
-    points.sort_by(point_cmp);
-
-    closest_points_aux(&points, 0, points.len())
-}
-
-fn sqr_dist((x1, y1): &Point, (x2, y2): &Point) -> f64 {
-    let dx = *x1 - *x2;
-    let dy = *y1 - *y2;
-
-    dx * dx + dy * dy
-}
-
-fn closest_points_aux(
-    points: &[Point],
-    mut start: usize,
-    mut end: usize,
-) -> Option<(Point, Point)> {
-    let n = end - start;
-
-    if n <= 1 {
-        return None;
-    }
-
-    if n <= 3 {
-        // bruteforce
-        let mut min = sqr_dist(&points[0], &points[1]);
-        let mut pair = (points[0], points[1]);
-
-        for i in 1..n {
-            for j in (i + 1)..n {
-                let new = sqr_dist(&points[i], &points[j]);
-                if new < min {
-                    min = new;
-                    pair = (points[i], points[j]);
-                }
-            }
-        }
-        return Some(pair);
-    }
-
-    let mid = (start + end) / 2;
-    let left = closest_points_aux(points, start, mid);
-    let right = closest_points_aux(points, mid, end);
-
-    let (mut min_sqr_dist, mut pair) = match (left, right) {
-        (Some((l1, l2)), Some((r1, r2))) => {
-            let dl = sqr_dist(&l1, &l2);
-            let dr = sqr_dist(&r1, &r2);
-            if dl < dr {
-                (dl, (l1, l2))
-            } else {
-                (dr, (r1, r2))
-            }
-        }
-        (Some((a, b)), None) => (sqr_dist(&a, &b), (a, b)),
-        (None, Some((a, b))) => (sqr_dist(&a, &b), (a, b)),
-        (None, None) => unreachable!(),
-    };
-
-    let mid_x = points[mid].0;
-    let dist = min_sqr_dist.sqrt();
-    while points[start].0 < mid_x - dist {
-        start += 1;
-    }
-    while points[end - 1].0 > mid_x + dist {
-        end -= 1;
-    }
-
-    let mut mids: Vec<&Point> = points[start..end].iter().collect();
-    mids.sort_by(|a, b| f64_cmp(&a.1, &b.1));
-
-    for (i, e) in mids.iter().enumerate() {
-        for k in 1..8 {
-            if i + k >= mids.len() {
-                break;
-            }
-
-            let new = sqr_dist(e, mids[i + k]);
-            if new < min_sqr_dist {
-                min_sqr_dist = new;
-                pair = (**e, *mids[i + k]);
-            }
-        }
-    }
-
-    Some(pair)
+    closest_pair(points).map(|(a, b, _)| (a, b))
 }
 
 #[cfg(test)]
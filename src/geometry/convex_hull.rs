@@ -0,0 +1,298 @@
+// The convex hull of a set of points is the smallest convex polygon containing
+// all of them. Graham scan sorts the points around the lowest one and walks
+// through them once, keeping only the left turns, in O(n log n) time. Jarvis
+// march wraps the points like a gift, finding each vertex from the previous
+// one, in O(nh) time for a hull of h vertices.
+// Source: https://en.wikipedia.org/wiki/Convex_hull_algorithms
+
+type Point = (f64, f64);
+use std::cmp::Ordering;
+
+/// The z coordinate of the cross product of `oa` and `ob`: positive when `o`,
+/// `a`, `b` make a counter-clockwise turn, negative for a clockwise one, and zero
+/// when they are collinear.
+fn cross(o: &Point, a: &Point, b: &Point) -> f64 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+fn sqr_dist(a: &Point, b: &Point) -> f64 {
+    (a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)
+}
+
+/// Orders by y coordinate, then by x coordinate.
+fn lowest_first(a: &Point, b: &Point) -> Ordering {
+    a.1.total_cmp(&b.1).then(a.0.total_cmp(&b.0))
+}
+
+/// Returns the distinct points, the lowest one (and the leftmost of the lowest
+/// ones) first.
+fn distinct_points(points: &[Point]) -> Vec<Point> {
+    let mut points = points.to_vec();
+    points.sort_by(lowest_first);
+    points.dedup();
+    points
+}
+
+/// Sorts the points after the first one, the pivot, by their angle around it,
+/// comparing the angles exactly with cross products, the closer point first when
+/// two angles are the same.
+///
+/// The pivot must be the lowest point, the leftmost one if there are several, so
+/// that the angles are in [0, pi) and the cross product orders them.
+fn sort_around_pivot(points: &mut [Point]) {
+    let pivot = points[0];
+    points[1..].sort_by(|a, b| {
+        let turn = cross(&pivot, a, b);
+        if turn > 0.0 {
+            Ordering::Less
+        } else if turn < 0.0 {
+            Ordering::Greater
+        } else {
+            sqr_dist(&pivot, a).total_cmp(&sqr_dist(&pivot, b))
+        }
+    });
+}
+
+/// Walks through `points`, sorted around the first one, popping the points that
+/// make a clockwise turn, and the ones going straight unless `keep_collinear`.
+fn scan(points: Vec<Point>, keep_collinear: bool) -> Vec<Point> {
+    let mut hull: Vec<Point> = Vec::with_capacity(points.len());
+    for point in points {
+        while let [.., a, b] = hull[..] {
+            let turn = cross(&a, &b, &point);
+            if turn > 0.0 || (keep_collinear && turn == 0.0) {
+                break;
+            }
+            hull.pop();
+        }
+        hull.push(point);
+    }
+    hull
+}
+
+/// Returns the vertices of the convex hull of `points` in counter-clockwise
+/// order, starting from the lowest point (the leftmost one if there are several).
+///
+/// Only the corners are kept: points lying on an edge of the hull are left out,
+/// as are duplicates. So all the points being on a line gives its two ends,
+/// and fewer than three distinct points are returned as they are.
+///
+/// The points are sorted by their angle around the first vertex, comparing the
+/// angles exactly with cross products, and the closer point comes first when two
+/// angles are the same. The scan then pops the points not making a left turn,
+/// the farther of collinear points coming last.
+pub fn graham_scan(points: &[Point]) -> Vec<Point> {
+    let mut points = distinct_points(points);
+    if points.len() < 3 {
+        return points;
+    }
+    sort_around_pivot(&mut points);
+    scan(points, false)
+}
+
+/// Like `graham_scan`, but keeping the duplicates and the points that go straight
+/// on from the previous ones, which is what `general::convex_hull_graham` returns.
+pub(crate) fn graham_scan_keeping_collinear(points: &[Point]) -> Vec<Point> {
+    let mut points = points.to_vec();
+    if points.is_empty() {
+        return points;
+    }
+    points.sort_by(lowest_first);
+    sort_around_pivot(&mut points);
+    scan(points, true)
+}
+
+/// Returns the vertices of the convex hull of `points` in counter-clockwise
+/// order, starting from the lowest point (the leftmost one if there are several).
+///
+/// It returns the same vertices as `graham_scan`, leaving out the points lying on
+/// an edge of the hull as well as duplicates.
+///
+/// From every vertex, the next one is the point that leaves all the others on
+/// the left, the farthest one if several are on the same line. It is faster than
+/// `graham_scan` when the hull has fewer than about `log(n)` vertices.
+pub fn jarvis_march(points: &[Point]) -> Vec<Point> {
+    let points = distinct_points(points);
+    if points.len() < 3 {
+        return points;
+    }
+
+    let mut hull = vec![points[0]];
+    loop {
+        let current = hull[hull.len() - 1];
+        let mut next = if points[0] == current {
+            points[1]
+        } else {
+            points[0]
+        };
+        for point in &points {
+            let turn = cross(&current, &next, point);
+            if turn < 0.0 || (turn == 0.0 && sqr_dist(&current, point) > sqr_dist(&current, &next))
+            {
+                next = *point;
+            }
+        }
+        if next == hull[0] {
+            return hull;
+        }
+        hull.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    fn random_points(rng: &mut PCG32, count: u32, range: u32) -> Vec<Point> {
+        (0..count)
+            .map(|_| {
+                (
+                    (rng.get_u32() % range) as f64,
+                    (rng.get_u32() % range) as f64,
+                )
+            })
+            .collect()
+    }
+
+    fn is_convex_hull_of(hull: &[Point], points: &[Point]) -> bool {
+        let n = hull.len();
+        (0..n).all(|i| {
+            let (a, b) = (&hull[i], &hull[(i + 1) % n]);
+            // strictly convex corners, and no point outside
+            cross(a, b, &hull[(i + 2) % n]) > 0.0 && points.iter().all(|p| cross(a, b, p) >= 0.0)
+        })
+    }
+
+    #[test]
+    fn square_with_inner_points() {
+        let points = [
+            (1.0, 1.0),
+            (0.0, 0.0),
+            (2.0, 2.0),
+            (0.5, 1.5),
+            (2.0, 0.0),
+            (0.0, 2.0),
+        ];
+        let hull = vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)];
+        assert_eq!(graham_scan(&points), hull);
+        assert_eq!(jarvis_march(&points), hull);
+    }
+
+    #[test]
+    // from https://codegolf.stackexchange.com/questions/11035/find-the-convex-hull-of-a-set-of-2d-points
+    fn known_example() {
+        let points = [
+            (4.4, 14.),
+            (6.7, 15.25),
+            (6.9, 12.8),
+            (2.1, 11.1),
+            (9.5, 14.9),
+            (13.2, 11.9),
+            (10.3, 12.3),
+            (6.8, 9.5),
+            (3.3, 7.7),
+            (0.6, 5.1),
+            (5.3, 2.4),
+            (8.45, 4.7),
+            (11.5, 9.6),
+            (13.8, 7.3),
+            (12.9, 3.1),
+            (11., 1.1),
+        ];
+        let hull = vec![
+            (11., 1.1),
+            (12.9, 3.1),
+            (13.8, 7.3),
+            (13.2, 11.9),
+            (9.5, 14.9),
+            (6.7, 15.25),
+            (4.4, 14.),
+            (2.1, 11.1),
+            (0.6, 5.1),
+            (5.3, 2.4),
+        ];
+        assert_eq!(graham_scan(&points), hull);
+        assert_eq!(jarvis_march(&points), hull);
+    }
+
+    #[test]
+    fn few_points() {
+        assert_eq!(graham_scan(&[]), vec![]);
+        assert_eq!(jarvis_march(&[]), vec![]);
+        assert_eq!(graham_scan(&[(1.0, 2.0)]), vec![(1.0, 2.0)]);
+        assert_eq!(jarvis_march(&[(1.0, 2.0)]), vec![(1.0, 2.0)]);
+        let pair = [(3.0, 4.0), (1.0, 2.0)];
+        assert_eq!(graham_scan(&pair), vec![(1.0, 2.0), (3.0, 4.0)]);
+        assert_eq!(jarvis_march(&pair), vec![(1.0, 2.0), (3.0, 4.0)]);
+        let triangle = [(0.0, 3.0), (3.0, 0.0), (0.0, 0.0)];
+        let hull = vec![(0.0, 0.0), (3.0, 0.0), (0.0, 3.0)];
+        assert_eq!(graham_scan(&triangle), hull);
+        assert_eq!(jarvis_march(&triangle), hull);
+    }
+
+    #[test]
+    fn duplicates() {
+        let points = [(1.0, 1.0); 5];
+        assert_eq!(graham_scan(&points), vec![(1.0, 1.0)]);
+        assert_eq!(jarvis_march(&points), vec![(1.0, 1.0)]);
+        let points = [
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (0.0, 0.0),
+            (0.0, 1.0),
+            (1.0, 0.0),
+            (0.0, 1.0),
+        ];
+        let hull = vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)];
+        assert_eq!(graham_scan(&points), hull);
+        assert_eq!(jarvis_march(&points), hull);
+    }
+
+    #[test]
+    fn collinear_points_are_left_out() {
+        let line: Vec<Point> = (0..10).map(|i| (i as f64, 2.0 * i as f64)).rev().collect();
+        assert_eq!(graham_scan(&line), vec![(0.0, 0.0), (9.0, 18.0)]);
+        assert_eq!(jarvis_march(&line), vec![(0.0, 0.0), (9.0, 18.0)]);
+
+        // a square with points along every edge
+        let mut points = Vec::new();
+        for i in 0..=4 {
+            let i = i as f64;
+            points.extend([(i, 0.0), (4.0, i), (i, 4.0), (0.0, i)]);
+        }
+        let hull = vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)];
+        assert_eq!(graham_scan(&points), hull);
+        assert_eq!(jarvis_march(&points), hull);
+    }
+
+    #[test]
+    fn same_polar_angle() {
+        // rays from the pivot with several points each, the farthest only on the hull
+        let mut points = vec![(0.0, 0.0)];
+        for k in 1..=5 {
+            let k = k as f64;
+            points.extend([(k, 0.0), (k, k), (0.0, k), (-k, k)]);
+        }
+        points.reverse();
+        let hull = vec![(0.0, 0.0), (5.0, 0.0), (5.0, 5.0), (-5.0, 5.0)];
+        assert_eq!(graham_scan(&points), hull);
+        assert_eq!(jarvis_march(&points), hull);
+    }
+
+    #[test]
+    fn random_point_sets() {
+        let mut rng = PCG32::new_default(48);
+        for round in 0..300 {
+            // small ranges make many duplicate and collinear points
+            let range = if round % 2 == 0 { 8 } else { 1000 };
+            let count = 1 + rng.get_u32() % 100;
+            let points = random_points(&mut rng, count, range);
+            let hull = graham_scan(&points);
+            assert_eq!(jarvis_march(&points), hull);
+            if hull.len() >= 3 {
+                assert!(is_convex_hull_of(&hull, &points), "{points:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+// Two segments intersect when the ends of each one are on different sides of
+// the line through the other one, or when an end of one lies on the other. The
+// sides are given by the sign of cross products, so no division is needed to
+// tell whether they intersect.
+// Source: https://en.wikipedia.org/wiki/Line%E2%80%93line_intersection
+
+use std::cmp::Ordering;
+
+type Point = (f64, f64);
+
+/// Tells on which side of the line through `a` and `b` the point `c` is:
+/// `Greater` when `a`, `b`, `c` turn counter-clockwise, `Less` when they turn
+/// clockwise, and `Equal` when they are collinear.
+///
+/// The sign is exact as long as the differences of the coordinates and their
+/// products are, which is the case for integers up to 2^26 in absolute value.
+fn orientation(a: Point, b: Point, c: Point) -> Ordering {
+    let left = (b.0 - a.0) * (c.1 - a.1);
+    let right = (b.1 - a.1) * (c.0 - a.0);
+    // not total_cmp, which orders -0.0 before 0.0; NaN counts as collinear
+    left.partial_cmp(&right).unwrap_or(Ordering::Equal)
+}
+
+/// Tells whether `c`, collinear with `a` and `b`, is on the segment between them.
+fn on_segment(a: Point, b: Point, c: Point) -> bool {
+    a.0.min(b.0) <= c.0 && c.0 <= a.0.max(b.0) && a.1.min(b.1) <= c.1 && c.1 <= a.1.max(b.1)
+}
+
+/// Returns whether the segments `p1p2` and `p3p4` have at least one point in
+/// common, including when an end of one lies on the other one, and when they
+/// are collinear and overlap.
+pub fn segments_intersect(p1: Point, p2: Point, p3: Point, p4: Point) -> bool {
+    let d1 = orientation(p3, p4, p1);
+    let d2 = orientation(p3, p4, p2);
+    let d3 = orientation(p1, p2, p3);
+    let d4 = orientation(p1, p2, p4);
+    if d1 != d2 && d3 != d4 && !d1.is_eq() && !d2.is_eq() && !d3.is_eq() && !d4.is_eq() {
+        return true;
+    }
+    (d1.is_eq() && on_segment(p3, p4, p1))
+        || (d2.is_eq() && on_segment(p3, p4, p2))
+        || (d3.is_eq() && on_segment(p1, p2, p3))
+        || (d4.is_eq() && on_segment(p1, p2, p4))
+}
+
+/// Returns the point where the segments `p1p2` and `p3p4` meet, or None if they
+/// do not intersect.
+///
+/// When an end of one segment lies on the other one, like in a T, that end is
+/// returned exactly. Collinear segments sharing only an end give that end, but
+/// collinear segments that overlap have a whole segment in common rather than a
+/// point, and give None: `segments_intersect` tells them apart from disjoint ones.
+pub fn segment_intersection_point(p1: Point, p2: Point, p3: Point, p4: Point) -> Option<Point> {
+    if !segments_intersect(p1, p2, p3, p4) {
+        return None;
+    }
+    let collinear =
+        orientation(p1, p2, p3).is_eq() && orientation(p1, p2, p4).is_eq() && p1 != p2 && p3 != p4;
+    if collinear {
+        // they only meet at a point if they share an end and go in opposite directions from it
+        let d = (p2.0 - p1.0, p2.1 - p1.1);
+        let along = |p: Point| (p.0 - p1.0) * d.0 + (p.1 - p1.1) * d.1;
+        let (low, high) = if along(p3) <= along(p4) {
+            (p3, p4)
+        } else {
+            (p4, p3)
+        };
+        let (start, end) = (along(p1), along(p2));
+        return if along(high) == start {
+            Some(p1)
+        } else if along(low) == end {
+            Some(p2)
+        } else {
+            None
+        };
+    }
+
+    // an end lying on the other segment, or a degenerate segment that is a point
+    for (end, a, b) in [(p1, p3, p4), (p2, p3, p4), (p3, p1, p2), (p4, p1, p2)] {
+        if orientation(a, b, end).is_eq() && on_segment(a, b, end) {
+            return Some(end);
+        }
+    }
+
+    // the segments cross properly: p1 + t * (p2 - p1) is on the line through p3 and p4
+    let r = (p2.0 - p1.0, p2.1 - p1.1);
+    let s = (p4.0 - p3.0, p4.1 - p3.1);
+    let t = ((p3.0 - p1.0) * s.1 - (p3.1 - p1.1) * s.0) / (r.0 * s.1 - r.1 * s.0);
+    Some((p1.0 + t * r.0, p1.1 + t * r.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perpendicular() {
+        assert!(segments_intersect((0., 0.), (4., 4.), (0., 4.), (4., 0.)));
+        assert_eq!(
+            segment_intersection_point((0., 0.), (4., 4.), (0., 4.), (4., 0.)),
+            Some((2., 2.))
+        );
+        assert_eq!(
+            segment_intersection_point((-1., 0.5), (3., 0.5), (1., -2.), (1., 2.)),
+            Some((1., 0.5))
+        );
+        // the lines cross, but not the segments
+        assert!(!segments_intersect(
+            (0., 0.),
+            (1., 1.),
+            (0., 4.),
+            (1.5, 2.5)
+        ));
+        assert_eq!(
+            segment_intersection_point((0., 0.), (1., 1.), (0., 4.), (1.5, 2.5)),
+            None
+        );
+    }
+
+    #[test]
+    fn shared_endpoint() {
+        assert!(segments_intersect((0., 0.), (2., 1.), (2., 1.), (5., -3.)));
+        assert_eq!(
+            segment_intersection_point((0., 0.), (2., 1.), (2., 1.), (5., -3.)),
+            Some((2., 1.))
+        );
+        assert_eq!(
+            segment_intersection_point((2., 1.), (0., 0.), (5., -3.), (2., 1.)),
+            Some((2., 1.))
+        );
+        // the cross products are -0.0 and 0.0 here, which are equal
+        assert!(segments_intersect((0., 0.), (1., -1.), (0., 0.), (-1., 2.)));
+        assert_eq!(
+            segment_intersection_point((0., 0.), (1., -1.), (0., 0.), (-1., 2.)),
+            Some((0., 0.))
+        );
+        for (a, b) in [
+            ((1., -1.), (-1., 2.)),
+            ((-3., 0.), (0., -2.)),
+            ((0., 5.), (-4., -1.)),
+        ] {
+            assert!(segments_intersect((0., 0.), a, b, (0., 0.)));
+            assert_eq!(
+                segment_intersection_point(a, (0., 0.), (0., 0.), b),
+                Some((0., 0.))
+            );
+        }
+    }
+
+    #[test]
+    fn coincident_points() {
+        // both segments are the same point
+        assert!(segments_intersect((0., 0.), (0., 0.), (0., 0.), (0., 0.)));
+        assert_eq!(
+            segment_intersection_point((0., 0.), (0., 0.), (0., 0.), (0., 0.)),
+            Some((0., 0.))
+        );
+        assert!(segments_intersect(
+            (-2., 3.),
+            (-2., 3.),
+            (-2., 3.),
+            (-2., 3.)
+        ));
+        assert!(!segments_intersect((0., 0.), (0., 0.), (1., 0.), (1., 0.)));
+        // a point at an end of a segment
+        assert_eq!(
+            segment_intersection_point((0., 0.), (0., 0.), (0., 0.), (-1., 2.)),
+            Some((0., 0.))
+        );
+        assert_eq!(
+            segment_intersection_point((1., -1.), (0., 0.), (0., 0.), (0., 0.)),
+            Some((0., 0.))
+        );
+    }
+
+    #[test]
+    fn t_intersection() {
+        // the end (1, 0) of the vertical segment is on the horizontal one
+        assert!(segments_intersect((0., 0.), (3., 0.), (1., 0.), (1., 5.)));
+        assert_eq!(
+            segment_intersection_point((0., 0.), (3., 0.), (1., 0.), (1., 5.)),
+            Some((1., 0.))
+        );
+        // an end at a third of a slanted segment, which a division would round
+        assert_eq!(
+            segment_intersection_point((0., 0.), (3., 1.), (1., 1. / 3.), (0., 5.)),
+            Some((1., 1. / 3.))
+        );
+        // stopping just short of the other segment
+        assert!(!segments_intersect(
+            (0., 0.),
+            (3., 0.),
+            (1., 0.001),
+            (1., 5.)
+        ));
+    }
+
+    #[test]
+    fn parallel() {
+        assert!(!segments_intersect((0., 0.), (4., 2.), (0., 1.), (4., 3.)));
+        assert_eq!(
+            segment_intersection_point((0., 0.), (4., 2.), (0., 1.), (4., 3.)),
+            None
+        );
+        // on the same line, but apart
+        assert!(!segments_intersect((0., 0.), (1., 1.), (2., 2.), (3., 3.)));
+        assert_eq!(
+            segment_intersection_point((0., 0.), (1., 1.), (2., 2.), (3., 3.)),
+            None
+        );
+    }
+
+    #[test]
+    fn collinear() {
+        // overlapping
+        assert!(segments_intersect((0., 0.), (4., 0.), (2., 0.), (6., 0.)));
+        assert_eq!(
+            segment_intersection_point((0., 0.), (4., 0.), (2., 0.), (6., 0.)),
+            None
+        );
+        // one inside the other
+        assert!(segments_intersect((0., 0.), (6., 6.), (5., 5.), (2., 2.)));
+        assert_eq!(
+            segment_intersection_point((0., 0.), (6., 6.), (5., 5.), (2., 2.)),
+            None
+        );
+        // end to end
+        assert!(segments_intersect((0., 0.), (2., 0.), (2., 0.), (5., 0.)));
+        assert_eq!(
+            segment_intersection_point((0., 0.), (2., 0.), (5., 0.), (2., 0.)),
+            Some((2., 0.))
+        );
+        assert_eq!(
+            segment_intersection_point((2., 0.), (0., 0.), (-3., 0.), (0., 0.)),
+            Some((0., 0.))
+        );
+    }
+
+    #[test]
+    fn degenerate_segments() {
+        // a point on a segment
+        assert!(segments_intersect((1., 1.), (1., 1.), (0., 0.), (2., 2.)));
+        assert_eq!(
+            segment_intersection_point((1., 1.), (1., 1.), (0., 0.), (2., 2.)),
+            Some((1., 1.))
+        );
+        assert!(!segments_intersect((1., 2.), (1., 2.), (0., 0.), (2., 2.)));
+        assert_eq!(
+            segment_intersection_point((3., 3.), (3., 3.), (3., 3.), (3., 3.)),
+            Some((3., 3.))
+        );
+    }
+
+    #[test]
+    fn nearly_parallel() {
+        // the tiny turns must not be lost to rounding
+        let (a, b) = ((0., 0.), (1e8, 1.));
+        assert!(segments_intersect(a, b, (5e7, 0.), (5e7, 1.)));
+        assert!(!segments_intersect(a, b, (5e7, 0.6), (5e7, 1.)));
+        assert!(segments_intersect(a, b, (5e7, 0.5), (5e7, 1.)));
+    }
+}
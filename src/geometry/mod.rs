@@ -1,3 +1,12 @@
+mod closest_pair;
 mod closest_points;
+mod convex_hull;
+mod line_segment;
+mod polygon;
 
+pub use self::closest_pair::closest_pair;
 pub use self::closest_points::closest_points;
+pub(crate) use self::convex_hull::graham_scan_keeping_collinear;
+pub use self::convex_hull::{graham_scan, jarvis_march};
+pub use self::line_segment::{segment_intersection_point, segments_intersect};
+pub use self::polygon::{point_in_polygon, polygon_area, PointLocation};
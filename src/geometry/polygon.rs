@@ -0,0 +1,175 @@
+// A polygon is given by its vertices in order, either clockwise or
+// counter-clockwise, the last one being joined to the first one.
+// Sources: https://en.wikipedia.org/wiki/Shoelace_formula
+// https://en.wikipedia.org/wiki/Point_in_polygon#Ray_casting_algorithm
+
+type Point = (f64, f64);
+
+/// Where a point is with respect to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointLocation {
+    Inside,
+    Outside,
+    OnBoundary,
+}
+
+/// Iterates over the edges of the polygon, the last one going back to the first vertex.
+fn edges(polygon: &[Point]) -> impl Iterator<Item = (&Point, &Point)> {
+    polygon.iter().zip(polygon.iter().cycle().skip(1))
+}
+
+/// Returns the area of the polygon, which must not cross itself, with the
+/// shoelace formula.
+///
+/// The sum of the cross products of the consecutive vertices is twice the area
+/// of the polygon, positive for counter-clockwise vertices and negative for
+/// clockwise ones. Fewer than three vertices have an area of 0.
+pub fn polygon_area(vertices: &[Point]) -> f64 {
+    let twice_signed_area: f64 = edges(vertices).map(|(a, b)| a.0 * b.1 - b.0 * a.1).sum();
+    twice_signed_area.abs() / 2.0
+}
+
+/// Tells whether `point` is inside `polygon`, outside it, or on one of its edges.
+///
+/// The polygon does not need to be convex. A ray going right from the point
+/// crosses the boundary an odd number of times when the point is inside. An edge
+/// counts as crossed when one of its ends is strictly above the point and the
+/// other is not, so that a ray through a vertex counts it once for the two edges
+/// meeting there if they go on different sides of the ray, and zero or two times
+/// otherwise, and a ray along a horizontal edge does not count it at all.
+pub fn point_in_polygon(point: Point, polygon: &[Point]) -> PointLocation {
+    let (x, y) = point;
+    let mut inside = false;
+    for (a, b) in edges(polygon) {
+        let on_line = (b.0 - a.0) * (y - a.1) == (b.1 - a.1) * (x - a.0);
+        if on_line
+            && a.0.min(b.0) <= x
+            && x <= a.0.max(b.0)
+            && a.1.min(b.1) <= y
+            && y <= a.1.max(b.1)
+        {
+            return PointLocation::OnBoundary;
+        }
+        if (a.1 > y) != (b.1 > y) {
+            // where the edge crosses the line of the ray
+            let crossing = a.0 + (y - a.1) * (b.0 - a.0) / (b.1 - a.1);
+            if crossing > x {
+                inside = !inside;
+            }
+        }
+    }
+    if inside {
+        PointLocation::Inside
+    } else {
+        PointLocation::Outside
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PointLocation::*;
+    use super::*;
+
+    // an arrow pointing up, not convex
+    const ARROW: [Point; 7] = [
+        (0., 0.),
+        (2., 2.),
+        (4., 0.),
+        (4., 4.),
+        (2., 6.),
+        (0., 4.),
+        (0., 0.5),
+    ];
+
+    #[test]
+    fn area() {
+        assert_eq!(polygon_area(&[]), 0.);
+        assert_eq!(polygon_area(&[(1., 1.), (3., 3.)]), 0.);
+        assert_eq!(polygon_area(&[(0., 0.), (4., 0.), (0., 3.)]), 6.);
+        // clockwise gives the same area
+        assert_eq!(polygon_area(&[(0., 3.), (4., 0.), (0., 0.)]), 6.);
+        let square = [(1., 1.), (3., 1.), (3., 3.), (1., 3.)];
+        assert_eq!(polygon_area(&square), 4.);
+        // 4 * 4 square, a triangle cut off the bottom and added on top
+        assert_eq!(polygon_area(&ARROW), 16.);
+        let l_shape = [(0., 0.), (2., 0.), (2., 1.), (1., 1.), (1., 2.), (0., 2.)];
+        assert_eq!(polygon_area(&l_shape), 3.);
+    }
+
+    #[test]
+    fn convex_polygon() {
+        let hexagon = [(1., 0.), (3., 0.), (4., 2.), (3., 4.), (1., 4.), (0., 2.)];
+        let n = hexagon.len() as f64;
+        let centroid = (
+            hexagon.iter().map(|p| p.0).sum::<f64>() / n,
+            hexagon.iter().map(|p| p.1).sum::<f64>() / n,
+        );
+        assert_eq!(point_in_polygon(centroid, &hexagon), Inside);
+        assert_eq!(point_in_polygon((100., -50.), &hexagon), Outside);
+        assert_eq!(point_in_polygon((2., 0.), &hexagon), OnBoundary);
+        assert_eq!(point_in_polygon((3.5, 1.), &hexagon), OnBoundary);
+        assert_eq!(point_in_polygon((4., 2.), &hexagon), OnBoundary);
+        assert_eq!(point_in_polygon((3.6, 1.), &hexagon), Outside);
+    }
+
+    #[test]
+    fn non_convex_polygon() {
+        assert_eq!(point_in_polygon((2., 4.), &ARROW), Inside);
+        assert_eq!(point_in_polygon((0.5, 1.), &ARROW), Inside);
+        // in the notch at the bottom
+        assert_eq!(point_in_polygon((2., 1.), &ARROW), Outside);
+        assert_eq!(point_in_polygon((2., 2.), &ARROW), OnBoundary);
+        assert_eq!(point_in_polygon((1., 1.), &ARROW), OnBoundary);
+        assert_eq!(point_in_polygon((3., 5.5), &ARROW), Outside);
+    }
+
+    #[test]
+    fn ray_through_vertices() {
+        // the ray from (1, 2) goes through the vertex (2, 2), the tip of the notch,
+        // and from (-1, 4) through both top corners of the sides
+        assert_eq!(point_in_polygon((1., 2.), &ARROW), Inside);
+        assert_eq!(point_in_polygon((-1., 2.), &ARROW), Outside);
+        assert_eq!(point_in_polygon((-1., 4.), &ARROW), Outside);
+        assert_eq!(point_in_polygon((1., 4.), &ARROW), Inside);
+        assert_eq!(point_in_polygon((-1., 6.), &ARROW), Outside);
+        assert_eq!(point_in_polygon((-1., 0.), &ARROW), Outside);
+    }
+
+    #[test]
+    fn ray_along_edges() {
+        // a staircase, with horizontal edges at the heights of the points
+        let stairs = [(0., 0.), (3., 0.), (3., 1.), (2., 1.), (2., 2.), (0., 2.)];
+        assert_eq!(point_in_polygon((-1., 1.), &stairs), Outside);
+        assert_eq!(point_in_polygon((1., 1.), &stairs), Inside);
+        assert_eq!(point_in_polygon((2.5, 1.), &stairs), OnBoundary);
+        assert_eq!(point_in_polygon((-1., 0.), &stairs), Outside);
+        assert_eq!(point_in_polygon((-1., 2.), &stairs), Outside);
+        assert_eq!(point_in_polygon((1., 0.5), &stairs), Inside);
+        assert_eq!(point_in_polygon((2.5, 1.5), &stairs), Outside);
+    }
+
+    #[test]
+    fn matches_area_on_a_grid() {
+        // the unit cells with their centre inside make up a polygon drawn on the grid
+        let shape = [
+            (0., 0.),
+            (4., 0.),
+            (4., 1.),
+            (1., 1.),
+            (1., 3.),
+            (3., 3.),
+            (3., 5.),
+            (0., 5.),
+        ];
+        let mut inside = 0;
+        for i in -1..8 {
+            for j in -1..8 {
+                if point_in_polygon((i as f64 + 0.5, j as f64 + 0.5), &shape) == Inside {
+                    inside += 1;
+                }
+            }
+        }
+        assert_eq!(inside as f64, polygon_area(&shape));
+        assert_eq!(inside, 12);
+    }
+}
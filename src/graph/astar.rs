@@ -0,0 +1,224 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// A 2-D grid of cells, where `grid[row][column]` is `true` for obstacles
+/// and `false` for passable cells.
+pub type Grid = Vec<Vec<bool>>;
+
+type Cell = (usize, usize);
+
+/// Manhattan distance between two cells, admissible for 4-directional moves.
+pub fn manhattan_distance(a: Cell, b: Cell) -> u64 {
+    (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as u64
+}
+
+/// Euclidean distance between two cells, rounded to the nearest integer.
+///
+/// It never exceeds the Manhattan distance, so it is admissible as well, but
+/// it is less informed and A* usually explores more cells with it.
+pub fn euclidean_distance(a: Cell, b: Cell) -> u64 {
+    let dr = a.0.abs_diff(b.0) as f64;
+    let dc = a.1.abs_diff(b.1) as f64;
+    (dr * dr + dc * dc).sqrt().round() as u64
+}
+
+fn neighbors(grid: &Grid, (row, column): Cell) -> impl Iterator<Item = Cell> + '_ {
+    let up = row.checked_sub(1).map(|r| (r, column));
+    let down = Some((row + 1, column));
+    let left = column.checked_sub(1).map(|c| (row, c));
+    let right = Some((row, column + 1));
+    [up, down, left, right]
+        .into_iter()
+        .flatten()
+        .filter(move |&(r, c)| r < grid.len() && c < grid[r].len() && !grid[r][c])
+}
+
+/// Finds a path between `start` and `goal` moving up, down, left or right
+/// through passable cells, each move costing 1.
+///
+/// Cells are explored in increasing order of `g + h`, where `g` is the cost
+/// from `start` and `h = heuristic(cell, goal)` estimates the remaining cost.
+/// The returned path, which includes both endpoints, is a shortest one as long
+/// as the heuristic is admissible, i.e. never overestimates the remaining cost.
+///
+/// Returns `None` when `goal` cannot be reached or either endpoint is blocked.
+pub fn astar(
+    grid: &Grid,
+    start: Cell,
+    goal: Cell,
+    heuristic: fn(Cell, Cell) -> u64,
+) -> Option<Vec<Cell>> {
+    let passable = |(r, c): Cell| r < grid.len() && c < grid[r].len() && !grid[r][c];
+    if !passable(start) || !passable(goal) {
+        return None;
+    }
+
+    let mut cost: Vec<Vec<Option<u64>>> = grid.iter().map(|row| vec![None; row.len()]).collect();
+    let mut came_from: Vec<Vec<Option<Cell>>> =
+        grid.iter().map(|row| vec![None; row.len()]).collect();
+    let mut closed: Vec<Vec<bool>> = grid.iter().map(|row| vec![false; row.len()]).collect();
+    let mut open = BinaryHeap::new();
+
+    cost[start.0][start.1] = Some(0);
+    open.push(Reverse((heuristic(start, goal), 0, start)));
+
+    while let Some(Reverse((_, g, cell))) = open.pop() {
+        if cell == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some(previous) = came_from[current.0][current.1] {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if closed[cell.0][cell.1] {
+            continue;
+        }
+        closed[cell.0][cell.1] = true;
+
+        for next in neighbors(grid, cell) {
+            let tentative = g + 1;
+            if cost[next.0][next.1].is_some_and(|known| known <= tentative) {
+                continue;
+            }
+            cost[next.0][next.1] = Some(tentative);
+            came_from[next.0][next.1] = Some(cell);
+            open.push(Reverse((
+                tentative + heuristic(next, goal),
+                tentative,
+                next,
+            )));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{dijkstra, WeightedGraph};
+    use super::*;
+    use crate::math::PCG32;
+
+    fn parse(rows: &[&str]) -> Grid {
+        rows.iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    fn is_valid_path(grid: &Grid, path: &[Cell], start: Cell, goal: Cell) -> bool {
+        path.first() == Some(&start)
+            && path.last() == Some(&goal)
+            && path.iter().all(|&(r, c)| !grid[r][c])
+            && path
+                .windows(2)
+                .all(|pair| manhattan_distance(pair[0], pair[1]) == 1)
+    }
+
+    fn dijkstra_distance(grid: &Grid, start: Cell, goal: Cell) -> Option<u64> {
+        let width = grid[0].len();
+        let index = |(r, c): Cell| r * width + c;
+        let mut graph = WeightedGraph::new(grid.len() * width);
+        for r in 0..grid.len() {
+            for c in 0..width {
+                if !grid[r][c] {
+                    for next in neighbors(grid, (r, c)) {
+                        graph.add_edge(index((r, c)), index(next), 1);
+                    }
+                }
+            }
+        }
+        dijkstra(&graph, index(start))[index(goal)]
+    }
+
+    #[test]
+    fn straight_line() {
+        let grid = parse(&["....."]);
+        assert_eq!(
+            astar(&grid, (0, 0), (0, 4), manhattan_distance),
+            Some(vec![(0, 0), (0, 1), (0, 2), (0, 3), (0, 4)])
+        );
+        assert_eq!(
+            astar(&grid, (0, 2), (0, 2), manhattan_distance),
+            Some(vec![(0, 2)])
+        );
+    }
+
+    #[test]
+    fn around_a_wall() {
+        let grid = parse(&["..#..", "..#..", "....."]);
+        let path = astar(&grid, (0, 0), (0, 4), manhattan_distance).unwrap();
+        assert!(is_valid_path(&grid, &path, (0, 0), (0, 4)));
+        assert_eq!(path.len(), 9);
+    }
+
+    #[test]
+    fn no_path() {
+        let grid = parse(&["..#..", "..#..", "..#.."]);
+        assert_eq!(astar(&grid, (0, 0), (2, 4), manhattan_distance), None);
+        assert_eq!(astar(&grid, (0, 0), (0, 2), manhattan_distance), None);
+        assert_eq!(astar(&grid, (0, 0), (5, 5), manhattan_distance), None);
+    }
+
+    #[test]
+    fn heuristics() {
+        assert_eq!(manhattan_distance((1, 5), (4, 1)), 7);
+        assert_eq!(euclidean_distance((1, 5), (4, 1)), 5);
+        assert_eq!(euclidean_distance((0, 0), (1, 1)), 1);
+        assert_eq!(euclidean_distance((2, 2), (2, 2)), 0);
+    }
+
+    #[test]
+    fn matches_dijkstra() {
+        let mut rng = PCG32::new_default(8675309);
+        for _ in 0..200 {
+            let grid: Grid = (0..12)
+                .map(|_| (0..15).map(|_| rng.get_u32() % 10 < 3).collect())
+                .collect();
+            let start = (0, 0);
+            let goal = (11, 14);
+            if grid[0][0] || grid[11][14] {
+                continue;
+            }
+            let expected = dijkstra_distance(&grid, start, goal);
+            for heuristic in [manhattan_distance, euclidean_distance] {
+                let path = astar(&grid, start, goal, heuristic);
+                assert_eq!(path.as_ref().map(|p| p.len() as u64 - 1), expected);
+                if let Some(path) = path {
+                    assert!(is_valid_path(&grid, &path, start, goal));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn inadmissible_heuristic() {
+        // Overestimating the remaining cost makes the search greedy: it commits
+        // to the pocket in the middle that points towards the goal and settles
+        // for the detour it finds from there
+        fn greedy(a: Cell, b: Cell) -> u64 {
+            100 * manhattan_distance(a, b)
+        }
+
+        let grid = parse(&[
+            "......", //
+            "..#..#", //
+            ".#..#.", //
+            ".#.#..", //
+            "......", //
+        ]);
+        let start = (2, 0);
+        let goal = (2, 5);
+        let optimal = astar(&grid, start, goal, manhattan_distance).unwrap();
+        let suboptimal = astar(&grid, start, goal, greedy).unwrap();
+        assert!(is_valid_path(&grid, &suboptimal, start, goal));
+        assert_eq!(
+            optimal.len() as u64 - 1,
+            dijkstra_distance(&grid, start, goal).unwrap()
+        );
+        assert_eq!(optimal.len(), 10);
+        assert_eq!(suboptimal.len(), 16);
+    }
+}
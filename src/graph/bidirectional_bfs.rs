@@ -0,0 +1,197 @@
+// Adjacency List
+type Graph = Vec<Vec<usize>>;
+
+/// Returns a shortest path from `source` to `target` in an unweighted,
+/// undirected graph whose adjacency lists hold every edge in both directions,
+/// or `None` if there is no path.
+///
+/// Runs a breadth-first search from both ends, expanding a whole level of the
+/// smaller frontier at a time, until a node reached from one end is reached
+/// from the other. The two searches reach about `b^(d/2)` nodes each, where a
+/// single search would reach `b^d`, for `b` neighbors per node and a distance
+/// of `d`.
+pub fn bidirectional_bfs(graph: &Graph, source: usize, target: usize) -> Option<Vec<usize>> {
+    if source == target {
+        return Some(vec![source]);
+    }
+    // the node each node was reached from by each search, with the source and
+    // target reached from themselves
+    let mut parents = [vec![None; graph.len()], vec![None; graph.len()]];
+    parents[0][source] = Some(source);
+    parents[1][target] = Some(target);
+    let mut frontiers = [vec![source], vec![target]];
+
+    while !frontiers[0].is_empty() && !frontiers[1].is_empty() {
+        let side = if frontiers[0].len() <= frontiers[1].len() {
+            0
+        } else {
+            1
+        };
+        let mut next = Vec::new();
+        for &node in &frontiers[side] {
+            for &neighbor in &graph[node] {
+                if parents[side][neighbor].is_some() {
+                    continue;
+                }
+                parents[side][neighbor] = Some(node);
+                // The searches have not met before this level, so no path is
+                // shorter than the levels of both searches, and this is one.
+                if parents[1 - side][neighbor].is_some() {
+                    let mut path = path_to_end(&parents[0], neighbor);
+                    path.reverse();
+                    path.pop();
+                    path.extend(path_to_end(&parents[1], neighbor));
+                    return Some(path);
+                }
+                next.push(neighbor);
+            }
+        }
+        frontiers[side] = next;
+    }
+    None
+}
+
+/// Returns the nodes from `node` back to the end the search started from.
+fn path_to_end(parents: &[Option<usize>], mut node: usize) -> Vec<usize> {
+    let mut path = vec![node];
+    while let Some(parent) = parents[node].filter(|&parent| parent != node) {
+        path.push(parent);
+        node = parent;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+    use std::collections::VecDeque;
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Graph {
+        let mut graph = vec![vec![]; n];
+        for &(u, v) in edges {
+            graph[u].push(v);
+            if u != v {
+                graph[v].push(u);
+            }
+        }
+        graph
+    }
+
+    /// Returns the distance from `source` to every node, by a single search.
+    fn distances(graph: &Graph, source: usize) -> Vec<Option<usize>> {
+        let mut distance = vec![None; graph.len()];
+        distance[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &neighbor in &graph[node] {
+                if distance[neighbor].is_none() {
+                    distance[neighbor] = Some(distance[node].unwrap() + 1);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        distance
+    }
+
+    fn assert_path(graph: &Graph, path: &[usize], source: usize, target: usize) {
+        assert_eq!(path.first(), Some(&source));
+        assert_eq!(path.last(), Some(&target));
+        for pair in path.windows(2) {
+            assert!(graph[pair[0]].contains(&pair[1]), "{path:?}");
+        }
+    }
+
+    #[test]
+    fn same_node() {
+        let graph = undirected(3, &[(0, 1)]);
+        assert_eq!(bidirectional_bfs(&graph, 1, 1), Some(vec![1]));
+        assert_eq!(bidirectional_bfs(&graph, 2, 2), Some(vec![2]));
+    }
+
+    #[test]
+    fn disconnected() {
+        let graph = undirected(6, &[(0, 1), (1, 2), (3, 4), (4, 5)]);
+        assert_eq!(bidirectional_bfs(&graph, 0, 5), None);
+        assert_eq!(bidirectional_bfs(&graph, 2, 3), None);
+        assert_eq!(bidirectional_bfs(&undirected(2, &[]), 0, 1), None);
+    }
+
+    #[test]
+    fn line() {
+        let graph = undirected(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+        assert_eq!(bidirectional_bfs(&graph, 0, 4), Some(vec![0, 1, 2, 3, 4]));
+        assert_eq!(bidirectional_bfs(&graph, 3, 0), Some(vec![3, 2, 1, 0]));
+        assert_eq!(bidirectional_bfs(&graph, 2, 3), Some(vec![2, 3]));
+    }
+
+    #[test]
+    fn grid() {
+        let (rows, cols) = (20, 30);
+        let id = |r: usize, c: usize| r * cols + c;
+        let mut edges = Vec::new();
+        for r in 0..rows {
+            for c in 0..cols {
+                if r + 1 < rows {
+                    edges.push((id(r, c), id(r + 1, c)));
+                }
+                if c + 1 < cols {
+                    edges.push((id(r, c), id(r, c + 1)));
+                }
+            }
+        }
+        let graph = undirected(rows * cols, &edges);
+        for (from, to) in [((0, 0), (19, 29)), ((5, 7), (5, 8)), ((12, 3), (2, 25))] {
+            let (source, target) = (id(from.0, from.1), id(to.0, to.1));
+            let path = bidirectional_bfs(&graph, source, target).unwrap();
+            assert_path(&graph, &path, source, target);
+            // the Manhattan distance
+            let distance = from.0.abs_diff(to.0) + from.1.abs_diff(to.1);
+            assert_eq!(path.len(), distance + 1);
+        }
+    }
+
+    #[test]
+    fn self_loops_and_multi_edges() {
+        let graph = undirected(
+            4,
+            &[
+                (0, 0),
+                (0, 1),
+                (0, 1),
+                (1, 1),
+                (1, 2),
+                (2, 1),
+                (2, 3),
+                (3, 3),
+            ],
+        );
+        assert_eq!(bidirectional_bfs(&graph, 0, 3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(bidirectional_bfs(&graph, 3, 0), Some(vec![3, 2, 1, 0]));
+        assert_eq!(bidirectional_bfs(&graph, 1, 1), Some(vec![1]));
+    }
+
+    #[test]
+    fn matches_bfs() {
+        let mut rng = PCG32::new_default(87);
+        for _ in 0..200 {
+            let n = 1 + rng.get_u32() as usize % 40;
+            let m = rng.get_u32() as usize % (2 * n);
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| (rng.get_u32() as usize % n, rng.get_u32() as usize % n))
+                .collect();
+            let graph = undirected(n, &edges);
+            let source = rng.get_u32() as usize % n;
+            let distance = distances(&graph, source);
+            for (target, &expected) in distance.iter().enumerate() {
+                match bidirectional_bfs(&graph, source, target) {
+                    Some(path) => {
+                        assert_path(&graph, &path, source, target);
+                        assert_eq!(Some(path.len() - 1), expected);
+                    }
+                    None => assert_eq!(expected, None),
+                }
+            }
+        }
+    }
+}
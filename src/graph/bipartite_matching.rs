@@ -135,9 +135,130 @@ impl BipartiteMatching {
         res
     }
 }
+
+/// Returns a maximum matching of a bipartite graph with `left_count` nodes on
+/// the left and `right_count` on the right, numbered from 0 on each side, and
+/// with `edges` from left to right nodes.
+///
+/// The matching is returned as the right node each left node is matched to,
+/// if any. It is found with the Hopcroft-Karp algorithm in `O(E sqrt(V))` time.
+pub fn hopcroft_karp(
+    left_count: usize,
+    right_count: usize,
+    edges: &[(usize, usize)],
+) -> Vec<Option<usize>> {
+    let mut graph = BipartiteMatching::new(left_count, right_count);
+    for &(u, v) in edges {
+        assert!(u < left_count && v < right_count, "invalid edge ({u}, {v})");
+        graph.add_edge(u + 1, v + 1);
+    }
+    graph.hopcroft_karp();
+    graph.mt1[1..]
+        .iter()
+        .map(|&v| (v > 0).then(|| v as usize - 1))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::PCG32;
+
+    /// Asserts that no two left nodes are matched to the same right node, and
+    /// that every matched pair is an edge, returning the size of the matching.
+    fn assert_matching(
+        matching: &[Option<usize>],
+        right_count: usize,
+        edges: &[(usize, usize)],
+    ) -> usize {
+        let mut used = vec![false; right_count];
+        for (u, v) in matching.iter().enumerate() {
+            if let Some(v) = *v {
+                assert!(!used[v], "{v} is matched twice");
+                used[v] = true;
+                assert!(edges.contains(&(u, v)));
+            }
+        }
+        used.iter().filter(|&&used| used).count()
+    }
+
+    /// Returns the size of a minimum vertex cover, by trying every set of nodes.
+    fn min_vertex_cover(left_count: usize, right_count: usize, edges: &[(usize, usize)]) -> usize {
+        (0u32..1 << (left_count + right_count))
+            .filter(|set| {
+                edges
+                    .iter()
+                    .all(|&(u, v)| set >> u & 1 == 1 || set >> (left_count + v) & 1 == 1)
+            })
+            .map(|set| set.count_ones() as usize)
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn known_graphs() {
+        // the graph of small_graph_hopcroft, numbered from 0
+        let edges = [
+            (0, 1),
+            (0, 2),
+            (2, 3),
+            (2, 0),
+            (3, 2),
+            (4, 2),
+            (4, 3),
+            (5, 5),
+        ];
+        let matching = hopcroft_karp(6, 6, &edges);
+        assert_eq!(assert_matching(&matching, 6, &edges), 5);
+        assert_eq!(matching[1], None);
+        assert_eq!(min_vertex_cover(6, 6, &edges), 5);
+
+        // three left nodes that all only know the same two right nodes
+        let edges = [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)];
+        let matching = hopcroft_karp(3, 2, &edges);
+        assert_eq!(assert_matching(&matching, 2, &edges), 2);
+        assert_eq!(min_vertex_cover(3, 2, &edges), 2);
+    }
+
+    #[test]
+    fn perfect_matchings() {
+        // a greedy choice of 0 - 0 has to be undone twice
+        let edges = [(0, 0), (0, 1), (1, 0), (1, 2), (2, 0)];
+        assert_eq!(hopcroft_karp(3, 3, &edges), vec![Some(1), Some(2), Some(0)]);
+
+        let n = 50;
+        let edges: Vec<(usize, usize)> = (0..n).flat_map(|u| [(u, u), (u, (u + 1) % n)]).collect();
+        let matching = hopcroft_karp(n, n, &edges);
+        assert_eq!(assert_matching(&matching, n, &edges), n);
+    }
+
+    #[test]
+    fn no_matching() {
+        assert_eq!(hopcroft_karp(3, 4, &[]), vec![None; 3]);
+        assert_eq!(hopcroft_karp(0, 4, &[]), vec![]);
+        assert_eq!(hopcroft_karp(2, 0, &[]), vec![None; 2]);
+    }
+
+    #[test]
+    fn konig_theorem() {
+        let mut rng = PCG32::new_default(89);
+        for _ in 0..300 {
+            let left_count = rng.get_u32() as usize % 7;
+            let right_count = 1 + rng.get_u32() as usize % 7;
+            let m = rng.get_u32() as usize % 15;
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| {
+                    let u = rng.get_u32() as usize % left_count.max(1);
+                    (u, rng.get_u32() as usize % right_count)
+                })
+                .filter(|&(u, _)| u < left_count)
+                .collect();
+            let matching = hopcroft_karp(left_count, right_count, &edges);
+            assert_eq!(matching.len(), left_count);
+            let size = assert_matching(&matching, right_count, &edges);
+            assert_eq!(size, min_vertex_cover(left_count, right_count, &edges));
+        }
+    }
     #[test]
     fn small_graph_kuhn() {
         let n1 = 6;
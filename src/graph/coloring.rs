@@ -0,0 +1,185 @@
+// Adjacency List
+type Graph = Vec<Vec<usize>>;
+
+/// Colors the nodes of an undirected graph, whose adjacency lists hold every
+/// edge in both directions, so that no two adjacent nodes share a color.
+///
+/// Returns the color of each node, from 0. The nodes are colored one at a
+/// time, from the highest degree to the lowest (the Welsh-Powell order), each
+/// with the smallest color none of its colored neighbors has. This uses at
+/// most one more color than the highest degree, but not always the fewest
+/// possible. Self-loops are ignored.
+pub fn greedy_coloring(graph: &Graph) -> Vec<usize> {
+    let n = graph.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&node| std::cmp::Reverse(graph[node].len()));
+
+    let mut coloring = vec![usize::MAX; n];
+    // the last node that each color was seen on a neighbor of
+    let mut taken_by = vec![usize::MAX; n];
+    for node in order {
+        for &neighbor in &graph[node] {
+            if neighbor != node && coloring[neighbor] != usize::MAX {
+                taken_by[coloring[neighbor]] = node;
+            }
+        }
+        // a node with d neighbors has a free color among the first d + 1
+        coloring[node] = (0..n).find(|&color| taken_by[color] != node).unwrap();
+    }
+    coloring
+}
+
+/// Returns an upper bound on the chromatic number of an undirected graph, the
+/// fewest colors its nodes can be colored with so that no two adjacent nodes
+/// share a color, as the number of colors used by `greedy_coloring`.
+pub fn chromatic_number_upper_bound(graph: &Graph) -> usize {
+    greedy_coloring(graph)
+        .into_iter()
+        .max()
+        .map_or(0, |color| color + 1)
+}
+
+/// Returns whether `coloring` has a color for each node of the graph, and no
+/// two adjacent nodes share a color. Self-loops are ignored.
+pub fn is_valid_coloring(graph: &Graph, coloring: &[usize]) -> bool {
+    coloring.len() == graph.len()
+        && graph.iter().enumerate().all(|(node, neighbors)| {
+            neighbors
+                .iter()
+                .all(|&neighbor| neighbor == node || coloring[neighbor] != coloring[node])
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Graph {
+        let mut graph = vec![vec![]; n];
+        for &(u, v) in edges {
+            graph[u].push(v);
+            if u != v {
+                graph[v].push(u);
+            }
+        }
+        graph
+    }
+
+    fn cycle(n: usize) -> Graph {
+        let edges: Vec<(usize, usize)> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+        undirected(n, &edges)
+    }
+
+    fn complete(n: usize) -> Graph {
+        let edges: Vec<(usize, usize)> = (0..n)
+            .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+            .collect();
+        undirected(n, &edges)
+    }
+
+    /// Returns the chromatic number, by trying every coloring with 1, 2, ...
+    /// colors.
+    fn chromatic_number(graph: &Graph) -> usize {
+        let n = graph.len();
+        (1..=n)
+            .find(|&colors| {
+                (0..colors.pow(n as u32)).any(|mut code| {
+                    let coloring: Vec<usize> = (0..n)
+                        .map(|_| {
+                            let color = code % colors;
+                            code /= colors;
+                            color
+                        })
+                        .collect();
+                    is_valid_coloring(graph, &coloring)
+                })
+            })
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn empty_graphs() {
+        assert_eq!(greedy_coloring(&vec![]), vec![]);
+        assert_eq!(chromatic_number_upper_bound(&vec![]), 0);
+        assert_eq!(greedy_coloring(&undirected(4, &[])), vec![0; 4]);
+        assert_eq!(chromatic_number_upper_bound(&undirected(4, &[])), 1);
+    }
+
+    #[test]
+    fn bipartite_graphs() {
+        // even cycles, a path, a star and complete bipartite graphs
+        let mut graphs = vec![cycle(4), cycle(10), cycle(100)];
+        graphs.push(undirected(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]));
+        graphs.push(undirected(6, &[(0, 1), (0, 2), (0, 3), (0, 4), (0, 5)]));
+        for (m, n) in [(1, 1), (2, 3), (5, 5), (4, 7)] {
+            let edges: Vec<(usize, usize)> = (0..m)
+                .flat_map(|u| (m..m + n).map(move |v| (u, v)))
+                .collect();
+            graphs.push(undirected(m + n, &edges));
+        }
+        for graph in graphs {
+            let coloring = greedy_coloring(&graph);
+            assert!(is_valid_coloring(&graph, &coloring));
+            assert_eq!(chromatic_number_upper_bound(&graph), 2);
+        }
+    }
+
+    #[test]
+    fn complete_graphs() {
+        for n in 1..12 {
+            let graph = complete(n);
+            let mut coloring = greedy_coloring(&graph);
+            assert!(is_valid_coloring(&graph, &coloring));
+            coloring.sort();
+            assert_eq!(coloring, (0..n).collect::<Vec<_>>());
+            assert_eq!(chromatic_number_upper_bound(&graph), n);
+        }
+    }
+
+    #[test]
+    fn odd_cycles() {
+        for n in [3, 5, 7, 101] {
+            let graph = cycle(n);
+            assert!(is_valid_coloring(&graph, &greedy_coloring(&graph)));
+            assert_eq!(chromatic_number_upper_bound(&graph), 3);
+        }
+    }
+
+    #[test]
+    fn invalid_colorings() {
+        let graph = cycle(4);
+        assert!(is_valid_coloring(&graph, &[0, 1, 0, 1]));
+        assert!(is_valid_coloring(&graph, &[2, 0, 1, 0]));
+        assert!(!is_valid_coloring(&graph, &[0, 1, 1, 0]));
+        assert!(!is_valid_coloring(&graph, &[0, 0, 0, 0]));
+        assert!(!is_valid_coloring(&graph, &[0, 1, 0]));
+
+        let graph = undirected(3, &[(0, 0), (0, 1), (1, 2)]);
+        assert!(is_valid_coloring(&graph, &[0, 1, 0]));
+        assert!(!is_valid_coloring(&graph, &[0, 1, 1]));
+    }
+
+    #[test]
+    fn bounds_on_random_graphs() {
+        let mut rng = PCG32::new_default(91);
+        for _ in 0..200 {
+            let n = 1 + rng.get_u32() as usize % 7;
+            let m = rng.get_u32() as usize % (2 * n);
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| (rng.get_u32() as usize % n, rng.get_u32() as usize % n))
+                .collect();
+            let graph = undirected(n, &edges);
+            let coloring = greedy_coloring(&graph);
+            assert!(is_valid_coloring(&graph, &coloring));
+
+            let bound = chromatic_number_upper_bound(&graph);
+            let max_degree = (0..n)
+                .map(|node| graph[node].iter().filter(|&&next| next != node).count())
+                .max()
+                .unwrap();
+            assert!(bound >= chromatic_number(&graph));
+            assert!(bound <= max_degree + 1);
+        }
+    }
+}
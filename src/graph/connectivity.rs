@@ -0,0 +1,242 @@
+// Adjacency List
+type Graph = Vec<Vec<usize>>;
+
+/// Returns the articulation points of an undirected graph, whose adjacency
+/// lists hold every edge in both directions, in increasing order.
+///
+/// An articulation point is a node whose removal, along with its edges,
+/// splits its connected component into several.
+pub fn articulation_points(graph: &Graph) -> Vec<usize> {
+    Tarjan::run(graph).articulation_points
+}
+
+/// Returns the bridges of an undirected graph, whose adjacency lists hold
+/// every edge in both directions, as `(u, v)` with `u < v`, in increasing
+/// order.
+///
+/// A bridge is an edge whose removal splits its connected component in two.
+/// Parallel edges between two nodes are never bridges.
+pub fn bridges(graph: &Graph) -> Vec<(usize, usize)> {
+    Tarjan::run(graph).bridges
+}
+
+/// The state of Tarjan's algorithm, which finds both articulation points and
+/// bridges with a single depth-first search in `O(n + m)` time.
+///
+/// A tree edge from `u` down to `v` is a bridge if no edge from the subtree of
+/// `v` goes back above `v`, and `u` is an articulation point if no edge from
+/// the subtree of `v` goes back above `u`, unless `u` is a root, which is one
+/// if it has several children.
+struct Tarjan<'a> {
+    graph: &'a Graph,
+    // the order in which the nodes were discovered, from 1, or 0 if they
+    // were not
+    discovered: Vec<usize>,
+    // the earliest discovered node an edge from the subtree of the node
+    // goes back to
+    low: Vec<usize>,
+    time: usize,
+    is_articulation_point: Vec<bool>,
+    articulation_points: Vec<usize>,
+    bridges: Vec<(usize, usize)>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn run(graph: &'a Graph) -> Self {
+        let n = graph.len();
+        let mut tarjan = Tarjan {
+            graph,
+            discovered: vec![0; n],
+            low: vec![0; n],
+            time: 0,
+            is_articulation_point: vec![false; n],
+            articulation_points: Vec::new(),
+            bridges: Vec::new(),
+        };
+        for root in 0..n {
+            if tarjan.discovered[root] == 0 {
+                tarjan.dfs(root, None);
+            }
+        }
+        tarjan.articulation_points = (0..n)
+            .filter(|&node| tarjan.is_articulation_point[node])
+            .collect();
+        tarjan.bridges.sort_unstable();
+        tarjan
+    }
+
+    fn dfs(&mut self, node: usize, parent: Option<usize>) {
+        self.time += 1;
+        self.discovered[node] = self.time;
+        self.low[node] = self.time;
+        let mut children = 0;
+        // only one of the edges to the parent is the tree edge, any parallel
+        // one goes back to it
+        let mut skipped_parent = false;
+
+        for &next in &self.graph[node] {
+            if Some(next) == parent && !skipped_parent {
+                skipped_parent = true;
+            } else if self.discovered[next] == 0 {
+                children += 1;
+                self.dfs(next, Some(node));
+                self.low[node] = self.low[node].min(self.low[next]);
+                if self.low[next] > self.discovered[node] {
+                    self.bridges.push((node.min(next), node.max(next)));
+                }
+                if parent.is_some() && self.low[next] >= self.discovered[node] {
+                    self.is_articulation_point[node] = true;
+                }
+            } else {
+                self.low[node] = self.low[node].min(self.discovered[next]);
+            }
+        }
+        if parent.is_none() && children > 1 {
+            self.is_articulation_point[node] = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Graph {
+        let mut graph = vec![vec![]; n];
+        for &(u, v) in edges {
+            graph[u].push(v);
+            if u != v {
+                graph[v].push(u);
+            }
+        }
+        graph
+    }
+
+    fn count_components(graph: &Graph, removed_node: Option<usize>) -> usize {
+        let mut visited = vec![false; graph.len()];
+        let mut count = 0;
+        for root in 0..graph.len() {
+            if visited[root] || Some(root) == removed_node {
+                continue;
+            }
+            count += 1;
+            visited[root] = true;
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                for &next in &graph[node] {
+                    if !visited[next] && Some(next) != removed_node {
+                        visited[next] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn empty_and_single() {
+        assert!(articulation_points(&vec![]).is_empty());
+        assert!(bridges(&vec![]).is_empty());
+        assert!(articulation_points(&vec![vec![]]).is_empty());
+        assert_eq!(bridges(&undirected(2, &[(0, 1)])), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn path() {
+        let graph = undirected(5, &[(3, 4), (0, 1), (2, 3), (1, 2)]);
+        assert_eq!(articulation_points(&graph), vec![1, 2, 3]);
+        assert_eq!(bridges(&graph), vec![(0, 1), (1, 2), (2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn cycle() {
+        let graph = undirected(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+        assert!(articulation_points(&graph).is_empty());
+        assert!(bridges(&graph).is_empty());
+    }
+
+    #[test]
+    fn complete_graph() {
+        for n in 3..10 {
+            let edges: Vec<(usize, usize)> = (0..n)
+                .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+                .collect();
+            let graph = undirected(n, &edges);
+            assert!(articulation_points(&graph).is_empty());
+            assert!(bridges(&graph).is_empty());
+        }
+    }
+
+    #[test]
+    fn known_graph() {
+        // two triangles joined through 2 - 3, with 6 hanging off 5
+        //
+        // 0       4
+        // | \   / |
+        // |  2-3  |
+        // | /   \ |
+        // 1       5 - 6
+        let graph = undirected(
+            7,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 0),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (5, 3),
+                (5, 6),
+            ],
+        );
+        assert_eq!(articulation_points(&graph), vec![2, 3, 5]);
+        assert_eq!(bridges(&graph), vec![(2, 3), (5, 6)]);
+    }
+
+    #[test]
+    fn parallel_edges_and_self_loops() {
+        let graph = undirected(4, &[(0, 1), (0, 1), (1, 2), (2, 2), (2, 3)]);
+        assert_eq!(articulation_points(&graph), vec![1, 2]);
+        assert_eq!(bridges(&graph), vec![(1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = PCG32::new_default(88);
+        for _ in 0..300 {
+            let n = 1 + rng.get_u32() as usize % 12;
+            let m = rng.get_u32() as usize % (2 * n);
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| (rng.get_u32() as usize % n, rng.get_u32() as usize % n))
+                .collect();
+            let graph = undirected(n, &edges);
+            let components = count_components(&graph, None);
+
+            let expected: Vec<usize> = (0..n)
+                .filter(|&node| {
+                    // its own component is gone if it is alone in it
+                    let alone = graph[node].iter().all(|&next| next == node);
+                    count_components(&graph, Some(node)) + usize::from(alone) > components
+                })
+                .collect();
+            assert_eq!(articulation_points(&graph), expected);
+
+            let mut expected = Vec::new();
+            for (i, &(u, v)) in edges.iter().enumerate() {
+                let rest: Vec<(usize, usize)> = edges
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, &edge)| edge)
+                    .collect();
+                if count_components(&undirected(n, &rest), None) > components {
+                    expected.push((u.min(v), u.max(v)));
+                }
+            }
+            expected.sort_unstable();
+            assert_eq!(bridges(&graph), expected);
+        }
+    }
+}
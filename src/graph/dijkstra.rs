@@ -1,47 +1,37 @@
+use super::WeightedGraph;
 use std::cmp::Reverse;
-use std::collections::{BTreeMap, BinaryHeap};
-use std::ops::Add;
-
-type Graph<V, E> = BTreeMap<V, BTreeMap<V, E>>;
-
-// performs Dijsktra's algorithm on the given graph from the given start
-// the graph is a positively-weighted undirected graph
-//
-// returns a map that for each reachable vertex associates the distance and the predecessor
-// since the start has no predecessor but is reachable, map[start] will be None
-pub fn dijkstra<V: Ord + Copy, E: Ord + Copy + Add<Output = E>>(
-    graph: &Graph<V, E>,
-    start: &V,
-) -> BTreeMap<V, Option<(V, E)>> {
-    let mut ans = BTreeMap::new();
-    let mut prio = BinaryHeap::new();
+use std::collections::BinaryHeap;
 
-    // start is the special case that doesn't have a predecessor
-    ans.insert(*start, None);
+// performs Dijsktra's algorithm on the given graph from the given source
+// and returns, for each vertex, its distance and its predecessor on a shortest path
+fn shortest_path_tree(graph: &WeightedGraph, source: usize) -> Vec<Option<(u64, Option<usize>)>> {
+    let mut ans = vec![None; graph.num_vertices()];
+    let mut settled = vec![false; graph.num_vertices()];
+    let mut prio = BinaryHeap::new();
 
-    for (new, weight) in &graph[start] {
-        ans.insert(*new, Some((*start, *weight)));
-        prio.push(Reverse((*weight, new, start)));
-    }
+    // source is the special case that doesn't have a predecessor
+    ans[source] = Some((0, None));
+    prio.push(Reverse((0, source)));
 
-    while let Some(Reverse((dist_new, new, prev))) = prio.pop() {
-        match ans[new] {
-            // what we popped is what is in ans, we'll compute it
-            Some((p, d)) if p == *prev && d == dist_new => {}
-            // otherwise it's not interesting
-            _ => continue,
+    while let Some(Reverse((dist, vertex))) = prio.pop() {
+        // a vertex can be pushed several times, only its first pop is interesting
+        if settled[vertex] {
+            continue;
         }
+        settled[vertex] = true;
 
-        for (next, weight) in &graph[new] {
-            match ans.get(next) {
+        for &(next, weight) in graph.neighbors(vertex) {
+            if settled[next] {
+                continue;
+            }
+            let alternative = dist + weight;
+            match ans[next] {
                 // if ans[next] is a lower dist than the alternative one, we do nothing
-                Some(Some((_, dist_next))) if dist_new + *weight >= *dist_next => {}
-                // if ans[next] is None then next is start and so the distance won't be changed, it won't be added again in prio
-                Some(None) => {}
-                // the new path is shorter, either new was not in ans or it was farther
+                Some((dist_next, _)) if alternative >= dist_next => {}
+                // the new path is shorter, either next was not reached yet or it was farther
                 _ => {
-                    ans.insert(*next, Some((*new, *weight + dist_new)));
-                    prio.push(Reverse((*weight + dist_new, next, new)));
+                    ans[next] = Some((alternative, Some(vertex)));
+                    prio.push(Reverse((alternative, next)));
                 }
             }
         }
@@ -50,109 +40,165 @@ pub fn dijkstra<V: Ord + Copy, E: Ord + Copy + Add<Output = E>>(
     ans
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{dijkstra, Graph};
-    use std::collections::BTreeMap;
+/// Computes the length of the shortest path from `source` to every vertex of
+/// a graph with non-negative edge weights.
+///
+/// The returned vector holds `None` for vertices that cannot be reached.
+/// Each vertex is settled once, so the running time is `O((V + E) log V)`.
+///
+/// Panics if `source` is not a vertex of the graph.
+pub fn dijkstra(graph: &WeightedGraph, source: usize) -> Vec<Option<u64>> {
+    shortest_path_tree(graph, source)
+        .into_iter()
+        .map(|entry| entry.map(|(dist, _)| dist))
+        .collect()
+}
 
-    fn add_edge<V: Ord + Copy, E: Ord>(graph: &mut Graph<V, E>, v1: V, v2: V, c: E) {
-        graph.entry(v1).or_insert_with(BTreeMap::new).insert(v2, c);
-        graph.entry(v2).or_insert_with(BTreeMap::new);
+/// Returns the vertices of a shortest path from `source` to `target`, both
+/// included, or `None` if `target` cannot be reached.
+pub fn dijkstra_path(graph: &WeightedGraph, source: usize, target: usize) -> Option<Vec<usize>> {
+    let tree = shortest_path_tree(graph, source);
+    let mut path = vec![target];
+    let mut current = tree[target]?;
+    while let (_, Some(predecessor)) = current {
+        path.push(predecessor);
+        current = tree[predecessor]?;
     }
+    path.reverse();
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::WeightedGraph;
+    use super::{dijkstra, dijkstra_path};
 
     #[test]
     fn single_vertex() {
-        let mut graph: Graph<usize, usize> = BTreeMap::new();
-        graph.insert(0, BTreeMap::new());
-
-        let mut dists = BTreeMap::new();
-        dists.insert(0, None);
-
-        assert_eq!(dijkstra(&graph, &0), dists);
+        let graph = WeightedGraph::new(1);
+        assert_eq!(dijkstra(&graph, 0), vec![Some(0)]);
+        assert_eq!(dijkstra_path(&graph, 0, 0), Some(vec![0]));
     }
 
     #[test]
     fn single_edge() {
-        let mut graph = BTreeMap::new();
-        add_edge(&mut graph, 0, 1, 2);
-
-        let mut dists_0 = BTreeMap::new();
-        dists_0.insert(0, None);
-        dists_0.insert(1, Some((0, 2)));
-
-        assert_eq!(dijkstra(&graph, &0), dists_0);
-
-        let mut dists_1 = BTreeMap::new();
-        dists_1.insert(1, None);
+        let mut graph = WeightedGraph::new(2);
+        graph.add_edge(0, 1, 2);
 
-        assert_eq!(dijkstra(&graph, &1), dists_1);
+        assert_eq!(dijkstra(&graph, 0), vec![Some(0), Some(2)]);
+        assert_eq!(dijkstra(&graph, 1), vec![None, Some(0)]);
     }
 
     #[test]
     fn tree_1() {
-        let mut graph = BTreeMap::new();
-        let mut dists = BTreeMap::new();
-        dists.insert(1, None);
+        let mut graph = WeightedGraph::new(200);
+        let mut dists = vec![None; 200];
+        dists[1] = Some(0);
         for i in 1..100 {
-            add_edge(&mut graph, i, i * 2, i * 2);
-            add_edge(&mut graph, i, i * 2 + 1, i * 2 + 1);
+            graph.add_edge(i, i * 2, i as u64 * 2);
+            graph.add_edge(i, i * 2 + 1, i as u64 * 2 + 1);
 
-            match dists[&i] {
-                Some((_, d)) => {
-                    dists.insert(i * 2, Some((i, d + i * 2)));
-                    dists.insert(i * 2 + 1, Some((i, d + i * 2 + 1)));
-                }
-                None => {
-                    dists.insert(i * 2, Some((i, i * 2)));
-                    dists.insert(i * 2 + 1, Some((i, i * 2 + 1)));
-                }
-            }
+            let d = dists[i].unwrap();
+            dists[i * 2] = Some(d + i as u64 * 2);
+            dists[i * 2 + 1] = Some(d + i as u64 * 2 + 1);
         }
 
-        assert_eq!(dijkstra(&graph, &1), dists);
+        assert_eq!(dijkstra(&graph, 1), dists);
+        assert_eq!(dijkstra_path(&graph, 1, 13), Some(vec![1, 3, 6, 13]));
     }
 
     #[test]
     fn graph_1() {
-        let mut graph = BTreeMap::new();
-        add_edge(&mut graph, 'a', 'c', 12);
-        add_edge(&mut graph, 'a', 'd', 60);
-        add_edge(&mut graph, 'b', 'a', 10);
-        add_edge(&mut graph, 'c', 'b', 20);
-        add_edge(&mut graph, 'c', 'd', 32);
-        add_edge(&mut graph, 'e', 'a', 7);
-
-        let mut dists_a = BTreeMap::new();
-        dists_a.insert('a', None);
-        dists_a.insert('c', Some(('a', 12)));
-        dists_a.insert('d', Some(('c', 44)));
-        dists_a.insert('b', Some(('c', 32)));
-        assert_eq!(dijkstra(&graph, &'a'), dists_a);
-
-        let mut dists_b = BTreeMap::new();
-        dists_b.insert('b', None);
-        dists_b.insert('a', Some(('b', 10)));
-        dists_b.insert('c', Some(('a', 22)));
-        dists_b.insert('d', Some(('c', 54)));
-        assert_eq!(dijkstra(&graph, &'b'), dists_b);
-
-        let mut dists_c = BTreeMap::new();
-        dists_c.insert('c', None);
-        dists_c.insert('b', Some(('c', 20)));
-        dists_c.insert('d', Some(('c', 32)));
-        dists_c.insert('a', Some(('b', 30)));
-        assert_eq!(dijkstra(&graph, &'c'), dists_c);
-
-        let mut dists_d = BTreeMap::new();
-        dists_d.insert('d', None);
-        assert_eq!(dijkstra(&graph, &'d'), dists_d);
-
-        let mut dists_e = BTreeMap::new();
-        dists_e.insert('e', None);
-        dists_e.insert('a', Some(('e', 7)));
-        dists_e.insert('c', Some(('a', 19)));
-        dists_e.insert('d', Some(('c', 51)));
-        dists_e.insert('b', Some(('c', 39)));
-        assert_eq!(dijkstra(&graph, &'e'), dists_e);
+        // vertices a, b, c, d, e are numbered 0 to 4
+        let mut graph = WeightedGraph::new(5);
+        graph.add_edge(0, 2, 12);
+        graph.add_edge(0, 3, 60);
+        graph.add_edge(1, 0, 10);
+        graph.add_edge(2, 1, 20);
+        graph.add_edge(2, 3, 32);
+        graph.add_edge(4, 0, 7);
+
+        assert_eq!(
+            dijkstra(&graph, 0),
+            vec![Some(0), Some(32), Some(12), Some(44), None]
+        );
+        assert_eq!(
+            dijkstra(&graph, 1),
+            vec![Some(10), Some(0), Some(22), Some(54), None]
+        );
+        assert_eq!(
+            dijkstra(&graph, 2),
+            vec![Some(30), Some(20), Some(0), Some(32), None]
+        );
+        assert_eq!(dijkstra(&graph, 3), vec![None, None, None, Some(0), None]);
+        assert_eq!(
+            dijkstra(&graph, 4),
+            vec![Some(7), Some(39), Some(19), Some(51), Some(0)]
+        );
+
+        assert_eq!(dijkstra_path(&graph, 4, 3), Some(vec![4, 0, 2, 3]));
+        assert_eq!(dijkstra_path(&graph, 3, 4), None);
+    }
+
+    #[test]
+    fn zero_weight_edges() {
+        let mut graph = WeightedGraph::new(4);
+        graph.add_edge(0, 1, 0);
+        graph.add_edge(1, 2, 0);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(2, 3, 0);
+
+        assert_eq!(
+            dijkstra(&graph, 0),
+            vec![Some(0), Some(0), Some(0), Some(0)]
+        );
+        assert_eq!(dijkstra_path(&graph, 0, 3), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn disconnected() {
+        let mut graph = WeightedGraph::new(5);
+        graph.add_undirected_edge(0, 1, 4);
+        graph.add_undirected_edge(2, 3, 1);
+        graph.add_undirected_edge(3, 4, 1);
+
+        assert_eq!(
+            dijkstra(&graph, 0),
+            vec![Some(0), Some(4), None, None, None]
+        );
+        assert_eq!(
+            dijkstra(&graph, 4),
+            vec![None, None, Some(2), Some(1), Some(0)]
+        );
+        assert_eq!(dijkstra_path(&graph, 0, 4), None);
+    }
+
+    #[test]
+    fn self_loops() {
+        let mut graph = WeightedGraph::new(3);
+        graph.add_edge(0, 0, 5);
+        graph.add_edge(0, 1, 3);
+        graph.add_edge(1, 1, 0);
+        graph.add_edge(1, 2, 3);
+
+        assert_eq!(dijkstra(&graph, 0), vec![Some(0), Some(3), Some(6)]);
+        assert_eq!(dijkstra_path(&graph, 0, 2), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn picks_shorter_of_parallel_paths() {
+        let mut graph = WeightedGraph::new(4);
+        graph.add_undirected_edge(0, 1, 1);
+        graph.add_undirected_edge(1, 3, 10);
+        graph.add_undirected_edge(0, 2, 4);
+        graph.add_undirected_edge(2, 3, 4);
+        graph.add_edge(0, 3, 9);
+
+        assert_eq!(
+            dijkstra(&graph, 0),
+            vec![Some(0), Some(1), Some(4), Some(8)]
+        );
+        assert_eq!(dijkstra_path(&graph, 0, 3), Some(vec![0, 2, 3]));
+        assert_eq!(dijkstra_path(&graph, 3, 1), Some(vec![3, 2, 0, 1]));
     }
 }
@@ -0,0 +1,69 @@
+/// An unweighted directed graph on the vertices `0..n`, stored as adjacency lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectedGraph {
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl DirectedGraph {
+    /// Creates a graph with `num_vertices` vertices and no edges.
+    pub fn new(num_vertices: usize) -> Self {
+        DirectedGraph {
+            adjacency: vec![Vec::new(); num_vertices],
+        }
+    }
+
+    /// Creates a graph with `num_vertices` vertices and the given `(from, to)` edges.
+    pub fn from_edges(num_vertices: usize, edges: &[(usize, usize)]) -> Self {
+        let mut graph = DirectedGraph::new(num_vertices);
+        for &(from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Adds an edge from `from` to `to`.
+    ///
+    /// Panics if either endpoint is not a vertex of the graph.
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        assert!(to < self.adjacency.len(), "vertex {} is out of range", to);
+        self.adjacency[from].push(to);
+    }
+
+    /// Returns the vertices that `vertex` has an edge to.
+    pub fn neighbors(&self, vertex: usize) -> &[usize] {
+        &self.adjacency[vertex]
+    }
+
+    /// Returns the graph with every edge reversed.
+    pub fn transpose(&self) -> DirectedGraph {
+        let mut transposed = DirectedGraph::new(self.num_vertices());
+        for (from, edges) in self.adjacency.iter().enumerate() {
+            for &to in edges {
+                transposed.add_edge(to, from);
+            }
+        }
+        transposed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DirectedGraph;
+
+    #[test]
+    fn build() {
+        let graph = DirectedGraph::from_edges(3, &[(0, 1), (0, 2), (2, 1)]);
+        assert_eq!(graph.num_vertices(), 3);
+        assert_eq!(graph.neighbors(0), &[1, 2]);
+        assert_eq!(graph.neighbors(1), &[] as &[usize]);
+
+        let transposed = graph.transpose();
+        assert_eq!(transposed.neighbors(1), &[0, 2]);
+        assert_eq!(transposed.neighbors(2), &[0]);
+        assert_eq!(transposed.transpose(), graph);
+    }
+}
@@ -1,191 +1,214 @@
-use num_traits::Zero;
-use std::collections::BTreeMap;
-use std::ops::Add;
+use super::NegativeCycleError;
 
-type Graph<V, E> = BTreeMap<V, BTreeMap<V, E>>;
+/// Distance matrix and successor matrix computed by [`floyd_warshall_with_next`].
+type ShortestPaths = (Vec<Vec<Option<i64>>>, Vec<Vec<Option<usize>>>);
 
 /// Performs the Floyd-Warshall algorithm on the input graph
-/// The graph is a weighted, directed graph with no negative cycles
+/// The graph is a weighted, directed graph given as an n×n adjacency matrix,
+/// where `adj[u][v] == Some(w)` is an edge from u to v of weight w
 ///
-/// Returns a map storing the distance from each node to all the others
-/// I.e. For each vertex u, map[u][v] == Some(distance) means
-/// distance is the sum of the weights of the edges on the shortest path
-/// from u to v
+/// Returns the distance matrix: `dist[u][v] == Some(distance)` means distance is
+/// the sum of the weights of the edges on the shortest path from u to v, and
+/// `None` means that v cannot be reached from u
 ///
-/// For a key v, if map[v].len() == 0, then v cannot reach any other vertex, but is in the graph
-/// (island node, or sink in the case of a directed graph)
-pub fn floyd_warshall<V: Ord + Copy, E: Ord + Copy + Add<Output = E> + num_traits::Zero>(
-    graph: &Graph<V, E>,
-) -> BTreeMap<V, BTreeMap<V, E>> {
-    let mut map: BTreeMap<V, BTreeMap<V, E>> = BTreeMap::new();
-    for (u, edges) in graph.iter() {
-        if !map.contains_key(u) {
-            map.insert(*u, BTreeMap::new());
+/// If the graph contains a negative cycle, an error holding a vertex of that
+/// cycle is returned instead
+pub fn floyd_warshall(
+    adj: &[Vec<Option<i64>>],
+) -> Result<Vec<Vec<Option<i64>>>, NegativeCycleError> {
+    floyd_warshall_with_next(adj).map(|(dist, _)| dist)
+}
+
+/// Like [`floyd_warshall`], but also returns the successor matrix:
+/// `next[u][v]` is the vertex following u on a shortest path from u to v
+///
+/// Paths can be rebuilt from it with [`floyd_warshall_path`]
+pub fn floyd_warshall_with_next(
+    adj: &[Vec<Option<i64>>],
+) -> Result<ShortestPaths, NegativeCycleError> {
+    let n = adj.len();
+    let mut dist = vec![vec![None; n]; n];
+    let mut next = vec![vec![None; n]; n];
+    for (u, row) in adj.iter().enumerate() {
+        assert_eq!(row.len(), n, "adjacency matrix must be square");
+        for (v, &weight) in row.iter().enumerate() {
+            if weight.is_some() {
+                dist[u][v] = weight;
+                next[u][v] = Some(v);
+            }
         }
-        map.entry(*u).or_default().insert(*u, Zero::zero());
-        for (v, weight) in edges.iter() {
-            if !map.contains_key(v) {
-                map.insert(*v, BTreeMap::new());
+        match dist[u][u] {
+            Some(weight) if weight < 0 => return Err(NegativeCycleError { vertex: u }),
+            _ => {
+                dist[u][u] = Some(0);
+                next[u][u] = Some(u);
             }
-            map.entry(*v).or_default().insert(*v, Zero::zero());
-            map.entry(*u).and_modify(|mp| {
-                mp.insert(*v, *weight);
-            });
         }
     }
-    let keys = map.keys().copied().collect::<Vec<_>>();
-    for &k in &keys {
-        for &i in &keys {
-            if map[&i].get(&k).is_none() {
-                continue;
-            }
-            for &j in &keys {
-                if i == j {
-                    continue;
-                }
-                if !map[&k].contains_key(&j) {
-                    continue;
-                }
-                let entry_i_j = map[&i].get(&j);
-                let entry_i_k = map[&i][&k];
-                let entry_k_j = map[&k][&j];
-                match entry_i_j {
-                    Some(&e) => {
-                        if e > entry_i_k + entry_k_j {
-                            map.entry(i).or_default().insert(j, entry_i_k + entry_k_j);
-                        }
-                    }
-                    None => {
-                        map.entry(i).or_default().insert(j, entry_i_k + entry_k_j);
-                    }
+
+    for k in 0..n {
+        for i in 0..n {
+            let dist_i_k = match dist[i][k] {
+                Some(d) => d,
+                None => continue,
+            };
+            for j in 0..n {
+                let dist_k_j = match dist[k][j] {
+                    Some(d) => d,
+                    None => continue,
                 };
+                if dist[i][j].is_none_or(|d| d > dist_i_k + dist_k_j) {
+                    dist[i][j] = Some(dist_i_k + dist_k_j);
+                    next[i][j] = next[i][k];
+                }
             }
         }
+        // The first negative diagonal entry comes from a closed walk through k
+        // whose other cycles are all non-negative, so k lies on a negative cycle
+        if (0..n).any(|i| matches!(dist[i][i], Some(d) if d < 0)) {
+            return Err(NegativeCycleError { vertex: k });
+        }
+    }
+
+    Ok((dist, next))
+}
+
+/// Rebuilds the shortest path from `from` to `to`, both included, out of the
+/// successor matrix returned by [`floyd_warshall_with_next`]
+///
+/// Returns `None` if `to` cannot be reached from `from`
+pub fn floyd_warshall_path(
+    next: &[Vec<Option<usize>>],
+    from: usize,
+    to: usize,
+) -> Option<Vec<usize>> {
+    let mut path = vec![from];
+    let mut current = from;
+    while current != to {
+        current = next[current][to]?;
+        path.push(current);
     }
-    map
+    Some(path)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{floyd_warshall, Graph};
-    use std::collections::BTreeMap;
+    use super::super::NegativeCycleError;
+    use super::{floyd_warshall, floyd_warshall_path, floyd_warshall_with_next};
 
-    fn add_edge<V: Ord + Copy, E: Ord + Copy>(graph: &mut Graph<V, E>, v1: V, v2: V, c: E) {
-        graph.entry(v1).or_insert_with(BTreeMap::new).insert(v2, c);
+    fn empty_graph(n: usize) -> Vec<Vec<Option<i64>>> {
+        vec![vec![None; n]; n]
     }
 
-    fn bi_add_edge<V: Ord + Copy, E: Ord + Copy>(graph: &mut Graph<V, E>, v1: V, v2: V, c: E) {
-        add_edge(graph, v1, v2, c);
-        add_edge(graph, v2, v1, c);
+    fn bi_add_edge(graph: &mut [Vec<Option<i64>>], v1: usize, v2: usize, c: i64) {
+        graph[v1][v2] = Some(c);
+        graph[v2][v1] = Some(c);
     }
 
     #[test]
     fn single_vertex() {
-        let mut graph: Graph<usize, usize> = BTreeMap::new();
-        graph.insert(0, BTreeMap::new());
-
-        let mut dists = BTreeMap::new();
-        dists.insert(0, BTreeMap::new());
-        dists.get_mut(&0).unwrap().insert(0, 0);
-        assert_eq!(floyd_warshall(&graph), dists);
+        let graph = empty_graph(1);
+        assert_eq!(floyd_warshall(&graph), Ok(vec![vec![Some(0)]]));
     }
 
     #[test]
     fn single_edge() {
-        let mut graph = BTreeMap::new();
+        let mut graph = empty_graph(3);
         bi_add_edge(&mut graph, 0, 1, 2);
         bi_add_edge(&mut graph, 1, 2, 3);
 
-        let mut dists_0 = BTreeMap::new();
-        dists_0.insert(0, BTreeMap::new());
-        dists_0.insert(1, BTreeMap::new());
-        dists_0.insert(2, BTreeMap::new());
-        dists_0.get_mut(&0).unwrap().insert(0, 0);
-        dists_0.get_mut(&1).unwrap().insert(1, 0);
-        dists_0.get_mut(&2).unwrap().insert(2, 0);
-        dists_0.get_mut(&1).unwrap().insert(0, 2);
-        dists_0.get_mut(&0).unwrap().insert(1, 2);
-        dists_0.get_mut(&1).unwrap().insert(2, 3);
-        dists_0.get_mut(&2).unwrap().insert(1, 3);
-        dists_0.get_mut(&2).unwrap().insert(0, 5);
-        dists_0.get_mut(&0).unwrap().insert(2, 5);
-
-        assert_eq!(floyd_warshall(&graph), dists_0);
+        let dists = vec![
+            vec![Some(0), Some(2), Some(5)],
+            vec![Some(2), Some(0), Some(3)],
+            vec![Some(5), Some(3), Some(0)],
+        ];
+        assert_eq!(floyd_warshall(&graph), Ok(dists));
     }
 
     #[test]
     fn graph_1() {
-        let mut graph = BTreeMap::new();
-        add_edge(&mut graph, 'a', 'c', 12);
-        add_edge(&mut graph, 'a', 'd', 60);
-        add_edge(&mut graph, 'b', 'a', 10);
-        add_edge(&mut graph, 'c', 'b', 20);
-        add_edge(&mut graph, 'c', 'd', 32);
-        add_edge(&mut graph, 'e', 'a', 7);
-
-        let mut dists_a = BTreeMap::new();
-        dists_a.insert('d', BTreeMap::new());
-
-        dists_a.entry('a').or_insert(BTreeMap::new()).insert('a', 0);
-        dists_a.entry('b').or_insert(BTreeMap::new()).insert('b', 0);
-        dists_a.entry('c').or_insert(BTreeMap::new()).insert('c', 0);
-        dists_a.entry('d').or_insert(BTreeMap::new()).insert('d', 0);
-        dists_a.entry('e').or_insert(BTreeMap::new()).insert('e', 0);
-        dists_a
-            .entry('a')
-            .or_insert(BTreeMap::new())
-            .insert('c', 12);
-        dists_a
-            .entry('c')
-            .or_insert(BTreeMap::new())
-            .insert('a', 30);
-        dists_a
-            .entry('c')
-            .or_insert(BTreeMap::new())
-            .insert('b', 20);
-        dists_a
-            .entry('c')
-            .or_insert(BTreeMap::new())
-            .insert('d', 32);
-        dists_a.entry('e').or_insert(BTreeMap::new()).insert('a', 7);
-        dists_a
-            .entry('b')
-            .or_insert(BTreeMap::new())
-            .insert('a', 10);
-        dists_a
-            .entry('a')
-            .or_insert(BTreeMap::new())
-            .insert('d', 44);
-        dists_a
-            .entry('a')
-            .or_insert(BTreeMap::new())
-            .insert('b', 32);
-        dists_a
-            .entry('a')
-            .or_insert(BTreeMap::new())
-            .insert('b', 32);
-        dists_a
-            .entry('b')
-            .or_insert(BTreeMap::new())
-            .insert('c', 22);
-
-        dists_a
-            .entry('b')
-            .or_insert(BTreeMap::new())
-            .insert('d', 54);
-        dists_a
-            .entry('e')
-            .or_insert(BTreeMap::new())
-            .insert('c', 19);
-        dists_a
-            .entry('e')
-            .or_insert(BTreeMap::new())
-            .insert('d', 51);
-        dists_a
-            .entry('e')
-            .or_insert(BTreeMap::new())
-            .insert('b', 39);
-
-        assert_eq!(floyd_warshall(&graph), dists_a);
+        // vertices a, b, c, d, e are numbered 0 to 4
+        let mut graph = empty_graph(5);
+        graph[0][2] = Some(12);
+        graph[0][3] = Some(60);
+        graph[1][0] = Some(10);
+        graph[2][1] = Some(20);
+        graph[2][3] = Some(32);
+        graph[4][0] = Some(7);
+
+        let dists = vec![
+            vec![Some(0), Some(32), Some(12), Some(44), None],
+            vec![Some(10), Some(0), Some(22), Some(54), None],
+            vec![Some(30), Some(20), Some(0), Some(32), None],
+            vec![None, None, None, Some(0), None],
+            vec![Some(7), Some(39), Some(19), Some(51), Some(0)],
+        ];
+        let (dist, next) = floyd_warshall_with_next(&graph).unwrap();
+        assert_eq!(dist, dists);
+        assert_eq!(floyd_warshall_path(&next, 4, 3), Some(vec![4, 0, 2, 3]));
+        assert_eq!(floyd_warshall_path(&next, 1, 1), Some(vec![1]));
+        assert_eq!(floyd_warshall_path(&next, 3, 0), None);
+    }
+
+    #[test]
+    fn complete_graph() {
+        let graph = vec![
+            vec![Some(0), Some(3), Some(8), Some(-4)],
+            vec![Some(1), Some(0), Some(4), Some(7)],
+            vec![Some(4), Some(-2), Some(0), Some(11)],
+            vec![Some(6), Some(5), Some(6), Some(0)],
+        ];
+
+        let dists = vec![
+            vec![Some(0), Some(0), Some(2), Some(-4)],
+            vec![Some(1), Some(0), Some(3), Some(-3)],
+            vec![Some(-1), Some(-2), Some(0), Some(-5)],
+            vec![Some(5), Some(4), Some(6), Some(0)],
+        ];
+        let (dist, next) = floyd_warshall_with_next(&graph).unwrap();
+        assert_eq!(dist, dists);
+        assert_eq!(floyd_warshall_path(&next, 0, 1), Some(vec![0, 3, 2, 1]));
+        assert_eq!(floyd_warshall_path(&next, 2, 3), Some(vec![2, 1, 0, 3]));
+        assert_eq!(floyd_warshall_path(&next, 3, 1), Some(vec![3, 2, 1]));
+    }
+
+    #[test]
+    fn sparse_graph() {
+        let mut graph = empty_graph(6);
+        graph[0][1] = Some(7);
+        graph[1][2] = Some(-2);
+        graph[2][0] = Some(1);
+        graph[3][4] = Some(5);
+
+        let dist = floyd_warshall(&graph).unwrap();
+        assert_eq!(dist[0], vec![Some(0), Some(7), Some(5), None, None, None]);
+        assert_eq!(dist[1], vec![Some(-1), Some(0), Some(-2), None, None, None]);
+        assert_eq!(dist[3], vec![None, None, None, Some(0), Some(5), None]);
+        assert_eq!(dist[5], vec![None, None, None, None, None, Some(0)]);
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let mut graph = empty_graph(5);
+        graph[0][1] = Some(1);
+        graph[1][2] = Some(2);
+        graph[2][3] = Some(-4);
+        graph[3][1] = Some(1);
+        graph[3][4] = Some(2);
+
+        let NegativeCycleError { vertex } = floyd_warshall(&graph).unwrap_err();
+        assert!([1, 2, 3].contains(&vertex));
+    }
+
+    #[test]
+    fn negative_self_loop() {
+        let mut graph = empty_graph(3);
+        graph[0][1] = Some(1);
+        graph[2][2] = Some(-1);
+
+        assert_eq!(
+            floyd_warshall(&graph),
+            Err(NegativeCycleError { vertex: 2 })
+        );
     }
 }
@@ -0,0 +1,243 @@
+use std::collections::VecDeque;
+
+/// A directed graph with a capacity on each edge, with nodes numbered from 0.
+pub struct FlowGraph {
+    // the edges, each followed by its reverse edge in the residual network,
+    // as the node they go to and their capacity
+    edges: Vec<(usize, u64)>,
+    // the indices of the edges and reverse edges from each node
+    adj: Vec<Vec<usize>>,
+}
+
+impl FlowGraph {
+    /// Returns a graph with `num_nodes` nodes and no edges.
+    pub fn new(num_nodes: usize) -> Self {
+        FlowGraph {
+            edges: Vec::new(),
+            adj: vec![Vec::new(); num_nodes],
+        }
+    }
+
+    /// Adds an edge from `from` to `to` with the given capacity.
+    pub fn add_edge(&mut self, from: usize, to: usize, capacity: u64) {
+        self.adj[from].push(self.edges.len());
+        self.edges.push((to, capacity));
+        self.adj[to].push(self.edges.len());
+        self.edges.push((from, 0));
+    }
+}
+
+/// Returns the value of a maximum flow from `source` to `sink`.
+///
+/// Uses the Edmonds-Karp algorithm, a Ford-Fulkerson method that augments the
+/// flow along a shortest path in the residual network each time, which takes
+/// `O(V E^2)` time.
+pub fn max_flow(graph: &FlowGraph, source: usize, sink: usize) -> u64 {
+    edmonds_karp(graph, source, sink).0
+}
+
+/// Returns the edges of a minimum cut between `source` and `sink`, from the
+/// nodes reachable from `source` in the residual network of a maximum flow to
+/// the others, in the order they were added.
+///
+/// The sum of their capacities is the value of a maximum flow.
+pub fn min_cut(graph: &FlowGraph, source: usize, sink: usize) -> Vec<(usize, usize)> {
+    let (_, residual) = edmonds_karp(graph, source, sink);
+    let reachable = shortest_paths(graph, &residual, source);
+    (0..graph.edges.len())
+        .step_by(2)
+        .map(|e| (graph.edges[e + 1].0, graph.edges[e].0))
+        .filter(|&(from, to)| reachable[from].is_some() && reachable[to].is_none())
+        .collect()
+}
+
+/// Returns the value of a maximum flow and the residual capacities of the
+/// edges.
+fn edmonds_karp(graph: &FlowGraph, source: usize, sink: usize) -> (u64, Vec<u64>) {
+    assert_ne!(source, sink, "the source is the sink");
+    let mut residual: Vec<u64> = graph.edges.iter().map(|&(_, capacity)| capacity).collect();
+    let mut flow = 0;
+    loop {
+        let parent_edge = shortest_paths(graph, &residual, source);
+        if parent_edge[sink].is_none() {
+            return (flow, residual);
+        }
+        // the edges of the path, from the sink back to the source
+        let path: Vec<usize> = std::iter::successors(parent_edge[sink], |&e| {
+            let from = graph.edges[e ^ 1].0;
+            if from == source {
+                None
+            } else {
+                parent_edge[from]
+            }
+        })
+        .collect();
+        let pushed = path.iter().map(|&e| residual[e]).min().unwrap();
+        for e in path {
+            residual[e] -= pushed;
+            residual[e ^ 1] += pushed;
+        }
+        flow += pushed;
+    }
+}
+
+/// Returns the edge each node is reached by from `source` in a breadth-first
+/// search of the residual network, or `None` if it is not reached.
+///
+/// The source itself has no such edge, and is marked with `Some(usize::MAX)`.
+fn shortest_paths(graph: &FlowGraph, residual: &[u64], source: usize) -> Vec<Option<usize>> {
+    let mut parent_edge = vec![None; graph.adj.len()];
+    parent_edge[source] = Some(usize::MAX);
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        for &e in &graph.adj[node] {
+            let next = graph.edges[e].0;
+            if residual[e] > 0 && parent_edge[next].is_none() {
+                parent_edge[next] = Some(e);
+                queue.push_back(next);
+            }
+        }
+    }
+    parent_edge
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::DinicMaxFlow;
+    use crate::math::PCG32;
+
+    fn network(num_nodes: usize, edges: &[(usize, usize, u64)]) -> FlowGraph {
+        let mut graph = FlowGraph::new(num_nodes);
+        for &(from, to, capacity) in edges {
+            graph.add_edge(from, to, capacity);
+        }
+        graph
+    }
+
+    /// Returns the sum of the capacities of the edges, counting parallel edges
+    /// as many times as they were added.
+    fn capacity(edges: &[(usize, usize, u64)], cut: &[(usize, usize)]) -> u64 {
+        let mut cut = cut.to_vec();
+        edges
+            .iter()
+            .filter_map(|&(from, to, capacity)| {
+                let i = cut.iter().position(|&edge| edge == (from, to))?;
+                cut.swap_remove(i);
+                Some(capacity)
+            })
+            .sum()
+    }
+
+    #[test]
+    fn two_paths() {
+        // a greedy depth-first search could push 1 through the middle edge at
+        // a time, but two shortest paths are enough
+        //   1
+        //  / \
+        // 0   | 3
+        //  \ / /
+        //   2-'
+        let edges = [
+            (0, 1, 1000),
+            (0, 2, 1000),
+            (1, 2, 1),
+            (1, 3, 1000),
+            (2, 3, 1000),
+        ];
+        let graph = network(4, &edges);
+        assert_eq!(max_flow(&graph, 0, 3), 2000);
+        assert_eq!(min_cut(&graph, 0, 3), vec![(0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn classic_network() {
+        let edges = [
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ];
+        let graph = network(6, &edges);
+        assert_eq!(max_flow(&graph, 0, 5), 23);
+        let cut = min_cut(&graph, 0, 5);
+        assert_eq!(cut, vec![(1, 3), (4, 3), (4, 5)]);
+        assert_eq!(capacity(&edges, &cut), 23);
+    }
+
+    #[test]
+    fn no_path() {
+        let edges = [(0, 1, 5), (2, 3, 7), (3, 2, 4)];
+        let graph = network(4, &edges);
+        assert_eq!(max_flow(&graph, 0, 3), 0);
+        assert_eq!(min_cut(&graph, 0, 3), vec![]);
+        // only going the wrong way
+        assert_eq!(max_flow(&graph, 3, 0), 0);
+        let graph = network(2, &[(1, 0, 3), (0, 1, 0)]);
+        assert_eq!(max_flow(&graph, 0, 1), 0);
+        assert_eq!(min_cut(&graph, 0, 1), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn antiparallel_edges() {
+        let edges = [
+            (0, 1, 10),
+            (1, 2, 4),
+            (2, 1, 6),
+            (1, 3, 3),
+            (3, 2, 8),
+            (2, 4, 20),
+        ];
+        let graph = network(5, &edges);
+        assert_eq!(max_flow(&graph, 0, 4), 7);
+        assert_eq!(max_flow(&graph, 2, 1), 6);
+        assert_eq!(max_flow(&graph, 1, 2), 7);
+        assert_eq!(min_cut(&graph, 1, 2), vec![(1, 2), (1, 3)]);
+    }
+
+    #[test]
+    fn max_flow_min_cut() {
+        let mut rng = PCG32::new_default(90);
+        for _ in 0..300 {
+            let n = 2 + rng.get_u32() as usize % 8;
+            let m = rng.get_u32() as usize % (3 * n);
+            let edges: Vec<(usize, usize, u64)> = (0..m)
+                .map(|_| {
+                    let from = rng.get_u32() as usize % n;
+                    let to = rng.get_u32() as usize % n;
+                    (from, to, (rng.get_u32() % 20) as u64)
+                })
+                .collect();
+            let flow_graph = network(n, &edges);
+            let flow = max_flow(&flow_graph, 0, n - 1);
+            let cut = min_cut(&flow_graph, 0, n - 1);
+            assert_eq!(capacity(&edges, &cut), flow);
+
+            // no cut is smaller: the source side is every set with the source
+            // and without the sink
+            let smallest = (0u32..1 << n)
+                .filter(|set| set & 1 == 1 && set >> (n - 1) & 1 == 0)
+                .map(|set| {
+                    edges
+                        .iter()
+                        .filter(|&&(from, to, _)| set >> from & 1 == 1 && set >> to & 1 == 0)
+                        .map(|&(_, _, capacity)| capacity)
+                        .sum::<u64>()
+                })
+                .min()
+                .unwrap();
+            assert_eq!(flow, smallest);
+
+            let mut dinic = DinicMaxFlow::<i64>::new(1, n, n);
+            for &(from, to, capacity) in &edges {
+                dinic.add_edge(from + 1, to + 1, capacity as i64);
+            }
+            assert_eq!(flow as i64, dinic.find_maxflow(i64::MAX));
+        }
+    }
+}
@@ -1,4 +1,16 @@
-use super::DisjointSetUnion;
+//! Minimum spanning trees with Kruskal's and Prim's algorithms.
+//!
+//! Kruskal's algorithm sorts all edges and runs in `O(E log E)`; it only needs
+//! an edge list and naturally produces a spanning forest of disconnected graphs.
+//! Prim's algorithm grows a single tree from a start vertex with a binary heap
+//! in `O(E log V)`, and does not pay for sorting edges it never looks at, which
+//! tends to make it the better choice on dense graphs. Both return trees of the
+//! same total weight on a connected graph.
+
+use super::{DisjointSetUnion, WeightedGraph};
+use crate::data_structures::UnionFind;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 
 #[derive(Debug)]
 pub struct Edge {
@@ -52,9 +64,80 @@ pub fn kruskal(mut edges: Vec<Edge>, number_of_vertices: i64) -> (i64, Vec<Edge>
     (total_cost, final_edges)
 }
 
+/// Computes a minimum spanning forest with Kruskal's algorithm.
+///
+/// `edges` are undirected `(u, v, weight)` triples on the vertices `0..num_vertices`.
+/// Edges are considered by increasing weight and kept whenever they join two
+/// different components, which a union-find tracks. The kept edges are returned
+/// in that order: `num_vertices - c` of them for a graph with `c` connected components.
+pub fn kruskal_mst(edges: &[(usize, usize, u64)], num_vertices: usize) -> Vec<(usize, usize, u64)> {
+    let mut sorted = edges.to_vec();
+    sorted.sort_by_key(|&(_, _, weight)| weight);
+
+    let mut components = UnionFind::new(num_vertices);
+    let mut forest = Vec::with_capacity(num_vertices.saturating_sub(1));
+    for (u, v, weight) in sorted {
+        if forest.len() + 1 >= num_vertices {
+            break;
+        }
+        if components.union(u, v) {
+            forest.push((u, v, weight));
+        }
+    }
+    forest
+}
+
+/// Computes a minimum spanning tree of the component containing `start` with
+/// Prim's algorithm.
+///
+/// `graph` should hold every undirected edge in both directions. Edges are
+/// returned as `(tree vertex, new vertex, weight)`, in the order they join the tree.
+pub fn prim_mst(graph: &WeightedGraph, start: usize) -> Vec<(usize, usize, u64)> {
+    let mut in_tree = vec![false; graph.num_vertices()];
+    let mut tree = Vec::new();
+    // the heap is a max heap, we have to use Reverse to simulate a min heap
+    // the elements are an edge: the cost, destination and source
+    let mut prio = BinaryHeap::new();
+
+    in_tree[start] = true;
+    for &(v, weight) in graph.neighbors(start) {
+        prio.push(Reverse((weight, v, start)));
+    }
+
+    while let Some(Reverse((weight, v, u))) = prio.pop() {
+        // the destination of the edge has already been seen
+        if in_tree[v] {
+            continue;
+        }
+        in_tree[v] = true;
+        tree.push((u, v, weight));
+        for &(next, next_weight) in graph.neighbors(v) {
+            if !in_tree[next] {
+                prio.push(Reverse((next_weight, next, v)));
+            }
+        }
+    }
+
+    tree
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::data_structures::UnionFind;
+    use crate::math::PCG32;
+
+    fn total_weight(edges: &[(usize, usize, u64)]) -> u64 {
+        edges.iter().map(|&(_, _, weight)| weight).sum()
+    }
+
+    fn component_count(edges: &[(usize, usize, u64)], num_vertices: usize) -> usize {
+        let mut components = UnionFind::new(num_vertices);
+        for &(u, v, _) in edges {
+            components.union(u, v);
+        }
+        components.component_count()
+    }
 
     #[test]
     fn test_seven_vertices_eleven_edges() {
@@ -131,4 +214,150 @@ mod tests {
         assert_eq!(actual_total_cost, expected_total_cost);
         assert_eq!(actual_final_edges, expected_used_edges);
     }
+
+    #[test]
+    fn kruskal_mst_seven_vertices_eleven_edges() {
+        let edges = [
+            (0, 1, 7),
+            (0, 3, 5),
+            (1, 2, 8),
+            (1, 3, 9),
+            (1, 4, 7),
+            (2, 4, 5),
+            (3, 4, 15),
+            (3, 5, 6),
+            (4, 5, 8),
+            (4, 6, 9),
+            (5, 6, 11),
+        ];
+        let mst = kruskal_mst(&edges, 7);
+        assert_eq!(total_weight(&mst), 39);
+        assert_eq!(mst.len(), 6);
+        assert_eq!(component_count(&mst, 7), 1);
+    }
+
+    #[test]
+    fn kruskal_mst_ten_vertices_twenty_edges() {
+        let edges = [
+            (0, 1, 3),
+            (0, 3, 6),
+            (0, 4, 9),
+            (1, 2, 2),
+            (1, 3, 4),
+            (1, 4, 9),
+            (2, 3, 2),
+            (2, 5, 8),
+            (2, 6, 9),
+            (3, 6, 9),
+            (4, 5, 8),
+            (4, 9, 18),
+            (5, 6, 7),
+            (5, 8, 9),
+            (5, 9, 10),
+            (6, 7, 4),
+            (6, 8, 5),
+            (7, 8, 1),
+            (7, 9, 4),
+            (8, 9, 3),
+        ];
+        let mst = kruskal_mst(&edges, 10);
+        assert_eq!(total_weight(&mst), 38);
+        assert_eq!(mst.len(), 9);
+        assert_eq!(component_count(&mst, 10), 1);
+    }
+
+    #[test]
+    fn kruskal_mst_disconnected() {
+        // components {0, 1, 2}, {3, 4} and {5}
+        let edges = [(0, 1, 4), (1, 2, 1), (0, 2, 2), (3, 4, 7), (4, 3, 1)];
+        let forest = kruskal_mst(&edges, 6);
+        assert_eq!(forest, vec![(1, 2, 1), (4, 3, 1), (0, 2, 2)]);
+        assert_eq!(forest.len(), 6 - 3);
+        assert_eq!(component_count(&forest, 6), 3);
+    }
+
+    #[test]
+    fn kruskal_mst_trivial() {
+        assert_eq!(kruskal_mst(&[], 0), vec![]);
+        assert_eq!(kruskal_mst(&[], 1), vec![]);
+        assert_eq!(kruskal_mst(&[(0, 0, 3)], 1), vec![]);
+    }
+
+    #[test]
+    fn prim_mst_seven_vertices_eleven_edges() {
+        let edges = [
+            (0, 1, 7),
+            (0, 3, 5),
+            (1, 2, 8),
+            (1, 3, 9),
+            (1, 4, 7),
+            (2, 4, 5),
+            (3, 4, 15),
+            (3, 5, 6),
+            (4, 5, 8),
+            (4, 6, 9),
+            (5, 6, 11),
+        ];
+        let mut graph = WeightedGraph::new(7);
+        for &(u, v, weight) in &edges {
+            graph.add_undirected_edge(u, v, weight);
+        }
+        let mst = prim_mst(&graph, 0);
+        assert_eq!(
+            mst,
+            vec![
+                (0, 3, 5),
+                (3, 5, 6),
+                (0, 1, 7),
+                (1, 4, 7),
+                (4, 2, 5),
+                (4, 6, 9)
+            ]
+        );
+        assert_eq!(total_weight(&mst), 39);
+    }
+
+    #[test]
+    fn prim_mst_only_spans_start_component() {
+        let mut graph = WeightedGraph::new(5);
+        graph.add_undirected_edge(0, 1, 3);
+        graph.add_undirected_edge(1, 2, 1);
+        graph.add_undirected_edge(0, 2, 2);
+        graph.add_undirected_edge(3, 4, 7);
+
+        assert_eq!(prim_mst(&graph, 1), vec![(1, 2, 1), (2, 0, 2)]);
+        assert_eq!(prim_mst(&graph, 4), vec![(4, 3, 7)]);
+        assert_eq!(prim_mst(&WeightedGraph::new(1), 0), vec![]);
+    }
+
+    #[test]
+    fn prim_and_kruskal_agree() {
+        let mut rng = PCG32::new_default(20231);
+        for _ in 0..200 {
+            let n = 2 + rng.get_u32() as usize % 40;
+            let mut edges = Vec::new();
+            // a random spanning tree keeps the graph connected
+            for v in 1..n {
+                let u = rng.get_u32() as usize % v;
+                edges.push((u, v, rng.get_u64() % 100));
+            }
+            for _ in 0..rng.get_u32() % 200 {
+                let u = rng.get_u32() as usize % n;
+                let v = rng.get_u32() as usize % n;
+                edges.push((u, v, rng.get_u64() % 100));
+            }
+            let mut graph = WeightedGraph::new(n);
+            for &(u, v, weight) in &edges {
+                graph.add_undirected_edge(u, v, weight);
+            }
+
+            let kruskal = kruskal_mst(&edges, n);
+            let start = rng.get_u32() as usize % n;
+            let prim = prim_mst(&graph, start);
+            assert_eq!(kruskal.len(), n - 1);
+            assert_eq!(prim.len(), n - 1);
+            assert_eq!(component_count(&prim, n), 1);
+            assert_eq!(total_weight(&prim), total_weight(&kruskal));
+        }
+    }
 }
@@ -1,38 +1,58 @@
+mod astar;
 mod bellman_ford;
+mod bidirectional_bfs;
 mod bipartite_matching;
 mod breadth_first_search;
 mod centroid_decomposition;
+mod coloring;
+mod connectivity;
 mod depth_first_search;
 mod depth_first_search_tic_tac_toe;
 mod dijkstra;
 mod dinic_maxflow;
+mod directed_graph;
 mod disjoint_set_union;
 mod floyd_warshall;
 mod graph_enumeration;
 mod heavy_light_decomposition;
 mod lowest_common_ancestor;
+mod max_flow;
 mod minimum_spanning_tree;
 mod prim;
 mod prufer_code;
 mod strongly_connected_components;
 mod topological_sort;
+mod traversal;
 mod two_satisfiability;
-pub use self::bellman_ford::bellman_ford;
-pub use self::bipartite_matching::BipartiteMatching;
+mod weighted_graph;
+pub use self::astar::{astar, euclidean_distance, manhattan_distance, Grid};
+pub use self::bellman_ford::{bellman_ford, NegativeCycleError};
+pub use self::bidirectional_bfs::bidirectional_bfs;
+pub use self::bipartite_matching::{hopcroft_karp, BipartiteMatching};
 pub use self::breadth_first_search::breadth_first_search;
 pub use self::centroid_decomposition::CentroidDecomposition;
+pub use self::coloring::{chromatic_number_upper_bound, greedy_coloring, is_valid_coloring};
+pub use self::connectivity::{articulation_points, bridges};
 pub use self::depth_first_search::depth_first_search;
 pub use self::depth_first_search_tic_tac_toe::minimax;
-pub use self::dijkstra::dijkstra;
+pub use self::dijkstra::{dijkstra, dijkstra_path};
 pub use self::dinic_maxflow::DinicMaxFlow;
+pub use self::directed_graph::DirectedGraph;
 pub use self::disjoint_set_union::DisjointSetUnion;
-pub use self::floyd_warshall::floyd_warshall;
+pub use self::floyd_warshall::{floyd_warshall, floyd_warshall_path, floyd_warshall_with_next};
 pub use self::graph_enumeration::enumerate_graph;
 pub use self::heavy_light_decomposition::HeavyLightDecomposition;
 pub use self::lowest_common_ancestor::{LowestCommonAncestorOffline, LowestCommonAncestorOnline};
-pub use self::minimum_spanning_tree::kruskal;
+pub use self::max_flow::{max_flow, min_cut, FlowGraph};
+pub use self::minimum_spanning_tree::{kruskal, kruskal_mst, prim_mst};
 pub use self::prim::{prim, prim_with_start};
 pub use self::prufer_code::{prufer_decode, prufer_encode};
-pub use self::strongly_connected_components::StronglyConnectedComponents;
-pub use self::topological_sort::topological_sort;
+pub use self::strongly_connected_components::{
+    kosaraju_scc, tarjans_scc, StronglyConnectedComponents,
+};
+pub use self::topological_sort::{
+    topological_sort, topological_sort_dfs, topological_sort_kahn, CycleError,
+};
+pub use self::traversal::{connected_components, BfsIter, DfsIter};
 pub use self::two_satisfiability::solve_two_satisfiability;
+pub use self::weighted_graph::WeightedGraph;
@@ -8,6 +8,8 @@ and its vertices are numbered from 1 to n. If this is not the case, one
 can use `src/graph/graph_enumeration.rs` to convert their graph.
 */
 
+use super::DirectedGraph;
+
 pub struct StronglyConnectedComponents {
     // The number of the SCC the vertex is in, starting from 1
     pub component: Vec<usize>,
@@ -102,6 +104,129 @@ impl StronglyConnectedComponents {
     }
 }
 
+/// Tarjan's algorithm on a `DirectedGraph` with vertices numbered from 0.
+///
+/// The depth-first search keeps its own stack instead of recursing, so it
+/// cannot overflow on long paths. Components are returned in reverse
+/// topological order of the condensation (a component comes before every
+/// component that has an edge into it), each with its members sorted.
+pub fn tarjans_scc(graph: &DirectedGraph) -> Vec<Vec<usize>> {
+    let n = graph.num_vertices();
+    let mut discover_time: Vec<Option<usize>> = vec![None; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut current_time = 0;
+    // each entry is a vertex and the index of the next edge to follow from it
+    let mut call_stack: Vec<(usize, usize)> = Vec::new();
+
+    for root in 0..n {
+        if discover_time[root].is_some() {
+            continue;
+        }
+        call_stack.push((root, 0));
+        while let Some(&mut (v, ref mut edge)) = call_stack.last_mut() {
+            if *edge == 0 && discover_time[v].is_none() {
+                discover_time[v] = Some(current_time);
+                low[v] = current_time;
+                current_time += 1;
+                stack.push(v);
+                on_stack[v] = true;
+            }
+            if let Some(&u) = graph.neighbors(v).get(*edge) {
+                *edge += 1;
+                match discover_time[u] {
+                    None => call_stack.push((u, 0)),
+                    Some(time) if on_stack[u] => low[v] = low[v].min(time),
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            // all edges of v are done, return to its parent
+            call_stack.pop();
+            if let Some(&(parent, _)) = call_stack.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            // No vertex with a lower discovery time is reachable from this one
+            // So it should be "the head" of a new SCC.
+            if Some(low[v]) == discover_time[v] {
+                let mut component = Vec::new();
+                loop {
+                    let u = stack.pop().unwrap();
+                    on_stack[u] = false;
+                    component.push(u);
+                    if u == v {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+/// Kosaraju's algorithm on a `DirectedGraph` with vertices numbered from 0.
+///
+/// A first depth-first search records the order in which vertices finish;
+/// a second one on the transposed graph, started from vertices in decreasing
+/// finish time, then visits exactly one component per start. The output has
+/// the same format as [`tarjans_scc`].
+pub fn kosaraju_scc(graph: &DirectedGraph) -> Vec<Vec<usize>> {
+    let n = graph.num_vertices();
+    let mut visited = vec![false; n];
+    let mut finish_order = Vec::with_capacity(n);
+    let mut call_stack: Vec<(usize, usize)> = Vec::new();
+    for root in 0..n {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        call_stack.push((root, 0));
+        while let Some(&mut (v, ref mut edge)) = call_stack.last_mut() {
+            if let Some(&u) = graph.neighbors(v).get(*edge) {
+                *edge += 1;
+                if !visited[u] {
+                    visited[u] = true;
+                    call_stack.push((u, 0));
+                }
+            } else {
+                call_stack.pop();
+                finish_order.push(v);
+            }
+        }
+    }
+
+    let transposed = graph.transpose();
+    let mut assigned = vec![false; n];
+    let mut components = Vec::new();
+    for &root in finish_order.iter().rev() {
+        if assigned[root] {
+            continue;
+        }
+        assigned[root] = true;
+        let mut component = vec![root];
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            for &u in transposed.neighbors(v) {
+                if !assigned[u] {
+                    assigned[u] = true;
+                    component.push(u);
+                    stack.push(u);
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(component);
+    }
+    // components were found in topological order of the condensation
+    components.reverse();
+    components
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,4 +286,101 @@ mod tests {
         assert_eq!(sccs.state, vec![0, 1, 2, 3, 4, 5, 6]);
         assert_eq!(sccs.num_components, 1);
     }
+
+    // checks that no edge goes from a component to one that was listed later
+    fn assert_reverse_topological(graph: &DirectedGraph, components: &[Vec<usize>]) {
+        let mut component_of = vec![usize::MAX; graph.num_vertices()];
+        for (i, component) in components.iter().enumerate() {
+            for &v in component {
+                assert_eq!(component_of[v], usize::MAX);
+                component_of[v] = i;
+            }
+        }
+        for u in 0..graph.num_vertices() {
+            for &v in graph.neighbors(u) {
+                assert!(component_of[u] >= component_of[v]);
+            }
+        }
+    }
+
+    fn check(graph: &DirectedGraph, expected: &[Vec<usize>]) {
+        let tarjan = tarjans_scc(graph);
+        let kosaraju = kosaraju_scc(graph);
+        assert_reverse_topological(graph, &tarjan);
+        assert_reverse_topological(graph, &kosaraju);
+        let mut sorted = tarjan.clone();
+        sorted.sort();
+        assert_eq!(sorted, expected);
+        let mut sorted = kosaraju.clone();
+        sorted.sort();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn single_vertex() {
+        let graph = DirectedGraph::new(1);
+        assert_eq!(tarjans_scc(&graph), vec![vec![0]]);
+        assert_eq!(kosaraju_scc(&graph), vec![vec![0]]);
+    }
+
+    #[test]
+    fn fully_connected() {
+        let mut graph = DirectedGraph::new(5);
+        for u in 0..5 {
+            for v in 0..5 {
+                if u != v {
+                    graph.add_edge(u, v);
+                }
+            }
+        }
+        check(&graph, &[vec![0, 1, 2, 3, 4]]);
+    }
+
+    #[test]
+    fn directed_acyclic() {
+        // the same graph as `acyclic`, numbered from 0
+        let graph = DirectedGraph::from_edges(5, &[(0, 1), (0, 3), (1, 2), (1, 3), (2, 4), (3, 4)]);
+        let expected: Vec<Vec<usize>> = (0..5).map(|v| vec![v]).collect();
+        check(&graph, &expected);
+        assert_eq!(tarjans_scc(&graph)[0], vec![4]);
+        assert_eq!(kosaraju_scc(&graph)[4], vec![0]);
+    }
+
+    #[test]
+    fn several_components() {
+        let graph = DirectedGraph::from_edges(
+            8,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 0),
+                (2, 3),
+                (3, 4),
+                (4, 3),
+                (4, 5),
+                (5, 6),
+                (6, 7),
+                (7, 5),
+                (1, 6),
+            ],
+        );
+        check(&graph, &[vec![0, 1, 2], vec![3, 4], vec![5, 6, 7]]);
+        assert_eq!(
+            tarjans_scc(&graph),
+            vec![vec![5, 6, 7], vec![3, 4], vec![0, 1, 2]]
+        );
+    }
+
+    #[test]
+    fn long_cycle() {
+        // deep enough to overflow a recursive search
+        let n = 200_000;
+        let mut graph = DirectedGraph::new(n);
+        for v in 0..n {
+            graph.add_edge(v, (v + 1) % n);
+        }
+        let expected: Vec<usize> = (0..n).collect();
+        assert_eq!(tarjans_scc(&graph), vec![expected.clone()]);
+        assert_eq!(kosaraju_scc(&graph), vec![expected]);
+    }
 }
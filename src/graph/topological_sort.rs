@@ -1,7 +1,21 @@
+use super::DirectedGraph;
 use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
 
 type Graph<V, E> = BTreeMap<V, Vec<(V, E)>>;
 
+/// Error returned when a graph has no topological order because it contains a cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleError;
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the graph contains a cycle")
+    }
+}
+
+impl std::error::Error for CycleError {}
+
 /// returns topological sort of the graph using Kahn's algorithm
 pub fn topological_sort<V: Ord + Copy, E: Ord>(graph: &Graph<V, E>) -> Vec<V> {
     let mut visited = BTreeMap::new();
@@ -36,11 +50,95 @@ pub fn topological_sort<V: Ord + Copy, E: Ord>(graph: &Graph<V, E>) -> Vec<V> {
     ret
 }
 
+/// Returns a topological order of the graph using Kahn's algorithm:
+/// vertices without incoming edges are repeatedly emitted and removed.
+///
+/// If some vertices are never freed, they lie on or behind a cycle and
+/// `CycleError` is returned.
+pub fn topological_sort_kahn(graph: &DirectedGraph) -> Result<Vec<usize>, CycleError> {
+    let n = graph.num_vertices();
+    let mut degree = vec![0; n];
+    for u in 0..n {
+        for &v in graph.neighbors(u) {
+            degree[v] += 1;
+        }
+    }
+    let mut queue: VecDeque<usize> = (0..n).filter(|&u| degree[u] == 0).collect();
+    let mut ret = Vec::with_capacity(n);
+    while let Some(u) = queue.pop_front() {
+        ret.push(u);
+        for &v in graph.neighbors(u) {
+            degree[v] -= 1;
+            if degree[v] == 0 {
+                queue.push_back(v);
+            }
+        }
+    }
+    if ret.len() == n {
+        Ok(ret)
+    } else {
+        Err(CycleError)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Unvisited,
+    OnStack,
+    Done,
+}
+
+/// Returns a topological order of the graph using depth-first search:
+/// vertices are emitted in reverse order of completion.
+///
+/// The search is iterative, so deep graphs cannot overflow the call stack.
+/// Reaching a vertex that is still on the search stack is a back-edge, in
+/// which case `CycleError` is returned.
+pub fn topological_sort_dfs(graph: &DirectedGraph) -> Result<Vec<usize>, CycleError> {
+    let n = graph.num_vertices();
+    let mut state = vec![State::Unvisited; n];
+    let mut ret = Vec::with_capacity(n);
+    // each entry is a vertex and the index of the next edge to follow from it
+    let mut stack = Vec::new();
+    for root in 0..n {
+        if state[root] != State::Unvisited {
+            continue;
+        }
+        state[root] = State::OnStack;
+        stack.push((root, 0));
+        while let Some((u, edge)) = stack.last_mut() {
+            let u = *u;
+            match graph.neighbors(u).get(*edge) {
+                Some(&v) => {
+                    *edge += 1;
+                    match state[v] {
+                        State::Unvisited => {
+                            state[v] = State::OnStack;
+                            stack.push((v, 0));
+                        }
+                        State::OnStack => return Err(CycleError),
+                        State::Done => {}
+                    }
+                }
+                None => {
+                    state[u] = State::Done;
+                    ret.push(u);
+                    stack.pop();
+                }
+            }
+        }
+    }
+    ret.reverse();
+    Ok(ret)
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
-    use super::{topological_sort, Graph};
+    use super::super::DirectedGraph;
+    use super::{topological_sort, topological_sort_dfs, topological_sort_kahn, CycleError, Graph};
+    use crate::math::PCG32;
     fn add_edge<V: Ord + Copy, E: Ord>(graph: &mut Graph<V, E>, from: V, to: V, weight: E) {
         let edges = graph.entry(from).or_insert(Vec::new());
         edges.push((to, weight));
@@ -59,4 +157,117 @@ mod tests {
 
         assert_eq!(topological_sort(&graph), vec![1, 2, 3, 4, 5, 6, 7]);
     }
+
+    fn is_topological_order(graph: &DirectedGraph, order: &[usize]) -> bool {
+        let mut position = vec![usize::MAX; graph.num_vertices()];
+        for (i, &u) in order.iter().enumerate() {
+            if u >= position.len() || position[u] != usize::MAX {
+                return false;
+            }
+            position[u] = i;
+        }
+        order.len() == graph.num_vertices()
+            && (0..graph.num_vertices()).all(|u| {
+                graph
+                    .neighbors(u)
+                    .iter()
+                    .all(|&v| position[u] < position[v])
+            })
+    }
+
+    fn permutations(n: usize) -> Vec<Vec<usize>> {
+        if n == 0 {
+            return vec![vec![]];
+        }
+        let mut ret = Vec::new();
+        for perm in permutations(n - 1) {
+            for i in 0..=perm.len() {
+                let mut p = perm.clone();
+                p.insert(i, n - 1);
+                ret.push(p);
+            }
+        }
+        ret
+    }
+
+    #[test]
+    fn chain() {
+        let graph = DirectedGraph::from_edges(4, &[(2, 0), (0, 3), (3, 1)]);
+        assert_eq!(topological_sort_kahn(&graph), Ok(vec![2, 0, 3, 1]));
+        assert_eq!(topological_sort_dfs(&graph), Ok(vec![2, 0, 3, 1]));
+    }
+
+    #[test]
+    fn empty_and_isolated() {
+        let graph = DirectedGraph::new(0);
+        assert_eq!(topological_sort_kahn(&graph), Ok(vec![]));
+        assert_eq!(topological_sort_dfs(&graph), Ok(vec![]));
+
+        let graph = DirectedGraph::new(3);
+        assert!(is_topological_order(
+            &graph,
+            &topological_sort_kahn(&graph).unwrap()
+        ));
+        assert!(is_topological_order(
+            &graph,
+            &topological_sort_dfs(&graph).unwrap()
+        ));
+    }
+
+    #[test]
+    fn any_valid_order_is_accepted() {
+        // a diamond 0 -> {1, 2} -> 3 plus an isolated vertex 4
+        let graph = DirectedGraph::from_edges(5, &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+        let valid: Vec<Vec<usize>> = permutations(5)
+            .into_iter()
+            .filter(|order| is_topological_order(&graph, order))
+            .collect();
+        // 2 orders of the diamond, times 5 positions for the isolated vertex
+        assert_eq!(valid.len(), 10);
+        assert!(valid.contains(&topological_sort_kahn(&graph).unwrap()));
+        assert!(valid.contains(&topological_sort_dfs(&graph).unwrap()));
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let self_loop = DirectedGraph::from_edges(2, &[(0, 1), (1, 1)]);
+        let triangle = DirectedGraph::from_edges(4, &[(3, 0), (0, 1), (1, 2), (2, 0)]);
+        let behind_cycle = DirectedGraph::from_edges(4, &[(0, 1), (1, 0), (1, 2), (2, 3)]);
+        for graph in [self_loop, triangle, behind_cycle] {
+            assert_eq!(topological_sort_kahn(&graph), Err(CycleError));
+            assert_eq!(topological_sort_dfs(&graph), Err(CycleError));
+        }
+    }
+
+    #[test]
+    fn random_dags() {
+        let mut rng = PCG32::new_default(1000);
+        for _ in 0..10 {
+            let n = 1000;
+            // edges always go forward in a random permutation of the vertices
+            let mut perm: Vec<usize> = (0..n).collect();
+            for i in (1..n).rev() {
+                perm.swap(i, rng.get_u32() as usize % (i + 1));
+            }
+            let mut graph = DirectedGraph::new(n);
+            for _ in 0..5000 {
+                let a = rng.get_u32() as usize % n;
+                let b = rng.get_u32() as usize % n;
+                if a != b {
+                    graph.add_edge(perm[a.min(b)], perm[a.max(b)]);
+                }
+            }
+            let kahn = topological_sort_kahn(&graph).unwrap();
+            let dfs = topological_sort_dfs(&graph).unwrap();
+            assert!(is_topological_order(&graph, &kahn));
+            assert!(is_topological_order(&graph, &dfs));
+
+            // edges in both directions between two vertices always form a cycle
+            let (a, b) = (perm[10], perm[n - 10]);
+            graph.add_edge(a, b);
+            graph.add_edge(b, a);
+            assert_eq!(topological_sort_kahn(&graph), Err(CycleError));
+            assert_eq!(topological_sort_dfs(&graph), Err(CycleError));
+        }
+    }
 }
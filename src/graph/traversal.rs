@@ -0,0 +1,207 @@
+use std::collections::VecDeque;
+
+// Adjacency List
+type Graph = Vec<Vec<usize>>;
+
+/// An iterator over the nodes reachable from a source node, in breadth-first
+/// order, visiting the neighbors of a node in the order of its adjacency list.
+pub struct BfsIter<'a> {
+    graph: &'a Graph,
+    visited: Vec<bool>,
+    queue: VecDeque<usize>,
+}
+
+impl<'a> BfsIter<'a> {
+    /// Returns an iterator starting from `source`, which is yielded first.
+    pub fn new(graph: &'a Graph, source: usize) -> Self {
+        let mut visited = vec![false; graph.len()];
+        visited[source] = true;
+        BfsIter {
+            graph,
+            visited,
+            queue: VecDeque::from([source]),
+        }
+    }
+}
+
+impl Iterator for BfsIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let node = self.queue.pop_front()?;
+        for &neighbor in &self.graph[node] {
+            // marked when queued, so that no node is queued twice
+            if !self.visited[neighbor] {
+                self.visited[neighbor] = true;
+                self.queue.push_back(neighbor);
+            }
+        }
+        Some(node)
+    }
+}
+
+/// An iterator over the nodes reachable from a source node, in depth-first
+/// preorder, visiting the neighbors of a node in the order of its adjacency
+/// list as a recursive depth-first search would.
+pub struct DfsIter<'a> {
+    graph: &'a Graph,
+    visited: Vec<bool>,
+    stack: Vec<usize>,
+}
+
+impl<'a> DfsIter<'a> {
+    /// Returns an iterator starting from `source`, which is yielded first.
+    pub fn new(graph: &'a Graph, source: usize) -> Self {
+        let mut iter = DfsIter::unvisited(graph);
+        iter.stack.push(source);
+        iter
+    }
+
+    /// Returns an iterator with nothing to visit and no node visited.
+    fn unvisited(graph: &'a Graph) -> Self {
+        DfsIter {
+            graph,
+            visited: vec![false; graph.len()],
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl Iterator for DfsIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        // a node may be pushed several times before it is visited
+        let node = loop {
+            let node = self.stack.pop()?;
+            if !self.visited[node] {
+                break node;
+            }
+        };
+        self.visited[node] = true;
+        // reversed, so that the first neighbor is popped first
+        for &neighbor in self.graph[node].iter().rev() {
+            if !self.visited[neighbor] {
+                self.stack.push(neighbor);
+            }
+        }
+        Some(node)
+    }
+}
+
+/// Returns the connected components of an undirected graph, whose adjacency
+/// lists hold every edge in both directions.
+///
+/// The components are ordered by their smallest node, each in depth-first
+/// order from that node.
+pub fn connected_components(graph: &Graph) -> Vec<Vec<usize>> {
+    let mut components = Vec::new();
+    // one iterator for all components, so that the nodes are visited once
+    let mut dfs = DfsIter::unvisited(graph);
+    for source in 0..graph.len() {
+        if !dfs.visited[source] {
+            dfs.stack.push(source);
+            components.push(dfs.by_ref().collect());
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the adjacency lists of an undirected graph with the given edges.
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Graph {
+        let mut graph = vec![vec![]; n];
+        for &(u, v) in edges {
+            graph[u].push(v);
+            graph[v].push(u);
+        }
+        graph
+    }
+
+    #[test]
+    fn traversal_order() {
+        //     0
+        //    / \
+        //   1   2
+        //  / \   \
+        // 3   4 - 5
+        let graph = undirected(6, &[(0, 1), (0, 2), (1, 3), (1, 4), (2, 5), (4, 5)]);
+        assert_eq!(
+            BfsIter::new(&graph, 0).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            DfsIter::new(&graph, 0).collect::<Vec<_>>(),
+            vec![0, 1, 3, 4, 5, 2]
+        );
+        assert_eq!(
+            BfsIter::new(&graph, 5).collect::<Vec<_>>(),
+            vec![5, 2, 4, 0, 1, 3]
+        );
+        assert_eq!(
+            DfsIter::new(&graph, 5).collect::<Vec<_>>(),
+            vec![5, 2, 0, 1, 3, 4]
+        );
+    }
+
+    #[test]
+    fn directed_edges() {
+        let graph = vec![vec![1], vec![2], vec![], vec![0]];
+        assert_eq!(BfsIter::new(&graph, 0).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(DfsIter::new(&graph, 1).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(
+            DfsIter::new(&graph, 3).collect::<Vec<_>>(),
+            vec![3, 0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn visits_every_node_once() {
+        // a cycle with chords, connected from every node
+        let n = 100;
+        let mut edges: Vec<(usize, usize)> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+        edges.extend((0..n).step_by(7).map(|i| (i, (i * 3) % n)));
+        let graph = undirected(n, &edges);
+        for source in [0, 42, 99] {
+            for mut order in [
+                BfsIter::new(&graph, source).collect::<Vec<_>>(),
+                DfsIter::new(&graph, source).collect::<Vec<_>>(),
+            ] {
+                assert_eq!(order[0], source);
+                order.sort();
+                assert_eq!(order, (0..n).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn components() {
+        let graph = undirected(8, &[(0, 3), (3, 5), (1, 2), (6, 7), (7, 1)]);
+        assert_eq!(
+            connected_components(&graph),
+            vec![vec![0, 3, 5], vec![1, 2, 7, 6], vec![4]]
+        );
+        assert_eq!(connected_components(&undirected(0, &[])).len(), 0);
+        assert_eq!(connected_components(&undirected(5, &[])).len(), 5);
+        assert_eq!(connected_components(&undirected(5, &[(0, 4)])).len(), 4);
+    }
+
+    #[test]
+    fn lazy_and_chainable() {
+        let graph = undirected(6, &[(0, 1), (0, 2), (1, 3), (1, 4), (2, 5), (4, 5)]);
+        // stops at the first match without visiting the rest
+        assert_eq!(BfsIter::new(&graph, 0).position(|v| v == 4), Some(4));
+        let even: Vec<usize> = DfsIter::new(&graph, 0).filter(|v| v % 2 == 0).collect();
+        assert_eq!(even, vec![0, 4, 2]);
+        let pairs: Vec<(usize, usize)> = BfsIter::new(&graph, 0)
+            .zip(DfsIter::new(&graph, 0))
+            .take(3)
+            .collect();
+        assert_eq!(pairs, vec![(0, 0), (1, 1), (2, 3)]);
+        assert_eq!(DfsIter::new(&graph, 3).nth(2), Some(0));
+        assert_eq!(BfsIter::new(&graph, 3).map(|v| v * 10).sum::<usize>(), 150);
+    }
+}
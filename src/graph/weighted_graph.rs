@@ -0,0 +1,82 @@
+/// A directed graph on the vertices `0..n`, stored as adjacency lists of
+/// `(neighbor, weight)` pairs.
+///
+/// The weight type defaults to `u64`; algorithms that allow negative weights
+/// use `WeightedGraph<i64>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedGraph<W = u64> {
+    adjacency: Vec<Vec<(usize, W)>>,
+}
+
+impl<W> WeightedGraph<W> {
+    /// Creates a graph with `num_vertices` vertices and no edges.
+    pub fn new(num_vertices: usize) -> Self {
+        WeightedGraph {
+            adjacency: (0..num_vertices).map(|_| Vec::new()).collect(),
+        }
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Adds a directed edge from `from` to `to`.
+    ///
+    /// Panics if either endpoint is not a vertex of the graph.
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) {
+        assert!(to < self.adjacency.len(), "vertex {} is out of range", to);
+        self.adjacency[from].push((to, weight));
+    }
+
+    /// Returns the outgoing edges of `vertex` as `(neighbor, weight)` pairs.
+    pub fn neighbors(&self, vertex: usize) -> &[(usize, W)] {
+        &self.adjacency[vertex]
+    }
+}
+
+impl<W: Copy> WeightedGraph<W> {
+    /// Adds an edge in both directions between `u` and `v`.
+    pub fn add_undirected_edge(&mut self, u: usize, v: usize, weight: W) {
+        self.add_edge(u, v, weight);
+        if u != v {
+            self.add_edge(v, u, weight);
+        }
+    }
+
+    /// Iterates over all edges as `(from, to, weight)` triples.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, W)> + '_ {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(from, edges)| edges.iter().map(move |&(to, weight)| (from, to, weight)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WeightedGraph;
+
+    #[test]
+    fn build() {
+        let mut graph = WeightedGraph::new(3);
+        graph.add_edge(0, 1, 5);
+        graph.add_undirected_edge(1, 2, 7);
+        graph.add_undirected_edge(2, 2, 1);
+
+        assert_eq!(graph.num_vertices(), 3);
+        assert_eq!(graph.neighbors(0), &[(1, 5)]);
+        assert_eq!(graph.neighbors(1), &[(2, 7)]);
+        assert_eq!(graph.neighbors(2), &[(1, 7), (2, 1)]);
+        assert_eq!(
+            graph.edges().collect::<Vec<_>>(),
+            vec![(0, 1, 5), (1, 2, 7), (2, 1, 7), (2, 2, 1)]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range() {
+        let mut graph: WeightedGraph = WeightedGraph::new(2);
+        graph.add_edge(0, 2, 1);
+    }
+}
@@ -8,6 +8,7 @@ pub mod conversions;
 pub mod data_structures;
 pub mod dynamic_programming;
 pub mod general;
+pub mod geometry;
 pub mod graph;
 pub mod math;
 pub mod navigation;
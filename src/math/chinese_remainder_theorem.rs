@@ -1,24 +1,54 @@
-use super::extended_euclidean_algorithm;
-
-fn mod_inv(x: i32, n: i32) -> Option<i32> {
-    let (g, x, _) = extended_euclidean_algorithm(x, n);
-    if g == 1 {
-        Some((x % n + n) % n)
-    } else {
-        None
+//! Chinese remainder theorem.
+//!
+//! A system of congruences x ≡ r_i (mod m_i) has a single solution modulo
+//! lcm(m_1, ..., m_k) whenever its congruences are pairwise compatible, that is,
+//! when r_i ≡ r_j (mod gcd(m_i, m_j)) for all i and j. For pairwise coprime
+//! moduli this always holds.
+//!
+//! Wikipedia reference: https://en.wikipedia.org/wiki/Chinese_remainder_theorem
+
+use super::{extended_gcd, mod_inverse};
+
+/// Returns the solution in `0..lcm(moduli)` of the system `x ≡ residues[i] (mod moduli[i])`.
+///
+/// The congruences are merged one at a time: x ≡ r (mod m) and x ≡ r' (mod m')
+/// have a common solution only if g = gcd(m, m') divides r' - r, and it is then
+/// x = r + m * k, where k solves (m / g) * k ≡ (r' - r) / g (mod m' / g).
+///
+/// Returns `None` if the system has no solution, if a modulus is not positive,
+/// or if the lcm of the moduli does not fit in an `i64`. An empty system is
+/// solved by 0. Panics if the slices have different lengths.
+pub fn crt(residues: &[i64], moduli: &[i64]) -> Option<i64> {
+    assert_eq!(
+        residues.len(),
+        moduli.len(),
+        "every residue needs a modulus"
+    );
+    let (mut solution, mut lcm) = (0_i64, 1_i64);
+    for (&residue, &modulus) in residues.iter().zip(moduli) {
+        if modulus <= 0 {
+            return None;
+        }
+        let residue = residue.rem_euclid(modulus);
+        let (gcd, _, _) = extended_gcd(lcm, modulus);
+        let difference = residue - solution;
+        if difference % gcd != 0 {
+            return None;
+        }
+        let step = modulus / gcd;
+        let k = (difference / gcd).rem_euclid(step) as i128 * mod_inverse(lcm / gcd, step)? as i128
+            % step as i128;
+        let next_lcm = i64::try_from(lcm as i128 * step as i128).ok()?;
+        solution = (solution as i128 + lcm as i128 * k).rem_euclid(next_lcm as i128) as i64;
+        lcm = next_lcm;
     }
+    Some(solution)
 }
 
 pub fn chinese_remainder_theorem(residues: &[i32], modulli: &[i32]) -> Option<i32> {
-    let prod = modulli.iter().product::<i32>();
-
-    let mut sum = 0;
-
-    for (&residue, &modulus) in residues.iter().zip(modulli) {
-        let p = prod / modulus;
-        sum += residue * mod_inv(p, modulus)? * p
-    }
-    Some(sum % prod)
+    let residues: Vec<i64> = residues.iter().map(|&r| r as i64).collect();
+    let moduli: Vec<i64> = modulli.iter().map(|&m| m as i64).collect();
+    crt(&residues, &moduli).and_then(|x| i32::try_from(x).ok())
 }
 
 #[cfg(test)]
@@ -32,4 +62,64 @@ mod tests {
         assert_eq!(chinese_remainder_theorem(&[1, 4, 6], &[1, 2, 0]), None);
         assert_eq!(chinese_remainder_theorem(&[2, 5, 7], &[6, 9, 15]), None);
     }
+
+    #[test]
+    fn classic_example() {
+        assert_eq!(crt(&[2, 3, 2], &[3, 5, 7]), Some(23));
+        assert_eq!(crt(&[-1, -2], &[3, 5]), Some(8));
+        assert_eq!(crt(&[], &[]), Some(0));
+        assert_eq!(crt(&[10], &[7]), Some(3));
+    }
+
+    #[test]
+    fn non_coprime_moduli() {
+        // compatible: x ≡ 2 (mod 2) and x ≡ 0 (mod 2) agree
+        assert_eq!(crt(&[2, 4], &[6, 8]), Some(20));
+        assert_eq!(crt(&[3, 3, 3], &[4, 6, 10]), Some(3));
+        // incompatible: x would have to be both even and odd
+        assert_eq!(crt(&[1, 2], &[4, 6]), None);
+        assert_eq!(crt(&[0, 1], &[5, 5]), None);
+    }
+
+    #[test]
+    fn invalid_moduli() {
+        assert_eq!(crt(&[1], &[0]), None);
+        assert_eq!(crt(&[1], &[-3]), None);
+        // the lcm of these primes does not fit in an i64
+        assert_eq!(
+            crt(&[1, 2, 3], &[1_000_000_007, 1_000_000_009, 998_244_353]),
+            None
+        );
+    }
+
+    #[test]
+    fn large_moduli() {
+        let moduli = [1_000_000_007, 998_244_353];
+        let x = 123_456_789_012_345_678;
+        let residues = moduli.map(|m| x % m);
+        assert_eq!(crt(&residues, &moduli), Some(x % (moduli[0] * moduli[1])));
+    }
+
+    #[test]
+    fn up_to_ten_congruences() {
+        let mut rng = crate::math::PCG32::new_default(36);
+        let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29];
+        for k in 1..=10 {
+            for _ in 0..50 {
+                // moduli sharing factors, built from small primes
+                let moduli: Vec<i64> = (0..k)
+                    .map(|_| {
+                        let a = primes[rng.get_u32() as usize % k];
+                        let b = primes[rng.get_u32() as usize % k];
+                        a * b
+                    })
+                    .collect();
+                let lcm = moduli.iter().fold(1, |l, &m| l / extended_gcd(l, m).0 * m);
+                let x = rng.get_u64() as i64 % lcm;
+                let x = x.rem_euclid(lcm);
+                let residues: Vec<i64> = moduli.iter().map(|&m| x % m).collect();
+                assert_eq!(crt(&residues, &moduli), Some(x));
+            }
+        }
+    }
 }
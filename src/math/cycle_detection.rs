@@ -0,0 +1,163 @@
+/// Finds the cycle of the sequence `start, f(start), f(f(start)), ...` with
+/// Floyd's tortoise and hare algorithm, in `O(mu + lambda)` steps and `O(1)`
+/// memory.
+///
+/// Returns `(mu, lambda)`, where `mu` is the index of the first element of
+/// the cycle and `lambda` is its length, or `None` if the tortoise takes
+/// `max_steps` steps without meeting the hare. A cycle is always found when
+/// `mu + lambda <= max_steps`.
+pub fn floyd_cycle_detection<F: Fn(usize) -> usize>(
+    f: F,
+    start: usize,
+    max_steps: usize,
+) -> Option<(usize, usize)> {
+    find_cycle(|x| Some(f(x)), start, max_steps)
+}
+
+/// Finds the cycle of a singly linked list stored in a slice, with the value
+/// of each node and the index of the next one, if any.
+///
+/// Returns `(mu, lambda)` for the nodes from `head`, where `mu` is the number
+/// of nodes before the cycle and `lambda` is its length, or `None` if the
+/// list ends.
+pub fn detect_linked_list_cycle<T>(
+    nodes: &[(T, Option<usize>)],
+    head: usize,
+) -> Option<(usize, usize)> {
+    // every node is in the list at most once before the cycle is found
+    find_cycle(|node| nodes[node].1, head, nodes.len())
+}
+
+/// `floyd_cycle_detection` over a sequence that ends where `f` returns `None`.
+fn find_cycle<F: Fn(usize) -> Option<usize>>(
+    f: F,
+    start: usize,
+    max_steps: usize,
+) -> Option<(usize, usize)> {
+    // The hare takes two steps for each one of the tortoise, so they meet at
+    // the first step i >= mu that is a multiple of lambda, which is less than
+    // mu + lambda.
+    let (mut tortoise, mut hare) = (start, start);
+    for step in 1.. {
+        if step > max_steps {
+            return None;
+        }
+        tortoise = f(tortoise)?;
+        hare = f(f(hare)?)?;
+        if tortoise == hare {
+            break;
+        }
+    }
+
+    // The hare is i steps ahead, a multiple of lambda, so walking both one
+    // step at a time from the start and from the hare meets at element mu.
+    let mut mu = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = f(tortoise)?;
+        hare = f(hare)?;
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    hare = f(tortoise)?;
+    while tortoise != hare {
+        hare = f(hare)?;
+        lambda += 1;
+    }
+    Some((mu, lambda))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Returns `(mu, lambda)` by remembering the index of every element.
+    fn brute_force<F: Fn(usize) -> usize>(f: F, start: usize) -> (usize, usize) {
+        let mut seen = HashMap::new();
+        let mut x = start;
+        for i in 0.. {
+            if let Some(&mu) = seen.get(&x) {
+                return (mu, i - mu);
+            }
+            seen.insert(x, i);
+            x = f(x);
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn simple_cycle() {
+        // 2 -> 4 -> 16 -> 3 -> 9 -> 12 -> 5 -> 12 -> ... modulo 19
+        let f = |x| x * x % 19;
+        assert_eq!(floyd_cycle_detection(f, 2, 100), Some(brute_force(f, 2)));
+        // 0, 1, 2, 3, 4, 5, 6, 4, ...
+        let f = |x| if x == 6 { 4 } else { x + 1 };
+        assert_eq!(floyd_cycle_detection(f, 0, 100), Some((4, 3)));
+        assert_eq!(floyd_cycle_detection(f, 1, 100), Some((3, 3)));
+        assert_eq!(floyd_cycle_detection(f, 5, 100), Some((0, 3)));
+    }
+
+    #[test]
+    fn fixed_points() {
+        // 100, 50, 25, 12, 6, 3, 1, 0, 0, ...
+        assert_eq!(floyd_cycle_detection(|x| x / 2, 100, 100), Some((7, 1)));
+        assert_eq!(floyd_cycle_detection(|x| x, 42, 100), Some((0, 1)));
+    }
+
+    #[test]
+    fn cycle_from_the_start() {
+        assert_eq!(floyd_cycle_detection(|x| (x + 1) % 5, 0, 100), Some((0, 5)));
+        assert_eq!(
+            floyd_cycle_detection(|x| (x + 3) % 10, 7, 100),
+            Some((0, 10))
+        );
+        assert_eq!(floyd_cycle_detection(|x| 1 - x, 1, 100), Some((0, 2)));
+    }
+
+    #[test]
+    fn no_cycle_within_limit() {
+        assert_eq!(floyd_cycle_detection(|x| x + 1, 0, 1000), None);
+        assert_eq!(floyd_cycle_detection(|x| x + 7, 3, 50), None);
+        // mu = 5 and lambda = 100, so they meet after 100 steps
+        let f = |x| if x == 104 { 5 } else { x + 1 };
+        assert_eq!(floyd_cycle_detection(f, 0, 99), None);
+        assert_eq!(floyd_cycle_detection(f, 0, 100), Some((5, 100)));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for modulus in 1..60 {
+            for c in 0..5 {
+                let f = |x: usize| (x * x + c) % modulus;
+                for start in 0..modulus {
+                    let expected = brute_force(f, start);
+                    assert_eq!(floyd_cycle_detection(f, start, modulus), Some(expected));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn linked_lists() {
+        // a -> b -> c -> d -> b
+        let nodes = [
+            ('a', Some(1)),
+            ('b', Some(2)),
+            ('c', Some(3)),
+            ('d', Some(1)),
+        ];
+        assert_eq!(detect_linked_list_cycle(&nodes, 0), Some((1, 3)));
+        assert_eq!(detect_linked_list_cycle(&nodes, 2), Some((0, 3)));
+
+        // a -> b -> c
+        let nodes = [("a", Some(1)), ("b", Some(2)), ("c", None)];
+        assert_eq!(detect_linked_list_cycle(&nodes, 0), None);
+        assert_eq!(detect_linked_list_cycle(&nodes, 2), None);
+
+        // a node pointing to itself
+        assert_eq!(detect_linked_list_cycle(&[(0, Some(0))], 0), Some((0, 1)));
+        assert_eq!(detect_linked_list_cycle(&[(0, None)], 0), None);
+    }
+}
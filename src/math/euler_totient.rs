@@ -0,0 +1,136 @@
+//! Euler's totient function.
+//!
+//! φ(n) counts the numbers in 1..=n that are coprime with n. For
+//! n = p1^k1 * ... * pr^kr it is n * (1 - 1/p1) * ... * (1 - 1/pr), so only the
+//! distinct prime factors of n matter.
+//!
+//! Wikipedia reference: https://en.wikipedia.org/wiki/Euler%27s_totient_function
+
+/// Returns φ(n), factorizing `n` by trial division in O(sqrt(n)) time.
+///
+/// φ(0) is taken to be 0.
+pub fn euler_totient(n: u64) -> u64 {
+    let mut result = n;
+    let mut rest = n;
+    let mut p = 2;
+    // not p * p <= rest, which overflows for a prime rest above (2^32 - 1)^2
+    while p <= rest / p {
+        if rest.is_multiple_of(p) {
+            while rest.is_multiple_of(p) {
+                rest /= p;
+            }
+            result -= result / p;
+        }
+        p += 1;
+    }
+    // what is left is a prime factor larger than the square root
+    if rest > 1 {
+        result -= result / rest;
+    }
+    result
+}
+
+/// Returns a table of length `limit + 1` holding φ(k) at index `k`, with 0 at index 0.
+///
+/// Like the sieve of Eratosthenes, every prime p, recognized by being left
+/// untouched by the smaller primes, multiplies each of its multiples by
+/// (1 - 1/p). It takes O(n log log n) time.
+pub fn euler_totient_sieve(limit: usize) -> Vec<u64> {
+    let mut phi: Vec<u64> = (0..=limit as u64).collect();
+    for p in 2..=limit {
+        if phi[p] == p as u64 {
+            for multiple in (p..=limit).step_by(p) {
+                phi[multiple] -= phi[multiple] / p as u64;
+            }
+        }
+    }
+    phi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{greatest_common_divisor_stein, primes_up_to};
+
+    #[test]
+    fn small_values() {
+        assert_eq!(euler_totient(0), 0);
+        assert_eq!(euler_totient(1), 1);
+        assert_eq!(euler_totient(9), 6);
+        assert_eq!(euler_totient(10), 4);
+        assert_eq!(euler_totient(36), 12);
+        assert_eq!(euler_totient(97 * 89), 96 * 88);
+        assert_eq!(euler_totient(1 << 40), 1 << 39);
+        assert_eq!(euler_totient_sieve(0), vec![0]);
+        assert_eq!(
+            euler_totient_sieve(10),
+            vec![0, 1, 1, 2, 2, 4, 2, 6, 4, 6, 4]
+        );
+    }
+
+    #[test]
+    fn primes() {
+        for p in primes_up_to(2000) {
+            assert_eq!(euler_totient(p as u64), p as u64 - 1);
+        }
+        assert_eq!(euler_totient(1_000_000_007), 1_000_000_006);
+        // 2^64 - 1 = 3 * 5 * 17 * 257 * 641 * 65537 * 6700417
+        assert_eq!(
+            euler_totient(u64::MAX),
+            2 * 4 * 16 * 256 * 640 * 65536 * 6700416
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn largest_prime() {
+        // trial division goes up to 2^32, where p * p would overflow
+        let p = u64::MAX - 58;
+        assert_eq!(euler_totient(p), p - 1);
+    }
+
+    #[test]
+    fn multiplicative() {
+        for m in 1..100 {
+            for n in 1..100 {
+                if greatest_common_divisor_stein(m, n) == 1 {
+                    assert_eq!(
+                        euler_totient(m * n),
+                        euler_totient(m) * euler_totient(n),
+                        "{m} * {n}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sum_over_divisors() {
+        for n in 1..=1000_u64 {
+            let sum: u64 = (1..=n)
+                .filter(|&d| n.is_multiple_of(d))
+                .map(euler_totient)
+                .sum();
+            assert_eq!(sum, n);
+        }
+    }
+
+    #[test]
+    fn sieve_matches_single_values() {
+        let phi = euler_totient_sieve(10_000);
+        assert_eq!(phi.len(), 10_001);
+        for (n, &value) in phi.iter().enumerate() {
+            assert_eq!(value, euler_totient(n as u64), "{n}");
+        }
+    }
+
+    #[test]
+    fn counts_coprime_numbers() {
+        for n in 1..=300_u64 {
+            let coprime = (1..=n)
+                .filter(|&k| greatest_common_divisor_stein(k, n) == 1)
+                .count();
+            assert_eq!(euler_totient(n), coprime as u64);
+        }
+    }
+}
@@ -1,11 +1,29 @@
-fn update_step(a: &mut i32, old_a: &mut i32, quotient: i32) {
+//! Extended Euclidean algorithm.
+//!
+//! Besides gcd(a, b), it finds Bezout coefficients x and y with
+//! a * x + b * y = gcd(a, b), by keeping every remainder of Euclid's algorithm
+//! written as a combination of a and b.
+//!
+//! Wikipedia reference: https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+
+fn update_step(a: &mut i128, old_a: &mut i128, quotient: i128) {
     let temp = *a;
     *a = *old_a - quotient * temp;
     *old_a = temp;
 }
 
-pub fn extended_euclidean_algorithm(a: i32, b: i32) -> (i32, i32, i32) {
-    let (mut old_r, mut rem) = (a, b);
+/// Returns `(gcd, x, y)` such that `a * x + b * y == gcd`, where `gcd` is the
+/// non-negative greatest common divisor of `a` and `b`.
+///
+/// The steps are computed in `i128`, since negating or dividing `i64::MIN`
+/// overflows `i64`, and the results fit back in an `i64` but for one case.
+///
+/// # Panics
+///
+/// Panics if the gcd is `2^63`, which only happens when `a` and `b` are each
+/// `0` or `i64::MIN`, and not both `0`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut rem) = (a as i128, b as i128);
     let (mut old_s, mut coeff_s) = (1, 0);
     let (mut old_t, mut coeff_t) = (0, 1);
 
@@ -17,7 +35,31 @@ pub fn extended_euclidean_algorithm(a: i32, b: i32) -> (i32, i32, i32) {
         update_step(&mut coeff_t, &mut old_t, quotient);
     }
 
-    (old_r, old_s, old_t)
+    if old_r < 0 {
+        (old_r, old_s, old_t) = (-old_r, -old_s, -old_t);
+    }
+    let gcd = i64::try_from(old_r).expect("gcd of 2^63 does not fit in an i64");
+    // each coefficient is at most 1 or max(|a|, |b|) / (2 gcd) in size
+    (gcd, old_s as i64, old_t as i64)
+}
+
+pub fn extended_euclidean_algorithm(a: i32, b: i32) -> (i32, i32, i32) {
+    let (gcd, x, y) = extended_gcd(a as i64, b as i64);
+    (gcd as i32, x as i32, y as i32)
+}
+
+/// Returns the `x` in `0..|m|` with `a * x ≡ 1 (mod m)`.
+///
+/// It only exists when `a` and `m` are coprime, and `None` is returned
+/// otherwise, as well as for `m == 0`.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    // both even have no inverse, and a gcd of 2^63 would not fit extended_gcd
+    if m == 0 || (a % 2 == 0 && m % 2 == 0) {
+        return None;
+    }
+    let (gcd, x, _) = extended_gcd(a, m);
+    // |i64::MIN| does not fit in an i64, but the result is below it
+    (gcd == 1).then(|| (x as i128).rem_euclid(m.unsigned_abs() as i128) as i64)
 }
 
 #[cfg(test)]
@@ -34,4 +76,100 @@ mod tests {
         assert_eq!(extended_euclidean_algorithm(33, 44), (11, -1, 1));
         assert_eq!(extended_euclidean_algorithm(50, 70), (10, 3, -2));
     }
+
+    #[test]
+    fn bezout_identity() {
+        let mut rng = crate::math::PCG32::new_default(35);
+        for _ in 0..1000 {
+            let a = rng.get_u32() as i64 - (1 << 31);
+            let b = rng.get_u32() as i64 % 10_000 - 5_000;
+            let (gcd, x, y) = extended_gcd(a, b);
+            assert!(gcd >= 0);
+            assert_eq!(a * x + b * y, gcd, "{a}, {b}");
+            if gcd != 0 {
+                assert_eq!(a % gcd, 0);
+                assert_eq!(b % gcd, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn negative_and_zero() {
+        assert_eq!(extended_gcd(-12, 18).0, 6);
+        assert_eq!(extended_gcd(12, -18).0, 6);
+        assert_eq!(extended_gcd(-12, -18).0, 6);
+        assert_eq!(extended_gcd(0, -7), (7, 0, -1));
+        assert_eq!(extended_gcd(-7, 0), (7, -1, 0));
+        assert_eq!(extended_gcd(0, 0), (0, 1, 0));
+    }
+
+    #[test]
+    fn extremes() {
+        for (a, b) in [
+            (i64::MIN, -1),
+            (i64::MIN, 1),
+            (-1, i64::MIN),
+            (i64::MIN, i64::MAX),
+            (i64::MAX, i64::MIN),
+            (i64::MIN, 3 << 61),
+            (i64::MAX, i64::MAX - 1),
+            (i64::MAX, 0),
+        ] {
+            let (gcd, x, y) = extended_gcd(a, b);
+            assert!(gcd >= 0);
+            assert_eq!(
+                a as i128 * x as i128 + b as i128 * y as i128,
+                gcd as i128,
+                "{a}, {b}"
+            );
+        }
+        assert_eq!(extended_gcd(i64::MIN, -1).0, 1);
+        assert_eq!(extended_gcd(i64::MIN, 3 << 61).0, 1 << 61);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn gcd_of_min_and_zero() {
+        extended_gcd(i64::MIN, 0);
+    }
+
+    #[test]
+    fn inverse_exists() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(3, -11), Some(4));
+        assert_eq!(mod_inverse(5, 1), Some(0));
+        assert_eq!(mod_inverse(1, i64::MIN), Some(1));
+        let inverse = mod_inverse(3, i64::MIN).unwrap();
+        assert!(inverse > 0);
+        assert_eq!((3 * inverse as i128).rem_euclid(1 << 63), 1);
+        let inverse = mod_inverse(-7, i64::MIN).unwrap();
+        assert_eq!((-7 * inverse as i128).rem_euclid(1 << 63), 1);
+        assert_eq!(mod_inverse(i64::MAX, i64::MIN), Some(i64::MAX));
+        for m in 2..200 {
+            for a in -200..200 {
+                if let Some(inverse) = mod_inverse(a, m) {
+                    assert!((0..m).contains(&inverse));
+                    assert_eq!((a * inverse).rem_euclid(m), 1, "{a} mod {m}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_missing() {
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(-4, 10), None);
+        assert_eq!(mod_inverse(0, 7), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_inverse(0, i64::MIN), None);
+        assert_eq!(mod_inverse(i64::MIN, i64::MIN), None);
+        assert_eq!(mod_inverse(6, i64::MIN), None);
+        for p in [2, 3, 5, 7] {
+            for k in 1..20 {
+                assert_eq!(mod_inverse(p * k, p * 13), None);
+            }
+        }
+    }
 }
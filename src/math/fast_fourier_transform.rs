@@ -166,6 +166,69 @@ pub fn inverse_fast_fourier_transform(
     result.iter().map(|x| x.re * scale).collect()
 }
 
+// The discrete Fourier transform of a signal x of length N is the spectrum
+// X_k = sum over n of x_n * e^(-2 pi i k n / N), for k in 0..N, whose k-th
+// entry measures the frequency of k periods over the whole signal.
+// Wikipedia reference: https://en.wikipedia.org/wiki/Discrete_Fourier_transform
+
+/// Computes the discrete Fourier transform of `signal` straight from its
+/// definition, in O(n^2). The spectrum is given as (real, imaginary) pairs.
+pub fn dft(signal: &[f64]) -> Vec<(f64, f64)> {
+    let n = signal.len();
+    (0..n)
+        .map(|k| {
+            signal
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(re, im), (t, &x)| {
+                    // k * t is reduced first, to keep the angle small and precise
+                    let angle = -std::f64::consts::TAU * ((k * t) % n) as f64 / n as f64;
+                    (re + x * angle.cos(), im + x * angle.sin())
+                })
+        })
+        .collect()
+}
+
+/// Computes the discrete Fourier transform of `signal` with the radix-2
+/// Cooley-Tukey algorithm, in O(n log n).
+///
+/// The signal is padded with zeros up to the next power of two, and the
+/// spectrum has that length.
+pub fn fft(signal: &[f64]) -> Vec<(f64, f64)> {
+    if signal.is_empty() {
+        return Vec::new();
+    }
+    let mut padded = signal.to_vec();
+    padded.resize(signal.len().next_power_of_two(), 0.0);
+    let permutation = fast_fourier_transform_input_permutation(padded.len());
+    // fast_fourier_transform uses the roots e^(2 pi i / n), which conjugates
+    // the spectrum of a real signal
+    fast_fourier_transform(&padded, &permutation)
+        .iter()
+        .map(|c| (c.re, -c.im))
+        .collect()
+}
+
+/// Computes the signal whose spectrum is `spectrum`, reverting `fft`.
+///
+/// Only the real parts are returned, since `fft` takes real signals. Panics if
+/// the length of `spectrum` is not a power of two.
+pub fn ifft(spectrum: &[(f64, f64)]) -> Vec<f64> {
+    if spectrum.is_empty() {
+        return Vec::new();
+    }
+    assert!(
+        spectrum.len().is_power_of_two(),
+        "the spectrum length must be a power of two"
+    );
+    let conjugates: Vec<Complex64> = spectrum
+        .iter()
+        .map(|&(re, im)| Complex64::new(re, -im))
+        .collect();
+    let permutation = fast_fourier_transform_input_permutation(spectrum.len());
+    inverse_fast_fourier_transform(&conjugates, &permutation)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,4 +283,100 @@ mod tests {
             assert!(almost_equal(*x, *y, EPSILON));
         }
     }
+
+    fn random_signal(seed: u64, n: usize) -> Vec<f64> {
+        let mut rng = crate::math::PCG32::new_default(seed);
+        (0..n)
+            .map(|_| rng.get_u32() as f64 / u32::MAX as f64 * 2.0 - 1.0)
+            .collect()
+    }
+
+    fn assert_spectra_eq(a: &[(f64, f64)], b: &[(f64, f64)]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!(almost_equal(x.0, y.0, EPSILON) && almost_equal(x.1, y.1, EPSILON));
+        }
+    }
+
+    #[test]
+    fn fft_matches_dft() {
+        assert_spectra_eq(
+            &fft(&[1.0, 2.0, 3.0, 4.0]),
+            &[(10.0, 0.0), (-2.0, 2.0), (-2.0, 0.0), (-2.0, -2.0)],
+        );
+        for n in [1, 2, 4, 8, 16, 64] {
+            let signal = random_signal(n as u64, n);
+            assert_spectra_eq(&fft(&signal), &dft(&signal));
+        }
+    }
+
+    #[test]
+    fn fft_pads_to_power_of_two() {
+        for n in [3, 5, 7, 12, 100] {
+            let signal = random_signal(n as u64, n);
+            let mut padded = signal.clone();
+            padded.resize(n.next_power_of_two(), 0.0);
+            let spectrum = fft(&signal);
+            assert_eq!(spectrum.len(), n.next_power_of_two());
+            assert_spectra_eq(&spectrum, &dft(&padded));
+            // the inverse gives back the signal followed by the padding
+            for (x, y) in ifft(&spectrum).iter().zip(&padded) {
+                assert!(almost_equal(*x, *y, EPSILON));
+            }
+        }
+        assert!(fft(&[]).is_empty());
+        assert!(ifft(&[]).is_empty());
+        assert!(dft(&[]).is_empty());
+    }
+
+    #[test]
+    fn ifft_reconstructs_signal() {
+        for n in [1, 2, 32, 1024] {
+            let signal = random_signal(7 * n as u64, n);
+            let restored = ifft(&fft(&signal));
+            for (x, y) in restored.iter().zip(&signal) {
+                assert!(almost_equal(*x, *y, 1e-9));
+            }
+        }
+    }
+
+    #[test]
+    fn pure_cosine_spike() {
+        // three periods over 16 samples
+        let n = 16;
+        let signal: Vec<f64> = (0..n)
+            .map(|t| (std::f64::consts::TAU * 3.0 * t as f64 / n as f64).cos())
+            .collect();
+        let spectrum = fft(&signal);
+        for (k, &(re, im)) in spectrum.iter().enumerate() {
+            // the energy is split between the frequency and its mirror
+            let expected = if k == 3 || k == n - 3 {
+                n as f64 / 2.0
+            } else {
+                0.0
+            };
+            assert!(almost_equal(re, expected, EPSILON), "{k}");
+            assert!(almost_equal(im, 0.0, EPSILON), "{k}");
+        }
+    }
+
+    #[test]
+    fn parseval_identity() {
+        for n in [8, 128, 512] {
+            let signal = random_signal(3 * n as u64, n);
+            let time_energy: f64 = signal.iter().map(|x| x * x).sum();
+            let frequency_energy: f64 = fft(&signal)
+                .iter()
+                .map(|(re, im)| re * re + im * im)
+                .sum::<f64>()
+                / n as f64;
+            assert!(almost_equal(time_energy, frequency_energy, 1e-9));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn ifft_needs_power_of_two() {
+        ifft(&[(1.0, 0.0), (0.0, 0.0), (0.0, 0.0)]);
+    }
 }
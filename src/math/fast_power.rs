@@ -13,6 +13,45 @@ pub fn fast_power(mut base: usize, mut power: usize, modulus: usize) -> usize {
     res
 }
 
+/// Returns `base^exp mod modulus`, by right-to-left binary exponentiation.
+///
+/// The bits of `exp` are read from the lowest: `base` is squared at every step,
+/// and multiplied into the result when the bit is set, so it takes
+/// `O(log exp)` multiplications. They are done in `u128`, so that any 64-bit
+/// modulus works. `0^0` is 1, reduced modulo `modulus`. Panics if `modulus` is 0.
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0, "the modulus must not be 0");
+    let modulus = modulus as u128;
+    let (mut base, mut exp) = (base as u128 % modulus, exp);
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// Returns `base^exp`, by binary exponentiation.
+///
+/// Panics if the result does not fit in an `i64`.
+pub fn pow_i64(mut base: i64, mut exp: u32) -> i64 {
+    let mut result: i64 = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.checked_mul(base).expect("pow_i64 overflowed");
+        }
+        exp >>= 1;
+        // the last square would not be used, and might overflow when the result does not
+        if exp > 0 {
+            base = base.checked_mul(base).expect("pow_i64 overflowed");
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -26,4 +65,85 @@ mod tests {
         assert_eq!(fast_power(3, 4, MOD), 81);
         assert_eq!(fast_power(2, 100, MOD), 976371285);
     }
+
+    #[test]
+    fn mod_pow_basic() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(2, 100, 1_000_000_007), 976371285);
+        assert_eq!(mod_pow(0, 0, 7), 1);
+        assert_eq!(mod_pow(0, 5, 7), 0);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+        for x in [0, 1, 2, 17, 1000, u64::MAX] {
+            for m in [2, 3, 10, 1_000_000_007, u64::MAX] {
+                assert_eq!(mod_pow(x, 0, m), 1);
+                assert_eq!(mod_pow(x, 1, m), x % m);
+            }
+        }
+    }
+
+    #[test]
+    fn mod_pow_large_modulus() {
+        // the largest prime below 2^64
+        let p = 18_446_744_073_709_551_557;
+        assert_eq!(mod_pow(3, 200, p), 13_293_435_361_704_887_469);
+        // Fermat's little theorem
+        for a in [2, 3, 123_456_789, p - 1] {
+            assert_eq!(mod_pow(a, p - 1, p), 1);
+        }
+    }
+
+    #[test]
+    fn mod_pow_matches_fast_power() {
+        for base in 1..30 {
+            for power in 0..30 {
+                assert_eq!(
+                    mod_pow(base, power, 1_000_003) as usize,
+                    fast_power(base as usize, power as usize, 1_000_003)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rsa() {
+        // the textbook example: n = 61 * 53, e = 17, d = 2753
+        assert_eq!(mod_pow(65, 17, 3233), 2790);
+        assert_eq!(mod_pow(2790, 2753, 3233), 65);
+
+        // n = 4294967291 * 4294967279, e = 65537
+        let n = 18_446_743_979_220_271_189;
+        let d = 9_331_878_932_546_167_513;
+        let message = 1_311_768_467_294_899_695;
+        let cipher = mod_pow(message, 65537, n);
+        assert_eq!(cipher, 11_625_468_769_183_520_343);
+        assert_eq!(mod_pow(cipher, d, n), message);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mod_pow_zero_modulus() {
+        mod_pow(2, 3, 0);
+    }
+
+    #[test]
+    fn signed_pow() {
+        assert_eq!(pow_i64(2, 10), 1024);
+        assert_eq!(pow_i64(-3, 3), -27);
+        assert_eq!(pow_i64(-3, 4), 81);
+        assert_eq!(pow_i64(0, 0), 1);
+        assert_eq!(pow_i64(7, 0), 1);
+        assert_eq!(pow_i64(2, 62), 1 << 62);
+        assert_eq!(pow_i64(-2, 63), i64::MIN);
+        for base in -20_i64..=20 {
+            for exp in 0..10 {
+                assert_eq!(pow_i64(base, exp), base.pow(exp));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn signed_pow_overflow() {
+        pow_i64(2, 63);
+    }
 }
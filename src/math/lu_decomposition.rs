@@ -0,0 +1,222 @@
+// LU decomposition with partial pivoting
+// Writes a square matrix A as P * A = L * U, where P permutes the rows of A,
+// L is lower triangular with ones on its diagonal and U is upper triangular.
+// Once A is decomposed in O(n^3), every system A * x = b is solved in O(n^2)
+// by forward then backward substitution.
+// Wikipedia reference: https://en.wikipedia.org/wiki/LU_decomposition
+
+use super::Matrix;
+
+/// Pivots smaller than this, relative to the largest entry of the matrix,
+/// are taken to be zero.
+const RELATIVE_EPSILON: f64 = 1e-12;
+
+/// Returns `(L, U, p)` such that row `i` of `L * U` is row `p[i]` of `a`.
+///
+/// At every step the remaining row with the largest entry in the current
+/// column is swapped up to become the pivot row, which keeps the multipliers
+/// stored in `L` at most 1 in absolute value. Returns `None` if a pivot is
+/// smaller than `1e-12` times the largest entry of `a` in absolute value,
+/// that is when `a` is singular or too close to singular for the solutions to
+/// be meaningful. Panics if `a` is not square.
+pub fn lu_decompose(a: &Matrix<f64>) -> Option<(Matrix<f64>, Matrix<f64>, Vec<usize>)> {
+    let n = a.rows();
+    assert_eq!(n, a.cols(), "only square matrices can be decomposed");
+    let mut u = Matrix::zero(n, n);
+    let mut largest: f64 = 0.0;
+    for i in 0..n {
+        for j in 0..n {
+            u[[i, j]] = a[[i, j]];
+            largest = largest.max(a[[i, j]].abs());
+        }
+    }
+    let tolerance = RELATIVE_EPSILON * largest;
+    let mut l = Matrix::identity(n);
+    let mut permutation: Vec<usize> = (0..n).collect();
+
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| u[[i, k]].abs().total_cmp(&u[[j, k]].abs()))
+            .unwrap();
+        if u[[pivot, k]].abs() <= tolerance {
+            return None;
+        }
+        if pivot != k {
+            permutation.swap(k, pivot);
+            for j in 0..n {
+                swap(&mut u, [k, j], [pivot, j]);
+            }
+            // the multipliers found so far follow their rows
+            for j in 0..k {
+                swap(&mut l, [k, j], [pivot, j]);
+            }
+        }
+        for i in k + 1..n {
+            let factor = u[[i, k]] / u[[k, k]];
+            l[[i, k]] = factor;
+            u[[i, k]] = 0.0;
+            for j in k + 1..n {
+                u[[i, j]] -= factor * u[[k, j]];
+            }
+        }
+    }
+    Some((l, u, permutation))
+}
+
+fn swap(m: &mut Matrix<f64>, a: [usize; 2], b: [usize; 2]) {
+    let temp = m[a];
+    m[a] = m[b];
+    m[b] = temp;
+}
+
+/// Solves `A * x = b`, given the decomposition `(l, u, p)` of `A` returned by
+/// `lu_decompose`.
+///
+/// Since `L * U * x = P * b`, it first solves `L * y = P * b` from the top row
+/// down, then `U * x = y` from the bottom row up. Panics if `b` does not have
+/// one entry per row.
+pub fn lu_solve(l: &Matrix<f64>, u: &Matrix<f64>, p: &[usize], b: &[f64]) -> Vec<f64> {
+    let n = l.rows();
+    assert_eq!(b.len(), n, "the right-hand side needs one entry per row");
+
+    let mut y = vec![0.0; n];
+    for i in 0..n {
+        let known: f64 = (0..i).map(|j| l[[i, j]] * y[j]).sum();
+        y[i] = b[p[i]] - known;
+    }
+
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let known: f64 = (i + 1..n).map(|j| u[[i, j]] * x[j]).sum();
+        x[i] = (y[i] - known) / u[[i, i]];
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    fn multiply(a: &Matrix<f64>, x: &[f64]) -> Vec<f64> {
+        (0..a.rows())
+            .map(|i| (0..a.cols()).map(|j| a[[i, j]] * x[j]).sum())
+            .collect()
+    }
+
+    fn assert_close(actual: &[f64], expected: &[f64], tolerance: f64) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < tolerance, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn known_system() {
+        // 2x + y - z = 8, -3x - y + 2z = -11, -2x + y + 2z = -3
+        let a = Matrix::from(vec![
+            vec![2.0, 1.0, -1.0],
+            vec![-3.0, -1.0, 2.0],
+            vec![-2.0, 1.0, 2.0],
+        ]);
+        let (l, u, p) = lu_decompose(&a).unwrap();
+        assert_close(
+            &lu_solve(&l, &u, &p, &[8.0, -11.0, -3.0]),
+            &[2.0, 3.0, -1.0],
+            1e-12,
+        );
+        // the largest entry of the first column, -3, is the first pivot
+        assert_eq!(p[0], 1);
+    }
+
+    #[test]
+    fn needs_pivoting() {
+        // the leading zero would stop elimination without row swaps
+        let a = Matrix::from(vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+        let (l, u, p) = lu_decompose(&a).unwrap();
+        assert_eq!(p, vec![1, 0]);
+        assert_eq!(l, Matrix::identity(2));
+        assert_eq!(u, Matrix::identity(2));
+        assert_close(&lu_solve(&l, &u, &p, &[3.0, 4.0]), &[4.0, 3.0], 1e-12);
+    }
+
+    #[test]
+    fn factors_reproduce_the_matrix() {
+        let mut rng = PCG32::new_default(39);
+        for n in 1..=12 {
+            let data = (0..n * n)
+                .map(|_| rng.get_u32() as f64 / u32::MAX as f64 * 10.0 - 5.0)
+                .collect();
+            let a = Matrix::new(data, n, n);
+            let (l, u, p) = lu_decompose(&a).unwrap();
+            for i in 0..n {
+                assert_eq!(l[[i, i]], 1.0);
+                for j in i + 1..n {
+                    assert_eq!(l[[i, j]], 0.0);
+                    assert_eq!(u[[j, i]], 0.0);
+                }
+                for j in 0..i {
+                    assert!(l[[i, j]].abs() <= 1.0);
+                }
+            }
+            let mut sorted = p.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..n).collect::<Vec<_>>());
+
+            let lu = &l * &u;
+            for i in 0..n {
+                for j in 0..n {
+                    assert!((lu[[i, j]] - a[[p[i], j]]).abs() < 1e-9);
+                }
+            }
+
+            let x: Vec<f64> = (0..n).map(|i| i as f64 - 3.5).collect();
+            let b = multiply(&a, &x);
+            assert_close(&lu_solve(&l, &u, &p, &b), &x, 1e-6);
+        }
+    }
+
+    #[test]
+    fn singular() {
+        let a = Matrix::from(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ]);
+        assert!(lu_decompose(&a).is_none());
+        assert!(lu_decompose(&Matrix::zero(2, 2)).is_none());
+    }
+
+    #[test]
+    fn near_singular() {
+        // rows equal up to rounding are rejected
+        let a = Matrix::from(vec![vec![1.0, 1.0], vec![1.0, 1.0 + 1e-15]]);
+        assert!(lu_decompose(&a).is_none());
+
+        // ill-conditioned but clearly invertible: the residual stays small
+        let a = Matrix::from(vec![vec![1.0, 1.0], vec![1.0, 1.0 + 1e-8]]);
+        let (l, u, p) = lu_decompose(&a).unwrap();
+        let b = [2.0, 2.0 + 1e-8];
+        let x = lu_solve(&l, &u, &p, &b);
+        assert_close(&multiply(&a, &x), &b, 1e-12);
+        assert_close(&x, &[1.0, 1.0], 1e-6);
+
+        // the threshold is relative, so scaling the matrix does not matter
+        let tiny = Matrix::from(vec![vec![1e-20, 2e-20], vec![3e-20, 4e-20]]);
+        let (l, u, p) = lu_decompose(&tiny).unwrap();
+        assert_close(&lu_solve(&l, &u, &p, &[5e-20, 11e-20]), &[1.0, 2.0], 1e-9);
+    }
+
+    #[test]
+    fn empty() {
+        let (l, u, p) = lu_decompose(&Matrix::zero(0, 0)).unwrap();
+        assert!(p.is_empty());
+        assert!(lu_solve(&l, &u, &p, &[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_square() {
+        lu_decompose(&Matrix::zero(2, 3));
+    }
+}
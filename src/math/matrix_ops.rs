@@ -17,6 +17,8 @@ macro_rules! matrix {
 pub trait MatrixElement:
     Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + AddAssign + Copy + From<u8>
 {
+    // Whether the type has negative values, so that any two elements can be subtracted
+    const SIGNED: bool;
 }
 
 // Define a macro to implement the MatrixElement trait for desired types
@@ -24,7 +26,9 @@ pub trait MatrixElement:
 macro_rules! matrix_element_type_def {
     ($T: ty) => {
         // Implement trait for type
-        impl MatrixElement for $T {}
+        impl MatrixElement for $T {
+            const SIGNED: bool = <$T>::MIN < 0 as $T;
+        }
 
         // Defining left-hand multiplication in this form
         // prevents errors for uncovered types
@@ -81,6 +85,14 @@ impl<T: MatrixElement> Matrix<T> {
         identity
     }
 
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
     pub fn transpose(&self) -> Self {
         // Transpose a matrix of any size
         let mut result = Matrix::zero(self.cols, self.rows);
@@ -216,11 +228,125 @@ impl<T: MatrixElement> From<Vec<Vec<T>>> for Matrix<T> {
     }
 }
 
+// Strassen's algorithm multiplies two n x n matrices split into 2 x 2 blocks
+// with 7 block products instead of 8, for O(n^log2(7)) = O(n^2.807) operations.
+// Since it does more additions, small blocks are multiplied the standard way.
+// Wikipedia reference: https://en.wikipedia.org/wiki/Strassen_algorithm
+
+/// The block size from which `strassen` switches to standard multiplication.
+pub const DEFAULT_STRASSEN_THRESHOLD: usize = 64;
+
+/// Multiplies two matrices with Strassen's algorithm, using standard
+/// multiplication for blocks of at most `DEFAULT_STRASSEN_THRESHOLD` rows.
+pub fn strassen<T: MatrixElement>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    strassen_with_threshold(a, b, DEFAULT_STRASSEN_THRESHOLD)
+}
+
+/// Multiplies two matrices with Strassen's algorithm, using standard
+/// multiplication for blocks of at most `threshold` rows (at least 1).
+///
+/// Matrices of any shape are padded with zeros to an n x n square, where n is
+/// the smallest size that can be halved until the blocks fit the threshold.
+///
+/// Strassen's algorithm subtracts blocks, which overflows for unsigned
+/// elements even when the product fits, so those are multiplied the standard
+/// way.
+pub fn strassen_with_threshold<T: MatrixElement>(
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    threshold: usize,
+) -> Matrix<T> {
+    if a.cols != b.rows {
+        panic!("Matrix dimensions do not match");
+    }
+    let threshold = threshold.max(1);
+    let largest = a.rows.max(a.cols).max(b.cols);
+    if largest <= threshold || !T::SIGNED {
+        return a * b;
+    }
+    let mut halvings = 0;
+    while largest.div_ceil(1 << halvings) > threshold {
+        halvings += 1;
+    }
+    let size = largest.div_ceil(1 << halvings) << halvings;
+    let product = strassen_square(&a.padded(size), &b.padded(size), threshold);
+    product.block(0, 0, a.rows, b.cols)
+}
+
+fn strassen_square<T: MatrixElement>(a: &Matrix<T>, b: &Matrix<T>, threshold: usize) -> Matrix<T> {
+    let n = a.rows;
+    if n <= threshold {
+        return a * b;
+    }
+    let h = n / 2;
+    let (a11, a12, a21, a22) = (
+        a.block(0, 0, h, h),
+        a.block(0, h, h, h),
+        a.block(h, 0, h, h),
+        a.block(h, h, h, h),
+    );
+    let (b11, b12, b21, b22) = (
+        b.block(0, 0, h, h),
+        b.block(0, h, h, h),
+        b.block(h, 0, h, h),
+        b.block(h, h, h, h),
+    );
+
+    let m1 = strassen_square(&(&a11 + &a22), &(&b11 + &b22), threshold);
+    let m2 = strassen_square(&(&a21 + &a22), &b11, threshold);
+    let m3 = strassen_square(&a11, &(&b12 - &b22), threshold);
+    let m4 = strassen_square(&a22, &(&b21 - &b11), threshold);
+    let m5 = strassen_square(&(&a11 + &a12), &b22, threshold);
+    let m6 = strassen_square(&(&a21 - &a11), &(&b11 + &b12), threshold);
+    let m7 = strassen_square(&(&a12 - &a22), &(&b21 + &b22), threshold);
+
+    let c11 = &(&(&m1 + &m4) - &m5) + &m7;
+    let c12 = &m3 + &m5;
+    let c21 = &m2 + &m4;
+    let c22 = &(&(&m1 - &m2) + &m3) + &m6;
+
+    let mut result = Matrix::zero(n, n);
+    for i in 0..h {
+        for j in 0..h {
+            result[[i, j]] = c11[[i, j]];
+            result[[i, j + h]] = c12[[i, j]];
+            result[[i + h, j]] = c21[[i, j]];
+            result[[i + h, j + h]] = c22[[i, j]];
+        }
+    }
+    result
+}
+
+impl<T: MatrixElement> Matrix<T> {
+    /// Copies the `rows x cols` block whose top left element is at `[row, col]`.
+    fn block(&self, row: usize, col: usize, rows: usize, cols: usize) -> Self {
+        let data = (row..row + rows)
+            .flat_map(|i| {
+                self.data[i * self.cols + col..i * self.cols + col + cols]
+                    .iter()
+                    .copied()
+            })
+            .collect();
+        Matrix::new(data, rows, cols)
+    }
+
+    /// Copies the matrix into the top left corner of a `size x size` matrix of zeros.
+    fn padded(&self, size: usize) -> Self {
+        let mut result = Matrix::zero(size, size);
+        for i in 0..self.rows {
+            result.data[i * size..i * size + self.cols]
+                .copy_from_slice(&self.data[i * self.cols..(i + 1) * self.cols]);
+        }
+        result
+    }
+}
+
 #[cfg(test)]
 // rustfmt skipped to prevent unformatting matrix definitions to a single line
 #[rustfmt::skip] 
 mod tests {
-    use super::Matrix;
+    use super::{strassen, strassen_with_threshold, Matrix};
+    use crate::math::PCG32;
     use std::panic;
 
     const DELTA: f64 = 1e-3;
@@ -566,4 +692,82 @@ mod tests {
 
         assert_f64_eq!(scalar * &a, scalar_mul);
     }
+
+    fn random_matrix(rng: &mut PCG32, rows: usize, cols: usize) -> Matrix<f64> {
+        let data = (0..rows * cols)
+            .map(|_| rng.get_u32() as f64 / u32::MAX as f64 * 2.0 - 1.0)
+            .collect();
+        Matrix::new(data, rows, cols)
+    }
+
+    fn max_difference(a: &Matrix<f64>, b: &Matrix<f64>) -> f64 {
+        assert_eq!((a.rows, a.cols), (b.rows, b.cols));
+        a.data.iter().zip(&b.data).map(|(x, y)| (x - y).abs()).fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_strassen_matches_standard() {
+        let mut rng = PCG32::new_default(38);
+        for n in [1, 2, 3, 64, 65, 128, 200] {
+            let a = random_matrix(&mut rng, n, n);
+            let b = random_matrix(&mut rng, n, n);
+            let expected = &a * &b;
+            let tolerance = 1e-9 * n as f64;
+            assert!(max_difference(&strassen(&a, &b), &expected) < tolerance, "{n}");
+            let deep = strassen_with_threshold(&a, &b, 8);
+            assert!(max_difference(&deep, &expected) < tolerance, "{n}");
+        }
+    }
+
+    #[test]
+    fn test_strassen_small_threshold() {
+        let a = matrix![
+            [1, 2, 3],
+            [4, 2, 6],
+            [3, 4, 1],
+            [2, 4, 8],
+        ];
+        let b = matrix![
+            [1, 3, 3, 2],
+            [7, 6, 2, 1],
+            [3, 4, 2, 1],
+        ];
+        for threshold in 0..5 {
+            assert_eq!(strassen_with_threshold(&a, &b, threshold), &a * &b);
+        }
+
+        let mut rng = PCG32::new_default(83);
+        for (rows, inner, cols) in [(5, 9, 3), (17, 1, 6), (1, 33, 1), (20, 20, 20)] {
+            let a = random_matrix(&mut rng, rows, inner);
+            let b = random_matrix(&mut rng, inner, cols);
+            assert!(max_difference(&strassen_with_threshold(&a, &b, 2), &(&a * &b)) < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_strassen_unsigned() {
+        // b12 - b22 and a21 - a11 would be negative
+        let a: Matrix<u32> = matrix![
+            [1, 0, 0, 0],
+            [0, 1, 0, 0],
+            [5, 0, 1, 0],
+            [0, 5, 0, 1],
+        ];
+        let b: Matrix<u32> = matrix![
+            [1, 0, 0, 0],
+            [0, 1, 0, 0],
+            [0, 0, 3, 0],
+            [0, 0, 0, 3],
+        ];
+        assert_eq!(strassen_with_threshold(&a, &b, 1), &a * &b);
+        assert_eq!(strassen(&b, &a), &b * &a);
+    }
+
+    #[test]
+    fn test_strassen_invalid_dimensions() {
+        let a: Matrix<i32> = Matrix::identity(3);
+        let b: Matrix<i32> = Matrix::zero(2, 3);
+        let result = panic::catch_unwind(|| strassen(&a, &b));
+        assert!(result.is_err());
+    }
 }
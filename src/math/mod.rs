@@ -6,7 +6,9 @@ mod baby_step_giant_step;
 mod ceil;
 mod chinese_remainder_theorem;
 mod collatz_sequence;
+mod cycle_detection;
 mod doomsday;
+mod euler_totient;
 mod extended_euclidean_algorithm;
 mod factors;
 mod fast_fourier_transform;
@@ -19,20 +21,22 @@ mod interest;
 mod karatsuba_multiplication;
 mod lcm_of_n_numbers;
 mod linear_sieve;
+mod lu_decomposition;
 mod matrix_ops;
 mod mersenne_primes;
-mod miller_rabin;
-mod newton_raphson;
 mod nthprime;
 mod pascal_triangle;
 mod perfect_numbers;
 mod pollard_rho;
+mod primality;
 mod prime_check;
 mod prime_factors;
 mod prime_numbers;
+mod prime_sieve;
 mod quadratic_residue;
 mod random;
-mod sieve_of_eratosthenes;
+mod reservoir_sampling;
+mod root_finding;
 mod signum;
 mod simpson_integration;
 mod sine;
@@ -46,16 +50,20 @@ pub use self::amicable_numbers::amicable_pairs_under_n;
 pub use self::armstrong_number::is_armstrong_number;
 pub use self::baby_step_giant_step::baby_step_giant_step;
 pub use self::ceil::ceil;
-pub use self::chinese_remainder_theorem::chinese_remainder_theorem;
+pub use self::chinese_remainder_theorem::{chinese_remainder_theorem, crt};
 pub use self::collatz_sequence::sequence;
+pub use self::cycle_detection::{detect_linked_list_cycle, floyd_cycle_detection};
 pub use self::doomsday::get_week_day;
-pub use self::extended_euclidean_algorithm::extended_euclidean_algorithm;
+pub use self::euler_totient::{euler_totient, euler_totient_sieve};
+pub use self::extended_euclidean_algorithm::{
+    extended_euclidean_algorithm, extended_gcd, mod_inverse,
+};
 pub use self::factors::factors;
 pub use self::fast_fourier_transform::{
-    fast_fourier_transform, fast_fourier_transform_input_permutation,
+    dft, fast_fourier_transform, fast_fourier_transform_input_permutation, fft, ifft,
     inverse_fast_fourier_transform,
 };
-pub use self::fast_power::fast_power;
+pub use self::fast_power::{fast_power, mod_pow, pow_i64};
 pub use self::faster_perfect_numbers::generate_perfect_numbers;
 pub use self::gaussian_elimination::gaussian_elimination;
 pub use self::gcd_of_n_numbers::gcd;
@@ -67,20 +75,22 @@ pub use self::interest::{compound_interest, simple_interest};
 pub use self::karatsuba_multiplication::multiply;
 pub use self::lcm_of_n_numbers::lcm;
 pub use self::linear_sieve::LinearSieve;
-pub use self::matrix_ops::Matrix;
+pub use self::lu_decomposition::{lu_decompose, lu_solve};
+pub use self::matrix_ops::{strassen, strassen_with_threshold, Matrix, DEFAULT_STRASSEN_THRESHOLD};
 pub use self::mersenne_primes::{get_mersenne_primes, is_mersenne_prime};
-pub use self::miller_rabin::miller_rabin;
-pub use self::newton_raphson::find_root;
 pub use self::nthprime::nthprime;
 pub use self::pascal_triangle::pascal_triangle;
 pub use self::perfect_numbers::perfect_numbers;
 pub use self::pollard_rho::{pollard_rho_factorize, pollard_rho_get_one_factor};
+pub use self::primality::{miller_rabin, miller_rabin_witness};
 pub use self::prime_check::prime_check;
 pub use self::prime_factors::prime_factors;
 pub use self::prime_numbers::prime_numbers;
+pub use self::prime_sieve::{primes_up_to, segmented_sieve, sieve_of_eratosthenes, PrimeIter};
 pub use self::quadratic_residue::cipolla;
 pub use self::random::PCG32;
-pub use self::sieve_of_eratosthenes::sieve_of_eratosthenes;
+pub use self::reservoir_sampling::reservoir_sample;
+pub use self::root_finding::{bisection, find_root, newton_raphson};
 pub use self::signum::signum;
 pub use self::simpson_integration::simpson_integration;
 pub use self::sine::sine;
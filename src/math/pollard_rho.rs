@@ -1,4 +1,4 @@
-use super::miller_rabin;
+use super::miller_rabin_witness;
 
 struct LinearCongruenceGenerator {
     // modulus as 2 ^ 32
@@ -112,7 +112,7 @@ pub fn pollard_rho_get_one_factor(number: u64, seed: &mut u32, check_is_prime: b
         if number > 3_215_031_000 {
             bases.append(&mut vec![11, 13, 17, 19, 23, 29, 31, 37]);
         }
-        if miller_rabin(number, &bases) == 0 {
+        if miller_rabin_witness(number, &bases) == 0 {
             return number;
         }
     }
@@ -211,7 +211,7 @@ mod test {
         let mut prime_check = 0_u64;
         for p in factors {
             prod *= *p;
-            prime_check |= miller_rabin(*p, &bases);
+            prime_check |= miller_rabin_witness(*p, &bases);
         }
         prime_check == 0 && prod == number
     }
@@ -0,0 +1,173 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{mod_pow, PCG32};
+
+fn check_prime_base(number: u64, base: u64, two_power: u64, odd_power: u64) -> bool {
+    // returns false if base is a witness
+    let mut x: u128 = mod_pow(base, odd_power, number) as u128;
+    let bnumber: u128 = number as u128;
+    if x == 1 || x == (bnumber - 1) {
+        return true;
+    }
+    for _ in 1..two_power {
+        x = (x * x) % bnumber;
+        if x == (bnumber - 1) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Runs the Miller-Rabin test of `number` for each of `bases`, returning 0 if
+/// `number` passes all of them, and otherwise the first base that witnesses
+/// that `number` is composite.
+pub fn miller_rabin_witness(number: u64, bases: &[u64]) -> u64 {
+    // returns zero on a probable prime, and a witness if number is not prime
+    // A base set for deterministic performance on 64 bit numbers is:
+    // [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37]
+    // another one for 32 bits:
+    // [2, 3, 5, 7], with smallest number to fail 3'215'031'751 = 151 * 751 * 28351
+    // note that all bases should be prime
+    if number <= 4 {
+        match number {
+            0 => {
+                panic!("0 is invalid input for Miller-Rabin. 0 is not prime by definition, but has no witness");
+            }
+            2 => return 0,
+            3 => return 0,
+            _ => return number,
+        }
+    }
+    if bases.contains(&number) {
+        return 0;
+    }
+    let two_power: u64 = (number - 1).trailing_zeros() as u64;
+    let odd_power = (number - 1) >> two_power;
+    for base in bases {
+        if !check_prime_base(number, *base, two_power, odd_power) {
+            return *base;
+        }
+    }
+    0
+}
+
+// Jaeschke showed that the smallest odd composite number passing the test for
+// all of the bases 2, 3, 5 and 7 (a strong pseudoprime to these bases) is
+// 3'215'031'751 = 151 * 751 * 28351, by checking every candidate below it. So
+// for smaller numbers these four bases give an exact answer.
+// Source: G. Jaeschke, "On strong pseudoprimes to several bases", Math. Comp. 61 (1993)
+const DETERMINISTIC_LIMIT: u64 = 3_215_031_751;
+const DETERMINISTIC_BASES: [u64; 4] = [2, 3, 5, 7];
+
+/// Tells whether `n` is prime with the Miller-Rabin test.
+///
+/// Below 3'215'031'751 the answer is exact, using the bases {2, 3, 5, 7}.
+/// Above, `rounds` random bases are tried: a prime always passes, while a
+/// composite number passes each round with probability at most 1/4, so it is
+/// wrongly reported as prime with probability at most `4^-rounds`.
+pub fn miller_rabin(n: u64, rounds: u32) -> bool {
+    match n {
+        0 | 1 => return false,
+        2 | 3 => return true,
+        _ if n.is_multiple_of(2) => return false,
+        _ if n < DETERMINISTIC_LIMIT => return miller_rabin_witness(n, &DETERMINISTIC_BASES) == 0,
+        _ => {}
+    }
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64;
+    let mut rng = PCG32::new_default(seed ^ n);
+    let two_power = (n - 1).trailing_zeros() as u64;
+    let odd_power = (n - 1) >> two_power;
+    (0..rounds).all(|_| {
+        let base = 2 + rng.get_u64() % (n - 3);
+        check_prime_base(n, base, two_power, odd_power)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        let default_bases = vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+        // these bases make miller rabin deterministic for any number < 2 ^ 64
+        // can use smaller number of bases for deterministic performance for numbers < 2 ^ 32
+
+        assert_eq!(miller_rabin_witness(3, &default_bases), 0);
+        assert_eq!(miller_rabin_witness(7, &default_bases), 0);
+        assert_eq!(miller_rabin_witness(11, &default_bases), 0);
+        assert_eq!(miller_rabin_witness(2003, &default_bases), 0);
+
+        assert_ne!(miller_rabin_witness(1, &default_bases), 0);
+        assert_ne!(miller_rabin_witness(4, &default_bases), 0);
+        assert_ne!(miller_rabin_witness(6, &default_bases), 0);
+        assert_ne!(miller_rabin_witness(21, &default_bases), 0);
+        assert_ne!(miller_rabin_witness(2004, &default_bases), 0);
+
+        // bigger test cases.
+        // primes are generated using openssl
+        // non primes are randomly picked and checked using openssl
+
+        // primes:
+        assert_eq!(miller_rabin_witness(3629611793, &default_bases), 0);
+        assert_eq!(miller_rabin_witness(871594686869, &default_bases), 0);
+        assert_eq!(miller_rabin_witness(968236663804121, &default_bases), 0);
+        assert_eq!(miller_rabin_witness(6920153791723773023, &default_bases), 0);
+
+        // random non primes:
+        assert_ne!(miller_rabin_witness(4546167556336341257, &default_bases), 0);
+        assert_ne!(miller_rabin_witness(4363186415423517377, &default_bases), 0);
+        assert_ne!(miller_rabin_witness(815479701131020226, &default_bases), 0);
+        // these two are made of two 31 bit prime factors:
+        // 1950202127 * 2058609037 = 4014703722618821699
+        assert_ne!(miller_rabin_witness(4014703722618821699, &default_bases), 0);
+        // 1679076769 * 2076341633 = 3486337000477823777
+        assert_ne!(miller_rabin_witness(3486337000477823777, &default_bases), 0);
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert!(!miller_rabin(0, 10));
+        assert!(!miller_rabin(1, 10));
+        assert!(miller_rabin(2, 10));
+        assert!(miller_rabin(3, 10));
+        assert!(!miller_rabin(4, 10));
+        assert!(!miller_rabin(1 << 40, 10));
+    }
+
+    #[test]
+    fn primes_up_to_ten_thousand() {
+        let table = crate::math::sieve_of_eratosthenes(10_000);
+        for (n, &is_prime) in table.iter().enumerate() {
+            assert_eq!(miller_rabin(n as u64, 10), is_prime, "{n}");
+        }
+    }
+
+    #[test]
+    fn carmichael_numbers() {
+        for n in [561, 1105, 1729, 2465, 2821, 6601, 8911, 41041, 825265] {
+            assert!(!miller_rabin(n, 10), "{n}");
+        }
+    }
+
+    #[test]
+    fn strong_pseudoprimes() {
+        // 2047 = 23 * 89 fools base 2 alone, and 3'215'031'751 all bases up to 7
+        assert!(!miller_rabin(2047, 10));
+        assert_eq!(miller_rabin_witness(3_215_031_751, &[2, 3, 5, 7]), 0);
+        assert!(!miller_rabin(3_215_031_751, 20));
+    }
+
+    #[test]
+    fn large_primes() {
+        assert!(miller_rabin((1 << 61) - 1, 20));
+        assert!(miller_rabin((1 << 31) - 1, 20));
+        assert!(miller_rabin(3_215_031_767, 20));
+        assert!(miller_rabin(18_446_744_073_709_551_557, 20));
+        assert!(!miller_rabin(((1 << 31) - 1) * ((1 << 31) - 1), 20));
+        assert!(!miller_rabin(4_014_703_722_618_821_699, 20));
+    }
+}
@@ -0,0 +1,291 @@
+// The Sieve of Eratosthenes finds the primes up to a limit by crossing out the
+// multiples of every prime, starting from its square, since smaller multiples
+// have a smaller prime factor and are already crossed out. It takes
+// O(n log log n) time. Source: https://en.wikipedia.org/wiki/Sieve_of_Eratosthenes
+
+use std::collections::HashMap;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A sieve storing one bit per number, set for the composite ones (and 0 and 1).
+struct BitSieve {
+    limit: usize,
+    words: Vec<u64>,
+}
+
+impl BitSieve {
+    fn new(limit: usize) -> Self {
+        let mut sieve = BitSieve {
+            limit,
+            words: vec![0; limit / WORD_BITS + 1],
+        };
+        sieve.cross_out(0);
+        sieve.cross_out(1);
+        for p in 2..=limit.isqrt() {
+            if !sieve.is_composite(p) {
+                for multiple in (p * p..=limit).step_by(p) {
+                    sieve.cross_out(multiple);
+                }
+            }
+        }
+        sieve
+    }
+
+    fn cross_out(&mut self, n: usize) {
+        self.words[n / WORD_BITS] |= 1 << (n % WORD_BITS);
+    }
+
+    fn is_composite(&self, n: usize) -> bool {
+        (self.words[n / WORD_BITS] >> (n % WORD_BITS)) & 1 == 1
+    }
+
+    /// Returns the primes in increasing order, finding the clear bits of every
+    /// word with `trailing_zeros` instead of testing the bits one by one.
+    fn primes(&self) -> Vec<usize> {
+        let mut primes = Vec::new();
+        for (index, &word) in self.words.iter().enumerate() {
+            let mut remaining = !word;
+            while remaining != 0 {
+                let n = index * WORD_BITS + remaining.trailing_zeros() as usize;
+                if n > self.limit {
+                    break;
+                }
+                primes.push(n);
+                // clears the lowest set bit
+                remaining &= remaining - 1;
+            }
+        }
+        primes
+    }
+}
+
+/// Returns a table of length `limit + 1` where the entry at `n` tells whether
+/// `n` is prime.
+pub fn sieve_of_eratosthenes(limit: usize) -> Vec<bool> {
+    let sieve = BitSieve::new(limit);
+    (0..=limit).map(|n| !sieve.is_composite(n)).collect()
+}
+
+/// Returns the primes smaller than or equal to `limit`, in increasing order.
+///
+/// The sieve packs 64 numbers in every `u64` word, so it only needs about
+/// `limit / 8` bytes.
+pub fn primes_up_to(limit: usize) -> Vec<usize> {
+    BitSieve::new(limit).primes()
+}
+
+/// Returns the primes in the range `low..=high`, in increasing order.
+///
+/// The sieve goes through the range in segments of `sqrt(high)` numbers,
+/// crossing out in each segment the multiples of the primes up to `sqrt(high)`.
+/// Only one segment and those primes are kept at any time, so it needs
+/// `O(sqrt(high))` memory however large the range is.
+pub fn segmented_sieve(low: u64, high: u64) -> Vec<u64> {
+    let low = low.max(2);
+    if low > high {
+        return Vec::new();
+    }
+    let root = high.isqrt();
+    let base_primes = primes_up_to(root as usize);
+    let segment_size = root.max(1);
+
+    let mut primes = Vec::new();
+    let mut start = low;
+    loop {
+        let end = high.min(start.saturating_add(segment_size - 1));
+        // is_prime[i] is about start + i
+        let mut is_prime = vec![true; (end - start + 1) as usize];
+        for &p in &base_primes {
+            let p = p as u64;
+            // the first multiple of p in the segment, larger than p itself
+            let first = (p * p).max(start.div_ceil(p).saturating_mul(p));
+            for multiple in (first..=end).step_by(p as usize) {
+                is_prime[(multiple - start) as usize] = false;
+            }
+        }
+        primes.extend((start..=end).filter(|n| is_prime[(n - start) as usize]));
+        if end == high {
+            return primes;
+        }
+        start = end + 1;
+    }
+}
+
+/// An endless iterator over the primes, in increasing order.
+///
+/// It is an incremental sieve: instead of a table with a fixed limit, it
+/// remembers for every prime `p` found so far the next odd multiple of `p` it
+/// will come across, so the memory grows with the number of primes below the
+/// square root of the current candidate.
+pub struct PrimeIter {
+    candidate: usize,
+    // the next odd composite to come, mapped to twice the prime it is a multiple of
+    composites: HashMap<usize, usize>,
+}
+
+impl PrimeIter {
+    pub fn new() -> Self {
+        PrimeIter {
+            candidate: 2,
+            composites: HashMap::new(),
+        }
+    }
+}
+
+impl Default for PrimeIter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for PrimeIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.candidate == 2 {
+            self.candidate = 3;
+            return Some(2);
+        }
+        loop {
+            let n = self.candidate;
+            self.candidate += 2;
+            match self.composites.remove(&n) {
+                Some(step) => {
+                    // moves the prime along to its next multiple nobody else claimed
+                    let mut next = n + step;
+                    while self.composites.contains_key(&next) {
+                        next += step;
+                    }
+                    self.composites.insert(next, step);
+                }
+                None => {
+                    if let Some(square) = n.checked_mul(n) {
+                        self.composites.insert(square, 2 * n);
+                    }
+                    return Some(n);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        assert_eq!(primes_up_to(0), vec![]);
+        assert_eq!(primes_up_to(11), vec![2, 3, 5, 7, 11]);
+        assert_eq!(primes_up_to(25), vec![2, 3, 5, 7, 11, 13, 17, 19, 23]);
+        assert_eq!(
+            primes_up_to(33),
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31]
+        );
+        assert_eq!(
+            primes_up_to(100),
+            vec![
+                2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79,
+                83, 89, 97
+            ]
+        );
+    }
+
+    #[test]
+    fn small_limits() {
+        assert_eq!(primes_up_to(1), vec![]);
+        assert_eq!(primes_up_to(2), vec![2]);
+        assert_eq!(sieve_of_eratosthenes(0), vec![false]);
+        assert_eq!(
+            sieve_of_eratosthenes(7),
+            vec![false, false, true, true, false, true, false, true]
+        );
+        // limits on both sides of a word boundary
+        assert_eq!(primes_up_to(63).last(), Some(&61));
+        assert_eq!(primes_up_to(64).last(), Some(&61));
+        assert_eq!(primes_up_to(67).last(), Some(&67));
+    }
+
+    #[test]
+    fn count_up_to_a_million() {
+        let primes = primes_up_to(1_000_000);
+        assert_eq!(primes.len(), 78_498);
+        assert_eq!(primes.last(), Some(&999_983));
+
+        let table = sieve_of_eratosthenes(1_000_000);
+        assert_eq!(table.iter().filter(|&&is_prime| is_prime).count(), 78_498);
+        assert!(primes.iter().all(|&p| table[p]));
+    }
+
+    #[test]
+    fn matches_trial_division() {
+        let table = sieve_of_eratosthenes(2000);
+        for (n, &is_prime) in table.iter().enumerate() {
+            let expected = n >= 2
+                && (2..n)
+                    .take_while(|d| d * d <= n)
+                    .all(|d| !n.is_multiple_of(d));
+            assert_eq!(is_prime, expected, "{n}");
+        }
+    }
+
+    #[test]
+    fn iterator() {
+        let first: Vec<usize> = PrimeIter::new().take(10).collect();
+        assert_eq!(first, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+
+        let lazy: Vec<usize> = PrimeIter::default()
+            .take_while(|&p| p <= 1_000_000)
+            .collect();
+        assert_eq!(lazy, primes_up_to(1_000_000));
+        assert_eq!(PrimeIter::new().nth(9_999), Some(104_729));
+    }
+
+    #[test]
+    fn uses_an_eighth_of_a_bool_table() {
+        let limit = 1_000_000;
+        let bits = BitSieve::new(limit).words.len() * std::mem::size_of::<u64>();
+        let bools = (limit + 1) * std::mem::size_of::<bool>();
+        assert!(bits * 8 <= bools + WORD_BITS);
+    }
+
+    #[test]
+    fn segmented_matches_simple_sieve() {
+        let primes: Vec<u64> = primes_up_to(300).into_iter().map(|p| p as u64).collect();
+        for low in 0..=300 {
+            for high in low..=300 {
+                let expected: Vec<u64> = primes
+                    .iter()
+                    .copied()
+                    .filter(|p| (low..=high).contains(p))
+                    .collect();
+                assert_eq!(segmented_sieve(low, high), expected, "[{low}, {high}]");
+            }
+        }
+    }
+
+    #[test]
+    fn segmented_degenerate_ranges() {
+        assert_eq!(segmented_sieve(2, 2), vec![2]);
+        assert_eq!(segmented_sieve(97, 97), vec![97]);
+        assert_eq!(segmented_sieve(91, 91), vec![]);
+        assert_eq!(segmented_sieve(0, 1), vec![]);
+        assert_eq!(segmented_sieve(10, 5), vec![]);
+        // entirely above sqrt(high)
+        assert_eq!(
+            segmented_sieve(100, 130),
+            vec![101, 103, 107, 109, 113, 127]
+        );
+    }
+
+    #[test]
+    fn segmented_above_a_billion() {
+        let low = 1_000_000_000;
+        let primes = segmented_sieve(low, low + 1000);
+        assert_eq!(primes[..3], [1_000_000_007, 1_000_000_009, 1_000_000_021]);
+        let expected: Vec<u64> = (low..=low + 1000)
+            .filter(|&n| (2..=n.isqrt()).all(|d| !n.is_multiple_of(d)))
+            .collect();
+        assert_eq!(primes, expected);
+    }
+}
@@ -0,0 +1,124 @@
+// Reservoir sampling draws a uniform random sample of k elements from a
+// stream whose length is not known in advance, storing only the sample.
+// Source: https://en.wikipedia.org/wiki/Reservoir_sampling#Simple:_Algorithm_R
+
+/// Returns `k` elements of `stream` chosen uniformly at random, all of them if
+/// it has no more than `k`, with Algorithm R.
+///
+/// The first `k` elements fill the reservoir, then the element at (0-based)
+/// position `i` takes the place of a random one of them with probability
+/// `k / (i + 1)`. By induction, after `n` elements each of them is in the
+/// reservoir with probability `k / n`. It takes O(n) time, one random number per
+/// element after the first `k`, and O(k) memory.
+///
+/// `rng(bound)` must return an index in `0..bound` that is uniformly random, so
+/// that any source of randomness can be used. The sample is not in a random
+/// order: an element that stays in the reservoir keeps its position.
+pub fn reservoir_sample<T: Clone, R: FnMut(usize) -> usize>(
+    stream: impl Iterator<Item = T>,
+    k: usize,
+    mut rng: R,
+) -> Vec<T> {
+    let mut reservoir = Vec::with_capacity(k);
+    if k == 0 {
+        return reservoir;
+    }
+    for (i, element) in stream.enumerate() {
+        if i < k {
+            reservoir.push(element);
+        } else {
+            let j = rng(i + 1);
+            if j < k {
+                reservoir[j] = element;
+            }
+        }
+    }
+    reservoir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    #[test]
+    fn small_cases() {
+        assert_eq!(reservoir_sample(1..=10, 0, |_| unreachable!()), vec![]);
+        assert_eq!(
+            reservoir_sample(std::iter::empty::<i32>(), 3, |_| 0),
+            vec![]
+        );
+        assert_eq!(
+            reservoir_sample(1..=3, 5, |_| unreachable!()),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            reservoir_sample(1..=3, 3, |_| unreachable!()),
+            vec![1, 2, 3]
+        );
+        let words = ["a", "b"].iter().map(|w| w.to_string());
+        assert_eq!(reservoir_sample(words, 2, |_| 0), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn uses_the_given_picks() {
+        // every later element replaces the first slot
+        assert_eq!(reservoir_sample(1..=6, 2, |_| 0), vec![6, 2]);
+        // no later element gets in
+        assert_eq!(reservoir_sample(1..=6, 2, |bound| bound - 1), vec![1, 2]);
+    }
+
+    #[test]
+    fn sample_of_distinct_elements() {
+        let mut rng = PCG32::new_default(62);
+        for _ in 0..100 {
+            let n = rng.get_u32() as usize % 50;
+            let k = rng.get_u32() as usize % 60;
+            let mut sample = reservoir_sample(0..n, k, |bound| rng.get_u32() as usize % bound);
+            assert_eq!(sample.len(), k.min(n));
+            sample.sort_unstable();
+            sample.dedup();
+            assert_eq!(sample.len(), k.min(n));
+            assert!(sample.iter().all(|&x| x < n));
+        }
+    }
+
+    #[test]
+    fn every_element_is_equally_likely() {
+        const TRIALS: usize = 50_000;
+        let (n, k) = (20, 5);
+        let mut rng = PCG32::new_default(26);
+        let mut counts = [0_usize; 20];
+        for _ in 0..TRIALS {
+            for x in reservoir_sample(0..n, k, |bound| rng.get_u64() as usize % bound) {
+                counts[x] += 1;
+            }
+        }
+        // each element is in the sample with probability k / n
+        let expected = (TRIALS * k / n) as f64;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        // the 99.9th percentile of the chi-squared distribution with 19 degrees of freedom
+        assert!(chi_squared < 43.82, "chi-squared {chi_squared}");
+        for &count in &counts {
+            assert!(
+                (count as f64 - expected).abs() < 0.05 * expected,
+                "{counts:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn stream_of_unknown_length() {
+        // only the sample is stored, however long the stream
+        let mut rng = PCG32::new_default(7);
+        let stream = (0_u64..)
+            .take_while(|&x| x < 1_000_000)
+            .filter(|x| x % 3 == 0);
+        let sample = reservoir_sample(stream, 10, |bound| rng.get_u64() as usize % bound);
+        assert_eq!(sample.len(), 10);
+        assert!(sample.iter().all(|x| x % 3 == 0));
+    }
+}
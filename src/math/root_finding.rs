@@ -0,0 +1,206 @@
+// Numerical root finding
+// Newton-Raphson follows the tangent of f at the current guess down to zero,
+// which roughly doubles the number of correct digits at every step near a
+// simple root, but needs the derivative and may diverge from a poor guess.
+// Bisection only needs a sign change of f over an interval, which it halves
+// at every step, so it is slower but always converges.
+// Wikipedia reference: https://en.wikipedia.org/wiki/Newton%27s_method
+// Wikipedia reference: https://en.wikipedia.org/wiki/Bisection_method
+
+pub fn find_root(f: fn(f64) -> f64, fd: fn(f64) -> f64, guess: f64, iterations: i32) -> f64 {
+    let mut result = guess;
+    for _ in 0..iterations {
+        result = iteration(f, fd, result);
+    }
+    result
+}
+
+pub fn iteration(f: fn(f64) -> f64, fd: fn(f64) -> f64, guess: f64) -> f64 {
+    guess - f(guess) / fd(guess)
+}
+
+/// Finds a root of `f` with Newton-Raphson iterations starting from `x0`.
+///
+/// Stops when a step moves the guess by at most `tol`, and returns `None` if
+/// that does not happen within `max_iter` steps, or if a step cannot be taken
+/// because the derivative `df` vanishes or the guess stops being finite.
+pub fn newton_raphson<F, DF>(f: F, df: DF, x0: f64, tol: f64, max_iter: usize) -> Option<f64>
+where
+    F: Fn(f64) -> f64,
+    DF: Fn(f64) -> f64,
+{
+    let mut x = x0;
+    for _ in 0..max_iter {
+        let step = f(x) / df(x);
+        if !step.is_finite() {
+            return None;
+        }
+        x -= step;
+        if step.abs() <= tol {
+            return Some(x);
+        }
+    }
+    None
+}
+
+/// Finds a root of `f` between `a` and `b` by bisection.
+///
+/// `f(a)` and `f(b)` must have opposite signs, or one of them be zero, and
+/// `None` is returned otherwise. The interval is halved, keeping the half
+/// where the sign changes, until it is at most `tol` wide, and its middle is
+/// returned, so the result is within `tol / 2` of a root of a continuous `f`.
+/// A `tol` too small for the precision of `f64` stops at the smallest interval.
+pub fn bisection<F>(f: F, a: f64, b: f64, tol: f64) -> Option<f64>
+where
+    F: Fn(f64) -> f64,
+{
+    let (mut lo, mut hi) = if a <= b { (a, b) } else { (b, a) };
+    let (f_lo, f_hi) = (f(lo), f(hi));
+    if f_lo == 0.0 {
+        return Some(lo);
+    }
+    if f_hi == 0.0 {
+        return Some(hi);
+    }
+    if f_lo.signum() == f_hi.signum() {
+        return None;
+    }
+    let lo_sign = f_lo.signum();
+    while hi - lo > tol {
+        let mid = lo + (hi - lo) / 2.0;
+        if mid == lo || mid == hi {
+            break;
+        }
+        let f_mid = f(mid);
+        if f_mid == 0.0 {
+            return Some(mid);
+        }
+        if f_mid.signum() == lo_sign {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(lo + (hi - lo) / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::f64::consts::{PI, SQRT_2};
+
+    fn math_fn(x: f64) -> f64 {
+        return x.cos() - (x * x * x);
+    }
+    fn math_fnd(x: f64) -> f64 {
+        return -x.sin() - 3.0 * (x * x);
+    }
+    #[test]
+    fn basic() {
+        assert_eq!(find_root(math_fn, math_fnd, 0.5, 6), 0.8654740331016144);
+    }
+
+    #[test]
+    fn newton_raphson_roots() {
+        let root = newton_raphson(|x| x * x - 2.0, |x| 2.0 * x, 1.0, 1e-12, 50).unwrap();
+        assert!((root - SQRT_2).abs() < 1e-12);
+
+        let root = newton_raphson(
+            |x| x * x * x - x - 2.0,
+            |x| 3.0 * x * x - 1.0,
+            1.5,
+            1e-12,
+            50,
+        );
+        assert!((root.unwrap() - 1.521_379_706_804_567_6).abs() < 1e-12);
+
+        let root = newton_raphson(f64::sin, f64::cos, 3.0, 1e-12, 50).unwrap();
+        assert!((root - PI).abs() < 1e-12);
+
+        let root = newton_raphson(|x| x.cos() - x, |x| -x.sin() - 1.0, 0.0, 1e-12, 50);
+        assert!((root.unwrap() - 0.739_085_133_215_160_6).abs() < 1e-12);
+
+        let root = newton_raphson(math_fn, math_fnd, 0.5, 1e-12, 50).unwrap();
+        assert!((root - 0.865_474_033_101_614_4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn newton_raphson_failures() {
+        // x^2 + 1 has no real root: the guesses wander forever
+        assert_eq!(
+            newton_raphson(|x| x * x + 1.0, |x| 2.0 * x, 0.5, 1e-12, 100),
+            None
+        );
+        // the tangent at 0 is horizontal
+        assert_eq!(
+            newton_raphson(|x| x * x - 1.0, |x| 2.0 * x, 0.0, 1e-12, 100),
+            None
+        );
+        // too few iterations to converge from far away
+        assert_eq!(
+            newton_raphson(|x| x * x - 2.0, |x| 2.0 * x, 1000.0, 1e-12, 5),
+            None
+        );
+    }
+
+    #[test]
+    fn newton_raphson_converges_quadratically() {
+        // the guesses newton_raphson evaluates f at, one per step
+        let guesses = RefCell::new(Vec::new());
+        let f = |x: f64| {
+            guesses.borrow_mut().push(x);
+            x * x - 2.0
+        };
+        let root = newton_raphson(f, |x| 2.0 * x, 1.5, 1e-15, 50).unwrap();
+        assert!((root - SQRT_2).abs() < 1e-15);
+        let errors: Vec<f64> = guesses.borrow().iter().map(|x| x - SQRT_2).collect();
+        // near the root, e_(n+1) is about e_n^2 * f''(r) / (2 f'(r)) = e_n^2 / (2 sqrt(2))
+        for pair in errors.windows(2).filter(|pair| pair[0] > 1e-6) {
+            let ratio = pair[1] / (pair[0] * pair[0]);
+            assert!((0.3..0.4).contains(&ratio), "{ratio}");
+        }
+        // so reaching full precision takes a handful of steps
+        assert!(errors.len() <= 6, "{errors:?}");
+    }
+
+    #[test]
+    fn bisection_roots() {
+        let root = bisection(|x| x * x - 2.0, 0.0, 2.0, 1e-12).unwrap();
+        assert!((root - SQRT_2).abs() < 1e-12);
+
+        let root = bisection(|x| x * x * x - x - 2.0, 1.0, 2.0, 1e-12).unwrap();
+        assert!((root - 1.521_379_706_804_567_6).abs() < 1e-12);
+
+        let root = bisection(f64::sin, 4.0, 2.0, 1e-12).unwrap();
+        assert!((root - PI).abs() < 1e-12);
+
+        assert_eq!(bisection(|x| x - 1.0, 1.0, 5.0, 1e-12), Some(1.0));
+        assert_eq!(bisection(|x| x - 3.0, 1.0, 5.0, 1e-12), Some(3.0));
+        // the precision of f64 runs out before the interval gets this small
+        let root = bisection(|x| x * x - 2.0, 0.0, 2.0, 0.0).unwrap();
+        assert!((root - SQRT_2).abs() < 1e-15);
+    }
+
+    #[test]
+    fn bisection_needs_a_sign_change() {
+        assert_eq!(bisection(|x| x * x + 1.0, -1.0, 1.0, 1e-9), None);
+        assert_eq!(bisection(|x| x * x - 2.0, 2.0, 3.0, 1e-9), None);
+    }
+
+    #[test]
+    fn bisection_halves_the_interval() {
+        // every step evaluates f once at the middle
+        for k in 1..30 {
+            let evaluations = Cell::new(0);
+            let f = |x: f64| {
+                evaluations.set(evaluations.get() + 1);
+                x * x - 2.0
+            };
+            let tol = 4.0 / (1u64 << k) as f64;
+            let root = bisection(f, 0.0, 4.0, tol).unwrap();
+            assert!((root - SQRT_2).abs() <= tol / 2.0);
+            assert_eq!(evaluations.get(), 2 + k);
+        }
+    }
+}
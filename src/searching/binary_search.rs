@@ -1,106 +1,187 @@
 use std::cmp::Ordering;
 
-pub fn binary_search<T: Ord>(item: &T, arr: &[T]) -> Option<usize> {
-    let mut is_asc = true;
-    if arr.len() > 1 {
-        is_asc = arr[0] < arr[(arr.len() - 1)];
+/// Searches a sorted slice for `target`, like `slice::binary_search`.
+///
+/// Returns `Ok` with the index of the first element equal to `target`, or
+/// `Err` with the index where `target` could be inserted to keep the slice
+/// sorted if there is none.
+pub fn binary_search<T: Ord>(arr: &[T], target: &T) -> Result<usize, usize> {
+    binary_search_by(arr, |x| x.cmp(target))
+}
+
+/// Searches a slice sorted according to `f`, like `slice::binary_search_by`.
+///
+/// `f` tells whether an element is less than, equal to or greater than the
+/// one searched for, and must return `Less` for a prefix of the slice,
+/// `Greater` for a suffix, and `Equal` in between. Returns `Ok` with the index
+/// of the first element for which it returns `Equal`, or else `Err` with the
+/// length of the prefix.
+pub fn binary_search_by<T, F: Fn(&T) -> Ordering>(arr: &[T], f: F) -> Result<usize, usize> {
+    let index = partition_point(arr, |x| f(x) == Ordering::Less);
+    match arr.get(index).map(&f) {
+        Some(Ordering::Equal) => Ok(index),
+        _ => Err(index),
     }
+}
+
+/// Returns the index of the first element greater than or equal to `target`,
+/// or the length of the slice if there is none.
+pub fn lower_bound<T: Ord>(arr: &[T], target: &T) -> usize {
+    partition_point(arr, |x| x < target)
+}
+
+/// Returns the index of the first element greater than `target`, or the
+/// length of the slice if there is none.
+///
+/// The elements equal to `target` are the ones in
+/// `lower_bound(arr, target)..upper_bound(arr, target)`.
+pub fn upper_bound<T: Ord>(arr: &[T], target: &T) -> usize {
+    partition_point(arr, |x| x <= target)
+}
+
+/// Returns the length of the prefix of `arr` whose elements satisfy `pred`,
+/// which must hold for a prefix of the slice and nowhere after it.
+fn partition_point<T, P: Fn(&T) -> bool>(arr: &[T], pred: P) -> usize {
     let mut left = 0;
     let mut right = arr.len();
 
+    // the prefix ends in left..=right
     while left < right {
         let mid = left + (right - left) / 2;
-
-        if is_asc {
-            match item.cmp(&arr[mid]) {
-                Ordering::Less => right = mid,
-                Ordering::Equal => return Some(mid),
-                Ordering::Greater => left = mid + 1,
-            }
+        if pred(&arr[mid]) {
+            left = mid + 1;
         } else {
-            match item.cmp(&arr[mid]) {
-                Ordering::Less => left = mid + 1,
-                Ordering::Equal => return Some(mid),
-                Ordering::Greater => right = mid,
-            }
+            right = mid;
         }
     }
-    None
+    left
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::PCG32;
 
     #[test]
     fn empty() {
-        let index = binary_search(&"a", &[]);
-        assert_eq!(index, None);
+        let index = binary_search(&[], &"a");
+        assert_eq!(index, Err(0));
+        assert_eq!(lower_bound(&[], &"a"), 0);
+        assert_eq!(upper_bound(&[], &"a"), 0);
     }
 
     #[test]
     fn one_item() {
-        let index = binary_search(&"a", &["a"]);
-        assert_eq!(index, Some(0));
+        let index = binary_search(&["a"], &"a");
+        assert_eq!(index, Ok(0));
+        assert_eq!(binary_search(&["b"], &"a"), Err(0));
+        assert_eq!(binary_search(&["b"], &"c"), Err(1));
     }
 
     #[test]
     fn search_strings_asc() {
-        let index = binary_search(&"a", &["a", "b", "c", "d", "google", "zoo"]);
-        assert_eq!(index, Some(0));
+        let index = binary_search(&["a", "b", "c", "d", "google", "zoo"], &"a");
+        assert_eq!(index, Ok(0));
 
-        let index = binary_search(&"google", &["a", "b", "c", "d", "google", "zoo"]);
-        assert_eq!(index, Some(4));
+        let index = binary_search(&["a", "b", "c", "d", "google", "zoo"], &"google");
+        assert_eq!(index, Ok(4));
     }
 
     #[test]
     fn search_strings_desc() {
-        let index = binary_search(&"a", &["zoo", "google", "d", "c", "b", "a"]);
-        assert_eq!(index, Some(5));
+        let arr = ["zoo", "google", "d", "c", "b", "a"];
+        let index = binary_search_by(&arr, |x| "a".cmp(x));
+        assert_eq!(index, Ok(5));
+
+        let index = binary_search_by(&arr, |x| "zoo".cmp(x));
+        assert_eq!(index, Ok(0));
 
-        let index = binary_search(&"zoo", &["zoo", "google", "d", "c", "b", "a"]);
-        assert_eq!(index, Some(0));
+        let index = binary_search_by(&arr, |x| "google".cmp(x));
+        assert_eq!(index, Ok(1));
 
-        let index = binary_search(&"google", &["zoo", "google", "d", "c", "b", "a"]);
-        assert_eq!(index, Some(1));
+        let index = binary_search_by(&arr, |x| "e".cmp(x));
+        assert_eq!(index, Err(2));
     }
 
     #[test]
     fn search_ints_asc() {
-        let index = binary_search(&4, &[1, 2, 3, 4]);
-        assert_eq!(index, Some(3));
+        let index = binary_search(&[1, 2, 3, 4], &4);
+        assert_eq!(index, Ok(3));
 
-        let index = binary_search(&3, &[1, 2, 3, 4]);
-        assert_eq!(index, Some(2));
+        let index = binary_search(&[1, 2, 3, 4], &3);
+        assert_eq!(index, Ok(2));
 
-        let index = binary_search(&2, &[1, 2, 3, 4]);
-        assert_eq!(index, Some(1));
+        let index = binary_search(&[1, 2, 3, 4], &2);
+        assert_eq!(index, Ok(1));
 
-        let index = binary_search(&1, &[1, 2, 3, 4]);
-        assert_eq!(index, Some(0));
+        let index = binary_search(&[1, 2, 3, 4], &1);
+        assert_eq!(index, Ok(0));
     }
 
     #[test]
-    fn search_ints_desc() {
-        let index = binary_search(&4, &[4, 3, 2, 1]);
-        assert_eq!(index, Some(0));
+    fn search_by_key() {
+        let pairs = [(1, 'a'), (3, 'b'), (3, 'c'), (7, 'd')];
+        assert_eq!(binary_search_by(&pairs, |p| p.0.cmp(&3)), Ok(1));
+        assert_eq!(binary_search_by(&pairs, |p| p.0.cmp(&7)), Ok(3));
+        assert_eq!(binary_search_by(&pairs, |p| p.0.cmp(&5)), Err(3));
+        assert_eq!(binary_search_by(&pairs, |p| p.0.cmp(&0)), Err(0));
+    }
 
-        let index = binary_search(&3, &[4, 3, 2, 1]);
-        assert_eq!(index, Some(1));
+    #[test]
+    fn not_found() {
+        let index = binary_search(&[1, 2, 3, 4], &5);
+        assert_eq!(index, Err(4));
 
-        let index = binary_search(&2, &[4, 3, 2, 1]);
-        assert_eq!(index, Some(2));
+        let index = binary_search(&[1, 2, 3, 4], &0);
+        assert_eq!(index, Err(0));
 
-        let index = binary_search(&1, &[4, 3, 2, 1]);
-        assert_eq!(index, Some(3));
+        let index = binary_search(&[1, 3, 5, 7], &4);
+        assert_eq!(index, Err(2));
     }
 
     #[test]
-    fn not_found() {
-        let index = binary_search(&5, &[1, 2, 3, 4]);
-        assert_eq!(index, None);
+    fn duplicates() {
+        let arr = [1, 2, 2, 2, 3, 5, 5];
+        assert_eq!(binary_search(&arr, &2), Ok(1));
+        assert_eq!(binary_search(&arr, &5), Ok(5));
+        assert_eq!(binary_search(&arr, &4), Err(5));
+        assert_eq!((lower_bound(&arr, &2), upper_bound(&arr, &2)), (1, 4));
+        assert_eq!((lower_bound(&arr, &5), upper_bound(&arr, &5)), (5, 7));
+        assert_eq!((lower_bound(&arr, &4), upper_bound(&arr, &4)), (5, 5));
+        assert_eq!((lower_bound(&arr, &0), upper_bound(&arr, &0)), (0, 0));
+        assert_eq!((lower_bound(&arr, &9), upper_bound(&arr, &9)), (7, 7));
+
+        let same = [4; 10];
+        assert_eq!(binary_search(&same, &4), Ok(0));
+        assert_eq!((lower_bound(&same, &4), upper_bound(&same, &4)), (0, 10));
+    }
 
-        let index = binary_search(&5, &[4, 3, 2, 1]);
-        assert_eq!(index, None);
+    #[test]
+    fn matches_std() {
+        let mut rng = PCG32::new_default(80);
+        for _ in 0..10_000 {
+            let len = rng.get_u32() as usize % 40;
+            let range = 1 + rng.get_u32() % 50;
+            let mut arr: Vec<u32> = (0..len).map(|_| rng.get_u32() % range).collect();
+            arr.sort();
+            let target = rng.get_u32() % (range + 2);
+
+            // std may return any of several equal elements, but its errors are exact
+            match (binary_search(&arr, &target), arr.binary_search(&target)) {
+                (Ok(i), Ok(_)) => {
+                    assert_eq!(arr[i], target);
+                    assert!(i == 0 || arr[i - 1] < target);
+                }
+                (found, expected) => assert_eq!(found, expected),
+            }
+            assert_eq!(
+                lower_bound(&arr, &target),
+                arr.partition_point(|&x| x < target)
+            );
+            assert_eq!(
+                upper_bound(&arr, &target),
+                arr.partition_point(|&x| x <= target)
+            );
+        }
     }
 }
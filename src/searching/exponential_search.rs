@@ -1,72 +1,104 @@
-use std::cmp::Ordering;
+use super::binary_search;
 
-pub fn exponential_search<T: Ord>(item: &T, arr: &[T]) -> Option<usize> {
+/// Searches a sorted slice for `target` and returns the index of an element
+/// equal to it.
+///
+/// Probes the indices 1, 2, 4, 8, ... until it passes the end of the slice or
+/// an element greater than `target`, and then binary searches the range since
+/// the previous probe. Finding an element at index `i` takes `O(log i)` time,
+/// which makes it fast for elements near the start of a long slice.
+pub fn exponential_search<T: Ord>(arr: &[T], target: &T) -> Option<usize> {
     let len = arr.len();
     if len == 0 {
         return None;
     }
     let mut upper = 1;
-    while (upper < len) && (&arr[upper] <= item) {
+    while upper < len && &arr[upper] <= target {
         upper *= 2;
     }
-    if upper > len {
-        upper = len
-    }
-
-    // binary search
-    let mut lower = upper / 2;
-    while lower < upper {
-        let mid = lower + (upper - lower) / 2;
-
-        match item.cmp(&arr[mid]) {
-            Ordering::Less => upper = mid,
-            Ordering::Equal => return Some(mid),
-            Ordering::Greater => lower = mid + 1,
-        }
-    }
-    None
+    // arr[lower] <= target, and arr[upper] > target if it exists
+    let lower = upper / 2;
+    let upper = upper.min(len);
+    binary_search(&arr[lower..upper], target)
+        .ok()
+        .map(|index| lower + index)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Searches the part of `arr` from `offset` on, returning an index in `arr`.
+    fn search_from<T: Ord>(arr: &[T], offset: usize, target: &T) -> Option<usize> {
+        exponential_search(&arr[offset..], target).map(|index| offset + index)
+    }
+
     #[test]
     fn empty() {
-        let index = exponential_search(&"a", &vec![]);
+        let index = exponential_search(&[], &"a");
         assert_eq!(index, None);
     }
 
     #[test]
     fn one_item() {
-        let index = exponential_search(&"a", &vec!["a"]);
+        let index = exponential_search(&["a"], &"a");
         assert_eq!(index, Some(0));
+        assert_eq!(exponential_search(&["a"], &"b"), None);
     }
 
     #[test]
     fn search_strings() {
-        let index = exponential_search(&"a", &vec!["a", "b", "c", "d", "google", "zoo"]);
+        let index = exponential_search(&["a", "b", "c", "d", "google", "zoo"], &"a");
         assert_eq!(index, Some(0));
     }
 
     #[test]
     fn search_ints() {
-        let index = exponential_search(&4, &vec![1, 2, 3, 4]);
+        let index = exponential_search(&[1, 2, 3, 4], &4);
         assert_eq!(index, Some(3));
 
-        let index = exponential_search(&3, &vec![1, 2, 3, 4]);
+        let index = exponential_search(&[1, 2, 3, 4], &3);
         assert_eq!(index, Some(2));
 
-        let index = exponential_search(&2, &vec![1, 2, 3, 4]);
+        let index = exponential_search(&[1, 2, 3, 4], &2);
         assert_eq!(index, Some(1));
 
-        let index = exponential_search(&1, &vec![1, 2, 3, 4]);
+        let index = exponential_search(&[1, 2, 3, 4], &1);
         assert_eq!(index, Some(0));
     }
 
+    #[test]
+    fn first_middle_and_last() {
+        let arr: Vec<u32> = (0..1000).map(|i| 3 * i).collect();
+        assert_eq!(exponential_search(&arr, &0), Some(0));
+        assert_eq!(exponential_search(&arr, &1500), Some(500));
+        assert_eq!(exponential_search(&arr, &2997), Some(999));
+        // on the probed indices and right after them
+        for i in [1, 2, 4, 64, 512, 513, 767, 768] {
+            assert_eq!(exponential_search(&arr, &(3 * i)), Some(i as usize));
+        }
+    }
+
     #[test]
     fn not_found() {
-        let index = exponential_search(&5, &vec![1, 2, 3, 4]);
+        let index = exponential_search(&[1, 2, 3, 4], &5);
         assert_eq!(index, None);
+
+        let arr: Vec<u32> = (0..1000).map(|i| 3 * i).collect();
+        assert_eq!(exponential_search(&arr, &1501), None);
+        assert_eq!(exponential_search(&arr, &3000), None);
+        assert_eq!(exponential_search(&[1, 2, 3, 4], &0), None);
+    }
+
+    #[test]
+    fn from_offset() {
+        let arr: Vec<u32> = (0..100).map(|i| 2 * i).collect();
+        assert_eq!(search_from(&arr, 37, &74), Some(37));
+        assert_eq!(search_from(&arr, 37, &100), Some(50));
+        assert_eq!(search_from(&arr, 37, &198), Some(99));
+        // before the offset, or at none of the indices
+        assert_eq!(search_from(&arr, 37, &72), None);
+        assert_eq!(search_from(&arr, 37, &101), None);
+        assert_eq!(search_from(&arr, 100, &0), None);
     }
 }
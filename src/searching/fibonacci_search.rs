@@ -1,84 +1,151 @@
-use std::cmp::min;
 use std::cmp::Ordering;
 
-pub fn fibonacci_search<T: Ord>(item: &T, arr: &[T]) -> Option<usize> {
+/// Searches a sorted slice for `target` and returns the index of an element
+/// equal to it.
+///
+/// Like binary search it halves the range left at each probe, but it splits a
+/// range of `F(m) - 1` elements, with `F(m)` a Fibonacci number, at the probed
+/// element into ranges of `F(m - 1) - 1` and `F(m - 2) - 1` elements. The
+/// probes are found with additions and subtractions only, and it takes
+/// `O(log n)` comparisons.
+pub fn fibonacci_search<T: Ord>(arr: &[T], target: &T) -> Option<usize> {
+    search_recording_probes(arr, target, &mut Vec::new())
+}
+
+/// `fibonacci_search`, also pushing the index of each element compared with
+/// `target` to `probes`.
+fn search_recording_probes<T: Ord>(
+    arr: &[T],
+    target: &T,
+    probes: &mut Vec<usize>,
+) -> Option<usize> {
     let len = arr.len();
-    if len == 0 {
-        return None;
+    // Fibonacci numbers up to the first one greater than len
+    let mut fib = vec![0, 1];
+    while fib[fib.len() - 1] <= len {
+        fib.push(fib[fib.len() - 1] + fib[fib.len() - 2]);
     }
-    let mut start = -1;
 
-    let mut f0 = 0;
-    let mut f1 = 1;
-    let mut f2 = 1;
-    while f2 < len {
-        f0 = f1;
-        f1 = f2;
-        f2 = f0 + f1;
-    }
-    while f2 > 1 {
-        let index = min((f0 as isize + start) as usize, len - 1);
-        match item.cmp(&arr[index]) {
-            Ordering::Less => {
-                f2 = f0;
-                f1 -= f0;
-                f0 = f2 - f1;
-            }
+    // target can only be in the F(m) - 1 elements from offset, where the ones
+    // past the end of the slice count as greater than it
+    let mut m = fib.len() - 1;
+    let mut offset = 0;
+    while fib[m] > 1 {
+        let index = offset + fib[m - 1] - 1;
+        let ordering = if index < len {
+            probes.push(index);
+            target.cmp(&arr[index])
+        } else {
+            Ordering::Less
+        };
+        match ordering {
+            Ordering::Less => m -= 1,
             Ordering::Equal => return Some(index),
             Ordering::Greater => {
-                f2 = f1;
-                f1 = f0;
-                f0 = f2 - f1;
-                start = index as isize;
+                m -= 2;
+                offset = index + 1;
             }
         }
     }
-    if (f1 != 0) && (&arr[len - 1] == item) {
-        return Some(len - 1);
-    }
     None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::PCG32;
+    use crate::searching::binary_search;
 
     #[test]
     fn empty() {
-        let index = fibonacci_search(&"a", &vec![]);
+        let index = fibonacci_search(&[], &"a");
         assert_eq!(index, None);
     }
 
     #[test]
     fn one_item() {
-        let index = fibonacci_search(&"a", &vec!["a"]);
+        let index = fibonacci_search(&["a"], &"a");
         assert_eq!(index, Some(0));
+        assert_eq!(fibonacci_search(&["a"], &"b"), None);
     }
 
     #[test]
     fn search_strings() {
-        let index = fibonacci_search(&"a", &vec!["a", "b", "c", "d", "google", "zoo"]);
+        let index = fibonacci_search(&["a", "b", "c", "d", "google", "zoo"], &"a");
         assert_eq!(index, Some(0));
     }
 
     #[test]
     fn search_ints() {
-        let index = fibonacci_search(&4, &vec![1, 2, 3, 4]);
+        let index = fibonacci_search(&[1, 2, 3, 4], &4);
         assert_eq!(index, Some(3));
 
-        let index = fibonacci_search(&3, &vec![1, 2, 3, 4]);
+        let index = fibonacci_search(&[1, 2, 3, 4], &3);
         assert_eq!(index, Some(2));
 
-        let index = fibonacci_search(&2, &vec![1, 2, 3, 4]);
+        let index = fibonacci_search(&[1, 2, 3, 4], &2);
         assert_eq!(index, Some(1));
 
-        let index = fibonacci_search(&1, &vec![1, 2, 3, 4]);
+        let index = fibonacci_search(&[1, 2, 3, 4], &1);
         assert_eq!(index, Some(0));
     }
 
     #[test]
     fn not_found() {
-        let index = fibonacci_search(&5, &vec![1, 2, 3, 4]);
+        let index = fibonacci_search(&[1, 2, 3, 4], &5);
         assert_eq!(index, None);
+        assert_eq!(fibonacci_search(&[1, 2, 3, 4], &0), None);
+        assert_eq!(fibonacci_search(&[1, 3, 5, 7, 9, 11], &8), None);
+    }
+
+    #[test]
+    fn first_and_last() {
+        for len in 1..100 {
+            let arr: Vec<usize> = (0..len).collect();
+            assert_eq!(fibonacci_search(&arr, &0), Some(0));
+            assert_eq!(fibonacci_search(&arr, &(len - 1)), Some(len - 1));
+            assert_eq!(fibonacci_search(&arr, &len), None);
+        }
+    }
+
+    #[test]
+    fn probes_on_ten_elements() {
+        // 13 is the first Fibonacci number greater than 10, so the first probe
+        // is at index 8 - 1, and the three elements after the end count as greater
+        let arr = [10, 20, 30, 40, 50, 60, 70, 80, 90, 100];
+        let cases: [(i32, Option<usize>, &[usize]); 10] = [
+            (80, Some(7), &[7]),
+            (50, Some(4), &[7, 4]),
+            (30, Some(2), &[7, 4, 2]),
+            (10, Some(0), &[7, 4, 2, 1, 0]),
+            (60, Some(5), &[7, 4, 6, 5]),
+            (100, Some(9), &[7, 9]),
+            (90, Some(8), &[7, 9, 8]),
+            (55, None, &[7, 4, 6, 5]),
+            (5, None, &[7, 4, 2, 1, 0]),
+            (105, None, &[7, 9]),
+        ];
+        for (target, expected, expected_probes) in cases {
+            let mut probes = Vec::new();
+            let index = search_recording_probes(&arr, &target, &mut probes);
+            assert_eq!(index, expected, "{target}");
+            assert_eq!(probes, expected_probes, "{target}");
+        }
+    }
+
+    #[test]
+    fn matches_binary_search() {
+        let mut rng = PCG32::new_default(85);
+        for _ in 0..2000 {
+            let len = rng.get_u32() as usize % 50;
+            let mut arr: Vec<u32> = (0..len).map(|_| rng.get_u32() % 100).collect();
+            arr.sort();
+            arr.dedup();
+            let target = rng.get_u32() % 102;
+            assert_eq!(
+                fibonacci_search(&arr, &target),
+                binary_search(&arr, &target).ok()
+            );
+        }
     }
 }
@@ -1,57 +1,156 @@
-pub fn interpolation_search<Ordering>(nums: &[i32], item: &i32) -> Result<usize, usize> {
-    // early check
-    if nums.is_empty() {
-        return Err(0);
-    }
-    let mut low: usize = 0;
-    let mut high: usize = nums.len() - 1;
-    while low <= high {
-        if *item < nums[low] || *item > nums[high] {
-            break;
-        }
-        let offset: usize = low
-            + (((high - low) / (nums[high] - nums[low]) as usize) * (*item - nums[low]) as usize);
-        match nums[offset].cmp(item) {
-            std::cmp::Ordering::Equal => return Ok(offset),
-            std::cmp::Ordering::Less => low = offset + 1,
-            std::cmp::Ordering::Greater => high = offset - 1,
+use std::cmp::Ordering;
+
+/// Searches a sorted slice for `target` and returns the index of an element
+/// equal to it.
+///
+/// Instead of probing the middle of the range left, as binary search does, it
+/// probes where `target` would be if the values grew linearly from one end of
+/// the range to the other:
+/// `lo + (target - arr[lo]) * (hi - lo) / (arr[hi] - arr[lo])`.
+/// On uniformly distributed values this takes `O(log log n)` probes on
+/// average, but `O(n)` in the worst case.
+pub fn interpolation_search<T: Ord + Into<f64> + Copy>(arr: &[T], target: T) -> Option<usize> {
+    search_counting_probes(arr, target).0
+}
+
+/// `interpolation_search`, also returning the number of elements compared
+/// with `target`.
+fn search_counting_probes<T: Ord + Into<f64> + Copy>(
+    arr: &[T],
+    target: T,
+) -> (Option<usize>, usize) {
+    let mut probes = 0;
+    if arr.is_empty() {
+        return (None, probes);
+    }
+    let mut lo = 0;
+    let mut hi = arr.len() - 1;
+    // target can only be in lo..=hi when it is between their values
+    while lo <= hi && arr[lo] <= target && target <= arr[hi] {
+        let position = if arr[lo] == arr[hi] {
+            lo + (hi - lo) / 2
+        } else {
+            let (low, high, target) = (arr[lo].into(), arr[hi].into(), target.into());
+            let offset = (target - low) * (hi - lo) as f64 / (high - low);
+            // rounding could put it just out of the range
+            (lo + offset as usize).min(hi)
+        };
+        probes += 1;
+        match arr[position].cmp(&target) {
+            Ordering::Equal => return (Some(position), probes),
+            Ordering::Less => lo = position + 1,
+            // arr[lo] <= target, so position > lo
+            Ordering::Greater => hi = position - 1,
         }
     }
-    Err(0)
+    (None, probes)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::cmp::Ordering;
+    use crate::math::PCG32;
+    use crate::searching::binary_search_by;
+    use std::cell::Cell;
 
     #[test]
-    fn returns_err_if_empty_slice() {
-        let nums = [];
-        assert_eq!(interpolation_search::<Ordering>(&nums, &3), Err(0));
+    fn returns_none_if_empty_slice() {
+        let nums: [i32; 0] = [];
+        assert_eq!(interpolation_search(&nums, 3), None);
     }
 
     #[test]
-    fn returns_err_if_target_not_found() {
+    fn returns_none_if_target_not_found() {
         let nums = [1, 2, 3, 4, 5, 6];
-        assert_eq!(interpolation_search::<Ordering>(&nums, &10), Err(0));
+        assert_eq!(interpolation_search(&nums, 10), None);
+        assert_eq!(interpolation_search(&nums, 0), None);
+        assert_eq!(interpolation_search(&[1, 4, 9, 16], 10), None);
     }
 
     #[test]
     fn returns_first_index() {
-        let index: Result<usize, usize> = interpolation_search::<Ordering>(&[1, 2, 3, 4, 5], &1);
-        assert_eq!(index, Ok(0));
+        let index = interpolation_search(&[1, 2, 3, 4, 5], 1);
+        assert_eq!(index, Some(0));
     }
 
     #[test]
     fn returns_last_index() {
-        let index: Result<usize, usize> = interpolation_search::<Ordering>(&[1, 2, 3, 4, 5], &5);
-        assert_eq!(index, Ok(4));
+        let index = interpolation_search(&[1, 2, 3, 4, 5], 5);
+        assert_eq!(index, Some(4));
     }
 
     #[test]
     fn returns_middle_index() {
-        let index: Result<usize, usize> = interpolation_search::<Ordering>(&[1, 2, 3, 4, 5], &3);
-        assert_eq!(index, Ok(2));
+        let index = interpolation_search(&[1, 2, 3, 4, 5], 3);
+        assert_eq!(index, Some(2));
+    }
+
+    #[test]
+    fn single_element() {
+        assert_eq!(interpolation_search(&[7u8], 7), Some(0));
+        assert_eq!(interpolation_search(&[7u8], 6), None);
+        assert_eq!(interpolation_search(&[7u8], 8), None);
+    }
+
+    #[test]
+    fn identical_elements() {
+        let nums = [4u16; 20];
+        assert_eq!(interpolation_search(&nums, 4), Some(9));
+        assert_eq!(interpolation_search(&nums, 3), None);
+        assert_eq!(interpolation_search(&nums, 5), None);
+
+        let nums = [1, 4, 4, 4, 4, 4, 4, 9];
+        assert_eq!(nums[interpolation_search(&nums, 4).unwrap()], 4);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = PCG32::new_default(81);
+        for round in 0..500 {
+            let len = rng.get_u32() as usize % 60;
+            // skewed values as well as uniform ones
+            let mut nums: Vec<i32> = (0..len)
+                .map(|_| {
+                    let x = (rng.get_u32() % 100) as i32;
+                    if round % 2 == 0 {
+                        x
+                    } else {
+                        x * x * x - 5000
+                    }
+                })
+                .collect();
+            nums.sort();
+            for target in nums.iter().copied().chain((0..20).map(|i| i * 37 - 300)) {
+                match interpolation_search(&nums, target) {
+                    Some(index) => assert_eq!(nums[index], target),
+                    None => assert!(!nums.contains(&target)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fewer_probes_than_binary_search() {
+        let mut rng = PCG32::new_default(1);
+        let mut nums: Vec<u32> = (0..1_000_000).map(|_| rng.get_u32()).collect();
+        nums.sort();
+        let (mut interpolation, mut binary) = (0, 0);
+        for _ in 0..1000 {
+            let target = nums[rng.get_u32() as usize % nums.len()];
+            let (found, probes) = search_counting_probes(&nums, target);
+            assert_eq!(nums[found.unwrap()], target);
+            interpolation += probes;
+
+            let comparisons = Cell::new(0);
+            let found = binary_search_by(&nums, |x| {
+                comparisons.set(comparisons.get() + 1);
+                x.cmp(&target)
+            });
+            assert!(found.is_ok());
+            binary += comparisons.get();
+        }
+        // about log2(10^6) = 20 comparisons for binary search, and 4 or 5 here
+        assert!(binary >= 19 * 1000);
+        assert!(interpolation * 4 < binary);
     }
 }
@@ -1,30 +1,51 @@
-use std::cmp::min;
+/// Searches a sorted slice for `target`, and returns the index of its first
+/// occurrence if there are several.
+///
+/// Jumps ahead by blocks of `sqrt(n)` elements until the last element of a
+/// block is not smaller than `target`, and then walks back through that block
+/// to its first element not smaller than `target`. This takes `O(sqrt(n))`
+/// comparisons, as both parts are bounded by the number of blocks and the
+/// block size.
+pub fn jump_search<T: Ord>(arr: &[T], target: &T) -> Option<usize> {
+    search_counting_comparisons(arr, target).0
+}
 
-pub fn jump_search<T: Ord>(item: &T, arr: &[T]) -> Option<usize> {
+/// `jump_search`, also returning the number of elements compared with `target`.
+fn search_counting_comparisons<T: Ord>(arr: &[T], target: &T) -> (Option<usize>, usize) {
+    let mut comparisons = 0;
     let len = arr.len();
-    if len == 0 {
-        return None;
-    }
-    let mut step = (len as f64).sqrt() as usize;
-    let mut prev = 0;
+    let block = (len as f64).sqrt().max(1.0) as usize;
 
-    while &arr[min(len, step) - 1] < item {
-        prev = step;
-        step += (len as f64).sqrt() as usize;
-        if prev >= len {
-            return None;
+    // the first block whose last element is not smaller than target
+    let mut start = 0;
+    let mut end = block.min(len);
+    while end > start {
+        comparisons += 1;
+        if &arr[end - 1] >= target {
+            break;
         }
+        start = end;
+        end = (end + block).min(len);
     }
-    while &arr[prev] < item {
-        prev += 1;
-        if prev == min(step, len) {
-            return None;
+    if start == len {
+        return (None, comparisons);
+    }
+
+    // every element before start is smaller than target, and arr[end - 1] is not
+    let mut i = end - 1;
+    while i > start {
+        comparisons += 1;
+        if &arr[i - 1] < target {
+            break;
         }
+        i -= 1;
     }
-    if &arr[prev] == item {
-        return Some(prev);
+    comparisons += 1;
+    if &arr[i] == target {
+        (Some(i), comparisons)
+    } else {
+        (None, comparisons)
     }
-    None
 }
 
 #[cfg(test)]
@@ -33,40 +54,83 @@ mod tests {
 
     #[test]
     fn empty() {
-        let index = jump_search(&"a", &vec![]);
+        let index = jump_search(&[], &"a");
         assert_eq!(index, None);
     }
 
     #[test]
     fn one_item() {
-        let index = jump_search(&"a", &vec!["a"]);
+        let index = jump_search(&["a"], &"a");
         assert_eq!(index, Some(0));
+        assert_eq!(jump_search(&["b"], &"a"), None);
+        assert_eq!(jump_search(&["b"], &"c"), None);
     }
 
     #[test]
     fn search_strings() {
-        let index = jump_search(&"a", &vec!["a", "b", "c", "d", "google", "zoo"]);
+        let index = jump_search(&["a", "b", "c", "d", "google", "zoo"], &"a");
         assert_eq!(index, Some(0));
     }
 
     #[test]
     fn search_ints() {
-        let index = jump_search(&4, &vec![1, 2, 3, 4]);
+        let index = jump_search(&[1, 2, 3, 4], &4);
         assert_eq!(index, Some(3));
 
-        let index = jump_search(&3, &vec![1, 2, 3, 4]);
+        let index = jump_search(&[1, 2, 3, 4], &3);
         assert_eq!(index, Some(2));
 
-        let index = jump_search(&2, &vec![1, 2, 3, 4]);
+        let index = jump_search(&[1, 2, 3, 4], &2);
         assert_eq!(index, Some(1));
 
-        let index = jump_search(&1, &vec![1, 2, 3, 4]);
+        let index = jump_search(&[1, 2, 3, 4], &1);
         assert_eq!(index, Some(0));
     }
 
     #[test]
     fn not_found() {
-        let index = jump_search(&5, &vec![1, 2, 3, 4]);
+        let index = jump_search(&[1, 2, 3, 4], &5);
         assert_eq!(index, None);
+        assert_eq!(jump_search(&[1, 2, 3, 4], &0), None);
+        assert_eq!(jump_search(&[1, 3, 5, 7, 9], &6), None);
+    }
+
+    #[test]
+    fn every_position() {
+        // even values only, so the odd ones fall between the elements
+        for len in [0, 1, 16, 1024] {
+            let arr: Vec<usize> = (0..len).map(|i| 2 * i).collect();
+            for i in 0..len {
+                assert_eq!(jump_search(&arr, &(2 * i)), Some(i));
+                assert_eq!(jump_search(&arr, &(2 * i + 1)), None);
+            }
+        }
+    }
+
+    #[test]
+    fn duplicates_give_the_first() {
+        let arr = [1, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 7, 7, 9];
+        assert_eq!(jump_search(&arr, &3), Some(1));
+        assert_eq!(jump_search(&arr, &7), Some(11));
+        let same = [5; 50];
+        assert_eq!(jump_search(&same, &5), Some(0));
+    }
+
+    #[test]
+    fn comparisons_are_sqrt_n() {
+        for len in [16, 1024, 10_000] {
+            let arr: Vec<usize> = (0..len).collect();
+            let sqrt = (len as f64).sqrt() as usize;
+            let mut total = 0;
+            for target in 0..len {
+                let (found, comparisons) = search_counting_comparisons(&arr, &target);
+                assert_eq!(found, Some(target));
+                // at most the number of blocks, then the size of a block
+                assert!(comparisons <= 2 * sqrt + 1);
+                total += comparisons;
+            }
+            // a target at a uniform position is reached halfway through both parts
+            assert!(total / len <= sqrt + 2);
+        }
     }
 }
@@ -13,7 +13,7 @@ mod ternary_search_min_max;
 mod ternary_search_min_max_recursive;
 mod ternary_search_recursive;
 
-pub use self::binary_search::binary_search;
+pub use self::binary_search::{binary_search, binary_search_by, lower_bound, upper_bound};
 pub use self::binary_search_recursive::binary_search_rec;
 pub use self::exponential_search::exponential_search;
 pub use self::fibonacci_search::fibonacci_search;
@@ -22,8 +22,10 @@ pub use self::jump_search::jump_search;
 pub use self::kth_smallest::kth_smallest;
 pub use self::kth_smallest_heap::kth_smallest_heap;
 pub use self::linear_search::linear_search;
-pub use self::quick_select::quick_select;
-pub use self::ternary_search::ternary_search;
+pub use self::quick_select::{median, quick_select, quickselect};
+pub use self::ternary_search::{
+    ternary_search, ternary_search_argmin, ternary_search_discrete_min,
+};
 pub use self::ternary_search_min_max::ternary_search_max;
 pub use self::ternary_search_min_max::ternary_search_min;
 pub use self::ternary_search_min_max_recursive::ternary_search_max_rec;
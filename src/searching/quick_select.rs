@@ -1,5 +1,10 @@
 // https://en.wikipedia.org/wiki/Quickselect
 
+use std::cmp::Ordering;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::math::PCG32;
+
 fn partition(list: &mut [i32], left: usize, right: usize, pivot_index: usize) -> usize {
     let pivot_value = list[pivot_index];
     list.swap(pivot_index, right); // Move pivot to end
@@ -29,6 +34,67 @@ pub fn quick_select(list: &mut [i32], left: usize, right: usize, index: usize) -
     }
 }
 
+/// Returns the `k`-th smallest element of `arr`, counting from 0, or None if
+/// `k >= arr.len()`. Equal elements count as many times as they appear, so that
+/// it is the element at index `k` once `arr` is sorted.
+///
+/// It works on a copy of `arr`, partitioning it around random pivots into the
+/// elements smaller than, equal to, and larger than the pivot, and going on only
+/// with the part the `k`-th smallest element is in. That takes O(n) expected
+/// time whatever the input, many equal elements included, and O(n^2) in the
+/// worst case, which is unlikely.
+pub fn quickselect<T: Ord + Clone>(arr: &[T], k: usize) -> Option<T> {
+    if k >= arr.len() {
+        return None;
+    }
+    let mut values = arr.to_vec();
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as u64);
+    let mut rng = PCG32::new_default(seed);
+
+    // the k-th smallest element is in values[lo..hi]
+    let (mut lo, mut hi) = (0, values.len());
+    loop {
+        let pivot_index = lo + (rng.get_u64() % (hi - lo) as u64) as usize;
+        let pivot = values[pivot_index].clone();
+        // values[lo..lt] < pivot, values[lt..i] == pivot, values[gt..hi] > pivot
+        let (mut lt, mut i, mut gt) = (lo, lo, hi);
+        while i < gt {
+            match values[i].cmp(&pivot) {
+                Ordering::Less => {
+                    values.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                }
+                Ordering::Equal => i += 1,
+                Ordering::Greater => {
+                    gt -= 1;
+                    values.swap(i, gt);
+                }
+            }
+        }
+        if k < lt {
+            hi = lt;
+        } else if k >= gt {
+            lo = gt;
+        } else {
+            return Some(pivot);
+        }
+    }
+}
+
+/// Returns the median of `arr`, or None if it is empty.
+///
+/// For an even number of elements, it is the lower of the two middle ones, since
+/// they cannot be averaged in general.
+pub fn median<T: Ord + Clone>(arr: &[T]) -> Option<T> {
+    if arr.is_empty() {
+        return None;
+    }
+    quickselect(arr, (arr.len() - 1) / 2)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,4 +107,62 @@ mod tests {
         let mut arr2 = [0, 3, 8];
         assert_eq!(quick_select(&mut arr2, 0, 0, 0), 0);
     }
+
+    #[test]
+    fn quickselect_small_slices() {
+        assert_eq!(quickselect::<i32>(&[], 0), None);
+        assert_eq!(quickselect(&[7], 0), Some(7));
+        assert_eq!(quickselect(&[7], 1), None);
+        assert_eq!(quickselect(&[3, 1, 2], 0), Some(1));
+        assert_eq!(quickselect(&[3, 1, 2], 2), Some(3));
+        assert_eq!(quickselect(&[3, 1, 2], 3), None);
+        assert_eq!(quickselect(&["pear", "apple", "fig"], 1), Some("fig"));
+    }
+
+    #[test]
+    fn quickselect_matches_sorting() {
+        let arr = [9, -4, 17, 0, 3, 3, 99, -50, 12, 8];
+        let mut sorted = arr.to_vec();
+        sorted.sort();
+        for (k, expected) in sorted.iter().enumerate() {
+            assert_eq!(quickselect(&arr, k), Some(*expected));
+        }
+    }
+
+    #[test]
+    fn quickselect_duplicates() {
+        let arr = [5, 1, 5, 5, 2, 5, 1];
+        let expected = [1, 1, 2, 5, 5, 5, 5];
+        for (k, &value) in expected.iter().enumerate() {
+            assert_eq!(quickselect(&arr, k), Some(value));
+        }
+        let same = vec![4; 10_000];
+        assert_eq!(quickselect(&same, 5_000), Some(4));
+    }
+
+    #[test]
+    fn median_of_slices() {
+        assert_eq!(median::<u8>(&[]), None);
+        assert_eq!(median(&[42]), Some(42));
+        assert_eq!(median(&[3, 1, 2]), Some(2));
+        // the lower middle element
+        assert_eq!(median(&[4, 1, 3, 2]), Some(2));
+        assert_eq!(median(&[10, 10, 1, 1, 10]), Some(10));
+        let sorted: Vec<u32> = (0..1001).collect();
+        assert_eq!(median(&sorted), Some(500));
+    }
+
+    #[test]
+    fn quickselect_stress() {
+        let mut rng = PCG32::new_default(63);
+        for _ in 0..10_000 {
+            let len = 1 + rng.get_u32() as usize % 40;
+            let range = 1 + rng.get_u32() % 100;
+            let mut arr: Vec<u32> = (0..len).map(|_| rng.get_u32() % range).collect();
+            let k = rng.get_u32() as usize % len;
+            let selected = quickselect(&arr, k);
+            arr.sort();
+            assert_eq!(selected, Some(arr[k]));
+        }
+    }
 }
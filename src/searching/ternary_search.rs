@@ -31,6 +31,62 @@ pub fn ternary_search<T: Ord>(
     None
 }
 
+/// Returns the point of `[lo, hi]` where `f` is smallest, within `tol`.
+///
+/// `f` must be unimodal on the interval: decreasing and then increasing. Each
+/// step compares `f` at the two points a third of the way from either end,
+/// and drops the third beyond the larger value, until the interval left is
+/// shorter than `tol`.
+///
+/// A monotone `f` is unimodal too, with its minimum at an end: the result is
+/// then within `tol` of `lo` if `f` is increasing, and of `hi` if it is
+/// decreasing. If `f` is constant on a part of the interval, the values there
+/// compare equal and it may return any point of that part.
+pub fn ternary_search_argmin<F: Fn(f64) -> f64>(f: F, mut lo: f64, mut hi: f64, tol: f64) -> f64 {
+    while hi - lo >= tol {
+        let mid1 = lo + (hi - lo) / 3.0;
+        let mid2 = hi - (hi - lo) / 3.0;
+        // tol is below the precision of floats this large
+        if mid1 <= lo || mid2 >= hi {
+            break;
+        }
+        if f(mid1) < f(mid2) {
+            hi = mid2;
+        } else {
+            lo = mid1;
+        }
+    }
+    (lo + hi) / 2.0
+}
+
+/// Returns the index of the smallest element of a slice that strictly
+/// decreases and then strictly increases, or `None` if it is empty.
+///
+/// Either part may be empty, so a strictly increasing or decreasing slice
+/// gives its first or last index. Uses `O(log n)` comparisons.
+pub fn ternary_search_discrete_min<T: Ord>(arr: &[T]) -> Option<usize> {
+    if arr.is_empty() {
+        return None;
+    }
+    let mut lo = 0;
+    let mut hi = arr.len() - 1;
+    while hi - lo > 2 {
+        let mid1 = lo + (hi - lo) / 3;
+        let mid2 = hi - (hi - lo) / 3;
+        match arr[mid1].cmp(&arr[mid2]) {
+            // mid2 is in the increasing part, after the minimum
+            Ordering::Less => hi = mid2 - 1,
+            // mid1 is in the decreasing part, before the minimum
+            Ordering::Greater => lo = mid1 + 1,
+            Ordering::Equal => {
+                lo = mid1 + 1;
+                hi = mid2 - 1;
+            }
+        }
+    }
+    (lo..=hi).min_by_key(|&i| &arr[i])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +144,52 @@ mod tests {
         let index = ternary_search(&2, &vec![1, 2, 3], 0, 3);
         assert_eq!(index, Some(1));
     }
+
+    #[test]
+    fn finds_min_of_parabolas() {
+        let x = ternary_search_argmin(|x| x * x - 4.0 * x + 4.0, -100.0, 100.0, 1e-9);
+        assert!((x - 2.0).abs() < 1e-6);
+
+        let x = ternary_search_argmin(|x| (x - 5.0).powi(2) + 3.0, 0.0, 1e6, 1e-9);
+        assert!((x - 5.0).abs() < 1e-6);
+
+        let f = |x: f64| x * x - 2.0 * x + 3.0;
+        let x = ternary_search_argmin(f, -1e10, 1e10, 1e-7);
+        assert!((f(x) - 2.0).abs() < 1e-9);
+
+        // tol is smaller than the distance between floats around 1e10
+        let x = ternary_search_argmin(|x| (x - 1e10).abs(), 0.0, 2e10, 1e-9);
+        assert!((x - 1e10).abs() < 1e-3);
+    }
+
+    #[test]
+    fn monotone_functions_give_an_end() {
+        let x = ternary_search_argmin(|x| 3.0 * x + 1.0, -2.0, 8.0, 1e-6);
+        assert!((x + 2.0).abs() < 1e-5);
+
+        let x = ternary_search_argmin(|x| -x.powi(3), -2.0, 8.0, 1e-6);
+        assert!((x - 8.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn finds_valley_of_bitonic_array() {
+        let arr = [9, 7, 4, 2, 1, 3, 5, 8, 12, 20];
+        assert_eq!(ternary_search_discrete_min(&arr), Some(4));
+
+        for valley in 0..30 {
+            // decreasing down to index valley, then increasing
+            let arr: Vec<i32> = (0..30i32).map(|i| (i - valley).pow(2) + i).collect();
+            let expected = (0..30).min_by_key(|&i| arr[i]);
+            assert_eq!(ternary_search_discrete_min(&arr), expected);
+        }
+    }
+
+    #[test]
+    fn discrete_edge_cases() {
+        assert_eq!(ternary_search_discrete_min::<i32>(&[]), None);
+        assert_eq!(ternary_search_discrete_min(&[5]), Some(0));
+        assert_eq!(ternary_search_discrete_min(&[5, 3]), Some(1));
+        assert_eq!(ternary_search_discrete_min(&[1, 2, 3, 4, 5, 6, 7]), Some(0));
+        assert_eq!(ternary_search_discrete_min(&[7, 6, 5, 4, 3, 2, 1]), Some(6));
+    }
 }
@@ -10,6 +10,10 @@ pub fn bucket_sort(arr: &[usize]) -> Vec<usize> {
     }
 
     let max = *arr.iter().max().unwrap();
+    // every element is 0, and there is nothing to divide by
+    if max == 0 {
+        return arr.to_vec();
+    }
     let len = arr.len();
     let mut buckets = vec![vec![]; len + 1];
 
@@ -77,4 +81,11 @@ mod tests {
         let res = bucket_sort(&arr);
         assert!(is_sorted(&res));
     }
+
+    #[test]
+    fn all_zeros() {
+        let arr: Vec<usize> = vec![0, 0, 0];
+        let res = bucket_sort(&arr);
+        assert_eq!(res, vec![0, 0, 0]);
+    }
 }
@@ -1,10 +1,11 @@
 /// In place counting sort for collections of u32
-/// O(n + maxval) in time, where maxval is the biggest value an input can possibly take
-/// O(maxval) in memory
-/// u32 is chosen arbitrarly, a counting sort probably should'nt be used on data that requires bigger types.
-
-pub fn counting_sort(arr: &mut [u32], maxval: usize) {
-    let mut occurences: Vec<usize> = vec![0; maxval + 1];
+/// O(n + max_value) in time, where max_value is the biggest value an input can possibly take
+/// O(max_value) in memory
+/// u32 is chosen arbitrarily, a counting sort probably shouldn't be used on data that requires bigger types.
+///
+/// Panics if an element is greater than `max_value`.
+pub fn counting_sort(arr: &mut [u32], max_value: u32) {
+    let mut occurences: Vec<usize> = vec![0; max_value as usize + 1];
 
     for &data in arr.iter() {
         occurences[data as usize] += 1;
@@ -49,10 +50,52 @@ pub fn generic_counting_sort<T: Into<u64> + From<u8> + AddAssign + Copy>(
     }
 }
 
+/// In place bucket sort for floats in [0, 1), the generalization of counting sort
+/// to values that are not integers.
+///
+/// The values are put in `n` buckets of the same width, each one sorted with an
+/// insertion sort, and then put back in order. That is O(n) on average when the
+/// values are spread evenly over [0, 1), and O(n^2) when they all fall in the
+/// same bucket.
+///
+/// Panics if an element is not in [0, 1).
+pub fn bucket_sort_f64(arr: &mut [f64]) {
+    let n = arr.len();
+    let mut buckets: Vec<Vec<f64>> = vec![Vec::new(); n];
+    for &x in arr.iter() {
+        assert!((0.0..1.0).contains(&x), "{x} is not in [0, 1)");
+        // rounding could give n for the largest floats below 1
+        let index = ((x * n as f64) as usize).min(n - 1);
+        buckets[index].push(x);
+    }
+
+    let mut i = 0;
+    for mut bucket in buckets {
+        insertion_sort_floats(&mut bucket);
+        for x in bucket {
+            arr[i] = x;
+            i += 1;
+        }
+    }
+}
+
+fn insertion_sort_floats(arr: &mut [f64]) {
+    for i in 1..arr.len() {
+        let cur = arr[i];
+        let mut j = i;
+        while j > 0 && cur < arr[j - 1] {
+            arr[j] = arr[j - 1];
+            j -= 1;
+        }
+        arr[j] = cur;
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::super::is_sorted;
     use super::*;
+    use crate::math::PCG32;
 
     #[test]
     fn counting_sort_descending() {
@@ -85,4 +128,95 @@ mod test {
 
         assert!(is_sorted(&ve2));
     }
+
+    #[test]
+    fn counting_sort_empty_and_single() {
+        let mut empty: Vec<u32> = vec![];
+        counting_sort(&mut empty, 10);
+        assert!(empty.is_empty());
+        let mut single = vec![7];
+        counting_sort(&mut single, 7);
+        assert_eq!(single, [7]);
+    }
+
+    #[test]
+    fn counting_sort_max_value_zero() {
+        let mut zeros = vec![0; 5];
+        counting_sort(&mut zeros, 0);
+        assert_eq!(zeros, [0; 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn counting_sort_value_above_max() {
+        counting_sort(&mut [1, 5, 2], 4);
+    }
+
+    #[test]
+    fn counting_sort_matches_sort() {
+        let mut rng = PCG32::new_default(64);
+        for _ in 0..500 {
+            let max_value = rng.get_u32() % 1000;
+            let len = rng.get_u32() as usize % 200;
+            let mut arr: Vec<u32> = (0..len).map(|_| rng.get_u32() % (max_value + 1)).collect();
+            let mut expected = arr.clone();
+            expected.sort();
+            counting_sort(&mut arr, max_value);
+            assert_eq!(arr, expected);
+        }
+    }
+
+    #[test]
+    fn bucket_sort_empty_and_single() {
+        let mut empty: Vec<f64> = vec![];
+        bucket_sort_f64(&mut empty);
+        assert!(empty.is_empty());
+        let mut single = vec![0.5];
+        bucket_sort_f64(&mut single);
+        assert_eq!(single, [0.5]);
+    }
+
+    #[test]
+    fn bucket_sort_basic() {
+        let mut arr = vec![0.78, 0.17, 0.39, 0.26, 0.72, 0.94, 0.21, 0.12, 0.23, 0.68];
+        bucket_sort_f64(&mut arr);
+        assert_eq!(
+            arr,
+            [0.12, 0.17, 0.21, 0.23, 0.26, 0.39, 0.68, 0.72, 0.78, 0.94]
+        );
+        // the ends of the range, and repeated elements
+        let mut arr = vec![0.5, 0.0, 0.999_999_999_999_999_9, 0.5, 0.0];
+        bucket_sort_f64(&mut arr);
+        assert!(is_sorted(&arr));
+        assert_eq!(arr[0], 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bucket_sort_out_of_range() {
+        bucket_sort_f64(&mut [0.5, 1.0]);
+    }
+
+    #[test]
+    fn bucket_sort_matches_sort() {
+        let mut rng = PCG32::new_default(46);
+        for round in 0..500 {
+            let len = rng.get_u32() as usize % 200;
+            let mut arr: Vec<f64> = (0..len)
+                .map(|_| {
+                    let x = rng.get_u32() as f64 / (u32::MAX as f64 + 1.0);
+                    // clustered values fill a few buckets only
+                    if round % 2 == 0 {
+                        x
+                    } else {
+                        x * x * x * x
+                    }
+                })
+                .collect();
+            let mut expected = arr.clone();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            bucket_sort_f64(&mut arr);
+            assert_eq!(arr, expected);
+        }
+    }
 }
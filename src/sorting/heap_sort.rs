@@ -26,7 +26,7 @@
 /// left_child(i)  = 2*i + 1
 /// right_child(i) = 2*i + 2
 /// ```
-
+///
 /// # Algorithm
 ///
 /// Heap sort has two steps:
@@ -59,8 +59,16 @@ pub fn heap_sort<T: Ord>(arr: &mut [T]) {
     }
 }
 
-/// Convert `arr` into a max heap.
-fn heapify<T: Ord>(arr: &mut [T]) {
+/// Convert `arr` into a max heap in O(n), with Floyd's algorithm.
+///
+/// The leaves are max heaps already. Moving each parent down, from the last one
+/// to the root, makes each subtree a max heap in turn. Most nodes are close to
+/// the leaves and move down only a few levels, which is why it takes O(n) rather
+/// than the O(n log n) of pushing the elements one by one.
+pub fn heapify<T: Ord>(arr: &mut [T]) {
+    if arr.len() <= 1 {
+        return;
+    }
     let last_parent = (arr.len() - 2) / 2;
     for i in (0..=last_parent).rev() {
         move_down(arr, i);
@@ -84,9 +92,10 @@ fn move_down<T: Ord>(arr: &mut [T], mut root: usize) {
             left
         };
 
-        if arr[max] > arr[root] {
-            arr.swap(root, max);
+        if arr[max] <= arr[root] {
+            break;
         }
+        arr.swap(root, max);
         root = max;
     }
 }
@@ -94,6 +103,17 @@ fn move_down<T: Ord>(arr: &mut [T], mut root: usize) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::PCG32;
+
+    fn assert_max_heap<T: Ord + std::fmt::Debug>(arr: &[T]) {
+        for i in 0..arr.len() {
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < arr.len() {
+                    assert!(arr[i] >= arr[child], "{arr:?} at {i}");
+                }
+            }
+        }
+    }
 
     #[test]
     fn empty() {
@@ -136,4 +156,57 @@ mod tests {
         heap_sort(&mut arr);
         assert_eq!(&arr, &vec![542, 542, 542, 542]);
     }
+
+    #[test]
+    fn reverse_sorted_array() {
+        let mut arr: Vec<u32> = (0..100).rev().collect();
+        heap_sort(&mut arr);
+        assert_eq!(arr, (0..100).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn random_arrays() {
+        let mut rng = PCG32::new_default(65);
+        for round in 0..300 {
+            let len = rng.get_u32() as usize % 300;
+            // a small range makes many repeated elements
+            let range = if round % 2 == 0 { 5 } else { u32::MAX };
+            let mut arr: Vec<u32> = (0..len).map(|_| rng.get_u32() % range).collect();
+            let mut expected = arr.clone();
+            expected.sort();
+            heap_sort(&mut arr);
+            assert_eq!(arr, expected);
+        }
+        let mut words = vec!["pear", "fig", "apple", "fig", "kiwi"];
+        heap_sort(&mut words);
+        assert_eq!(words, ["apple", "fig", "fig", "kiwi", "pear"]);
+    }
+
+    #[test]
+    fn heapify_makes_max_heaps() {
+        let mut empty: [i32; 0] = [];
+        heapify(&mut empty);
+        let mut single = [3];
+        heapify(&mut single);
+        assert_eq!(single, [3]);
+
+        let mut sorted: Vec<i32> = (0..50).collect();
+        heapify(&mut sorted);
+        assert_max_heap(&sorted);
+        assert_eq!(sorted[0], 49);
+
+        let mut rng = PCG32::new_default(56);
+        for _ in 0..300 {
+            let len = rng.get_u32() as usize % 200;
+            let mut arr: Vec<u32> = (0..len).map(|_| rng.get_u32() % 20).collect();
+            let mut elements = arr.clone();
+            heapify(&mut arr);
+            assert_max_heap(&arr);
+            // the same elements, in another order
+            let mut heap_elements = arr.clone();
+            elements.sort();
+            heap_elements.sort();
+            assert_eq!(heap_elements, elements);
+        }
+    }
 }
@@ -0,0 +1,276 @@
+/// Sort a mutable slice using introsort.
+///
+/// Introsort is a quicksort that watches its recursion depth. A quicksort is
+/// fast on average, but some inputs make it pick bad pivots over and over, and
+/// take O(n^2) time. Once the depth goes over `2 * floor(log2(n))`, which it
+/// only does on such inputs, introsort sorts what is left with heap sort, which
+/// takes O(n log n) time whatever the input. The subslices of at most 16
+/// elements are sorted with insertion sort, faster than both on so few elements.
+///
+/// The pivot is the median of the first, middle and last elements, which makes
+/// sorted and reverse sorted inputs as fast as random ones.
+///
+/// Source: https://en.wikipedia.org/wiki/Introsort
+pub fn introsort<T: Ord>(arr: &mut [T]) {
+    if arr.len() <= 1 {
+        return;
+    }
+    let depth_limit = 2 * arr.len().ilog2() as usize;
+    introsort_aux(arr, depth_limit);
+}
+
+/// Subslices this small are sorted with insertion sort.
+const INSERTION_SORT_THRESHOLD: usize = 16;
+
+fn introsort_aux<T: Ord>(arr: &mut [T], depth_limit: usize) {
+    if arr.len() <= INSERTION_SORT_THRESHOLD {
+        insertion_sort(arr);
+        return;
+    }
+    if depth_limit == 0 {
+        super::heap_sort(arr);
+        return;
+    }
+    let pivot = partition(arr);
+    let (left, right) = arr.split_at_mut(pivot);
+    introsort_aux(left, depth_limit - 1);
+    introsort_aux(&mut right[1..], depth_limit - 1);
+}
+
+/// Moves the median of the first, middle and last elements to the front.
+fn move_median_of_three_to_front<T: Ord>(arr: &mut [T]) {
+    let (a, b, c) = (0, arr.len() / 2, arr.len() - 1);
+    let median = if (arr[a] <= arr[b]) == (arr[b] <= arr[c]) {
+        b
+    } else if (arr[b] <= arr[a]) == (arr[a] <= arr[c]) {
+        a
+    } else {
+        c
+    };
+    arr.swap(0, median);
+}
+
+/// Partitions `arr` around the median of three and returns the final index of
+/// the pivot, with the elements before it lesser or equal and the elements after
+/// it greater or equal.
+///
+/// Both scans stop on elements equal to the pivot, which splits slices of
+/// equal elements in the middle rather than at an end.
+fn partition<T: Ord>(arr: &mut [T]) -> usize {
+    move_median_of_three_to_front(arr);
+    let (mut i, mut j) = (1, arr.len() - 1);
+    loop {
+        while i <= j && arr[i] < arr[0] {
+            i += 1;
+        }
+        while i <= j && arr[j] > arr[0] {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        arr.swap(i, j);
+        i += 1;
+        j -= 1;
+    }
+    arr.swap(0, j);
+    j
+}
+
+/// Insertion sort with swaps, as the elements cannot be copied.
+fn insertion_sort<T: Ord>(arr: &mut [T]) {
+    for i in 1..arr.len() {
+        let mut j = i;
+        while j > 0 && arr[j] < arr[j - 1] {
+            arr.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+    use std::cell::{Cell, RefCell};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn small_arrays() {
+        let mut empty: Vec<i32> = vec![];
+        introsort(&mut empty);
+        assert!(empty.is_empty());
+        let mut single = vec![1];
+        introsort(&mut single);
+        assert_eq!(single, [1]);
+        let mut words = vec!["d", "bb", "a", "cc"];
+        introsort(&mut words);
+        assert_eq!(words, ["a", "bb", "cc", "d"]);
+    }
+
+    #[test]
+    fn sorted_and_reverse_sorted() {
+        for len in [16, 17, 100, 1000] {
+            let expected: Vec<u32> = (0..len).collect();
+            let mut sorted = expected.clone();
+            introsort(&mut sorted);
+            assert_eq!(sorted, expected);
+            let mut reversed: Vec<u32> = (0..len).rev().collect();
+            introsort(&mut reversed);
+            assert_eq!(reversed, expected);
+        }
+    }
+
+    #[test]
+    fn random_arrays() {
+        let mut rng = PCG32::new_default(66);
+        for round in 0..300 {
+            let len = rng.get_u32() as usize % 1000;
+            // a small range makes many repeated elements
+            let range = if round % 2 == 0 { 3 } else { u32::MAX };
+            let mut arr: Vec<u32> = (0..len).map(|_| rng.get_u32() % range).collect();
+            let mut expected = arr.clone();
+            expected.sort();
+            introsort(&mut arr);
+            assert_eq!(arr, expected);
+        }
+        let mut same = vec![7; 10_000];
+        introsort(&mut same);
+        assert_eq!(same, vec![7; 10_000]);
+    }
+
+    /// McIlroy's adversary, which decides the values of the elements while they
+    /// are being sorted so that every pivot is one of the smallest elements.
+    /// Source: https://www.cs.dartmouth.edu/~doug/mdmspe.pdf
+    struct Adversary {
+        values: Vec<usize>,
+        gas: usize,
+        solid: usize,
+        candidate: usize,
+    }
+
+    impl Adversary {
+        fn compare(&mut self, x: usize, y: usize) -> Ordering {
+            // an element still made of gas is greater than all the solid ones
+            if self.values[x] == self.gas && self.values[y] == self.gas {
+                let frozen = if x == self.candidate { x } else { y };
+                self.values[frozen] = self.solid;
+                self.solid += 1;
+            }
+            if self.values[x] == self.gas {
+                self.candidate = x;
+            } else if self.values[y] == self.gas {
+                self.candidate = y;
+            }
+            self.values[x].cmp(&self.values[y])
+        }
+    }
+
+    struct Lazy<'a> {
+        index: usize,
+        adversary: &'a RefCell<Adversary>,
+    }
+
+    impl PartialEq for Lazy<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.cmp(other).is_eq()
+        }
+    }
+
+    impl Eq for Lazy<'_> {}
+
+    impl PartialOrd for Lazy<'_> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Lazy<'_> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.adversary.borrow_mut().compare(self.index, other.index)
+        }
+    }
+
+    /// Returns an input of `n` elements that makes quicksort with the median of
+    /// three, without the depth limit, pick bad pivots all the way down.
+    fn median_of_three_killer(n: usize) -> Vec<usize> {
+        let adversary = RefCell::new(Adversary {
+            values: vec![n; n],
+            gas: n,
+            solid: 0,
+            candidate: 0,
+        });
+        let mut lazy: Vec<Lazy> = (0..n)
+            .map(|index| Lazy {
+                index,
+                adversary: &adversary,
+            })
+            .collect();
+        introsort_aux(&mut lazy, usize::MAX);
+        adversary.into_inner().values
+    }
+
+    struct Counted<'a> {
+        value: usize,
+        comparisons: &'a Cell<usize>,
+    }
+
+    impl PartialEq for Counted<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.cmp(other).is_eq()
+        }
+    }
+
+    impl Eq for Counted<'_> {}
+
+    impl PartialOrd for Counted<'_> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Counted<'_> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.comparisons.set(self.comparisons.get() + 1);
+            self.value.cmp(&other.value)
+        }
+    }
+
+    fn count_comparisons(values: &[usize], depth_limit: Option<usize>) -> usize {
+        let comparisons = Cell::new(0);
+        let mut arr: Vec<Counted> = values
+            .iter()
+            .map(|&value| Counted {
+                value,
+                comparisons: &comparisons,
+            })
+            .collect();
+        match depth_limit {
+            Some(depth_limit) => introsort_aux(&mut arr, depth_limit),
+            None => introsort(&mut arr),
+        }
+        assert!(arr.windows(2).all(|w| w[0].value <= w[1].value));
+        comparisons.get()
+    }
+
+    #[test]
+    fn heap_sort_fallback_on_adversarial_input() {
+        let n = 4000;
+        let killer = median_of_three_killer(n);
+        // without the depth limit, the quicksort takes a quadratic time
+        let quicksort_comparisons = count_comparisons(&killer, Some(usize::MAX));
+        assert!(quicksort_comparisons > n * n / 8, "{quicksort_comparisons}");
+        // with it, heap sort takes over and keeps it O(n log n)
+        let introsort_comparisons = count_comparisons(&killer, None);
+        let n_log_n = n * n.ilog2() as usize;
+        assert!(
+            introsort_comparisons < 6 * n_log_n,
+            "{introsort_comparisons}"
+        );
+        let mut sorted = killer.clone();
+        introsort(&mut sorted);
+        let mut expected = killer;
+        expected.sort();
+        assert_eq!(sorted, expected);
+    }
+}
@@ -10,15 +10,18 @@ mod exchange_sort;
 mod gnome_sort;
 mod heap_sort;
 mod insertion_sort;
+mod introsort;
 mod merge_sort;
 mod odd_even_sort;
 mod pancake_sort;
+mod parallel_merge_sort;
 mod patience_sort;
 mod pigeonhole_sort;
 mod quick_sort;
 mod radix_sort;
 mod selection_sort;
 mod shell_sort;
+mod shuffle;
 mod sleep_sort;
 mod stooge_sort;
 mod tim_sort;
@@ -28,24 +31,29 @@ pub use self::bubble_sort::bubble_sort;
 pub use self::bucket_sort::bucket_sort;
 pub use self::cocktail_shaker_sort::cocktail_shaker_sort;
 pub use self::comb_sort::comb_sort;
-pub use self::counting_sort::counting_sort;
 pub use self::counting_sort::generic_counting_sort;
+pub use self::counting_sort::{bucket_sort_f64, counting_sort};
 pub use self::cycle_sort::cycle_sort;
 pub use self::dutch_national_flag_sort::dutch_national_flag_sort;
 pub use self::exchange_sort::exchange_sort;
 pub use self::gnome_sort::gnome_sort;
-pub use self::heap_sort::heap_sort;
+pub use self::heap_sort::{heap_sort, heapify};
 pub use self::insertion_sort::insertion_sort;
+pub use self::introsort::introsort;
 pub use self::merge_sort::bottom_up_merge_sort;
 pub use self::merge_sort::top_down_merge_sort;
 pub use self::odd_even_sort::odd_even_sort;
 pub use self::pancake_sort::pancake_sort;
+pub use self::parallel_merge_sort::parallel_merge_sort;
 pub use self::patience_sort::patience_sort;
 pub use self::pigeonhole_sort::pigeonhole_sort;
 pub use self::quick_sort::{partition, quick_sort};
-pub use self::radix_sort::radix_sort;
+pub use self::radix_sort::{radix_sort, radix_sort_with_base, RadixKey};
 pub use self::selection_sort::selection_sort;
 pub use self::shell_sort::shell_sort;
+#[cfg(feature = "sort-instrumentation")]
+pub use self::shell_sort::shell_sort_comparisons;
+pub use self::shuffle::{fisher_yates_shuffle, fisher_yates_shuffle_seeded};
 pub use self::sleep_sort::sleep_sort;
 pub use self::stooge_sort::stooge_sort;
 pub use self::tim_sort::tim_sort;
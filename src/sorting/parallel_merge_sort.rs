@@ -0,0 +1,177 @@
+/// Sort a mutable slice using a merge sort that sorts both halves in parallel.
+///
+/// With the `rayon` feature, the halves are sorted with `rayon::join`, which runs
+/// them on two threads of its pool when one is idle. Without it, they are sorted
+/// one after the other, and it is a sequential merge sort. Subslices of at most
+/// `SEQUENTIAL_THRESHOLD` elements, too small for the threads to be worth it,
+/// are sorted with the standard library sort.
+///
+/// The merge is stable and needs no copy of the elements: it computes where
+/// each element goes, and then moves them there by following the cycles of
+/// that permutation with swaps, in O(n) time and O(n) extra indices.
+pub fn parallel_merge_sort<T: Ord + Send>(arr: &mut [T]) {
+    merge_sort_with_threshold(arr, SEQUENTIAL_THRESHOLD);
+}
+
+/// Subslices this small are sorted sequentially.
+const SEQUENTIAL_THRESHOLD: usize = 1024;
+
+#[cfg(feature = "rayon")]
+use rayon::join;
+
+/// Runs both closures one after the other, like `rayon::join` does on a single thread.
+#[cfg(not(feature = "rayon"))]
+fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    (a(), b())
+}
+
+fn merge_sort_with_threshold<T: Ord + Send>(arr: &mut [T], threshold: usize) {
+    if arr.len() <= threshold.max(1) {
+        arr.sort();
+        return;
+    }
+    let mid = arr.len() / 2;
+    let (left, right) = arr.split_at_mut(mid);
+    join(
+        || merge_sort_with_threshold(left, threshold),
+        || merge_sort_with_threshold(right, threshold),
+    );
+    merge(arr, mid);
+}
+
+/// Merges `arr[..mid]` and `arr[mid..]`, both sorted, keeping equal elements in
+/// their order.
+fn merge<T: Ord>(arr: &mut [T], mid: usize) {
+    // order[k] is the index the element going at index k is at now
+    let mut order = Vec::with_capacity(arr.len());
+    let (mut l, mut r) = (0, mid);
+    while l < mid || r < arr.len() {
+        if r == arr.len() || (l < mid && arr[l] <= arr[r]) {
+            order.push(l);
+            l += 1;
+        } else {
+            order.push(r);
+            r += 1;
+        }
+    }
+
+    for start in 0..arr.len() {
+        // each cycle of the permutation is done from its first index
+        let mut k = start;
+        while order[k] != start && order[k] != k {
+            let source = order[k];
+            arr.swap(k, source);
+            order[k] = k;
+            k = source;
+        }
+        order[k] = k;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    #[test]
+    fn small_arrays() {
+        let mut empty: Vec<i32> = vec![];
+        parallel_merge_sort(&mut empty);
+        assert!(empty.is_empty());
+        let mut single = vec![1];
+        parallel_merge_sort(&mut single);
+        assert_eq!(single, [1]);
+        let mut words = vec!["d".to_string(), "bb".to_string(), "a".to_string()];
+        parallel_merge_sort(&mut words);
+        assert_eq!(words, ["a", "bb", "d"]);
+    }
+
+    #[test]
+    fn merges_down_to_single_elements() {
+        // with no sequential sort, every element goes through the merges
+        let mut rng = PCG32::new_default(67);
+        for round in 0..300 {
+            let len = rng.get_u32() as usize % 300;
+            let range = if round % 2 == 0 { 4 } else { u32::MAX };
+            let mut arr: Vec<u32> = (0..len).map(|_| rng.get_u32() % range).collect();
+            let mut expected = arr.clone();
+            expected.sort();
+            merge_sort_with_threshold(&mut arr, 1);
+            assert_eq!(arr, expected);
+        }
+    }
+
+    #[test]
+    fn stable() {
+        // sorting by key only, equal keys must keep the order of their indices
+        #[derive(Debug)]
+        struct Item(u32, usize);
+        impl PartialEq for Item {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Item {}
+        impl PartialOrd for Item {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Item {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut rng = PCG32::new_default(76);
+        let mut items: Vec<Item> = (0..5000).map(|i| Item(rng.get_u32() % 10, i)).collect();
+        merge_sort_with_threshold(&mut items, 8);
+        for w in items.windows(2) {
+            assert!(w[0].0 < w[1].0 || (w[0].0 == w[1].0 && w[0].1 < w[1].1));
+        }
+    }
+
+    #[test]
+    fn large_arrays() {
+        let mut rng = PCG32::new_default(7);
+        let mut arr: Vec<u64> = (0..100_000).map(|_| rng.get_u64()).collect();
+        let mut expected = arr.clone();
+        expected.sort();
+        parallel_merge_sort(&mut arr);
+        assert_eq!(arr, expected);
+
+        let mut reversed: Vec<u32> = (0..50_000).rev().collect();
+        parallel_merge_sort(&mut reversed);
+        assert_eq!(reversed, (0..50_000).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    #[ignore]
+    #[cfg(feature = "rayon")]
+    fn faster_than_sequential() {
+        // Run this in release mode, on several cores: the halves are sorted on
+        // as many threads as there are cores
+        use std::time::Instant;
+        let mut rng = PCG32::new_default(1);
+        let arr: Vec<u64> = (0..1_000_000).map(|_| rng.get_u64()).collect();
+
+        let mut parallel = arr.clone();
+        let start = Instant::now();
+        parallel_merge_sort(&mut parallel);
+        let parallel_time = start.elapsed();
+
+        let mut sequential = arr;
+        let start = Instant::now();
+        sequential.sort();
+        let sequential_time = start.elapsed();
+
+        assert_eq!(parallel, sequential);
+        assert!(parallel_time < sequential_time);
+    }
+}
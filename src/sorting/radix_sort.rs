@@ -1,43 +1,134 @@
-/// Sorts the elements of `arr` in-place using radix sort.
+/// Unsigned integer keys that can be split into fixed-width digits.
+pub trait RadixKey: Copy {
+    /// Number of bits in the key type.
+    const BITS: u32;
+
+    /// Returns the digit of `self` that starts at bit `shift`, masked by `mask`.
+    fn digit(self, shift: u32, mask: usize) -> usize;
+
+    /// Returns the number of significant bits in `self`.
+    fn significant_bits(self) -> u32;
+
+    /// Returns `self` widened to a `u64`.
+    fn to_u64(self) -> u64;
+}
+
+macro_rules! impl_radix_key {
+    ($($t:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                #[inline]
+                fn digit(self, shift: u32, mask: usize) -> usize {
+                    (self >> shift) as usize & mask
+                }
+
+                #[inline]
+                fn significant_bits(self) -> u32 {
+                    Self::BITS - self.leading_zeros()
+                }
+
+                #[inline]
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_key!(u8, u16, u32, u64, usize);
+
+/// The default radix, sorting one byte per pass.
+pub const DEFAULT_RADIX: usize = 256;
+
+/// Sorts the elements of `arr` in-place using LSD radix sort in base 256.
 ///
 /// Time complexity is `O((n + b) * logb(k))`, where `n` is the number of elements,
 /// `b` is the base (the radix), and `k` is the largest element.
 /// When `n` and `b` are roughly the same maginitude, this algorithm runs in linear time.
 ///
 /// Space complexity is `O(n + b)`.
-pub fn radix_sort(arr: &mut [u64]) {
-    let max: usize = match arr.iter().max() {
-        Some(&x) => x as usize,
-        None => return,
-    };
-    // Make radix a power of 2 close to arr.len() for optimal runtime
-    let radix = arr.len().next_power_of_two();
-    // Counting sort by each digit from least to most significant
-    let mut place = 1;
-    while place <= max {
-        let digit_of = |x| x as usize / place % radix;
-        // Count digit occurrences
-        let mut counter = vec![0; radix];
-        for &x in arr.iter() {
-            counter[digit_of(x)] += 1;
-        }
-        // Compute last index of each digit
-        for i in 1..radix {
-            counter[i] += counter[i - 1];
+pub fn radix_sort<T: RadixKey>(arr: &mut [T]) {
+    radix_sort_with_base(arr, DEFAULT_RADIX);
+}
+
+/// Sorts the elements of `arr` in-place using LSD radix sort in the given `base`,
+/// and returns the number of counting passes that were performed.
+///
+/// Only as many passes as there are digits in the largest element are made, so
+/// a slice whose values all fit in a single digit is sorted in one pass. Digits
+/// in a power of two base are taken with shifts and masks, and in any other
+/// base, such as 10, with division.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+pub fn radix_sort_with_base<T: RadixKey>(arr: &mut [T], base: usize) -> usize {
+    assert!(base >= 2, "radix must be at least two");
+    if arr.len() < 2 {
+        return 0;
+    }
+    let mut buffer = arr.to_vec();
+    let mut passes = 0;
+    if base.is_power_of_two() {
+        let max_bits = arr.iter().map(|&x| x.significant_bits()).max().unwrap_or(0);
+        let digit_bits = base.trailing_zeros();
+        let mask = base - 1;
+        let mut shift = 0;
+        while shift < max_bits {
+            counting_pass(arr, &mut buffer, base, |x| x.digit(shift, mask));
+            passes += 1;
+            shift += digit_bits;
         }
-        // Write elements to their new indices
-        for &x in arr.to_owned().iter().rev() {
-            counter[digit_of(x)] -= 1;
-            arr[counter[digit_of(x)]] = x;
+    } else {
+        let max = arr.iter().map(|&x| x.to_u64()).max().unwrap_or(0);
+        let radix = base as u64;
+        // the value of a unit of the current digit, None once it exceeds u64
+        let mut place = Some(1u64);
+        while let Some(unit) = place.filter(|&unit| unit <= max) {
+            counting_pass(arr, &mut buffer, base, |x| {
+                (x.to_u64() / unit % radix) as usize
+            });
+            passes += 1;
+            place = unit.checked_mul(radix);
         }
-        place *= radix;
+    }
+    passes
+}
+
+/// Stably sorts `arr` by the digit of each element, which is less than `base`,
+/// using `buffer`, of the same length, as scratch space.
+fn counting_pass<T: Copy>(
+    arr: &mut [T],
+    buffer: &mut [T],
+    base: usize,
+    digit: impl Fn(T) -> usize,
+) {
+    // Count digit occurrences
+    let mut counter = vec![0usize; base];
+    for &x in arr.iter() {
+        counter[digit(x)] += 1;
+    }
+    // Compute last index of each digit
+    for i in 1..base {
+        counter[i] += counter[i - 1];
+    }
+    // Write elements to their new indices, walking backwards for stability
+    buffer.copy_from_slice(arr);
+    for &x in buffer.iter().rev() {
+        let d = digit(x);
+        counter[d] -= 1;
+        arr[counter[d]] = x;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::is_sorted;
-    use super::radix_sort;
+    use super::{radix_sort, radix_sort_with_base};
+    use crate::math::PCG32;
 
     #[test]
     fn empty() {
@@ -48,15 +139,104 @@ mod tests {
 
     #[test]
     fn descending() {
-        let mut v = vec![201, 127, 64, 37, 24, 4, 1];
+        let mut v: Vec<u64> = vec![201, 127, 64, 37, 24, 4, 1];
         radix_sort(&mut v);
         assert!(is_sorted(&v));
     }
 
     #[test]
     fn ascending() {
-        let mut v = vec![1, 4, 24, 37, 64, 127, 201];
+        let mut v: Vec<u64> = vec![1, 4, 24, 37, 64, 127, 201];
         radix_sort(&mut v);
         assert!(is_sorted(&v));
     }
+
+    #[test]
+    fn single_element() {
+        let mut v = [42u32];
+        assert_eq!(radix_sort_with_base(&mut v, 256), 0);
+        assert_eq!(v, [42]);
+    }
+
+    #[test]
+    fn identical_elements() {
+        let mut v = [7u32; 16];
+        radix_sort(&mut v);
+        assert_eq!(v, [7; 16]);
+    }
+
+    #[test]
+    fn single_digit() {
+        let mut v = [200u32, 3, 255, 0, 17, 3];
+        assert_eq!(radix_sort_with_base(&mut v, 256), 1);
+        assert_eq!(v, [0, 3, 3, 17, 200, 255]);
+    }
+
+    #[test]
+    fn random_u32() {
+        let mut rng = PCG32::new_default(271828);
+        for &base in &[2, 16, 256, 65536] {
+            let mut v: Vec<u32> = rng.iter_mut().take(1000).collect();
+            let mut expected = v.clone();
+            expected.sort();
+            radix_sort_with_base(&mut v, base);
+            assert_eq!(v, expected);
+        }
+    }
+
+    #[test]
+    fn other_widths() {
+        let mut rng = PCG32::new_default(141421);
+        let mut small: Vec<u16> = (0..500).map(|_| rng.get_u16().0).collect();
+        let mut expected = small.clone();
+        expected.sort();
+        radix_sort(&mut small);
+        assert_eq!(small, expected);
+
+        let mut large: Vec<u64> = (0..500).map(|_| rng.get_u64()).collect();
+        let mut expected = large.clone();
+        expected.sort();
+        radix_sort(&mut large);
+        assert_eq!(large, expected);
+    }
+
+    #[test]
+    fn pass_count() {
+        // Every value uses the full 32 bits, so base 256 needs exactly 4 passes
+        // and base 16 exactly 8, independently of the number of elements.
+        let mut rng = PCG32::new_default(1729);
+        for &len in &[10, 100, 1000] {
+            let mut v: Vec<u32> = rng.iter_mut().take(len).map(|x| x | 1 << 31).collect();
+            assert_eq!(radix_sort_with_base(&mut v.clone(), 16), 8);
+            assert_eq!(radix_sort_with_base(&mut v, 256), 4);
+            assert!(is_sorted(&v));
+        }
+    }
+
+    #[test]
+    fn any_base() {
+        let mut v = [170u32, 45, 75, 90, 802, 24, 2, 66];
+        assert_eq!(radix_sort_with_base(&mut v, 10), 3);
+        assert_eq!(v, [2, 24, 45, 66, 75, 90, 170, 802]);
+
+        let mut rng = PCG32::new_default(314159);
+        for &base in &[3, 10, 1000, 100_000] {
+            let mut v: Vec<u64> = (0..1000).map(|_| rng.get_u64()).collect();
+            let mut expected = v.clone();
+            expected.sort();
+            radix_sort_with_base(&mut v, base);
+            assert_eq!(v, expected);
+        }
+
+        // the place of the last digit would overflow a u64
+        let mut v = [u64::MAX, 0, u64::MAX - 1, 1 << 63];
+        assert_eq!(radix_sort_with_base(&mut v, 10), 20);
+        assert_eq!(v, [0, 1 << 63, u64::MAX - 1, u64::MAX]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_base() {
+        radix_sort_with_base(&mut [3u32, 1, 2], 1);
+    }
 }
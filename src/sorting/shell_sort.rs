@@ -1,35 +1,74 @@
-pub fn shell_sort<T: Ord + Copy>(values: &mut [T]) {
+/// Marcin Ciura's empirically derived gap sequence.
+pub const CIURA_GAPS: [usize; 8] = [1, 4, 10, 23, 57, 132, 301, 701];
+
+/// Sorts `values` in-place using Shell sort.
+///
+/// `gaps` is an ascending gap sequence; when `None`, the Ciura sequence is used,
+/// extended by a factor of 2.25 for slices longer than its largest gap. Gaps that
+/// are not smaller than the slice length are skipped, and a final pass with gap 1
+/// is always made so that any sequence yields a sorted result.
+pub fn shell_sort<T: Ord>(values: &mut [T], gaps: Option<&[usize]>) {
+    sort_with_gaps(values, gaps, || {});
+}
+
+/// Sorts `values` like [`shell_sort`] and returns the number of comparisons made,
+/// so that gap sequences can be compared empirically.
+#[cfg(feature = "sort-instrumentation")]
+pub fn shell_sort_comparisons<T: Ord>(values: &mut [T], gaps: Option<&[usize]>) -> usize {
+    let mut comparisons = 0;
+    sort_with_gaps(values, gaps, || comparisons += 1);
+    comparisons
+}
+
+fn default_gaps(len: usize) -> Vec<usize> {
+    let mut gaps = CIURA_GAPS.to_vec();
+    let mut gap = *gaps.last().unwrap();
+    while gap < len {
+        gap = gap * 9 / 4;
+        gaps.push(gap);
+    }
+    gaps
+}
+
+fn sort_with_gaps<T: Ord>(values: &mut [T], gaps: Option<&[usize]>, mut on_compare: impl FnMut()) {
+    let gaps = match gaps {
+        Some(gaps) => gaps.to_vec(),
+        None => default_gaps(values.len()),
+    };
+
     // shell sort works by swiping the value at a given gap and decreasing the gap to 1
-    fn insertion<T: Ord + Copy>(values: &mut [T], start: usize, gap: usize) {
-        for i in ((start + gap)..values.len()).step_by(gap) {
-            let val_current = values[i];
+    let len = values.len();
+    let usable = gaps
+        .iter()
+        .rev()
+        .copied()
+        .filter(|&gap| gap > 1 && gap < len);
+    for gap in usable.chain(std::iter::once(1)) {
+        for i in gap..len {
             let mut pos = i;
             // make swaps
-            while pos >= gap && values[pos - gap] > val_current {
-                values[pos] = values[pos - gap];
+            while pos >= gap {
+                on_compare();
+                if values[pos - gap] <= values[pos] {
+                    break;
+                }
+                values.swap(pos - gap, pos);
                 pos -= gap;
             }
-            values[pos] = val_current;
-        }
-    }
-
-    let mut count_sublist = values.len() / 2; // makes gap as long as half of the array
-    while count_sublist > 0 {
-        for pos_start in 0..count_sublist {
-            insertion(values, pos_start, count_sublist);
         }
-        count_sublist /= 2; // makes gap as half of previous
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::shell_sort;
+    use super::*;
+    use crate::math::PCG32;
+    use crate::sorting::is_sorted;
 
     #[test]
     fn basic() {
         let mut vec = vec![3, 5, 6, 3, 1, 4];
-        shell_sort(&mut vec);
+        shell_sort(&mut vec, None);
         for i in 0..vec.len() - 1 {
             assert!(vec[i] <= vec[i + 1]);
         }
@@ -38,14 +77,14 @@ mod test {
     #[test]
     fn empty() {
         let mut vec: Vec<i32> = vec![];
-        shell_sort(&mut vec);
+        shell_sort(&mut vec, None);
         assert_eq!(vec, vec![]);
     }
 
     #[test]
     fn reverse() {
         let mut vec = vec![6, 5, 4, 3, 2, 1];
-        shell_sort(&mut vec);
+        shell_sort(&mut vec, None);
         for i in 0..vec.len() - 1 {
             assert!(vec[i] <= vec[i + 1]);
         }
@@ -54,9 +93,67 @@ mod test {
     #[test]
     fn already_sorted() {
         let mut vec = vec![1, 2, 3, 4, 5, 6];
-        shell_sort(&mut vec);
+        shell_sort(&mut vec, None);
         for i in 0..vec.len() - 1 {
             assert!(vec[i] <= vec[i + 1]);
         }
     }
+
+    #[test]
+    fn not_copy() {
+        let mut vec = vec!["pear".to_string(), "apple".to_string(), "fig".to_string()];
+        shell_sort(&mut vec, None);
+        assert_eq!(vec, ["apple", "fig", "pear"]);
+    }
+
+    #[test]
+    fn gap_sequences() {
+        let powers_of_two: Vec<usize> = (0..14).map(|k| 1 << k).collect();
+        let knuth: Vec<usize> = std::iter::successors(Some(1usize), |&h| Some(3 * h + 1))
+            .take_while(|&h| h < 10_000)
+            .collect();
+        let without_one = [5, 19, 41];
+        let sequences: [Option<&[usize]>; 5] = [
+            None,
+            Some(&CIURA_GAPS),
+            Some(&powers_of_two),
+            Some(&knuth),
+            Some(&without_one),
+        ];
+
+        let mut rng = PCG32::new_default(314);
+        for len in [0, 1, 2, 10, 100, 1000, 10_000] {
+            let random: Vec<u32> = rng.iter_mut().take(len).collect();
+            let ascending: Vec<u32> = (0..len as u32).collect();
+            let descending: Vec<u32> = (0..len as u32).rev().collect();
+            for input in [random, ascending, descending] {
+                let mut expected = input.clone();
+                expected.sort();
+                for gaps in sequences {
+                    let mut vec = input.clone();
+                    shell_sort(&mut vec, gaps);
+                    assert!(is_sorted(&vec));
+                    assert_eq!(vec, expected);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "sort-instrumentation")]
+    #[test]
+    fn comparisons() {
+        let mut sorted: Vec<u32> = (0..1000).collect();
+        // An already sorted slice needs one comparison per element for each gap
+        let gaps = [1, 4, 10];
+        assert_eq!(
+            shell_sort_comparisons(&mut sorted, Some(&gaps)),
+            999 + 996 + 990
+        );
+
+        let mut rng = PCG32::new_default(2718);
+        let random: Vec<u32> = rng.iter_mut().take(5000).collect();
+        let ciura = shell_sort_comparisons(&mut random.clone(), None);
+        let insertion = shell_sort_comparisons(&mut random.clone(), Some(&[1]));
+        assert!(ciura < insertion);
+    }
 }
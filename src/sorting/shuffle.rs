@@ -0,0 +1,176 @@
+// The Fisher-Yates shuffle puts the elements of a slice in a uniformly random
+// order: going from the last position down, it swaps the element there with
+// one picked at random among those not placed yet, itself included. Each of
+// the n! orders comes from exactly one sequence of picks, in O(n) time.
+// Source: https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle
+
+/// Shuffles `slice` in place, taking its random numbers from `rng`.
+///
+/// `rng(bound)` must return an index in `0..bound` that is uniformly random,
+/// for the shuffle to be, so any source of randomness can be used, a seeded one
+/// giving the same order every time.
+///
+/// Panics if `rng` returns an index out of its bound.
+pub fn fisher_yates_shuffle<T, R: FnMut(usize) -> usize>(slice: &mut [T], mut rng: R) {
+    for i in (1..slice.len()).rev() {
+        let j = rng(i + 1);
+        assert!(j <= i, "rng({}) returned {j}", i + 1);
+        slice.swap(i, j);
+    }
+}
+
+/// Shuffles `slice` in place with random numbers from a Xorshift64 generator
+/// seeded with `seed`, so that the same seed always gives the same order.
+pub fn fisher_yates_shuffle_seeded<T>(slice: &mut [T], seed: u64) {
+    let mut generator = Xorshift64::new(seed);
+    fisher_yates_shuffle(slice, |bound| generator.below(bound));
+}
+
+/// Marsaglia's xorshift generator, fast and simple but not meant for cryptography.
+/// Source: https://www.jstatsoft.org/article/view/v008i14
+struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    fn new(seed: u64) -> Self {
+        // the state must not be 0, which would only ever give 0
+        Xorshift64 {
+            state: if seed == 0 {
+                0x9e37_79b9_7f4a_7c15
+            } else {
+                seed
+            },
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// A uniform number in `0..bound`, rejecting the largest numbers that would
+    /// make the smallest remainders a little more likely.
+    fn below(&mut self, bound: usize) -> usize {
+        let bound = bound as u64;
+        let limit = u64::MAX - u64::MAX % bound;
+        loop {
+            let x = self.next();
+            if x < limit {
+                return (x % bound) as usize;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+    use std::collections::HashMap;
+
+    fn sorted<T: Ord + Clone>(slice: &[T]) -> Vec<T> {
+        let mut sorted = slice.to_vec();
+        sorted.sort();
+        sorted
+    }
+
+    #[test]
+    fn empty_and_single() {
+        let mut empty: [i32; 0] = [];
+        fisher_yates_shuffle(&mut empty, |_| unreachable!());
+        fisher_yates_shuffle_seeded(&mut empty, 1);
+        let mut single = [7];
+        fisher_yates_shuffle(&mut single, |_| unreachable!());
+        fisher_yates_shuffle_seeded(&mut single, 1);
+        assert_eq!(single, [7]);
+    }
+
+    #[test]
+    fn keeps_the_elements() {
+        let original: Vec<u32> = (0..1000).map(|i| i % 37).collect();
+        for seed in 0..20 {
+            let mut shuffled = original.clone();
+            fisher_yates_shuffle_seeded(&mut shuffled, seed);
+            assert_eq!(sorted(&shuffled), sorted(&original));
+        }
+        let mut words = ["a", "b", "b", "c", "c", "c"];
+        let mut rng = PCG32::new_default(61);
+        fisher_yates_shuffle(&mut words, |bound| rng.get_u32() as usize % bound);
+        assert_eq!(sorted(&words), ["a", "b", "b", "c", "c", "c"]);
+    }
+
+    #[test]
+    fn seeded_is_deterministic() {
+        let mut a: Vec<u32> = (0..100).collect();
+        let mut b = a.clone();
+        fisher_yates_shuffle_seeded(&mut a, 42);
+        fisher_yates_shuffle_seeded(&mut b, 42);
+        assert_eq!(a, b);
+        assert_ne!(a, (0..100).collect::<Vec<u32>>());
+        fisher_yates_shuffle_seeded(&mut b, 43);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn uses_the_given_picks() {
+        // always picking the first element rotates the slice to the left
+        let mut values = [1, 2, 3, 4];
+        fisher_yates_shuffle(&mut values, |_| 0);
+        assert_eq!(values, [2, 3, 4, 1]);
+        // always picking the element itself leaves the slice as it is
+        let mut values = [1, 2, 3, 4];
+        fisher_yates_shuffle(&mut values, |bound| bound - 1);
+        assert_eq!(values, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_out_of_bound_indices() {
+        let mut values = [1, 2, 3];
+        fisher_yates_shuffle(&mut values, |bound| bound);
+    }
+
+    #[test]
+    fn permutations_are_uniform() {
+        const TRIALS: usize = 100_000;
+        let mut generator = Xorshift64::new(2024);
+        let mut counts: HashMap<[u8; 4], usize> = HashMap::new();
+        for _ in 0..TRIALS {
+            let mut values = [0, 1, 2, 3];
+            fisher_yates_shuffle(&mut values, |bound| generator.below(bound));
+            *counts.entry(values).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 24);
+        let expected = TRIALS as f64 / 24.0;
+        let chi_squared: f64 = counts
+            .values()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        // the 99.9th percentile of the chi-squared distribution with 23 degrees of freedom
+        assert!(chi_squared < 49.73, "chi-squared {chi_squared}");
+    }
+
+    #[test]
+    fn biased_picks_are_detected() {
+        // the classic mistake of picking among all the elements every time
+        const TRIALS: usize = 100_000;
+        let mut generator = Xorshift64::new(2024);
+        let mut counts: HashMap<[u8; 3], usize> = HashMap::new();
+        for _ in 0..TRIALS {
+            let mut values = [0, 1, 2];
+            for i in (1..3).rev() {
+                values.swap(i, generator.below(3));
+            }
+            *counts.entry(values).or_insert(0) += 1;
+        }
+        let expected = TRIALS as f64 / 6.0;
+        let chi_squared: f64 = counts
+            .values()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_squared > 20.52, "chi-squared {chi_squared}");
+    }
+}
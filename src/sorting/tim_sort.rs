@@ -1,7 +1,15 @@
 use std::cmp;
 
-static MIN_MERGE: usize = 32;
+/// Arrays shorter than this are sorted with a single insertion sort.
+const MIN_MERGE: usize = 64;
+/// Number of consecutive wins by one run before a merge switches to galloping mode.
+const MIN_GALLOP: usize = 7;
 
+/// Computes the minimum run length for an array of length `n`.
+///
+/// For `n < MIN_MERGE` this is `n` itself, otherwise it is a value `k` in
+/// `MIN_MERGE / 2..=MIN_MERGE` such that `n / k` is close to, but strictly
+/// less than, a power of two.
 fn min_run_length(mut n: usize) -> usize {
     let mut r = 0;
     while n >= MIN_MERGE {
@@ -11,95 +19,178 @@ fn min_run_length(mut n: usize) -> usize {
     n + r
 }
 
-fn insertion_sort(arr: &mut Vec<i32>, left: usize, right: usize) -> &Vec<i32> {
-    for i in (left + 1)..(right + 1) {
-        let temp = arr[i];
-        let mut j = (i - 1) as i32;
+/// Returns the length of the run starting at the beginning of `arr`,
+/// reversing it first if it is strictly descending.
+fn count_run_and_make_ascending<T: Ord>(arr: &mut [T]) -> usize {
+    let n = arr.len();
+    if n < 2 {
+        return n;
+    }
+    let mut end = 2;
+    if arr[1] < arr[0] {
+        // Strictly descending runs only, so reversing them keeps the sort stable
+        while end < n && arr[end] < arr[end - 1] {
+            end += 1;
+        }
+        arr[..end].reverse();
+    } else {
+        while end < n && arr[end] >= arr[end - 1] {
+            end += 1;
+        }
+    }
+    end
+}
 
-        while j >= (left as i32) && arr[j as usize] > temp {
-            arr[(j + 1) as usize] = arr[j as usize];
+/// Sorts `arr` by insertion, assuming the first `sorted` elements are already in order.
+fn insertion_sort<T: Ord>(arr: &mut [T], sorted: usize) {
+    for i in cmp::max(sorted, 1)..arr.len() {
+        let mut j = i;
+        while j > 0 && arr[j] < arr[j - 1] {
+            arr.swap(j, j - 1);
             j -= 1;
         }
-        arr[(j + 1) as usize] = temp;
     }
-    arr
 }
 
-fn merge(arr: &mut Vec<i32>, l: usize, m: usize, r: usize) -> &Vec<i32> {
-    let len1 = m - l + 1;
-    let len2 = r - m;
-    let mut left = vec![0; len1];
-    let mut right = vec![0; len2];
-
-    left[..len1].clone_from_slice(&arr[l..(len1 + l)]);
-
-    for x in 0..len2 {
-        right[x] = arr[m + 1 + x];
+/// Returns the length of the longest prefix of `run` whose elements satisfy `pred`,
+/// probing at exponentially growing offsets before finishing with a binary search.
+fn gallop<T>(run: &[T], pred: impl Fn(&T) -> bool) -> usize {
+    let mut hi = 1;
+    while hi <= run.len() && pred(&run[hi - 1]) {
+        hi *= 2;
     }
+    let lo = hi / 2;
+    let hi = cmp::min(hi, run.len());
+    lo + run[lo..hi].partition_point(pred)
+}
 
-    let mut i = 0;
-    let mut j = 0;
-    let mut k = l;
+/// Merges the adjacent sorted runs `arr[..mid]` and `arr[mid..]`.
+fn merge<T: Ord + Clone>(arr: &mut [T], mid: usize) {
+    let left = arr[..mid].to_vec();
+    let (mut i, mut j, mut k) = (0, mid, 0);
+    let (mut left_wins, mut right_wins) = (0, 0);
 
-    while i < len1 && j < len2 {
-        if left[i] <= right[j] {
-            arr[k] = left[i];
-            i += 1;
-        } else {
-            arr[k] = right[j];
+    while i < left.len() && j < arr.len() {
+        if left_wins >= MIN_GALLOP {
+            // Copy every left element that does not exceed the next right one
+            let count = gallop(&left[i..], |x| *x <= arr[j]);
+            arr[k..k + count].clone_from_slice(&left[i..i + count]);
+            i += count;
+            k += count;
+            left_wins = 0;
+        } else if right_wins >= MIN_GALLOP {
+            // Move every right element strictly less than the next left one
+            let count = gallop(&arr[j..], |x| *x < left[i]);
+            for _ in 0..count {
+                arr[k] = arr[j].clone();
+                j += 1;
+                k += 1;
+            }
+            right_wins = 0;
+        } else if arr[j] < left[i] {
+            arr[k] = arr[j].clone();
             j += 1;
+            k += 1;
+            right_wins += 1;
+            left_wins = 0;
+        } else {
+            arr[k] = left[i].clone();
+            i += 1;
+            k += 1;
+            left_wins += 1;
+            right_wins = 0;
         }
-        k += 1;
-    }
-
-    while i < len1 {
-        arr[k] = left[i];
-        k += 1;
-        i += 1;
     }
 
-    while j < len2 {
-        arr[k] = right[j];
-        k += 1;
-        j += 1;
-    }
-    arr
+    // Whatever is left of the right run is already in place
+    arr[k..k + left.len() - i].clone_from_slice(&left[i..]);
 }
 
-pub fn tim_sort(arr: &mut Vec<i32>, n: usize) {
-    let min_run = min_run_length(MIN_MERGE);
+/// Merges the runs at positions `i` and `i + 1` of the run stack.
+fn merge_at<T: Ord + Clone>(arr: &mut [T], runs: &mut Vec<(usize, usize)>, i: usize) {
+    let (start, len1) = runs[i];
+    let len2 = runs[i + 1].1;
+    merge(&mut arr[start..start + len1 + len2], len1);
+    runs[i].1 += len2;
+    runs.remove(i + 1);
+}
 
-    let mut i = 0;
-    while i < n {
-        insertion_sort(arr, i, cmp::min(i + MIN_MERGE - 1, n - 1));
-        i += min_run;
+/// Merges runs on top of the stack until the Timsort invariants hold again:
+/// every run is longer than the sum of the two runs above it, and every run
+/// is longer than the one above it.
+fn merge_collapse<T: Ord + Clone>(arr: &mut [T], runs: &mut Vec<(usize, usize)>) {
+    while runs.len() > 1 {
+        let n = runs.len();
+        let c = runs[n - 1].1;
+        let b = runs[n - 2].1;
+        if (n >= 3 && runs[n - 3].1 <= b + c) || (n >= 4 && runs[n - 4].1 <= runs[n - 3].1 + b) {
+            if runs[n - 3].1 < c {
+                merge_at(arr, runs, n - 3);
+            } else {
+                merge_at(arr, runs, n - 2);
+            }
+        } else if b <= c {
+            merge_at(arr, runs, n - 2);
+        } else {
+            break;
+        }
     }
+}
 
-    let mut size = min_run;
-    while size < n {
-        let mut left = 0;
-        while left < n {
-            let mid = left + size - 1;
-            let right = cmp::min(left + 2 * size - 1, n - 1);
-            if mid < right {
-                merge(arr, left, mid, right);
-            }
+/// Merges all remaining runs into one.
+fn merge_force_collapse<T: Ord + Clone>(arr: &mut [T], runs: &mut Vec<(usize, usize)>) {
+    while runs.len() > 1 {
+        let n = runs.len();
+        if n >= 3 && runs[n - 3].1 < runs[n - 1].1 {
+            merge_at(arr, runs, n - 3);
+        } else {
+            merge_at(arr, runs, n - 2);
+        }
+    }
+}
 
-            left += 2 * size;
+/// Sorts `arr` in-place using Timsort, a stable and adaptive merge sort.
+///
+/// The array is split into natural runs (descending runs are reversed), short
+/// runs are extended to the minimum run length with insertion sort, and runs are
+/// merged using a stack that keeps merges balanced. Merges switch to galloping
+/// mode when one run keeps winning, so long monotone stretches are copied in bulk.
+///
+/// Time complexity is `O(n log n)` in the worst case and `O(n)` on presorted input.
+/// Space complexity is `O(n)`.
+pub fn tim_sort<T: Ord + Clone>(arr: &mut [T]) {
+    let n = arr.len();
+    if n < 2 {
+        return;
+    }
+    let min_run = min_run_length(n);
+    // Stack of pending runs stored as (start, length)
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < n {
+        let mut run_len = count_run_and_make_ascending(&mut arr[start..]);
+        if run_len < min_run {
+            let forced = cmp::min(min_run, n - start);
+            insertion_sort(&mut arr[start..start + forced], run_len);
+            run_len = forced;
         }
-        size *= 2;
+        runs.push((start, run_len));
+        merge_collapse(arr, &mut runs);
+        start += run_len;
     }
+    merge_force_collapse(arr, &mut runs);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::PCG32;
+    use crate::sorting::is_sorted;
 
     #[test]
     fn basic() {
         let mut array = vec![-2, 7, 15, -14, 0, 15, 0, 7, -7, -4, -13, 5, 8, -14, 12];
-        let arr_len = array.len();
-        tim_sort(&mut array, arr_len);
+        tim_sort(&mut array);
         for i in 0..array.len() - 1 {
             assert!(array[i] <= array[i + 1]);
         }
@@ -108,24 +199,107 @@ mod tests {
     #[test]
     fn empty() {
         let mut array = Vec::<i32>::new();
-        let arr_len = array.len();
-        tim_sort(&mut array, arr_len);
+        tim_sort(&mut array);
         assert_eq!(array, vec![]);
     }
 
     #[test]
     fn one_element() {
         let mut array = vec![3];
-        let arr_len = array.len();
-        tim_sort(&mut array, arr_len);
+        tim_sort(&mut array);
         assert_eq!(array, vec![3]);
     }
 
     #[test]
     fn pre_sorted() {
         let mut array = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
-        let arr_len = array.len();
-        tim_sort(&mut array, arr_len);
+        tim_sort(&mut array);
         assert_eq!(array, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
     }
+
+    #[test]
+    fn min_run() {
+        assert_eq!(min_run_length(10), 10);
+        for n in 64..5000 {
+            let run = min_run_length(n);
+            assert!((32..=64).contains(&run), "n = {}, run = {}", n, run);
+        }
+    }
+
+    #[test]
+    fn descending_runs() {
+        let mut array: Vec<i32> = (0..500).rev().chain(500..1000).collect();
+        tim_sort(&mut array);
+        assert_eq!(array, (0..1000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn galloping() {
+        // Every run spans the same four value ranges, so merges see long streaks from each side
+        let mut array: Vec<i32> = (0..2000).map(|x| (x % 4) * 1000 + x / 4).collect();
+        tim_sort(&mut array);
+        assert_eq!(
+            array,
+            (0..2000)
+                .map(|x| x / 500 * 1000 + x % 500)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = PCG32::new_default(161803);
+        for len in [10, 63, 64, 65, 1000, 5000] {
+            let mut array: Vec<u32> = rng.iter_mut().take(len).map(|x| x % 100).collect();
+            let mut expected = array.clone();
+            expected.sort();
+            tim_sort(&mut array);
+            assert!(is_sorted(&array));
+            assert_eq!(array, expected);
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct Item {
+        key: u32,
+        id: usize,
+    }
+
+    impl PartialEq for Item {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+
+    impl Eq for Item {}
+
+    impl PartialOrd for Item {
+        fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Item {
+        fn cmp(&self, other: &Self) -> cmp::Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    #[test]
+    fn stable() {
+        let mut rng = PCG32::new_default(57721);
+        let mut items: Vec<Item> = (0..3000)
+            .map(|id| Item {
+                key: rng.get_u32() % 20,
+                id,
+            })
+            .collect();
+        tim_sort(&mut items);
+        for pair in items.windows(2) {
+            assert!(pair[0].key <= pair[1].key);
+            if pair[0].key == pair[1].key {
+                assert!(pair[0].id < pair[1].id);
+            }
+        }
+    }
 }
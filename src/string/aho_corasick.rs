@@ -1,101 +1,192 @@
-use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::VecDeque;
-use std::rc::{Rc, Weak};
+
+const ROOT: usize = 0;
 
 #[derive(Default)]
 struct ACNode {
-    trans: BTreeMap<char, Rc<RefCell<ACNode>>>,
-    suffix: Weak<RefCell<ACNode>>, // the suffix(fail) link
-    lengths: Vec<usize>,           // lengths of matched patterns ended at this node
+    trans: BTreeMap<char, usize>,
+    suffix: usize,         // the suffix(fail) link
+    output: Option<usize>, // the nearest node on the suffix chain that ends a pattern
+    patterns: Vec<usize>,  // indices of the patterns ending exactly at this node
 }
 
-#[derive(Default)]
+/// An occurrence of `patterns[pattern_index]` in a text, covering the bytes `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub pattern_index: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// An automaton finding all occurrences of a set of patterns in a single pass over a text.
+///
+/// It is a trie of the patterns where every node also links to the node of its
+/// longest proper suffix, which is where the search resumes when the next
+/// character cannot extend the current match. Searching takes
+/// `O(n + number of matches)` time, however many patterns there are.
 pub struct AhoCorasick {
-    root: Rc<RefCell<ACNode>>,
+    nodes: Vec<ACNode>,
+    pattern_lengths: Vec<usize>,
 }
 
 impl AhoCorasick {
-    pub fn new(words: &[&str]) -> Self {
-        let root = Rc::new(RefCell::new(ACNode::default()));
-        for word in words {
-            let mut cur = Rc::clone(&root);
-            for c in word.chars() {
-                cur = Rc::clone(Rc::clone(&cur).borrow_mut().trans.entry(c).or_default());
+    pub fn new(patterns: &[&str]) -> Self {
+        let mut nodes = vec![ACNode::default()];
+        for (index, pattern) in patterns.iter().enumerate() {
+            let mut cur = ROOT;
+            for c in pattern.chars() {
+                cur = match nodes[cur].trans.get(&c) {
+                    Some(&next) => next,
+                    None => {
+                        nodes.push(ACNode::default());
+                        let next = nodes.len() - 1;
+                        nodes[cur].trans.insert(c, next);
+                        next
+                    }
+                };
             }
-            cur.borrow_mut().lengths.push(word.len());
+            nodes[cur].patterns.push(index);
         }
-        Self::build_suffix(Rc::clone(&root));
-        Self { root }
+        let mut automaton = Self {
+            nodes,
+            pattern_lengths: patterns.iter().map(|p| p.len()).collect(),
+        };
+        automaton.build_suffix();
+        automaton
     }
 
-    fn build_suffix(root: Rc<RefCell<ACNode>>) {
+    /// Sets the suffix links in breadth-first order, so that the links of all
+    /// shallower nodes are known when a node is reached.
+    fn build_suffix(&mut self) {
         let mut q = VecDeque::new();
-        q.push_back(Rc::clone(&root));
+        q.push_back(ROOT);
         while let Some(parent) = q.pop_front() {
-            let parent = parent.borrow();
-            for (c, child) in &parent.trans {
-                q.push_back(Rc::clone(child));
-                let mut child = child.borrow_mut();
-                let mut suffix = parent.suffix.upgrade();
-                loop {
-                    match &suffix {
-                        None => {
-                            child.lengths.extend(root.borrow().lengths.clone());
-                            child.suffix = Rc::downgrade(&root);
-                            break;
-                        }
-                        Some(node) => {
-                            if node.borrow().trans.contains_key(c) {
-                                let node = &node.borrow().trans[c];
-                                child.lengths.extend(node.borrow().lengths.clone());
-                                child.suffix = Rc::downgrade(node);
-                                break;
-                            } else {
-                                suffix = suffix.unwrap().borrow().suffix.upgrade();
-                            }
-                        }
-                    }
-                }
+            let transitions: Vec<(char, usize)> = self.nodes[parent]
+                .trans
+                .iter()
+                .map(|(&c, &n)| (c, n))
+                .collect();
+            for (c, child) in transitions {
+                q.push_back(child);
+                let suffix = if parent == ROOT {
+                    ROOT
+                } else {
+                    self.next_state(self.nodes[parent].suffix, c)
+                };
+                self.nodes[child].suffix = suffix;
+                self.nodes[child].output = if self.nodes[suffix].patterns.is_empty() {
+                    self.nodes[suffix].output
+                } else {
+                    Some(suffix)
+                };
             }
         }
     }
 
-    pub fn search<'a>(&self, s: &'a str) -> Vec<&'a str> {
-        let mut ans = vec![];
-        let mut cur = Rc::clone(&self.root);
-        let mut position: usize = 0;
-        for (_, c) in s.chars().enumerate() {
-            loop {
-                if let Some(child) = Rc::clone(&cur).borrow().trans.get(&c) {
-                    cur = Rc::clone(child);
-                    break;
-                }
-                let suffix = cur.borrow().suffix.clone();
-                match suffix.upgrade() {
-                    Some(node) => cur = node,
-                    None => break,
-                }
+    /// Follows the suffix links from `state` until `c` can be appended.
+    fn next_state(&self, mut state: usize, c: char) -> usize {
+        loop {
+            if let Some(&next) = self.nodes[state].trans.get(&c) {
+                return next;
             }
-            position += c.len_utf8();
-            for &len in &cur.borrow().lengths {
-                ans.push(&s[position - len..position]);
+            if state == ROOT {
+                return ROOT;
             }
+            state = self.nodes[state].suffix;
+        }
+    }
+
+    /// Returns all occurrences of all patterns in `text`, overlapping ones
+    /// included, with byte offsets into `text`.
+    ///
+    /// Matches are sorted by their end, and matches ending at the same position
+    /// from the longest to the shortest. Empty patterns match at every
+    /// character boundary.
+    pub fn search(&self, text: &str) -> Vec<Match> {
+        let mut ans = vec![];
+        self.report(ROOT, 0, &mut ans);
+        let mut cur = ROOT;
+        for (position, c) in text.char_indices() {
+            cur = self.next_state(cur, c);
+            self.report(cur, position + c.len_utf8(), &mut ans);
         }
         ans
     }
+
+    /// Pushes the patterns ending at `end` when the automaton is in `state`.
+    fn report(&self, state: usize, end: usize, ans: &mut Vec<Match>) {
+        let mut node = Some(state);
+        while let Some(index) = node {
+            for &pattern_index in &self.nodes[index].patterns {
+                ans.push(Match {
+                    pattern_index,
+                    start: end - self.pattern_lengths[pattern_index],
+                    end,
+                });
+            }
+            node = self.nodes[index].output;
+        }
+    }
+}
+
+impl Default for AhoCorasick {
+    fn default() -> Self {
+        Self::new(&[])
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::string::kmp_search;
+
+    fn matched<'a>(text: &'a str, matches: &[Match]) -> Vec<&'a str> {
+        matches.iter().map(|m| &text[m.start..m.end]).collect()
+    }
+
+    /// Returns the matches of every pattern found separately by KMP, in the order of `search`.
+    fn kmp_matches(text: &str, patterns: &[&str]) -> Vec<Match> {
+        let mut expected: Vec<Match> = patterns
+            .iter()
+            .enumerate()
+            .flat_map(|(pattern_index, pattern)| {
+                kmp_search(text, pattern)
+                    .into_iter()
+                    .map(move |start| Match {
+                        pattern_index,
+                        start,
+                        end: start + pattern.len(),
+                    })
+            })
+            .collect();
+        expected.sort_by_key(|m| (m.end, m.start, m.pattern_index));
+        expected
+    }
+
+    fn sorted(mut matches: Vec<Match>) -> Vec<Match> {
+        matches.sort_by_key(|m| (m.end, m.start, m.pattern_index));
+        matches
+    }
 
     #[test]
     fn test_aho_corasick() {
         let dict = ["abc", "abcd", "xyz", "acxy", "efg", "123", "678", "6543"];
         let ac = AhoCorasick::new(&dict);
-        let res = ac.search("ababcxyzacxy12678acxy6543");
-        assert_eq!(res, ["abc", "xyz", "acxy", "678", "acxy", "6543",]);
+        let text = "ababcxyzacxy12678acxy6543";
+        let res = ac.search(text);
+        assert_eq!(
+            matched(text, &res),
+            ["abc", "xyz", "acxy", "678", "acxy", "6543",]
+        );
+        assert_eq!(
+            res[0],
+            Match {
+                pattern_index: 0,
+                start: 2,
+                end: 5
+            }
+        );
     }
 
     #[test]
@@ -114,9 +205,10 @@ mod tests {
             "ハンバーガー",
         ];
         let ac = AhoCorasick::new(&dict);
-        let res = ac.search("ababc中xyzacxy12678acxyハンバーガー6543中文");
+        let text = "ababc中xyzacxy12678acxyハンバーガー6543中文";
+        let res = ac.search(text);
         assert_eq!(
-            res,
+            matched(text, &res),
             [
                 "abc",
                 "abc中",
@@ -130,4 +222,85 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn overlapping_matches() {
+        let patterns = ["he", "she", "his", "hers", "e"];
+        let ac = AhoCorasick::new(&patterns);
+        let text = "ushers";
+        let res = ac.search(text);
+        assert_eq!(matched(text, &res), ["she", "he", "e", "hers"]);
+        assert_eq!(
+            res.iter().map(|m| m.pattern_index).collect::<Vec<_>>(),
+            [1, 0, 4, 3]
+        );
+
+        let ac = AhoCorasick::new(&["aa"]);
+        let starts: Vec<usize> = ac.search("aaaa").iter().map(|m| m.start).collect();
+        assert_eq!(starts, [0, 1, 2]);
+    }
+
+    #[test]
+    fn duplicate_and_empty_patterns() {
+        let ac = AhoCorasick::new(&["ab", "", "ab"]);
+        let res = ac.search("aab");
+        let indices: Vec<usize> = res.iter().map(|m| m.pattern_index).collect();
+        assert_eq!(indices, [1, 1, 1, 0, 2, 1]);
+        assert!(AhoCorasick::default().search("abc").is_empty());
+    }
+
+    #[test]
+    fn agrees_with_knuth_morris_pratt() {
+        let mut rng = crate::math::PCG32::new_default(2718);
+        let alphabet = ['a', 'b', 'c', 'ü'];
+        let mut random_string = |max_len: u32| -> String {
+            let len = rng.get_u32() % (max_len + 1);
+            (0..len)
+                .map(|_| alphabet[rng.get_u32() as usize % alphabet.len()])
+                .collect()
+        };
+        for _ in 0..100 {
+            let owned: Vec<String> = (0..5).map(|_| random_string(4)).collect();
+            let patterns: Vec<&str> = owned.iter().map(String::as_str).collect();
+            // the automaton is built once and reused for several texts
+            let ac = AhoCorasick::new(&patterns);
+            for _ in 0..5 {
+                let text = random_string(80);
+                assert_eq!(sorted(ac.search(&text)), kmp_matches(&text, &patterns));
+            }
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn faster_than_repeated_knuth_morris_pratt() {
+        // Run this in release mode: searching for many patterns reads each text
+        // once instead of once per pattern
+        use std::time::Instant;
+        let owned: Vec<String> = (0..500).map(|i| format!("p{i}q")).collect();
+        let patterns: Vec<&str> = owned.iter().map(String::as_str).collect();
+        let texts: Vec<String> = (0..20)
+            .map(|t| (0..2000).map(|i| format!("p{}q ", i * t % 700)).collect())
+            .collect();
+
+        let start = Instant::now();
+        let ac = AhoCorasick::new(&patterns);
+        let found: usize = texts.iter().map(|text| ac.search(text).len()).sum();
+        let automaton_time = start.elapsed();
+
+        let start = Instant::now();
+        let expected: usize = texts
+            .iter()
+            .map(|text| {
+                patterns
+                    .iter()
+                    .map(|p| kmp_search(text, p).len())
+                    .sum::<usize>()
+            })
+            .sum();
+        let kmp_time = start.elapsed();
+
+        assert_eq!(found, expected);
+        assert!(automaton_time < kmp_time);
+    }
 }
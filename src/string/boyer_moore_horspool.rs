@@ -0,0 +1,167 @@
+// The Boyer-Moore-Horspool algorithm is a simplification of Boyer-Moore that
+// only keeps the bad-character rule. It compares the pattern with the text from
+// right to left, and after every attempt shifts the pattern according to the text
+// byte aligned with its last position. Source: https://en.wikipedia.org/wiki/Boyer%E2%80%93Moore%E2%80%93Horspool_algorithm
+
+/// A pattern preprocessed for Boyer-Moore-Horspool search, which can be
+/// searched for in any number of texts.
+pub struct BmhSearcher {
+    pattern: Vec<u8>,
+    // shift[b] is how far the pattern may move when the text byte aligned
+    // with its last position is b
+    shift: [usize; 256],
+}
+
+impl BmhSearcher {
+    pub fn new(pattern: &[u8]) -> Self {
+        let m = pattern.len();
+        let mut shift = [m.max(1); 256];
+        // the last byte is left out, so that a match never shifts by 0
+        for (i, &b) in pattern.iter().enumerate().take(m.saturating_sub(1)) {
+            shift[b as usize] = m - 1 - i;
+        }
+        BmhSearcher {
+            pattern: pattern.to_vec(),
+            shift,
+        }
+    }
+
+    /// Returns the starting positions of all occurrences of the pattern in
+    /// `text`, overlapping ones included.
+    ///
+    /// The empty pattern matches at every position, from 0 to `text.len()`.
+    /// Usually sublinear, since the pattern often jumps by its whole length,
+    /// but `O(nm)` in the worst case.
+    pub fn find_all(&self, text: &[u8]) -> Vec<usize> {
+        let (n, m) = (text.len(), self.pattern.len());
+        if m == 0 {
+            return (0..=n).collect();
+        }
+        let mut positions = Vec::new();
+        let mut shift = 0;
+        while shift + m <= n {
+            let window = &text[shift..shift + m];
+            if window.iter().rev().eq(self.pattern.iter().rev()) {
+                positions.push(shift);
+            }
+            shift += self.shift[window[m - 1] as usize];
+        }
+        positions
+    }
+}
+
+/// Returns the starting positions of all occurrences of `pattern` in `text`.
+///
+/// See [`BmhSearcher::find_all`]; use a `BmhSearcher` directly to search
+/// several texts for the same pattern.
+pub fn bmh_search(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+    BmhSearcher::new(pattern).find_all(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+    use crate::string::kmp_search;
+
+    fn naive_search(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        if pattern.len() > text.len() {
+            return vec![];
+        }
+        (0..=text.len() - pattern.len())
+            .filter(|&i| &text[i..i + pattern.len()] == pattern)
+            .collect()
+    }
+
+    #[test]
+    fn test_bmh_search() {
+        let text = b"AABCAB12AFAABCABFFEGABCAB";
+        assert_eq!(bmh_search(text, b"ABCAB"), [1, 11, 20]);
+        assert_eq!(bmh_search(text, b"FFF"), []);
+        assert_eq!(bmh_search(text, b"CAB"), [3, 13, 22]);
+    }
+
+    #[test]
+    fn repeated_characters() {
+        assert_eq!(bmh_search(b"aaaaa", b"aa"), [0, 1, 2, 3]);
+        assert_eq!(bmh_search(b"aaabaaab", b"aab"), [1, 5]);
+        assert_eq!(bmh_search(b"abababab", b"abab"), [0, 2, 4]);
+        assert_eq!(bmh_search(b"baaaaab", b"aaab"), [3]);
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(bmh_search(b"abc", b"abcd"), []);
+        assert_eq!(bmh_search(b"", b"a"), []);
+        assert_eq!(bmh_search(b"abc", b""), [0, 1, 2, 3]);
+        assert_eq!(bmh_search(b"abc", b"abc"), [0]);
+        assert_eq!(bmh_search(&[0, 255, 0, 255], &[255]), [1, 3]);
+    }
+
+    #[test]
+    fn searcher_reuse() {
+        let searcher = BmhSearcher::new(b"needle");
+        assert_eq!(searcher.find_all(b"haystack with a needle"), [16]);
+        assert_eq!(searcher.find_all(b"needleneedle"), [0, 6]);
+        assert_eq!(searcher.find_all(b"no match"), []);
+    }
+
+    #[test]
+    fn matches_knuth_morris_pratt() {
+        let mut rng = PCG32::new_default(404);
+        for _ in 0..1000 {
+            let text: String = (0..rng.get_u32() % 60)
+                .map(|_| (b'a' + (rng.get_u32() % 3) as u8) as char)
+                .collect();
+            let pattern: String = (0..1 + rng.get_u32() % 4)
+                .map(|_| (b'a' + (rng.get_u32() % 3) as u8) as char)
+                .collect();
+            assert_eq!(
+                bmh_search(text.as_bytes(), pattern.as_bytes()),
+                kmp_search(&text, &pattern)
+            );
+        }
+    }
+
+    #[test]
+    fn matches_naive_search_on_bytes() {
+        let mut rng = PCG32::new_default(808);
+        for _ in 0..500 {
+            let alphabet = 1 + rng.get_u32() % 256;
+            let text: Vec<u8> = (0..rng.get_u32() % 100)
+                .map(|_| (rng.get_u32() % alphabet) as u8)
+                .collect();
+            let start = rng.get_u32() as usize % (text.len() + 1);
+            let len = rng.get_u32() as usize % 5;
+            // take the pattern from the text half of the time, so that it is found
+            let pattern: Vec<u8> = if rng.get_u32().is_multiple_of(2) {
+                text[start..(start + len).min(text.len())].to_vec()
+            } else {
+                (0..len).map(|_| (rng.get_u32() % alphabet) as u8).collect()
+            };
+            assert_eq!(bmh_search(&text, &pattern), naive_search(&text, &pattern));
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn faster_than_naive_search() {
+        // Run this in release mode: with a large alphabet most attempts fail on
+        // the first comparison and move the pattern by its whole length
+        use std::time::Instant;
+        let mut rng = PCG32::new_default(1);
+        let text: Vec<u8> = (0..4_000_000).map(|_| rng.get_u32() as u8).collect();
+        let pattern = &text[3_000_000..3_000_032];
+
+        let start = Instant::now();
+        let found = bmh_search(&text, pattern);
+        let bmh_time = start.elapsed();
+
+        let start = Instant::now();
+        let expected = naive_search(&text, pattern);
+        let naive_time = start.elapsed();
+
+        assert_eq!(found, expected);
+        assert!(bmh_time < naive_time);
+    }
+}
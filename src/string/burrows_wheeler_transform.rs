@@ -1,3 +1,68 @@
+// The Burrows-Wheeler transform rearranges a text into runs of equal characters
+// that are easy to compress, and can be undone knowing only the position of the
+// text among its sorted rotations. It is the first stage of bzip2.
+// Source: https://en.wikipedia.org/wiki/Burrows%E2%80%93Wheeler_transform
+
+/// Returns the last column of the sorted rotations of `input`, along with the
+/// row where `input` itself ends up.
+///
+/// The rotations are sorted with plain comparisons, which takes `O(n^2 log n)`
+/// time. Sorting the rotations amounts to building a suffix array, so this
+/// could be brought down to `O(n)` with SA-IS or DC3.
+pub fn bwt(input: &[u8]) -> (Vec<u8>, usize) {
+    let n = input.len();
+    let rotation = |start: usize| input[start..].iter().chain(&input[..start]);
+    let mut rows: Vec<usize> = (0..n).collect();
+    // stable, so that the text comes first among equal rotations
+    rows.sort_by(|&a, &b| rotation(a).cmp(rotation(b)));
+
+    let encoded = rows
+        .iter()
+        .map(|&start| input[(start + n - 1) % n])
+        .collect();
+    let index = rows.iter().position(|&start| start == 0).unwrap_or(0);
+    (encoded, index)
+}
+
+/// Reverts `bwt`, given the last column `data` and the row `orig_idx` of the
+/// original text.
+///
+/// Sorting the last column stably gives the first column, and the k-th
+/// occurrence of a byte in both columns comes from the same rotation, which
+/// lets the text be read back in `O(n)`. Panics if `orig_idx` is out of bounds
+/// for non-empty `data`.
+pub fn ibwt(data: &[u8], orig_idx: usize) -> Vec<u8> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+    assert!(orig_idx < data.len(), "row {} is out of bounds", orig_idx);
+
+    // counting sort: next[j] is the row whose last byte is the first byte of row j
+    let mut starts = [0; 256];
+    for &b in data {
+        starts[b as usize] += 1;
+    }
+    let mut total = 0;
+    for start in starts.iter_mut() {
+        let count = *start;
+        *start = total;
+        total += count;
+    }
+    let mut next = vec![0; data.len()];
+    for (row, &b) in data.iter().enumerate() {
+        next[starts[b as usize]] = row;
+        starts[b as usize] += 1;
+    }
+
+    let mut decoded = Vec::with_capacity(data.len());
+    let mut row = next[orig_idx];
+    for _ in 0..data.len() {
+        decoded.push(data[row]);
+        row = next[row];
+    }
+    decoded
+}
+
 pub fn burrows_wheeler_transform(input: String) -> (String, usize) {
     let len = input.len();
 
@@ -119,4 +184,59 @@ mod tests {
             ""
         );
     }
+
+    #[test]
+    fn banana() {
+        assert_eq!(bwt(b"BANANA"), (b"NNBAAA".to_vec(), 3));
+        assert_eq!(ibwt(b"NNBAAA", 3), b"BANANA");
+        // with an end marker smaller than every letter the text is always row 0
+        assert_eq!(bwt(b"BANANA\0"), (b"ANNB\0AA".to_vec(), 4));
+        assert_eq!(bwt(b"\0BANANA"), (b"ANNB\0AA".to_vec(), 0));
+    }
+
+    #[test]
+    fn bytes_empty_and_single() {
+        assert_eq!(bwt(b""), (vec![], 0));
+        assert_eq!(ibwt(b"", 0), b"");
+        assert_eq!(bwt(b"x"), (b"x".to_vec(), 0));
+        assert_eq!(ibwt(b"x", 0), b"x");
+        assert_eq!(bwt(&[0]), (vec![0], 0));
+    }
+
+    #[test]
+    fn periodic_input() {
+        assert_eq!(bwt(b"aaaa"), (b"aaaa".to_vec(), 0));
+        assert_eq!(ibwt(b"aaaa", 0), b"aaaa");
+        let (encoded, index) = bwt(b"abcabc");
+        assert_eq!(encoded, b"ccaabb");
+        assert_eq!(ibwt(&encoded, index), b"abcabc");
+    }
+
+    #[test]
+    fn clusters_repeated_context() {
+        let text = b"the cat sat on the mat and the hat";
+        let (encoded, _) = bwt(text);
+        let runs = |s: &[u8]| 1 + s.windows(2).filter(|w| w[0] != w[1]).count();
+        assert!(runs(&encoded) < runs(text));
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let mut rng = crate::math::PCG32::new_default(29);
+        for _ in 0..300 {
+            let alphabet = 1 + rng.get_u32() % 256;
+            let data: Vec<u8> = (0..rng.get_u32() % 64)
+                .map(|_| (rng.get_u32() % alphabet) as u8)
+                .collect();
+            let (encoded, index) = bwt(&data);
+            assert_eq!(encoded.len(), data.len());
+            assert_eq!(ibwt(&encoded, index), data);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_out_of_bounds() {
+        ibwt(b"abc", 3);
+    }
 }
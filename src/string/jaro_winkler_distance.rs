@@ -5,62 +5,79 @@
 // of the Jaro distance metric (1989, Matthew A. Jaro).
 
 pub fn jaro_winkler_distance(str1: &str, str2: &str) -> f64 {
-    if str1.is_empty() || str2.is_empty() {
+    jaro_winkler(str1, str2, 0.1)
+}
+
+/// Returns the Jaro similarity of `s1` and `s2`, between 0.0 (nothing in
+/// common) and 1.0 (equal strings).
+///
+/// Two characters match when they are equal and at most
+/// `floor(max(|s1|, |s2|) / 2) - 1` positions apart. With `m` matches, of which
+/// `t` pairs appear in a different order in the two strings, the similarity is
+/// `(m / |s1| + m / |s2| + (m - t) / m) / 3`.
+///
+/// Strings are compared character by character. Two empty strings are equal,
+/// while an empty string has nothing in common with any other.
+pub fn jaro(s1: &str, s2: &str) -> f64 {
+    let s1: Vec<char> = s1.chars().collect();
+    let s2: Vec<char> = s2.chars().collect();
+    if s1.is_empty() && s2.is_empty() {
+        return 1.0;
+    }
+    if s1.is_empty() || s2.is_empty() {
         return 0.0;
     }
-    fn get_matched_characters(s1: &str, s2: &str) -> String {
-        let mut s2 = s2.to_string();
-        let mut matched: Vec<char> = Vec::new();
-        let limit = std::cmp::min(s1.len(), s2.len()) / 2;
-        for (i, l) in s1.chars().enumerate() {
-            let left = std::cmp::max(0, i as i32 - limit as i32) as usize;
-            let right = std::cmp::min(i + limit + 1, s2.len());
-            if s2[left..right].contains(l) {
-                matched.push(l);
-                let a = &s2[0..s2.find(l).expect("this exists")];
-                let b = &s2[(s2.find(l).expect("this exists") + 1)..];
-                s2 = format!("{a} {b}");
+
+    let window = (s1.len().max(s2.len()) / 2).saturating_sub(1);
+    let mut matched1 = vec![false; s1.len()];
+    let mut matched2 = vec![false; s2.len()];
+    let mut matches = 0;
+    for (i, c) in s1.iter().enumerate() {
+        let lo = i.saturating_sub(window);
+        let hi = (i + window + 1).min(s2.len());
+        for j in lo..hi {
+            if !matched2[j] && s2[j] == *c {
+                matched1[i] = true;
+                matched2[j] = true;
+                matches += 1;
+                break;
             }
         }
-        matched.iter().collect::<String>()
     }
-
-    let matching_1 = get_matched_characters(str1, str2);
-    let matching_2 = get_matched_characters(str2, str1);
-    let match_count = matching_1.len();
+    if matches == 0 {
+        return 0.0;
+    }
 
     // transposition
-    let transpositions = {
-        let mut count = 0;
-        for (c1, c2) in matching_1.chars().zip(matching_2.chars()) {
-            if c1 != c2 {
-                count += 1;
-            }
-        }
-        count / 2
-    };
+    let in_order1 = s1.iter().zip(&matched1).filter(|(_, &m)| m);
+    let in_order2 = s2.iter().zip(&matched2).filter(|(_, &m)| m);
+    let transpositions = in_order1
+        .zip(in_order2)
+        .filter(|((c1, _), (c2, _))| c1 != c2)
+        .count()
+        / 2;
 
-    let jaro: f64 = {
-        if match_count == 0 {
-            return 0.0;
-        } else {
-            (1_f64 / 3_f64)
-                * (match_count as f64 / str1.len() as f64
-                    + match_count as f64 / str2.len() as f64
-                    + (match_count - transpositions) as f64 / match_count as f64)
-        }
-    };
+    let m = matches as f64;
+    (m / s1.len() as f64 + m / s2.len() as f64 + (m - transpositions as f64) / m) / 3.0
+}
 
-    let mut prefix_len = 0.0;
-    let bound = std::cmp::min(std::cmp::min(str1.len(), str2.len()), 4);
-    for (c1, c2) in str1[..bound].chars().zip(str2[..bound].chars()) {
-        if c1 == c2 {
-            prefix_len += 1.0;
-        } else {
-            break;
-        }
-    }
-    jaro + (0.1 * prefix_len * (1.0 - jaro))
+/// Returns the Jaro-Winkler similarity of `s1` and `s2`, which raises the
+/// [`jaro`] similarity of strings sharing a common prefix.
+///
+/// With `l` the length of the common prefix, up to 4 characters, the
+/// similarity is `jaro + l * p * (1 - jaro)`. The scaling factor `p` is
+/// usually 0.1 and is clamped to `[0.0, 0.25]`, which keeps the result in
+/// `[0.0, 1.0]`.
+pub fn jaro_winkler(s1: &str, s2: &str, p: f64) -> f64 {
+    let p = p.clamp(0.0, 0.25);
+    let similarity = jaro(s1, s2);
+    let prefix_len = s1
+        .chars()
+        .zip(s2.chars())
+        .take(4)
+        .take_while(|(c1, c2)| c1 == c2)
+        .count();
+    similarity + prefix_len as f64 * p * (1.0 - similarity)
 }
 
 #[cfg(test)]
@@ -82,4 +99,83 @@ mod tests {
         let a = jaro_winkler_distance("hello world", "HeLLo W0rlD");
         assert_eq!(a, 0.6363636363636364);
     }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn winkler_examples() {
+        assert_close(jaro("MARTHA", "MARHTA"), 0.944);
+        assert_close(jaro_winkler("MARTHA", "MARHTA", 0.1), 0.961);
+        assert_close(jaro("DWAYNE", "DUANE"), 0.822);
+        assert_close(jaro_winkler("DWAYNE", "DUANE", 0.1), 0.840);
+        assert_close(jaro("DIXON", "DICKSONX"), 0.767);
+        assert_close(jaro_winkler("DIXON", "DICKSONX", 0.1), 0.813);
+        assert_close(jaro_winkler("JELLYFISH", "SMELLYFISH", 0.1), 0.896);
+    }
+
+    #[test]
+    fn empty_strings() {
+        assert_eq!(jaro("", ""), 1.0);
+        assert_eq!(jaro_winkler("", "", 0.1), 1.0);
+        assert_eq!(jaro("abc", ""), 0.0);
+        assert_eq!(jaro_winkler("", "abc", 0.1), 0.0);
+    }
+
+    #[test]
+    fn matching_window() {
+        // "ab" and "ba" have a window of 0, so the swapped letters do not match
+        assert_eq!(jaro("ab", "ba"), 0.0);
+        assert_eq!(jaro("abc", "xyz"), 0.0);
+        assert_close(jaro("CRATE", "TRACE"), 0.733);
+    }
+
+    #[test]
+    fn scaling_factor_is_clamped() {
+        let similarity = jaro("MARTHA", "MARHTA");
+        assert_eq!(jaro_winkler("MARTHA", "MARHTA", 0.0), similarity);
+        assert_eq!(jaro_winkler("MARTHA", "MARHTA", -1.0), similarity);
+        assert_eq!(
+            jaro_winkler("MARTHA", "MARHTA", 1.0),
+            jaro_winkler("MARTHA", "MARHTA", 0.25)
+        );
+        assert!(jaro_winkler("abcdx", "abcdy", 1.0) <= 1.0);
+    }
+
+    #[test]
+    fn unicode() {
+        assert_eq!(jaro("café", "café"), 1.0);
+        assert_close(
+            jaro_winkler("crème", "creme", 0.1),
+            jaro_winkler("crxme", "creme", 0.1),
+        );
+    }
+
+    #[test]
+    fn symmetric_and_bounded() {
+        let words = [
+            "",
+            "a",
+            "martha",
+            "marhta",
+            "dwayne",
+            "duane",
+            "dixon",
+            "dicksonx",
+            "日本語",
+            "日本",
+        ];
+        for a in words {
+            for b in words {
+                let (forward, backward) = (jaro_winkler(a, b, 0.1), jaro_winkler(b, a, 0.1));
+                assert_eq!(forward, backward);
+                assert!((0.0..=1.0).contains(&forward));
+                assert_eq!(jaro(a, b), jaro(b, a));
+            }
+        }
+    }
 }
@@ -1,41 +1,91 @@
-pub fn knuth_morris_pratt(st: String, pat: String) -> Vec<usize> {
-    if st.is_empty() || pat.is_empty() {
-        return vec![];
-    }
-
-    let string = st.into_bytes();
-    let pattern = pat.into_bytes();
-
-    // build the partial match table
-    let mut partial = vec![0];
+/// Builds the failure table of `pattern`, also known as the prefix function:
+/// `table[i]` is the length of the longest proper prefix of `pattern[..=i]`
+/// that is also a suffix of it.
+///
+/// The table is computed over the bytes of `pattern` in `O(m)` time.
+pub fn build_failure_table(pattern: &str) -> Vec<usize> {
+    let pattern = pattern.as_bytes();
+    let mut table = vec![0; pattern.len()];
+    let mut j = 0;
     for i in 1..pattern.len() {
-        let mut j = partial[i - 1];
         while j > 0 && pattern[j] != pattern[i] {
-            j = partial[j - 1];
+            j = table[j - 1];
         }
-        partial.push(if pattern[j] == pattern[i] { j + 1 } else { j });
+        if pattern[j] == pattern[i] {
+            j += 1;
+        }
+        table[i] = j;
     }
+    table
+}
 
-    // and read 'string' to find 'pattern'
+/// Returns the starting positions of all occurrences of `pattern` in `text`,
+/// overlapping ones included, in `O(n + m)` time.
+///
+/// Positions are byte offsets, so `&text[i..]` starts with `pattern` for each of
+/// them. Since UTF-8 is self-synchronizing, they always fall on character
+/// boundaries. The empty pattern matches at every character boundary, from 0
+/// up to and including `text.len()`.
+pub fn kmp_search(text: &str, pattern: &str) -> Vec<usize> {
+    if pattern.is_empty() {
+        return text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(text.len()))
+            .collect();
+    }
+
+    let table = build_failure_table(pattern);
+    let pattern = pattern.as_bytes();
     let mut ret = vec![];
     let mut j = 0;
 
-    for (i, &c) in string.iter().enumerate() {
+    for (i, &c) in text.as_bytes().iter().enumerate() {
         while j > 0 && c != pattern[j] {
-            j = partial[j - 1];
+            j = table[j - 1];
         }
         if c == pattern[j] {
             j += 1;
         }
         if j == pattern.len() {
             ret.push(i + 1 - j);
-            j = partial[j - 1];
+            j = table[j - 1];
         }
     }
 
     ret
 }
 
+/// Returns `true` if `pattern` occurs in `text`.
+pub fn kmp_contains(text: &str, pattern: &str) -> bool {
+    if pattern.is_empty() {
+        return true;
+    }
+    let table = build_failure_table(pattern);
+    let pattern = pattern.as_bytes();
+    let mut j = 0;
+    for &c in text.as_bytes() {
+        while j > 0 && c != pattern[j] {
+            j = table[j - 1];
+        }
+        if c == pattern[j] {
+            j += 1;
+        }
+        if j == pattern.len() {
+            return true;
+        }
+    }
+    false
+}
+
+pub fn knuth_morris_pratt(st: String, pat: String) -> Vec<usize> {
+    if st.is_empty() || pat.is_empty() {
+        return vec![];
+    }
+
+    kmp_search(&st, &pat)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,4 +144,77 @@ mod tests {
         let index = knuth_morris_pratt("".to_string(), "abcdef".to_string());
         assert_eq!(index, vec![]);
     }
+
+    fn naive_search(text: &str, pattern: &str) -> Vec<usize> {
+        let (text, pattern) = (text.as_bytes(), pattern.as_bytes());
+        if pattern.len() > text.len() {
+            return vec![];
+        }
+        (0..=text.len() - pattern.len())
+            .filter(|&i| &text[i..i + pattern.len()] == pattern)
+            .collect()
+    }
+
+    #[test]
+    fn failure_table() {
+        assert_eq!(build_failure_table(""), vec![]);
+        assert_eq!(build_failure_table("aaaa"), vec![0, 1, 2, 3]);
+        assert_eq!(build_failure_table("abcd"), vec![0, 0, 0, 0]);
+        assert_eq!(build_failure_table("ABCDABD"), vec![0, 0, 0, 0, 1, 2, 0]);
+        assert_eq!(build_failure_table("aabaaab"), vec![0, 1, 0, 1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn overlapping_matches() {
+        assert_eq!(kmp_search("aaaa", "aaa"), vec![0, 1]);
+        assert_eq!(kmp_search("abababab", "abab"), vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn empty_pattern() {
+        assert_eq!(kmp_search("abc", ""), vec![0, 1, 2, 3]);
+        assert_eq!(kmp_search("", ""), vec![0]);
+        assert_eq!(kmp_search("añb", ""), vec![0, 1, 3, 4]);
+        assert!(kmp_contains("", ""));
+        assert!(kmp_contains("abc", ""));
+    }
+
+    #[test]
+    fn pattern_longer_than_text() {
+        assert_eq!(kmp_search("abc", "abcd"), vec![]);
+        assert!(!kmp_contains("abc", "abcd"));
+    }
+
+    #[test]
+    fn unicode() {
+        let text = "知识就是力量，力量就是知识";
+        let matches = kmp_search(text, "知识");
+        assert_eq!(matches, vec![0, 33]);
+        assert!(matches.iter().all(|&i| text[i..].starts_with("知识")));
+        assert_eq!(kmp_search("héhého", "hé"), vec![0, 3]);
+        assert!(kmp_contains("crème brûlée", "brû"));
+        assert!(!kmp_contains("crème brûlée", "bru"));
+    }
+
+    #[test]
+    fn matches_naive_search() {
+        let mut rng = crate::math::PCG32::new_default(42);
+        let alphabet = ['a', 'b', 'é'];
+        let mut random_string = |max_len: u32| -> String {
+            let len = rng.get_u32() % (max_len + 1);
+            (0..len)
+                .map(|_| alphabet[rng.get_u32() as usize % alphabet.len()])
+                .collect()
+        };
+        for _ in 0..1000 {
+            let text = random_string(40);
+            let pattern = random_string(4);
+            if pattern.is_empty() {
+                continue;
+            }
+            let expected = naive_search(&text, &pattern);
+            assert_eq!(kmp_search(&text, &pattern), expected);
+            assert_eq!(kmp_contains(&text, &pattern), !expected.is_empty());
+        }
+    }
 }
@@ -1,36 +1,109 @@
 use std::cmp::min;
 
 pub fn levenshtein_distance(string1: &str, string2: &str) -> usize {
-    if string1.is_empty() {
-        return string2.len();
-    }
+    levenshtein(string1, string2)
+}
 
-    let mut d = Vec::with_capacity(string1.len());
-    for i in 0..=string1.len() {
-        d.push(i);
-    }
+/// Returns the minimum number of single-character insertions, deletions and
+/// substitutions that turn `a` into `b`.
+///
+/// Strings are compared character by character, so a multi-byte character
+/// counts as one edit. Only two rows of the dynamic programming table are
+/// kept, each as long as the shorter string, for `O(min(n, m))` space.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    levenshtein_with_costs(a, b, 1, 1, 1)
+}
 
-    let mut j = 1;
-    for c2 in string2.chars() {
-        let mut previous_substitution_cost = d[0];
-        d[0] = j;
+/// Like [`levenshtein`], but an insertion costs `ins`, a deletion costs `del`
+/// and a substitution costs `sub`.
+///
+/// A substitution is never worse than a deletion followed by an insertion, so
+/// its effective cost is `min(sub, ins + del)`.
+pub fn levenshtein_with_costs(a: &str, b: &str, ins: usize, del: usize, sub: usize) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // Deleting from `a` is inserting into `b`, so swapping the strings swaps the costs
+    let (rows, columns, ins, del) = if a.len() < b.len() {
+        (b, a, del, ins)
+    } else {
+        (a, b, ins, del)
+    };
 
-        let mut i = 1;
-        for c1 in string1.chars() {
-            let deletion_cost = d[i - 1] + 1;
-            let insertion_cost = d[i] + 1;
-            let substitution_cost = previous_substitution_cost + usize::from(c1 != c2);
+    // previous[j] = distance between rows[..i - 1] and columns[..j]
+    let mut previous: Vec<usize> = (0..=columns.len()).map(|j| j * ins).collect();
+    let mut current = vec![0; columns.len() + 1];
+    for (i, c1) in rows.iter().enumerate() {
+        current[0] = (i + 1) * del;
+        for (j, c2) in columns.iter().enumerate() {
+            let deletion_cost = previous[j + 1] + del;
+            let insertion_cost = current[j] + ins;
+            let substitution_cost = previous[j] + if c1 == c2 { 0 } else { sub };
+            current[j + 1] = min3(deletion_cost, insertion_cost, substitution_cost);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
 
-            previous_substitution_cost = d[i];
-            d[i] = min3(deletion_cost, insertion_cost, substitution_cost);
+    previous[columns.len()]
+}
+
+/// Computes the Levenshtein distance between `a` and `b` together with an
+/// optimal alignment of them.
+///
+/// The two returned strings have the same number of characters, and `'-'`
+/// marks a gap: a column `(x, '-')` deletes `x`, `('-', y)` inserts `y` and
+/// `(x, y)` keeps or substitutes `x`. The full table is needed to trace the
+/// alignment back, so this takes `O(nm)` space.
+pub fn edit_distance_alignment(a: &str, b: &str) -> (usize, String, String) {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
 
-            i += 1;
+    // distances[i][j] = distance between a[..i] and b[..j]
+    let mut distances = vec![vec![0; m + 1]; n + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in distances[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            distances[i][j] = min3(
+                distances[i - 1][j] + 1,
+                distances[i][j - 1] + 1,
+                distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]),
+            );
         }
+    }
 
-        j += 1;
+    // Walk back from the bottom-right corner, preferring substitutions
+    let (mut aligned_a, mut aligned_b) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        if i > 0
+            && j > 0
+            && distances[i][j] == distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1])
+        {
+            aligned_a.push(a[i - 1]);
+            aligned_b.push(b[j - 1]);
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && distances[i][j] == distances[i - 1][j] + 1 {
+            aligned_a.push(a[i - 1]);
+            aligned_b.push('-');
+            i -= 1;
+        } else {
+            aligned_a.push('-');
+            aligned_b.push(b[j - 1]);
+            j -= 1;
+        }
     }
 
-    d[d.len() - 1]
+    (
+        distances[n][m],
+        aligned_a.into_iter().rev().collect(),
+        aligned_b.into_iter().rev().collect(),
+    )
 }
 
 #[cfg(test)]
@@ -76,6 +149,102 @@ mod levenshtein_distance_should {
     fn return_3_with_winner_and_win() {
         assert_eq!(3, levenshtein_distance("winner", "win"));
     }
+
+    #[test]
+    fn count_unicode_characters() {
+        assert_eq!(1, levenshtein_distance("héllo", "hello"));
+        assert_eq!(1, levenshtein_distance("日本語", "日本人"));
+        assert_eq!(3, levenshtein_distance("", "日本語"));
+    }
+}
+
+#[cfg(test)]
+mod levenshtein_should {
+    use super::{edit_distance_alignment, levenshtein, levenshtein_with_costs};
+
+    #[test]
+    fn handle_empty_strings() {
+        assert_eq!(0, levenshtein("", ""));
+        assert_eq!(3, levenshtein("abc", ""));
+        assert_eq!(3, levenshtein("", "abc"));
+        assert_eq!(6, levenshtein_with_costs("", "abc", 2, 3, 1));
+        assert_eq!(9, levenshtein_with_costs("abc", "", 2, 3, 1));
+    }
+
+    #[test]
+    fn return_0_with_identical_strings() {
+        assert_eq!(0, levenshtein("GATTACA", "GATTACA"));
+        assert_eq!(0, levenshtein_with_costs("ñandú", "ñandú", 5, 5, 5));
+    }
+
+    #[test]
+    fn count_single_character_differences() {
+        assert_eq!(1, levenshtein("flaw", "flaws"));
+        assert_eq!(1, levenshtein("flaw", "law"));
+        assert_eq!(1, levenshtein("flaw", "flow"));
+        assert_eq!(1, levenshtein("naïve", "naive"));
+    }
+
+    #[test]
+    fn match_known_benchmarks() {
+        assert_eq!(3, levenshtein("kitten", "sitting"));
+        assert_eq!(2, levenshtein("flaw", "lawn"));
+        assert_eq!(5, levenshtein("intention", "execution"));
+        // DNA sequences
+        assert_eq!(4, levenshtein("GATTACA", "GCATGCU"));
+        assert_eq!(6, levenshtein("ACGTACGT", "TGCATGCA"));
+        assert_eq!(
+            13,
+            levenshtein(
+                "AGGCTATCACCTGACCTCCAGGCCGATGCCC",
+                "TAGCTATCACGACCGCGGTCGATTTGCCCGAC"
+            )
+        );
+    }
+
+    #[test]
+    fn apply_costs() {
+        // The textbook variant where a substitution costs two
+        assert_eq!(8, levenshtein_with_costs("intention", "execution", 1, 1, 2));
+        // Expensive substitutions are replaced by a deletion and an insertion
+        assert_eq!(6, levenshtein_with_costs("abc", "xyz", 1, 1, 5));
+        // Insertions and deletions are not symmetric
+        assert_eq!(10, levenshtein_with_costs("kitten", "sitting", 2, 3, 4));
+        assert_eq!(11, levenshtein_with_costs("sitting", "kitten", 2, 3, 4));
+    }
+
+    #[test]
+    fn align() {
+        let (distance, a, b) = edit_distance_alignment("kitten", "sitting");
+        assert_eq!(distance, 3);
+        assert_eq!(a, "kitten-");
+        assert_eq!(b, "sitting");
+
+        let (distance, a, b) = edit_distance_alignment("", "ab");
+        assert_eq!((distance, a.as_str(), b.as_str()), (2, "--", "ab"));
+    }
+
+    #[test]
+    fn produce_consistent_alignments() {
+        let pairs = [
+            ("GATTACA", "GCATGCU"),
+            ("intention", "execution"),
+            ("日本語の文章", "日本の文書"),
+            ("abc", ""),
+        ];
+        for (x, y) in pairs {
+            let (distance, a, b) = edit_distance_alignment(x, y);
+            assert_eq!(distance, levenshtein(x, y));
+            let a: Vec<char> = a.chars().collect();
+            let b: Vec<char> = b.chars().collect();
+            assert_eq!(a.len(), b.len());
+            assert_eq!(a.iter().filter(|&&c| c != '-').collect::<String>(), x);
+            assert_eq!(b.iter().filter(|&&c| c != '-').collect::<String>(), y);
+            assert!(a.iter().zip(&b).all(|(&p, &q)| p != '-' || q != '-'));
+            let edits = a.iter().zip(&b).filter(|(p, q)| p != q).count();
+            assert_eq!(edits, distance);
+        }
+    }
 }
 
 fn min3(a: usize, b: usize, c: usize) -> usize {
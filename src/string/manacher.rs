@@ -1,91 +1,153 @@
-pub fn manacher(s: String) -> String {
-    let l = s.len();
-    if l <= 1 {
-        return s;
-    }
-
-    // MEMO: We need to detect odd palindrome as well,
-    // therefore, inserting dummy string so that
-    // we can find a pair with dummy center character.
-    let mut chars: Vec<char> = Vec::with_capacity(s.len() * 2 + 1);
-    for c in s.chars() {
-        chars.push('#');
-        chars.push(c);
-    }
-    chars.push('#');
+/// Returns the radii computed by Manacher's algorithm over the transformed
+/// string `#c_0#c_1#...#c_(n-1)#`, where `n` is the number of characters of `s`.
+///
+/// The dummy `#` separators make every palindrome odd, so even palindromes of
+/// `s` get a center as well. `radii[i]` is the largest `r` such that the `2r + 1`
+/// transformed positions centered at `i` form a palindrome, which is also the
+/// length of the corresponding palindrome of `s`: odd positions are centered on
+/// a character of `s`, even ones between two characters.
+///
+/// The separators are never compared with actual characters, so `s` may
+/// contain `#` as well. Runs in `O(n)` time.
+pub fn palindrome_radii(s: &str) -> Vec<usize> {
+    let chars: Vec<char> = s.chars().collect();
+    let len = 2 * chars.len() + 1;
+    // Two positions at the same distance from a center have the same parity:
+    // either both are separators or both are characters
+    let matches =
+        |left: usize, right: usize| left.is_multiple_of(2) || chars[left / 2] == chars[right / 2];
 
-    // List: storing the length of palindrome at each index of string
-    let mut length_of_palindrome = vec![1usize; chars.len()];
-    // Integer: Current checking palindrome's center index
-    let mut current_center: usize = 0;
-    // Integer: Right edge index existing the radius away from current center
-    let mut right_from_current_center: usize = 0;
-
-    for i in 0..chars.len() {
-        // 1: Check if we are looking at right side of palindrome.
-        if right_from_current_center > i && i > current_center {
-            // 1-1: If so copy from the left side of palindrome.
-            // If the value + index exceeds the right edge index, we should cut and check palindrome later #3.
-            length_of_palindrome[i] = std::cmp::min(
-                right_from_current_center - i,
-                length_of_palindrome[2 * current_center - i],
-            );
-            // 1-2: Move the checking palindrome to new index if it exceeds the right edge.
-            if length_of_palindrome[i] + i >= right_from_current_center {
-                current_center = i;
-                right_from_current_center = length_of_palindrome[i] + i;
-                // 1-3: If radius exceeds the end of list, it means checking is over.
-                // You will never get the larger value because the string will get only shorter.
-                if right_from_current_center >= chars.len() - 1 {
-                    break;
-                }
-            } else {
-                // 1-4: If the checking index doesn't exceeds the right edge,
-                // it means the length is just as same as the left side.
-                // You don't need to check anymore.
-                continue;
-            }
-        }
-
-        // Integer: Current radius from checking index
-        // If it's copied from left side and more than 1,
-        // it means it's ensured so you don't need to check inside radius.
-        let mut radius: usize = (length_of_palindrome[i] - 1) / 2;
-        radius += 1;
-        // 2: Checking palindrome.
-        // Need to care about overflow usize.
-        while i >= radius && i + radius <= chars.len() - 1 && chars[i - radius] == chars[i + radius]
-        {
-            length_of_palindrome[i] += 2;
+    let mut radii = vec![0; len];
+    // The palindrome reaching furthest to the right found so far, and its center
+    let (mut center, mut right) = (0, 0);
+    for i in 0..len {
+        // Inside that palindrome, i mirrors 2 * center - i, so it gets at least
+        // the same radius, as long as it does not go past the right edge
+        let mut radius = if i < right {
+            radii[2 * center - i].min(right - i)
+        } else {
+            0
+        };
+        while radius < i && i + radius + 1 < len && matches(i - radius - 1, i + radius + 1) {
             radius += 1;
         }
+        radii[i] = radius;
+        if i + radius > right {
+            center = i;
+            right = i + radius;
+        }
     }
+    radii
+}
 
-    // 3: Find the maximum length and generate answer.
-    let center_of_max = length_of_palindrome
+/// Returns the longest palindromic substring of `s`, as a slice of it.
+///
+/// When several palindromes share the maximum length, the rightmost one is
+/// returned. Palindromes are made of characters rather than bytes.
+pub fn manacher(s: &str) -> &str {
+    let radii = palindrome_radii(s);
+    let (center, radius) = radii
         .iter()
+        .copied()
         .enumerate()
-        .max_by_key(|(_, &value)| value)
-        .map(|(idx, _)| idx)
+        .max_by_key(|&(_, r)| r)
         .unwrap();
-    let radius_of_max = (length_of_palindrome[center_of_max] - 1) / 2;
-    let answer = &chars[(center_of_max - radius_of_max)..(center_of_max + radius_of_max + 1)]
-        .iter()
-        .collect::<String>();
-    answer.replace('#', "")
+    // The palindrome covers the characters (center - radius) / 2 .. (center + radius) / 2
+    let (first, last) = ((center - radius) / 2, (center + radius) / 2);
+    let mut boundaries = s
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(s.len()));
+    let start = boundaries.nth(first).unwrap();
+    let end = if last == first {
+        start
+    } else {
+        boundaries.nth(last - first - 1).unwrap()
+    };
+    &s[start..end]
 }
 
 #[cfg(test)]
 mod tests {
-    use super::manacher;
+    use super::{manacher, palindrome_radii};
+
+    fn is_palindrome(s: &str) -> bool {
+        s.chars().eq(s.chars().rev())
+    }
 
     #[test]
     fn get_longest_palindrome_by_manacher() {
-        assert_eq!(manacher("babad".to_string()), "aba".to_string());
-        assert_eq!(manacher("cbbd".to_string()), "bb".to_string());
-        assert_eq!(manacher("a".to_string()), "a".to_string());
+        assert_eq!(manacher("babad"), "aba");
+        assert_eq!(manacher("cbbd"), "bb");
+        assert_eq!(manacher("a"), "a");
+
+        let ac_ans = manacher("ac");
+        assert!(ac_ans == "a" || ac_ans == "c");
+    }
 
-        let ac_ans = manacher("ac".to_string());
-        assert!(ac_ans == *"a" || ac_ans == *"c");
+    #[test]
+    fn empty_and_single_character() {
+        assert_eq!(manacher(""), "");
+        assert_eq!(palindrome_radii(""), vec![0]);
+        assert_eq!(manacher("x"), "x");
+        assert_eq!(palindrome_radii("x"), vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn fully_palindromic() {
+        assert_eq!(manacher("racecar"), "racecar");
+        assert_eq!(manacher("abba"), "abba");
+        assert_eq!(manacher("aaaa"), "aaaa");
+    }
+
+    #[test]
+    fn no_palindrome_longer_than_one() {
+        assert_eq!(manacher("abcdef"), "f");
+        assert!(palindrome_radii("abcdef").iter().all(|&r| r <= 1));
+    }
+
+    #[test]
+    fn even_and_odd_palindromes() {
+        assert_eq!(manacher("xyzabbaq"), "abba");
+        assert_eq!(manacher("qqracecarxy"), "racecar");
+        // the center of "abba" lies between the two b's
+        assert_eq!(palindrome_radii("abba"), vec![0, 1, 0, 1, 4, 1, 0, 1, 0]);
+        assert_eq!(palindrome_radii("aba"), vec![0, 1, 0, 3, 0, 1, 0]);
+    }
+
+    #[test]
+    fn separators_in_input() {
+        assert_eq!(manacher("#a#"), "#a#");
+        assert_eq!(manacher("ab#"), "#");
+    }
+
+    #[test]
+    fn unicode() {
+        assert_eq!(manacher("xyz日本日abc"), "日本日");
+        assert_eq!(manacher("été"), "été");
+        assert_eq!(manacher("añña!"), "añña");
+        assert_eq!(manacher("xß"), "ß");
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = crate::math::PCG32::new_default(3);
+        for _ in 0..300 {
+            let len = rng.get_u32() % 20;
+            let s: String = (0..len)
+                .map(|_| ['a', 'b', 'é'][rng.get_u32() as usize % 3])
+                .collect();
+            let chars: Vec<char> = s.chars().collect();
+            let longest = (0..=chars.len())
+                .flat_map(|i| (i..=chars.len()).map(move |j| (i, j)))
+                .map(|(i, j)| chars[i..j].iter().collect::<String>())
+                .filter(|candidate| is_palindrome(candidate))
+                .map(|candidate| candidate.chars().count())
+                .max()
+                .unwrap();
+            let found = manacher(&s);
+            assert!(is_palindrome(found));
+            assert_eq!(found.chars().count(), longest);
+        }
     }
 }
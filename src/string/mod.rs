@@ -1,6 +1,7 @@
 mod aho_corasick;
 mod anagram;
 mod autocomplete_using_trie;
+mod boyer_moore_horspool;
 mod boyer_moore_search;
 mod burrows_wheeler_transform;
 mod duval_algorithm;
@@ -11,30 +12,46 @@ mod levenshtein_distance;
 mod manacher;
 mod palindrome;
 mod rabin_karp;
+mod regex;
 mod reverse;
 mod run_length_encoding;
+mod soundex;
 mod suffix_array;
 mod suffix_tree;
 mod z_algorithm;
 
-pub use self::aho_corasick::AhoCorasick;
+pub use self::aho_corasick::{AhoCorasick, Match};
 pub use self::anagram::check_anagram;
 pub use self::autocomplete_using_trie::Autocomplete;
+pub use self::boyer_moore_horspool::{bmh_search, BmhSearcher};
 pub use self::boyer_moore_search::boyer_moore_search;
 pub use self::burrows_wheeler_transform::{
-    burrows_wheeler_transform, inv_burrows_wheeler_transform,
+    burrows_wheeler_transform, bwt, ibwt, inv_burrows_wheeler_transform,
 };
 pub use self::duval_algorithm::duval_algorithm;
 pub use self::hamming_distance::hamming_distance;
-pub use self::jaro_winkler_distance::jaro_winkler_distance;
-pub use self::knuth_morris_pratt::knuth_morris_pratt;
-pub use self::levenshtein_distance::levenshtein_distance;
-pub use self::manacher::manacher;
+pub use self::jaro_winkler_distance::{jaro, jaro_winkler, jaro_winkler_distance};
+pub use self::knuth_morris_pratt::{
+    build_failure_table, kmp_contains, kmp_search, knuth_morris_pratt,
+};
+pub use self::levenshtein_distance::{
+    edit_distance_alignment, levenshtein, levenshtein_distance, levenshtein_with_costs,
+};
+pub use self::manacher::{manacher, palindrome_radii};
 pub use self::palindrome::is_palindrome;
-pub use self::rabin_karp::rabin_karp;
+pub use self::rabin_karp::{
+    rabin_karp, rabin_karp_multi_search, rabin_karp_search, rabin_karp_search_with_params,
+};
+pub use self::regex::regex_match;
 pub use self::reverse::reverse;
-pub use self::run_length_encoding::{run_length_decoding, run_length_encoding};
-pub use self::suffix_array::generate_suffix_array;
+pub use self::run_length_encoding::{
+    rle_decode, rle_decode_str, rle_encode, rle_encode_str, run_length_decoding,
+    run_length_encoding, RleError,
+};
+pub use self::soundex::soundex;
+pub use self::suffix_array::{
+    generate_suffix_array, lcp_array, longest_repeated_substring, suffix_array,
+};
 pub use self::suffix_tree::{Node, SuffixTree};
 pub use self::z_algorithm::match_pattern;
-pub use self::z_algorithm::z_array;
+pub use self::z_algorithm::{z_array, z_search};
@@ -1,67 +1,137 @@
-const MODULUS: u16 = 101;
-const BASE: u16 = 256;
+use std::collections::HashMap;
+
+/// The base of the polynomial hash used by [`rabin_karp_search`].
+pub const DEFAULT_BASE: u64 = 256;
+/// The modulus of the polynomial hash used by [`rabin_karp_search`], a prime.
+pub const DEFAULT_MODULUS: u64 = 1_000_000_007;
 
 pub fn rabin_karp(target: String, pattern: String) -> Vec<usize> {
     // Quick exit
-    if target.is_empty() || pattern.is_empty() || pattern.len() > target.len() {
+    if target.is_empty() || pattern.is_empty() {
         return vec![];
     }
 
-    let pattern_hash = hash(pattern.as_str());
+    rabin_karp_search(&target, &pattern)
+}
 
-    // Pre-calculate BASE^(n-1)
-    let mut pow_rem: u16 = 1;
-    for _ in 0..pattern.len() - 1 {
-        pow_rem *= BASE;
-        pow_rem %= MODULUS;
+/// Returns the starting positions, as byte offsets, of all occurrences of
+/// `pattern` in `text`, overlapping ones included.
+///
+/// Like `kmp_search`, the empty pattern matches at every character boundary.
+pub fn rabin_karp_search(text: &str, pattern: &str) -> Vec<usize> {
+    rabin_karp_search_with_params(text, pattern, DEFAULT_BASE, DEFAULT_MODULUS)
+}
+
+/// [`rabin_karp_search`] with the hash `BASE^(m-1) * s_0 + ... + BASE^0 * s_(m-1) mod MODULUS`
+/// of the `m` bytes of a window computed from the given `base` and `modulus`.
+///
+/// Every window whose hash equals the hash of `pattern` is compared byte by
+/// byte, so collisions only cost time: the search takes `O(n + m)` expected
+/// time for a good choice of parameters, and `O(nm)` when every window collides.
+pub fn rabin_karp_search_with_params(
+    text: &str,
+    pattern: &str,
+    base: u64,
+    modulus: u64,
+) -> Vec<usize> {
+    assert!(modulus > 0, "the modulus must be positive");
+    if pattern.is_empty() {
+        return char_boundaries(text);
     }
+    let pattern = pattern.as_bytes();
+    let pattern_hash = hash(pattern, base, modulus);
+    rolling_hashes(text.as_bytes(), pattern.len(), base, modulus)
+        .filter(|&(i, window_hash)| {
+            window_hash == pattern_hash && &text.as_bytes()[i..i + pattern.len()] == pattern
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
 
-    let mut rolling_hash = 0;
-    let mut ret = vec![];
-    for i in 0..=target.len() - pattern.len() {
-        rolling_hash = if i == 0 {
-            hash(&target[0..pattern.len()])
-        } else {
-            recalculate_hash(
-                target.as_str(),
-                i - 1,
-                i + pattern.len() - 1,
-                rolling_hash,
-                pow_rem,
-            )
-        };
-        if rolling_hash == pattern_hash && pattern[..] == target[i..i + pattern.len()] {
-            ret.push(i);
+/// Searches `text` for all of `patterns` at once, returning the match positions of each.
+///
+/// Text windows are hashed once per distinct pattern length rather than once
+/// per pattern, and each hash is looked up among all patterns of that length.
+pub fn rabin_karp_multi_search<'a>(
+    text: &str,
+    patterns: &[&'a str],
+) -> HashMap<&'a str, Vec<usize>> {
+    let mut by_length: HashMap<usize, HashMap<u64, Vec<&'a str>>> = HashMap::new();
+    let mut matches = HashMap::new();
+    for &pattern in patterns {
+        if matches.insert(pattern, vec![]).is_some() {
+            continue;
+        }
+        if pattern.is_empty() {
+            matches.insert(pattern, char_boundaries(text));
+            continue;
         }
+        by_length
+            .entry(pattern.len())
+            .or_default()
+            .entry(hash(pattern.as_bytes(), DEFAULT_BASE, DEFAULT_MODULUS))
+            .or_default()
+            .push(pattern);
     }
-    ret
+
+    let bytes = text.as_bytes();
+    for (len, candidates) in by_length {
+        for (i, window_hash) in rolling_hashes(bytes, len, DEFAULT_BASE, DEFAULT_MODULUS) {
+            for &pattern in candidates.get(&window_hash).into_iter().flatten() {
+                if &bytes[i..i + len] == pattern.as_bytes() {
+                    matches.get_mut(pattern).unwrap().push(i);
+                }
+            }
+        }
+    }
+    matches
+}
+
+fn char_boundaries(text: &str) -> Vec<usize> {
+    text.char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+        .collect()
 }
 
 // hash(s) is defined as BASE^(n-1) * s_0 + BASE^(n-2) * s_1 + ... + BASE^0 * s_(n-1)
-fn hash(s: &str) -> u16 {
-    let mut res: u16 = 0;
-    for &c in s.as_bytes().iter() {
-        res = (res * BASE % MODULUS + c as u16) % MODULUS;
+fn hash(s: &[u8], base: u64, modulus: u64) -> u64 {
+    let mut res = 0;
+    for &c in s {
+        res = (mul_mod(res, base, modulus) + c as u64) % modulus;
     }
     res
 }
 
-// new_hash = (old_hash - BASE^(n-1) * s_(i-n)) * BASE + s_i
-fn recalculate_hash(
-    s: &str,
-    old_index: usize,
-    new_index: usize,
-    old_hash: u16,
-    pow_rem: u16,
-) -> u16 {
-    let mut new_hash = old_hash;
-    let (old_ch, new_ch) = (
-        s.as_bytes()[old_index] as u16,
-        s.as_bytes()[new_index] as u16,
-    );
-    new_hash = (new_hash + MODULUS - pow_rem * old_ch % MODULUS) % MODULUS;
-    new_hash = (new_hash * BASE + new_ch) % MODULUS;
-    new_hash
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+/// Yields `(i, hash(&s[i..i + len]))` for every window of `len` bytes of `s`,
+/// where new_hash = (old_hash - BASE^(len-1) * s_(i-1)) * BASE + s_(i+len-1)
+fn rolling_hashes(
+    s: &[u8],
+    len: usize,
+    base: u64,
+    modulus: u64,
+) -> impl Iterator<Item = (usize, u64)> + '_ {
+    let windows = (s.len() + 1).saturating_sub(len);
+    // Pre-calculate BASE^(len-1)
+    let mut pow_rem = 1 % modulus;
+    for _ in 1..len {
+        pow_rem = mul_mod(pow_rem, base, modulus);
+    }
+    let mut rolling_hash = 0;
+    (0..windows).map(move |i| {
+        rolling_hash = if i == 0 {
+            hash(&s[..len], base, modulus)
+        } else {
+            let old = mul_mod(pow_rem, s[i - 1] as u64, modulus);
+            let removed = (rolling_hash + modulus - old) % modulus;
+            (mul_mod(removed, base, modulus) + s[i + len - 1] as u64) % modulus
+        };
+        (i, rolling_hash)
+    })
 }
 
 #[cfg(test)]
@@ -70,19 +140,19 @@ mod tests {
 
     #[test]
     fn hi_hash() {
-        let hash_result = hash("hi");
+        let hash_result = hash(b"hi", 256, 101);
         assert_eq!(hash_result, 65);
     }
 
     #[test]
     fn abr_hash() {
-        let hash_result = hash("abr");
+        let hash_result = hash(b"abr", 256, 101);
         assert_eq!(hash_result, 4);
     }
 
     #[test]
     fn bra_hash() {
-        let hash_result = hash("bra");
+        let hash_result = hash(b"bra", 256, 101);
         assert_eq!(hash_result, 30);
     }
 
@@ -140,4 +210,63 @@ mod tests {
         let index = rabin_karp("".to_string(), "abcdef".to_string());
         assert_eq!(index, vec![]);
     }
+
+    #[test]
+    fn unicode() {
+        let text = "naïve café, naïve crème";
+        assert_eq!(rabin_karp_search(text, "naïve"), vec![0, 14]);
+        assert_eq!(rabin_karp_search(text, "ï"), vec![2, 16]);
+        assert_eq!(rabin_karp_search("añb", ""), vec![0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn spurious_collisions() {
+        // With a base of 1 the hash is the sum of the bytes, so every window
+        // that is an anagram of the pattern collides with it
+        let text = "ba".repeat(500);
+        let matches = rabin_karp_search_with_params(&text, "ab", 1, DEFAULT_MODULUS);
+        assert_eq!(matches, (1..999).step_by(2).collect::<Vec<_>>());
+
+        // With a modulus of 1 every window collides with every pattern
+        let text = "abcabcabd".repeat(100);
+        let collide = |pattern| rabin_karp_search_with_params(&text, pattern, DEFAULT_BASE, 1);
+        assert_eq!(collide("abd"), (6..900).step_by(9).collect::<Vec<_>>());
+        assert_eq!(collide("bca"), rabin_karp_search(&text, "bca"));
+        assert_eq!(collide("abe"), vec![]);
+    }
+
+    #[test]
+    fn matches_knuth_morris_pratt() {
+        let mut rng = crate::math::PCG32::new_default(7);
+        let mut random_string = |max_len: u32| -> String {
+            let len = rng.get_u32() % (max_len + 1);
+            (0..len)
+                .map(|_| ['x', 'y', 'ü'][rng.get_u32() as usize % 3])
+                .collect()
+        };
+        for _ in 0..500 {
+            let text = random_string(50);
+            let pattern = random_string(5);
+            let expected = crate::string::kmp_search(&text, &pattern);
+            assert_eq!(rabin_karp_search(&text, &pattern), expected);
+            assert_eq!(
+                rabin_karp_search_with_params(&text, &pattern, 31, 13),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn multi_search() {
+        let text = "she sells seashells by the seashore";
+        let patterns = ["she", "sea", "shells", "s", "", "missing", "sea"];
+        let matches = rabin_karp_multi_search(text, &patterns);
+        assert_eq!(matches.len(), 6);
+        for pattern in patterns {
+            assert_eq!(matches[pattern], crate::string::kmp_search(text, pattern));
+        }
+        assert_eq!(matches["sea"], vec![10, 27]);
+        assert_eq!(matches["missing"], vec![]);
+        assert!(rabin_karp_multi_search(text, &[]).is_empty());
+    }
 }
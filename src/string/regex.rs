@@ -0,0 +1,508 @@
+//! Regular expressions matched by simulating a Thompson NFA.
+//!
+//! The pattern is compiled into a nondeterministic automaton with a state per
+//! character and operator, and the text is read one character at a time while
+//! keeping the set of every state the automaton could be in. Unlike
+//! backtracking, this takes `O(m n)` time for a pattern of length `m` and a
+//! text of length `n`, even for patterns such as `(a*)*b`.
+
+/// Returns whether the whole of `text` matches `pattern`.
+///
+/// The pattern supports:
+/// - `.`, any character
+/// - `*`, `+` and `?`, zero or more, one or more, and zero or one of what
+///   precedes them
+/// - `[...]`, any of a set of characters and ranges such as `a-z`, or any
+///   character not in it with `[^...]`
+/// - `(...)` to group, and `|` between alternatives
+/// - `\` before any of these to match it literally
+///
+/// # Panics
+///
+/// Panics if the pattern is not well formed, for instance with unbalanced
+/// parentheses or a quantifier with nothing to repeat.
+pub fn regex_match(pattern: &str, text: &str) -> bool {
+    Nfa::compile(pattern).matches(text)
+}
+
+/// A set of characters a state can read.
+enum CharClass {
+    Any,
+    Char(char),
+    Ranges {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl CharClass {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            CharClass::Any => true,
+            CharClass::Char(expected) => c == *expected,
+            CharClass::Ranges { negated, ranges } => {
+                ranges.iter().any(|&(low, high)| low <= c && c <= high) != *negated
+            }
+        }
+    }
+}
+
+/// A state of the automaton, with the states it leads to.
+enum State {
+    /// Reads a character of the class.
+    Read(CharClass, usize),
+    /// Goes to either state without reading anything.
+    Split(usize, usize),
+    /// Goes to the state without reading anything.
+    Jump(usize),
+    Match,
+}
+
+/// The place of a link to a next state, yet to be set, in a state.
+#[derive(Clone, Copy)]
+enum Hole {
+    Next(usize),
+    SplitSecond(usize),
+}
+
+impl Hole {
+    fn state(self) -> usize {
+        match self {
+            Hole::Next(state) | Hole::SplitSecond(state) => state,
+        }
+    }
+}
+
+/// A part of the automaton, with its first state and the links out of it.
+struct Fragment {
+    start: usize,
+    holes: Vec<Hole>,
+}
+
+struct Nfa {
+    states: Vec<State>,
+    start: usize,
+}
+
+impl Nfa {
+    fn compile(pattern: &str) -> Self {
+        let mut parser = Parser {
+            pattern: pattern.chars().collect(),
+            position: 0,
+            states: Vec::new(),
+        };
+        let fragment = parser.alternation();
+        if parser.position < parser.pattern.len() {
+            panic!("invalid pattern: unmatched ')' in {pattern:?}");
+        }
+        let end = parser.push(State::Match);
+        parser.patch(&fragment.holes, end);
+        Nfa {
+            states: parser.states,
+            start: fragment.start,
+        }
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        // the reading states of the current set, and the character count
+        // each state was last added at, so that it is added once per step
+        let mut current = Vec::new();
+        let mut added_at = vec![usize::MAX; self.states.len()];
+        let mut matched = self.add(self.start, 0, &mut current, &mut added_at);
+
+        for (step, c) in text.chars().enumerate() {
+            let mut next = Vec::new();
+            matched = false;
+            for &state in &current {
+                if let State::Read(class, to) = &self.states[state] {
+                    if class.contains(c) {
+                        matched |= self.add(*to, step + 1, &mut next, &mut added_at);
+                    }
+                }
+            }
+            if next.is_empty() && !matched {
+                return false;
+            }
+            current = next;
+        }
+        matched
+    }
+
+    /// Adds the reading states reached from `state` without reading anything
+    /// to `set`, and returns whether the final state is one of those reached.
+    fn add(&self, state: usize, step: usize, set: &mut Vec<usize>, added_at: &mut [usize]) -> bool {
+        if added_at[state] == step {
+            return false;
+        }
+        added_at[state] = step;
+        match self.states[state] {
+            State::Read(..) => {
+                set.push(state);
+                false
+            }
+            State::Split(first, second) => {
+                // both are followed, so that a match through either is found
+                let first = self.add(first, step, set, added_at);
+                self.add(second, step, set, added_at) || first
+            }
+            State::Jump(to) => self.add(to, step, set, added_at),
+            State::Match => true,
+        }
+    }
+}
+
+/// Builds the automaton by recursive descent on the pattern.
+struct Parser {
+    pattern: Vec<char>,
+    position: usize,
+    states: Vec<State>,
+}
+
+// the placeholder for the links that are not set yet
+const UNSET: usize = usize::MAX;
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.pattern.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn push(&mut self, state: State) -> usize {
+        self.states.push(state);
+        self.states.len() - 1
+    }
+
+    fn patch(&mut self, holes: &[Hole], to: usize) {
+        for &hole in holes {
+            match (hole, &mut self.states[hole.state()]) {
+                (Hole::Next(_), State::Read(_, next) | State::Jump(next)) => *next = to,
+                (Hole::SplitSecond(_), State::Split(_, second)) => *second = to,
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    /// Parses alternatives separated by `|`.
+    fn alternation(&mut self) -> Fragment {
+        let mut fragment = self.concatenation();
+        while self.peek() == Some('|') {
+            self.position += 1;
+            let other = self.concatenation();
+            let split = self.push(State::Split(fragment.start, other.start));
+            fragment.holes.extend(other.holes);
+            fragment.start = split;
+        }
+        fragment
+    }
+
+    /// Parses a sequence of repeated atoms, up to a `|` or `)`.
+    fn concatenation(&mut self) -> Fragment {
+        let mut fragment: Option<Fragment> = None;
+        while !matches!(self.peek(), None | Some('|') | Some(')')) {
+            let next = self.repetition();
+            fragment = Some(match fragment {
+                None => next,
+                Some(previous) => {
+                    self.patch(&previous.holes, next.start);
+                    Fragment {
+                        start: previous.start,
+                        holes: next.holes,
+                    }
+                }
+            });
+        }
+        // the empty pattern goes on without reading anything
+        fragment.unwrap_or_else(|| {
+            let jump = self.push(State::Jump(UNSET));
+            Fragment {
+                start: jump,
+                holes: vec![Hole::Next(jump)],
+            }
+        })
+    }
+
+    /// Parses an atom followed by any number of quantifiers.
+    fn repetition(&mut self) -> Fragment {
+        let mut fragment = self.atom();
+        while let Some(quantifier @ ('*' | '+' | '?')) = self.peek() {
+            self.position += 1;
+            let split = self.push(State::Split(fragment.start, UNSET));
+            fragment = match quantifier {
+                // the split goes back to the start after each repetition
+                '*' => {
+                    self.patch(&fragment.holes, split);
+                    Fragment {
+                        start: split,
+                        holes: vec![Hole::SplitSecond(split)],
+                    }
+                }
+                '+' => {
+                    self.patch(&fragment.holes, split);
+                    Fragment {
+                        start: fragment.start,
+                        holes: vec![Hole::SplitSecond(split)],
+                    }
+                }
+                _ => {
+                    fragment.holes.push(Hole::SplitSecond(split));
+                    Fragment {
+                        start: split,
+                        holes: fragment.holes,
+                    }
+                }
+            };
+        }
+        fragment
+    }
+
+    /// Parses a character, a character class or a group.
+    fn atom(&mut self) -> Fragment {
+        let class = match self.next() {
+            Some('(') => {
+                let fragment = self.alternation();
+                if self.next() != Some(')') {
+                    panic!("invalid pattern: unmatched '('");
+                }
+                return fragment;
+            }
+            Some('[') => self.class(),
+            Some('.') => CharClass::Any,
+            Some('\\') => CharClass::Char(self.escaped()),
+            Some(c @ ('*' | '+' | '?')) => {
+                panic!("invalid pattern: nothing to repeat before {c:?}")
+            }
+            Some(c) => CharClass::Char(c),
+            None => unreachable!(),
+        };
+        let read = self.push(State::Read(class, UNSET));
+        Fragment {
+            start: read,
+            holes: vec![Hole::Next(read)],
+        }
+    }
+
+    /// Parses a character class, after its `[`.
+    fn class(&mut self) -> CharClass {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.position += 1;
+        }
+        let mut ranges = Vec::new();
+        // a ']' right at the start is a character of the class
+        let mut first = true;
+        loop {
+            let low = match self.next() {
+                None => panic!("invalid pattern: unmatched '['"),
+                Some(']') if !first => break,
+                Some('\\') => self.escaped(),
+                Some(c) => c,
+            };
+            first = false;
+            // a '-' before the closing ']' is a character of the class
+            let high = if self.peek() == Some('-')
+                && !matches!(self.pattern.get(self.position + 1), None | Some(']'))
+            {
+                self.position += 1;
+                match self.next() {
+                    Some('\\') => self.escaped(),
+                    Some(c) => c,
+                    None => unreachable!(),
+                }
+            } else {
+                low
+            };
+            if low > high {
+                panic!("invalid pattern: empty range {low:?}-{high:?}");
+            }
+            ranges.push((low, high));
+        }
+        CharClass::Ranges { negated, ranges }
+    }
+
+    /// Returns the character after a `\`.
+    fn escaped(&mut self) -> char {
+        self.next()
+            .unwrap_or_else(|| panic!("invalid pattern: nothing to escape after '\\'"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PCG32;
+
+    /// Matches patterns of characters, `.` and `*` by dynamic programming.
+    fn dp_match(pattern: &str, text: &str) -> bool {
+        let p: Vec<char> = pattern.chars().collect();
+        let t: Vec<char> = text.chars().collect();
+        // matched[i][j]: whether t[i..] matches p[j..]
+        let mut matched = vec![vec![false; p.len() + 1]; t.len() + 1];
+        matched[t.len()][p.len()] = true;
+        for i in (0..=t.len()).rev() {
+            for j in (0..p.len()).rev() {
+                let first = i < t.len() && (p[j] == '.' || p[j] == t[i]);
+                matched[i][j] = if j + 1 < p.len() && p[j + 1] == '*' {
+                    matched[i][j + 2] || (first && matched[i + 1][j])
+                } else {
+                    first && matched[i + 1][j + 1]
+                };
+            }
+        }
+        matched[0][0]
+    }
+
+    #[test]
+    fn leetcode_cases() {
+        let cases = [
+            ("a", "aa", false),
+            ("a*", "aa", true),
+            (".*", "ab", true),
+            ("c*a*b", "aab", true),
+            ("mis*is*p*.", "mississippi", false),
+            ("mis*is*ip*.", "mississippi", true),
+            (".*c", "ab", false),
+            ("a*a", "aaa", true),
+            ("ab*a*c*a", "aaa", true),
+            ("c*", "", true),
+            (".*..a*", "a", false),
+            ("a", "", false),
+            ("", "", true),
+            ("", "a", false),
+        ];
+        for (pattern, text, expected) in cases {
+            assert_eq!(regex_match(pattern, text), expected, "{pattern} {text}");
+            assert_eq!(dp_match(pattern, text), expected, "{pattern} {text}");
+        }
+    }
+
+    #[test]
+    fn dot_star_matches_everything() {
+        for text in ["", "a", "hello world", "*+?[]()|\\", "日本語"] {
+            assert!(regex_match(".*", text));
+        }
+    }
+
+    #[test]
+    fn quantifiers() {
+        assert!(!regex_match("a+", ""));
+        assert!(regex_match("a+", "a"));
+        assert!(regex_match("a+", "aaaa"));
+        assert!(!regex_match("a+", "aab"));
+        assert!(regex_match("ba+b", "baaab"));
+        assert!(!regex_match("ba+b", "bb"));
+
+        assert!(regex_match("colou?r", "color"));
+        assert!(regex_match("colou?r", "colour"));
+        assert!(!regex_match("colou?r", "colouur"));
+        assert!(regex_match("a??", ""));
+        assert!(regex_match("a+?", "aaa"));
+    }
+
+    #[test]
+    fn nested_quantifiers() {
+        assert!(regex_match("(a*b)+", "b"));
+        assert!(regex_match("(a*b)+", "aab"));
+        assert!(regex_match("(a*b)+", "abaaabbb"));
+        assert!(!regex_match("(a*b)+", ""));
+        assert!(!regex_match("(a*b)+", "aba"));
+        assert!(regex_match("((ab)?c)*", "abccabc"));
+        assert!(!regex_match("((ab)?c)*", "abcb"));
+        assert!(regex_match("(a*)*", ""));
+        assert!(regex_match("(a|b*)+c", "abbac"));
+
+        // exponential for backtracking, but not here
+        let text = "a".repeat(1000);
+        assert!(!regex_match("(a*)*b", &text));
+        assert!(!regex_match("(a|aa)*c", &text));
+        assert!(regex_match("(a|aa)*", &text));
+    }
+
+    #[test]
+    fn alternation_and_groups() {
+        assert!(regex_match("cat|dog", "cat"));
+        assert!(regex_match("cat|dog", "dog"));
+        assert!(!regex_match("cat|dog", "cog"));
+        assert!(regex_match("(c|d)o(g|t)", "cot"));
+        assert!(regex_match("a(|b)c", "ac"));
+        assert!(regex_match("a(|b)c", "abc"));
+        assert!(regex_match("()", ""));
+    }
+
+    #[test]
+    fn character_classes() {
+        assert!(regex_match("[abc]+", "abcabc"));
+        assert!(!regex_match("[abc]+", "abcd"));
+        assert!(regex_match("[a-z]+[0-9]*", "hello42"));
+        assert!(!regex_match("[a-z]+[0-9]*", "Hello42"));
+        assert!(regex_match("[^0-9]*", "no digits"));
+        assert!(!regex_match("[^0-9]*", "one 1"));
+        assert!(regex_match("[-a]*", "a-a"));
+        assert!(regex_match("[a-]*", "a-a"));
+        assert!(regex_match("[]]", "]"));
+        assert!(regex_match("[^]]", "a"));
+        assert!(regex_match("[.*]+", ".*."));
+        assert!(!regex_match("[.*]", "a"));
+    }
+
+    #[test]
+    fn escapes() {
+        assert!(regex_match(r"a\.b", "a.b"));
+        assert!(!regex_match(r"a\.b", "axb"));
+        assert!(regex_match(r"\(\*\)", "(*)"));
+        assert!(regex_match(r"[\]\\]+", r"]\"));
+        assert!(regex_match(r"1\+1", "1+1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "unmatched '('")]
+    fn unmatched_open() {
+        regex_match("(ab", "ab");
+    }
+
+    #[test]
+    #[should_panic(expected = "unmatched ')'")]
+    fn unmatched_close() {
+        regex_match("ab)", "ab");
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing to repeat")]
+    fn nothing_to_repeat() {
+        regex_match("*a", "a");
+    }
+
+    #[test]
+    #[should_panic(expected = "unmatched '['")]
+    fn unterminated_class() {
+        regex_match("[ab", "a");
+    }
+
+    #[test]
+    fn matches_dynamic_programming() {
+        let mut rng = PCG32::new_default(94);
+        for _ in 0..2000 {
+            let pattern_len = rng.get_u32() % 8;
+            let mut pattern = String::new();
+            for _ in 0..pattern_len {
+                pattern.push(['a', 'b', '.'][rng.get_u32() as usize % 3]);
+                if rng.get_u32().is_multiple_of(3) {
+                    pattern.push('*');
+                }
+            }
+            let text_len = rng.get_u32() % 8;
+            let text: String = (0..text_len)
+                .map(|_| ['a', 'b'][rng.get_u32() as usize % 2])
+                .collect();
+            assert_eq!(
+                regex_match(&pattern, &text),
+                dp_match(&pattern, &text),
+                "{pattern} {text}"
+            );
+        }
+    }
+}
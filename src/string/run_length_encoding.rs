@@ -1,28 +1,115 @@
-pub fn run_length_encoding(target: String) -> String {
-    if target.trim().is_empty() {
-        return "String is Empty!".to_string();
+use std::fmt;
+
+/// The reasons a run-length encoding cannot be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RleError {
+    /// A run repeats its element zero times, which `rle_encode` never produces.
+    ZeroLengthRun,
+    /// A character of an encoded string is not preceded by its count.
+    MissingCount,
+    /// An encoded string ends with a count but no character.
+    MissingCharacter,
+    /// A run length of an encoded string, or the length of the decoded bytes,
+    /// does not fit in a `usize`.
+    CountOverflow,
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RleError::ZeroLengthRun => write!(f, "run of length zero"),
+            RleError::MissingCount => write!(f, "character without a run length"),
+            RleError::MissingCharacter => write!(f, "run length without a character"),
+            RleError::CountOverflow => write!(f, "run length too large"),
+        }
     }
-    let mut count: i32 = 0;
-    let mut base_character: String = "".to_string();
-    let mut encoded_target = String::new();
+}
 
-    for c in target.as_str().chars() {
-        if base_character == *"" {
-            base_character = c.to_string();
+impl std::error::Error for RleError {}
+
+/// Splits `input` into maximal runs of equal bytes, as `(byte, run length)` pairs.
+pub fn rle_encode(input: &[u8]) -> Vec<(u8, usize)> {
+    let mut runs: Vec<(u8, usize)> = Vec::new();
+    for &byte in input {
+        match runs.last_mut() {
+            Some((last, count)) if *last == byte => *count += 1,
+            _ => runs.push((byte, 1)),
+        }
+    }
+    runs
+}
+
+/// Expands `(byte, run length)` pairs back into the bytes they encode.
+///
+/// Consecutive runs of the same byte are accepted, but runs of length 0 are
+/// rejected with [`RleError::ZeroLengthRun`], and runs adding up to more than
+/// `usize::MAX` bytes with [`RleError::CountOverflow`].
+pub fn rle_decode(encoded: &[(u8, usize)]) -> Result<Vec<u8>, RleError> {
+    // checked before allocating, so that invalid runs allocate nothing
+    let len = encoded.iter().try_fold(0_usize, |len, &(_, count)| {
+        if count == 0 {
+            return Err(RleError::ZeroLengthRun);
         }
-        if c.to_string() == base_character {
+        len.checked_add(count).ok_or(RleError::CountOverflow)
+    })?;
+    let mut decoded = Vec::with_capacity(len);
+    for &(byte, count) in encoded {
+        decoded.extend(std::iter::repeat_n(byte, count));
+    }
+    Ok(decoded)
+}
+
+/// Encodes every maximal run of equal characters of `input` as its length in
+/// decimal followed by the character, e.g. `"aaab"` becomes `"3a1b"`.
+///
+/// Runs of ASCII digits cannot be told apart from run lengths, so
+/// [`rle_decode_str`] only restores inputs that contain none.
+pub fn rle_encode_str(input: &str) -> String {
+    let mut encoded = String::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut count = 1;
+        while chars.next_if_eq(&c).is_some() {
             count += 1;
-        } else {
-            encoded_target.push_str(&count.to_string());
-            count = 1;
-            encoded_target.push_str(&base_character);
-            base_character = c.to_string();
         }
+        encoded.push_str(&count.to_string());
+        encoded.push(c);
+    }
+    encoded
+}
+
+/// Decodes a string produced by [`rle_encode_str`].
+pub fn rle_decode_str(encoded: &str) -> Result<String, RleError> {
+    let mut decoded = String::new();
+    let mut count: Option<usize> = None;
+    for c in encoded.chars() {
+        match c.to_digit(10) {
+            Some(digit) => {
+                let extended = count
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(digit as usize));
+                count = Some(extended.ok_or(RleError::CountOverflow)?);
+            }
+            None => match count.take() {
+                None => return Err(RleError::MissingCount),
+                Some(0) => return Err(RleError::ZeroLengthRun),
+                Some(n) => decoded.extend(std::iter::repeat_n(c, n)),
+            },
+        }
+    }
+    match count {
+        Some(_) => Err(RleError::MissingCharacter),
+        None => Ok(decoded),
+    }
+}
+
+pub fn run_length_encoding(target: String) -> String {
+    if target.trim().is_empty() {
+        return "String is Empty!".to_string();
     }
-    encoded_target.push_str(&count.to_string());
-    encoded_target.push_str(&base_character);
 
-    encoded_target
+    rle_encode_str(&target)
 }
 
 pub fn run_length_decoding(target: String) -> String {
@@ -135,4 +222,82 @@ mod tests {
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaabbbcccccdddddddddd".to_string()
         )
     }
+
+    #[test]
+    fn encode_bytes() {
+        assert_eq!(rle_encode(b""), vec![]);
+        assert_eq!(rle_encode(b"x"), vec![(b'x', 1)]);
+        assert_eq!(rle_encode(b"abc"), vec![(b'a', 1), (b'b', 1), (b'c', 1)]);
+        assert_eq!(rle_encode(&[7; 1000]), vec![(7, 1000)]);
+        assert_eq!(
+            rle_encode(b"aaabccdddd"),
+            vec![(b'a', 3), (b'b', 1), (b'c', 2), (b'd', 4)]
+        );
+    }
+
+    #[test]
+    fn long_runs_are_not_capped() {
+        let input: Vec<u8> = [vec![0; 256], vec![1; 70_000], vec![0; 1]].concat();
+        let encoded = rle_encode(&input);
+        assert_eq!(encoded, vec![(0, 256), (1, 70_000), (0, 1)]);
+        assert_eq!(rle_decode(&encoded), Ok(input));
+        assert_eq!(
+            rle_decode_str(&rle_encode_str(&"z".repeat(300))),
+            Ok("z".repeat(300))
+        );
+    }
+
+    #[test]
+    fn decode_bytes() {
+        assert_eq!(rle_decode(&[]), Ok(vec![]));
+        assert_eq!(rle_decode(&[(b'a', 2), (b'a', 1)]), Ok(b"aaa".to_vec()));
+        assert_eq!(
+            rle_decode(&[(b'a', 2), (b'b', 0)]),
+            Err(RleError::ZeroLengthRun)
+        );
+        // neither is allocated for
+        assert_eq!(
+            rle_decode(&[(b'a', usize::MAX), (b'b', 0)]),
+            Err(RleError::ZeroLengthRun)
+        );
+        assert_eq!(
+            rle_decode(&[(b'a', usize::MAX), (b'b', 1)]),
+            Err(RleError::CountOverflow)
+        );
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(rle_encode_str(""), "");
+        assert_eq!(rle_encode_str("ééén"), "3é1n");
+        assert_eq!(rle_decode_str("3é1n"), Ok("ééén".to_string()));
+        assert_eq!(rle_decode_str("12ab"), Err(RleError::MissingCount));
+        assert_eq!(rle_decode_str("2a3"), Err(RleError::MissingCharacter));
+        assert_eq!(rle_decode_str("0a"), Err(RleError::ZeroLengthRun));
+        assert_eq!(
+            rle_decode_str("99999999999999999999a"),
+            Err(RleError::CountOverflow)
+        );
+        assert_eq!(
+            rle_decode_str(&format!("{}0a", usize::MAX)),
+            Err(RleError::CountOverflow)
+        );
+        assert_eq!(RleError::ZeroLengthRun.to_string(), "run of length zero");
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = crate::math::PCG32::new_default(17);
+        for _ in 0..500 {
+            let len = rng.get_u32() % 100;
+            // few distinct values, so that runs are frequent
+            let input: Vec<u8> = (0..len).map(|_| (rng.get_u32() % 3) as u8).collect();
+            let encoded = rle_encode(&input);
+            assert!(encoded.windows(2).all(|w| w[0].0 != w[1].0));
+            assert_eq!(rle_decode(&encoded), Ok(input.clone()));
+
+            let text: String = input.iter().map(|&b| ['a', 'ß', ' '][b as usize]).collect();
+            assert_eq!(rle_decode_str(&rle_encode_str(&text)), Ok(text));
+        }
+    }
 }
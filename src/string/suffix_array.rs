@@ -2,94 +2,198 @@
 // It is a data structure used in, among others, full-text indices, data-compression algorithms,
 // and the field of bibliometrics. Source: https://en.wikipedia.org/wiki/Suffix_array
 
-use std::cmp::Ordering;
+/// Returns the starting byte offsets of the non-empty suffixes of `s`, in
+/// lexicographic order of the suffixes.
+///
+/// Uses prefix doubling: after the round for `k`, the suffixes are sorted by
+/// their first `k` bytes, and each round sorts by pairs of ranks from the
+/// previous one with two counting sorts, so the whole takes O(n log n) time.
+pub fn suffix_array(s: &str) -> Vec<usize> {
+    let bytes = s.as_bytes();
+    let n = bytes.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut rank: Vec<usize> = bytes.iter().map(|&b| b as usize).collect();
+    let mut sa = counting_sort_by_rank(0..n, &rank, 256);
+    let mut classes = rerank(&sa, &mut rank, |_| None);
+    let mut k = 1;
+    while classes < n {
+        // sorted by the second half first: suffixes with an empty second half
+        // come before all others, then the rest in the order of the last round
+        let by_second = (n - k..n).chain(sa.iter().filter(|&&i| i >= k).map(|&i| i - k));
+        let sorted = counting_sort_by_rank(by_second, &rank, classes);
+        let previous = rank.clone();
+        classes = rerank(&sorted, &mut rank, |i| previous.get(i + k).copied());
+        sa = sorted;
+        k *= 2;
+    }
+    sa
+}
 
-#[derive(Clone)]
-struct Suffix {
-    index: usize,
-    rank: (i32, i32),
+/// Stable counting sort of `order` by `rank`, whose values are below `classes`.
+fn counting_sort_by_rank(
+    order: impl Iterator<Item = usize> + Clone,
+    rank: &[usize],
+    classes: usize,
+) -> Vec<usize> {
+    let mut start = vec![0; classes + 1];
+    for i in order.clone() {
+        start[rank[i] + 1] += 1;
+    }
+    for c in 0..classes {
+        start[c + 1] += start[c];
+    }
+    let mut sorted = vec![0; rank.len()];
+    for i in order {
+        sorted[start[rank[i]]] = i;
+        start[rank[i]] += 1;
+    }
+    sorted
 }
 
-impl Suffix {
-    fn cmp(&self, b: &Self) -> Ordering {
-        let a = self;
-        let ((a1, a2), (b1, b2)) = (a.rank, b.rank);
-        match a1.cmp(&b1) {
-            Ordering::Equal => {
-                if a2 < b2 {
-                    Ordering::Less
-                } else {
-                    Ordering::Greater
-                }
-            }
-            o => o,
+/// Gives the suffixes in `sa` new ranks from 0, equal for neighbours with the
+/// same rank and the same `second` key, and returns the number of ranks.
+fn rerank(sa: &[usize], rank: &mut [usize], second: impl Fn(usize) -> Option<usize>) -> usize {
+    let keys: Vec<_> = sa.iter().map(|&i| (rank[i], second(i))).collect();
+    let mut classes = 0;
+    for (j, &i) in sa.iter().enumerate() {
+        if j > 0 && keys[j] != keys[j - 1] {
+            classes += 1;
         }
+        rank[i] = classes;
     }
+    classes + 1
 }
 
-pub fn generate_suffix_array(txt: &str) -> Vec<usize> {
-    let n = txt.len();
-    let mut suffixes: Vec<Suffix> = vec![
-        Suffix {
-            index: 0,
-            rank: (-1, -1)
-        };
-        n
-    ];
-    for (i, suf) in suffixes.iter_mut().enumerate() {
-        suf.index = i;
-        suf.rank.0 = (txt.chars().nth(i).expect("this should exist") as u32 - 'a' as u32) as i32;
-        suf.rank.1 = if (i + 1) < n {
-            (txt.chars().nth(i + 1).expect("this should exist") as u32 - 'a' as u32) as i32
-        } else {
-            -1
+/// Returns the LCP array of `s` for its suffix array `sa`: `lcp[i]` is the
+/// length of the longest common prefix of the suffixes at `sa[i - 1]` and
+/// `sa[i]`, and `lcp[0]` is 0.
+///
+/// Uses Kasai's algorithm, in O(n) time: going from the suffix at `i` to the
+/// one at `i + 1` loses one byte, so the common prefix with the suffix before
+/// it shrinks by at most one.
+pub fn lcp_array(s: &str, sa: &[usize]) -> Vec<usize> {
+    let bytes = s.as_bytes();
+    let n = sa.len();
+    let mut position = vec![0; n];
+    for (j, &i) in sa.iter().enumerate() {
+        position[i] = j;
+    }
+    let mut lcp = vec![0; n];
+    let mut h = 0;
+    for i in 0..n {
+        if position[i] == 0 {
+            h = 0;
+            continue;
         }
+        let previous = sa[position[i] - 1];
+        while i + h < n && previous + h < n && bytes[i + h] == bytes[previous + h] {
+            h += 1;
+        }
+        lcp[position[i]] = h;
+        h = h.saturating_sub(1);
     }
-    suffixes.sort_by(|a, b| a.cmp(b));
-    let mut ind = vec![0; n];
-    let mut k = 4;
-    while k < 2 * n {
-        let mut rank = 0;
-        let mut prev_rank = suffixes[0].rank.0;
-        suffixes[0].rank.0 = rank;
-        ind[suffixes[0].index] = 0;
+    lcp
+}
 
-        for i in 1..n {
-            if suffixes[i].rank.0 == prev_rank && suffixes[i].rank.1 == suffixes[i - 1].rank.1 {
-                prev_rank = suffixes[i].rank.0;
-                suffixes[i].rank.0 = rank;
-            } else {
-                prev_rank = suffixes[i].rank.0;
-                rank += 1;
-                suffixes[i].rank.0 = rank;
-            }
-            ind[suffixes[i].index] = i;
+/// Returns the longest substring occurring at least twice in `s`, the first
+/// one in the order of the suffix array if there are several, and an empty
+/// string if no character repeats. The occurrences may overlap.
+pub fn longest_repeated_substring(s: &str) -> &str {
+    let sa = suffix_array(s);
+    let lcp = lcp_array(s, &sa);
+    // the suffixes compared must start on character boundaries; the common
+    // prefix of two such neighbours is the minimum of the LCPs between them
+    let mut best = (0, 0);
+    let mut common = usize::MAX;
+    let mut seen_boundary = false;
+    for (j, &i) in sa.iter().enumerate() {
+        common = common.min(lcp[j]);
+        if !s.is_char_boundary(i) {
+            continue;
         }
-        for i in 0..n {
-            let next_index = suffixes[i].index + (k / 2);
-            suffixes[i].rank.1 = if next_index < n {
-                suffixes[ind[next_index]].rank.0
-            } else {
-                -1
+        if seen_boundary {
+            let mut len = common;
+            while !s.is_char_boundary(i + len) {
+                len -= 1;
+            }
+            if len > best.1 {
+                best = (i, len);
             }
         }
-        suffixes.sort_by(|a, b| a.cmp(b));
-        k *= 2;
-    }
-    let mut suffix_arr = Vec::new();
-    for suf in suffixes {
-        suffix_arr.push(suf.index);
+        seen_boundary = true;
+        common = usize::MAX;
     }
-    suffix_arr
+    &s[best.0..best.0 + best.1]
+}
+
+/// Same as `suffix_array`.
+pub fn generate_suffix_array(txt: &str) -> Vec<usize> {
+    suffix_array(txt)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn naive_suffix_array(s: &str) -> Vec<usize> {
+        let mut sa: Vec<usize> = (0..s.len()).collect();
+        sa.sort_by_key(|&i| &s.as_bytes()[i..]);
+        sa
+    }
+
     #[test]
     fn test_suffix_array() {
         let a = generate_suffix_array("banana");
         assert_eq!(a, vec![5, 3, 1, 0, 4, 2]);
+        // with a sentinel smaller than every letter, its suffix comes first
+        assert_eq!(suffix_array("banana$"), vec![6, 5, 3, 1, 0, 4, 2]);
+        assert!(suffix_array("").is_empty());
+        assert_eq!(suffix_array("a"), vec![0]);
+        assert_eq!(suffix_array("aaaa"), vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_suffix_array_matches_naive() {
+        let texts = [
+            "mississippi",
+            "abracadabra",
+            "Hello, World! 123",
+            "abababababababab",
+            "zyxwvutsrqponmlkjihgfedcba",
+            "yabbadabbadoo",
+            "çiçekçi",
+        ];
+        for text in texts {
+            assert_eq!(suffix_array(text), naive_suffix_array(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_lcp_array() {
+        let sa = suffix_array("banana");
+        // a, ana, anana, banana, na, nana
+        assert_eq!(lcp_array("banana", &sa), vec![0, 1, 3, 0, 0, 2]);
+
+        let sa = suffix_array("mississippi");
+        assert_eq!(
+            lcp_array("mississippi", &sa),
+            vec![0, 1, 1, 4, 0, 0, 1, 0, 2, 1, 3]
+        );
+        assert!(lcp_array("", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_longest_repeated_substring() {
+        assert_eq!(longest_repeated_substring("banana"), "ana");
+        assert_eq!(longest_repeated_substring("mississippi"), "issi");
+        assert_eq!(longest_repeated_substring("abracadabra"), "abra");
+        assert_eq!(longest_repeated_substring("abcdef"), "");
+        assert_eq!(longest_repeated_substring(""), "");
+        assert_eq!(longest_repeated_substring("aaaa"), "aaa");
+        // "ç" and "ğ" share their first byte, which is not a repeat of a character
+        assert_eq!(longest_repeated_substring("çğ"), "");
+        assert_eq!(longest_repeated_substring("xçiçekçi"), "çi");
     }
 }
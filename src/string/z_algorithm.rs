@@ -39,8 +39,46 @@ fn match_with_z_array<T: Eq>(
     }
 }
 
-pub fn z_array<T: Eq>(input: &[T]) -> Vec<usize> {
-    match_with_z_array(input, input, 1, false)
+/// Computes the Z-array of the bytes of `s`: `z[i]` is the length of the
+/// longest substring starting at `s[i]` that is also a prefix of `s`.
+///
+/// By convention `z[0]` is 0 rather than `s.len()`, since the whole string
+/// trivially matches itself.
+pub fn z_array(s: &str) -> Vec<usize> {
+    let bytes = s.as_bytes();
+    match_with_z_array(bytes, bytes, 1, false)
+}
+
+/// Returns the starting positions, as byte offsets, of all occurrences of
+/// `pattern` in `text`, overlapping ones included, in `O(n + m)` time.
+///
+/// It computes the Z-array of `pattern + separator + text`, where the separator
+/// is a symbol that matches no byte: the occurrences are then exactly the
+/// positions in `text` whose Z-value is the length of `pattern`.
+/// Like `kmp_search`, the empty pattern matches at every character boundary.
+pub fn z_search(text: &str, pattern: &str) -> Vec<usize> {
+    if pattern.is_empty() {
+        return text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(text.len()))
+            .collect();
+    }
+    const SEPARATOR: u16 = 256;
+    let m = pattern.len();
+    let concat: Vec<u16> = pattern
+        .bytes()
+        .map(u16::from)
+        .chain(std::iter::once(SEPARATOR))
+        .chain(text.bytes().map(u16::from))
+        .collect();
+    match_with_z_array(&concat, &concat, 1, false)
+        .into_iter()
+        .enumerate()
+        .skip(m + 1)
+        .filter(|&(_, z)| z == m)
+        .map(|(i, _)| i - m - 1)
+        .collect()
 }
 
 pub fn match_pattern<T: Eq>(input: &[T], pattern: &[T]) -> Vec<usize> {
@@ -54,8 +92,41 @@ mod tests {
     #[test]
     fn test_z_array() {
         let string = "aabaabab";
-        let array = z_array(string.as_bytes());
+        let array = z_array(string);
         assert_eq!(array, vec![0, 1, 0, 4, 1, 0, 1, 0]);
+        assert_eq!(z_array("aaaa"), vec![0, 3, 2, 1]);
+        assert_eq!(z_array("a"), vec![0]);
+        assert_eq!(z_array(""), vec![]);
+    }
+
+    #[test]
+    fn search() {
+        assert_eq!(z_search("aaaa", "aaa"), vec![0, 1]);
+        assert_eq!(z_search("abababa", "aba"), vec![0, 2, 4]);
+        assert_eq!(z_search("abc", "abcd"), vec![]);
+        assert_eq!(z_search("abc", ""), vec![0, 1, 2, 3]);
+        // the separator must not match any byte, including '$'
+        assert_eq!(z_search("a$a$", "a$"), vec![0, 2]);
+        assert_eq!(z_search("日本語の日本", "日本"), vec![0, 12]);
+    }
+
+    #[test]
+    fn matches_knuth_morris_pratt() {
+        let mut rng = crate::math::PCG32::new_default(1000);
+        let mut random_string = |max_len: u32| -> String {
+            let len = rng.get_u32() % (max_len + 1);
+            (0..len)
+                .map(|_| ['a', 'b', '$', 'ß'][rng.get_u32() as usize % 4])
+                .collect()
+        };
+        for _ in 0..1000 {
+            let text = random_string(60);
+            let pattern = random_string(4);
+            assert_eq!(
+                z_search(&text, &pattern),
+                crate::string::kmp_search(&text, &pattern)
+            );
+        }
     }
 
     #[test]